@@ -1,5 +1,5 @@
 use armagnac::{
-    core::ArmVersion::V7M,
+    core::Config,
     decoder::{
         BasicInstructionDecoder, LruCachedInstuctionDecoder, Lut16AndGrouped32InstructionDecoder,
     },
@@ -22,7 +22,8 @@ pub fn benchmark(c: &mut Criterion) {
     });
 
     // Test with faster instruction decoder
-    harness.proc.instruction_decoder = Box::new(Lut16AndGrouped32InstructionDecoder::new(V7M));
+    harness.proc.instruction_decoder =
+        Box::new(Lut16AndGrouped32InstructionDecoder::new(&Config::v7m()));
 
     g.bench_function("math_lut16grouped32_decoder", |b| {
         b.iter(|| black_box(harness.call1("bench_math", 5.0f32.to_bits())))
@@ -30,7 +31,7 @@ pub fn benchmark(c: &mut Criterion) {
 
     // Test with basic instruction decoder cached
     harness.proc.instruction_decoder = Box::new(LruCachedInstuctionDecoder::new(
-        BasicInstructionDecoder::new(harness.proc.version),
+        BasicInstructionDecoder::new(&Config::v7m()),
         NonZeroUsize::new(20000).unwrap(),
     ));
 
@@ -40,7 +41,7 @@ pub fn benchmark(c: &mut Criterion) {
 
     // Test with faster instruction decoder cached
     harness.proc.instruction_decoder = Box::new(LruCachedInstuctionDecoder::new(
-        Lut16AndGrouped32InstructionDecoder::new(V7M),
+        Lut16AndGrouped32InstructionDecoder::new(&Config::v7m()),
         NonZeroUsize::new(20000).unwrap(),
     ));
 
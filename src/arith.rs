@@ -53,11 +53,19 @@ pub fn unsigned_sat_q(i: i64, n: u8) -> (i64, bool) {
 }
 
 /// Returns the arithmetic shift right of `value` and the carry output.
+///
+/// # Arguments
+///
+/// * `value` - Value to be shifted
+/// * `shift` - Shift count. Must be > 0.
 pub fn asr_c(value: u32, shift: u32) -> (u32, bool) {
-    assert!((shift > 0) && (shift < 32));
+    // It is possible to have shift >= 32 if shift amount comes from a register, or with ASR #32
+    // immediate shifts. In that case all bits are equal to the sign bit.
+    assert!(shift > 0);
+    let shift = shift.min(32);
     (
-        ((value as i32) >> shift) as u32,
-        value & (1 << (shift - 1)) != 0,
+        ((value as i32 as i64) >> shift) as u32,
+        (value as i32 as i64) & (1 << (shift - 1)) != 0,
     )
 }
 
@@ -426,6 +434,9 @@ mod tests {
         assert_eq!(asr_c(0x80000000, 8), (0xff800000, false));
         assert_eq!(asr_c(0x80000000, 31), (0xffffffff, false));
         assert_eq!(asr_c(0xc0000000, 31), (0xffffffff, true));
+        assert_eq!(asr_c(0x80000000, 32), (0xffffffff, true));
+        assert_eq!(asr_c(0x7fffffff, 32), (0x00000000, false));
+        assert_eq!(asr_c(0x80000000, 200), (0xffffffff, true));
     }
 
     #[test]
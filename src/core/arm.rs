@@ -5,18 +5,27 @@
 
 use crate::{
     align::Align,
-    core::{exclusive_monitor::LocalMonitor, Condition, Config, Coprocessor, Irq, MonitorState},
+    core::{
+        exclusive_monitor::LocalMonitor, Condition, Config, Coprocessor, CustomDatapath,
+        FloatingPointExtension, Irq, LoBranch, LoBranchInfo, MonitorState, MveExtension,
+    },
+    debug::{DataWatchpointTrace, DebugControlBlock, DebugRegisters},
     decoder::{BasicInstructionDecoder, InstructionDecode, InstructionDecodeError},
+    fpb::{FetchPatch, FlashPatchBreakpoint},
+    fpu::FloatingPointControl,
     helpers::BitAccess,
     instructions::{Instruction, InstructionSize},
+    itm::InstrumentationTraceMacrocell,
     memory::{Env, MemoryAccessError, MemoryInterface, MemoryOpAction, RamMemory},
-    mpu::{v7m::MpuV7M, v8m::MemoryProtectionUnitV8M},
-    registers::{CoreRegisters, Mode, RegisterIndex},
-    system_control::SystemControl,
+    mpu::{self, v7m::MpuV7M, v8m::MemoryProtectionUnitV8M, Access, MemoryProtectionUnit},
+    pac::compute_pac,
+    registers::{BankedRegisters, CoreRegisters, MaskRegister, Mode, RegisterIndex, SecurityState},
+    security::{ImplementationDefinedAttributionUnit, SecurityAttributes, SecurityAttributionUnit},
+    system_control::{SoftwareTriggerInterrupt, SystemControl, SystemControlNonSecureAlias},
 };
 use core::panic;
 use std::{
-    cell::RefCell,
+    cell::{RefCell, RefMut},
     collections::BTreeSet,
     ops::{Index, Range},
     rc::Rc,
@@ -26,6 +35,27 @@ struct MemoryMap {
     address: u32,
     size: u32,
     iface: Rc<RefCell<dyn MemoryInterface>>,
+    /// Cycle at which the interface `update` method must be called next, if requested.
+    next_update: Option<u64>,
+    /// True if the interface does not schedule its updates and must be updated at every cycle.
+    /// See [MemoryInterface::scheduled_updates].
+    every_cycle: bool,
+}
+
+impl MemoryMap {
+    /// Moves the actions requested by the interface during an operation to `actions`. Update
+    /// requests are not forwarded but scheduled for this mapping.
+    fn collect_actions(&mut self, env: Env, actions: &mut Vec<MemoryOpAction>) {
+        for action in env.actions {
+            match action {
+                MemoryOpAction::Update(n) => {
+                    let cycles = env.cycles + n as u64;
+                    self.next_update = Some(self.next_update.map_or(cycles, |c| c.min(cycles)));
+                }
+                _ => actions.push(action),
+            }
+        }
+    }
 }
 
 /// Errors that may happen during emulation.
@@ -52,6 +82,13 @@ pub enum RunError {
         value: u32,
         cause: MemoryAccessError,
     },
+    /// Instruction execution has been aborted by a synchronous fault, such as a MemManage fault
+    /// caused by an MPU access violation.
+    ///
+    /// This error is handled by the processor which then takes the corresponding exception, so it
+    /// is never returned by [Emulator::next_event]. It can still be returned when calling
+    /// [Instruction::execute] directly.
+    Fault(Irq),
 }
 
 impl From<InstructionDecodeError> for RunError {
@@ -59,7 +96,7 @@ impl From<InstructionDecodeError> for RunError {
         match e {
             InstructionDecodeError::Unknown => RunError::InstructionUnknown,
             InstructionDecodeError::Unpredictable => RunError::InstructionUnpredictable,
-            InstructionDecodeError::Undefined => todo!(),
+            InstructionDecodeError::Undefined => RunError::InstructionUndefined,
         }
     }
 }
@@ -68,6 +105,31 @@ struct CodeHook {
     range: Range<usize>,
 }
 
+/// Kind of data accesses matched by a data hook. See [Processor::hook_data].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataHookKind {
+    Read,
+    Write,
+    /// Both read and write accesses.
+    Access,
+}
+
+impl DataHookKind {
+    /// Returns `true` if a read access, or a write access if `write` is `true`, is matched.
+    pub(crate) fn matches(&self, write: bool) -> bool {
+        match self {
+            DataHookKind::Read => !write,
+            DataHookKind::Write => write,
+            DataHookKind::Access => true,
+        }
+    }
+}
+
+struct DataHook {
+    range: Range<usize>,
+    kind: DataHookKind,
+}
+
 /// Possible events returned during processor execution.
 #[derive(Clone)]
 pub enum Event {
@@ -78,10 +140,38 @@ pub enum Event {
     Instruction {
         ins: InstructionBox,
     },
+    /// A data access matched a hook defined with [Processor::hook_data]. Only the first matching
+    /// access of an instruction is reported, and this event is returned before the event of the
+    /// instruction which performed the access.
+    DataHook {
+        address: u32,
+        write: bool,
+    },
+    /// A DWT comparator configured to generate debug events matched, while halting debug is
+    /// enabled (see [DebugRegisters::halting_debug]). The comparator number is given. This event
+    /// is returned before the event of the instruction which caused the match.
+    Watchpoint(u8),
     /// CPU reset.
     Reset,
     Break(u8),
     DebugHint(u8),
+    /// The processor entered lockup state, because a fault occurred while executing at a priority
+    /// which does not allow HardFault to be taken (for instance in the HardFault or NMI
+    /// handlers). Execution is stopped until a NMI is taken or the processor is reset with
+    /// [Processor::reset]. This event is emitted again at each step while the processor remains
+    /// locked up.
+    Lockup,
+    /// SEV instruction has been executed. In multi-processor systems, the event must be forwarded
+    /// to the other processors with [Processor::signal_event].
+    SendEvent,
+    /// The processor entered sleep mode with SCR.SLEEPDEEP set, requesting a deep sleep to the
+    /// system. Execution resumes normally on wakeup.
+    DeepSleep,
+    /// The processor is sleeping in WFI or WFE and no peripheral update is scheduled, so it
+    /// cannot wake up by itself. This event is emitted at each step until an interrupt is
+    /// requested with [Processor::request_interrupt] or an event is signaled with
+    /// [Processor::signal_event].
+    Idle,
 }
 
 struct MemoryMappings(Vec<MemoryMap>);
@@ -91,17 +181,6 @@ impl MemoryMappings {
         Self(Vec::new())
     }
 
-    fn get(&self, address: u32) -> Option<&MemoryMap> {
-        for mapping in self.0.iter() {
-            let start = mapping.address as usize;
-            let end = mapping.address as usize + mapping.size as usize;
-            if ((address as usize) >= start) && ((address as usize) < end) {
-                return Some(mapping);
-            }
-        }
-        None
-    }
-
     fn get_mut(&mut self, address: u32) -> Option<&mut MemoryMap> {
         for mapping in self.0.iter_mut() {
             let start = mapping.address as usize;
@@ -148,6 +227,30 @@ pub enum ArmVersion {
     /// - Cortex-M33
     /// - Cortex-M35P
     V8M,
+    /// ARMv8.1-M
+    ///
+    /// Architecture built on ARMv8-M with low-overhead loops and branch future instructions.
+    ///
+    /// Implemented by:
+    /// - Cortex-M55
+    /// - Cortex-M85
+    V81M,
+}
+
+impl ArmVersion {
+    /// Returns `true` if instructions and features of architecture version `other` are available
+    /// in this version. ArmV8.1-M being an extension of ArmV8-M, it includes all of its
+    /// instructions.
+    ///
+    /// ```
+    /// # use armagnac::core::ArmVersion;
+    /// assert!(ArmVersion::V81M.includes(ArmVersion::V8M));
+    /// assert!(!ArmVersion::V8M.includes(ArmVersion::V81M));
+    /// assert!(!ArmVersion::V7EM.includes(ArmVersion::V7M));
+    /// ```
+    pub fn includes(self, other: ArmVersion) -> bool {
+        self == other || (self == ArmVersion::V81M && other == ArmVersion::V8M)
+    }
 }
 
 /// ARM processor state and attached peripherals.
@@ -180,11 +283,11 @@ pub enum ArmVersion {
 /// initialization time:
 ///
 /// ```
-/// use armagnac::core::{Processor, Config, ArmVersion};
+/// use armagnac::core::{Processor, Config};
 /// use armagnac::decoder::{Lut16AndGrouped32InstructionDecoder};
 ///
 /// let mut proc = Processor::new(Config::v7m());
-/// proc.instruction_decoder = Box::new(Lut16AndGrouped32InstructionDecoder::new(ArmVersion::V7M));
+/// proc.instruction_decoder = Box::new(Lut16AndGrouped32InstructionDecoder::new(&Config::v7m()));
 /// ```
 ///
 /// You can also write your own decoder by implementing the [InstructionDecode] trait. For example,
@@ -195,6 +298,9 @@ pub struct Processor {
     pub version: ArmVersion,
     /// r0-r15 and sys registers.
     pub registers: CoreRegisters,
+    /// Current execution priority, updated on exception entry and return, and before each
+    /// instruction. Lower values have higher priority, and 256 is the priority of Thread mode
+    /// without any priority boosting.
     pub execution_priority: i16,
     /// Indicates which exceptions are currently active.
     exception_active: Vec<bool>,
@@ -203,6 +309,9 @@ pub struct Processor {
     memory_mappings: MemoryMappings,
     /// The local monitor tags a memory address for exclusive accesses.
     pub local_monitor: LocalMonitor,
+    /// Low-overhead branch information cache (LO_BRANCH_INFO) of ArmV8.1-M. `None` when the cache
+    /// is invalid.
+    pub lo_branch_info: Option<LoBranchInfo>,
     /// Parses word or double-word values to decode them as executable ARM instructions.
     /// Since this is a performance critical task of the emulator, different implementation with
     /// different optimisation strategies, which may depend on the context, may be selected.
@@ -213,6 +322,11 @@ pub struct Processor {
     /// (Wait For Interrupt) instructions are used.
     pub cycles: u64,
     code_hooks: Vec<CodeHook>,
+    data_hooks: Vec<DataHook>,
+    /// First data access which matched a data hook during current instruction execution.
+    data_hook_event: Option<Event>,
+    /// First DWT comparator which generated a debug event during current instruction execution.
+    watchpoint_event: Option<u8>,
     /// Read and write access in peripherals may trigger actions. For instance, writing to a
     /// special register may perform a software reset. Those special actions from peripherals are
     /// stacked in this attribute during instruction emulation, and then processed one the
@@ -223,15 +337,50 @@ pub struct Processor {
     /// System control registers peripheral.
     /// Needed for instance to fetch VTOR during an exception.
     system_control: Rc<RefCell<SystemControl>>,
+    /// Memory Protection Unit used to validate memory accesses, if the architecture has one.
+    mpu: Option<Rc<RefCell<dyn MemoryProtectionUnit>>>,
+    /// Floating-point context control registers. Mapped in the System Control Space only when
+    /// the floating-point extension is implemented.
+    fp_control: Rc<RefCell<FloatingPointControl>>,
+    /// Debug Control Block and Data Watchpoint and Trace unit registers.
+    debug: Rc<RefCell<DebugRegisters>>,
+    /// Flash Patch and Breakpoint unit. Mapped only on ArmV7-M.
+    fpb: Rc<RefCell<FlashPatchBreakpoint>>,
+    /// Instrumentation Trace Macrocell. Not mapped on ArmV6-M.
+    itm: Rc<RefCell<InstrumentationTraceMacrocell>>,
     /// Coprocessors.
     /// If Arm profile does not support coprocessors, this vector remains empty.
     pub coprocessors: Vec<Option<Rc<RefCell<dyn Coprocessor>>>>,
+    /// Custom Datapath Extension coprocessors implementations, for coprocessors 0 to 7.
+    /// See [Config::custom_datapath].
+    pub custom_datapaths: Vec<Option<Rc<RefCell<dyn CustomDatapath>>>>,
     /// When poping PC from the stack (during exception return for instance), PC should be aligned,
     /// otherwise execution is unpredictable according to the ARM specification. However, some
     /// implementation may still set LSB to 1 for Thumb mode, and this can work on some hardware.
     /// So to allow emulation in that case, `tolerate_pop_stack_unaligned_pc` can be set to `true`.
     /// If `false` (the default) an error will be reported by the emulation if PC is unaligned.
     pub tolerate_pop_stack_unaligned_pc: bool,
+    /// When `true`, memory access errors raise BusFault exceptions instead of aborting emulation.
+    /// See [Config::bus_faults].
+    bus_faults: bool,
+    /// Floating-point extension, if any. See [Config::fpu].
+    fpu: Option<FloatingPointExtension>,
+    /// Whether the Security Extension is implemented. See [Config::security_extension].
+    security_extension: bool,
+    /// M-profile Vector Extension, if any. See [Config::mve].
+    mve: Option<MveExtension>,
+    /// Whether the PACBTI extension is implemented. See [Config::pacbti].
+    pacbti: bool,
+    /// Bit mask of the Custom Datapath Extension coprocessors. See [Config::custom_datapath].
+    custom_datapath: u8,
+    /// Security Attribution Unit. Mapped in the System Control Space only when the Security
+    /// Extension is implemented.
+    sau: Rc<RefCell<SecurityAttributionUnit>>,
+    /// Implementation Defined Attribution Unit, if the platform has one.
+    idau: Option<Rc<RefCell<dyn ImplementationDefinedAttributionUnit>>>,
+    /// Set when an instruction attempts to move the stack pointer below its limit. See
+    /// [Processor::set_sp].
+    stack_limit_violation: bool,
     /// Stacked events from emulation.
     events: Vec<Event>,
 }
@@ -254,9 +403,39 @@ impl Processor {
         let exception_count = 16usize.checked_add(config.external_exceptions).unwrap();
         let coprocessor_count = match version {
             ArmVersion::V6M => 0,
-            ArmVersion::V7M | ArmVersion::V7EM | ArmVersion::V8M => 16,
+            ArmVersion::V7M | ArmVersion::V7EM | ArmVersion::V8M | ArmVersion::V81M => 16,
         };
-        let system_control = Rc::new(RefCell::new(SystemControl::new()));
+        assert!(
+            config.fpu.is_none() || version != ArmVersion::V6M,
+            "ArmV6-M does not support the floating-point extension"
+        );
+        assert!(
+            !config.security_extension || version.includes(ArmVersion::V8M),
+            "Only ArmV8-M supports the Security Extension"
+        );
+        assert!(
+            config.mve.is_none() || version == ArmVersion::V81M,
+            "Only ArmV8.1-M supports the M-profile Vector Extension"
+        );
+        assert!(
+            config.mve.is_none() || config.fpu.is_some(),
+            "The M-profile Vector Extension requires the floating-point extension"
+        );
+        assert!(
+            !config.pacbti || version == ArmVersion::V81M,
+            "Only ArmV8.1-M supports the PACBTI extension"
+        );
+        assert!(
+            config.custom_datapath == 0 || version.includes(ArmVersion::V8M),
+            "Only ArmV8-M supports the Custom Datapath Extension"
+        );
+        let mut system_control = SystemControl::new();
+        system_control.security_extension = config.security_extension;
+        system_control.set_version(version);
+        system_control.set_pacbti(config.pacbti);
+        system_control.external_interrupts = config.external_exceptions;
+        let system_control = Rc::new(RefCell::new(system_control));
+        let debug = Rc::new(RefCell::new(DebugRegisters::new(version)));
 
         let mut processor = Self {
             version,
@@ -264,33 +443,86 @@ impl Processor {
             state: State::Running,
             memory_mappings: MemoryMappings::new(),
             local_monitor: LocalMonitor::new(config.exclusives_reservation_granule),
-            execution_priority: 0,
+            lo_branch_info: None,
+            execution_priority: 256,
             exception_active: (0..exception_count).map(|_| false).collect(),
-            instruction_decoder: Box::new(BasicInstructionDecoder::new(version)),
+            instruction_decoder: Box::new(BasicInstructionDecoder::new(&config)),
             cycles: 0,
             code_hooks: Vec::new(),
+            data_hooks: Vec::new(),
+            data_hook_event: None,
+            watchpoint_event: None,
             memory_op_actions: Vec::new(),
             interrupt_requests: BTreeSet::new(),
             system_control: system_control.clone(),
+            mpu: None,
+            fp_control: Rc::new(RefCell::new(FloatingPointControl::new(
+                config.fpu == Some(FloatingPointExtension::DoublePrecision),
+                config.mve,
+            ))),
+            debug: debug.clone(),
+            fpb: Rc::new(RefCell::new(FlashPatchBreakpoint::default())),
+            itm: Rc::new(RefCell::new(InstrumentationTraceMacrocell::new(
+                debug.clone(),
+            ))),
             coprocessors: (0..coprocessor_count).map(|_| None).collect(),
+            custom_datapaths: (0..8).map(|_| None).collect(),
             tolerate_pop_stack_unaligned_pc: false,
+            bus_faults: config.bus_faults,
+            fpu: config.fpu,
+            security_extension: config.security_extension,
+            mve: config.mve,
+            pacbti: config.pacbti,
+            custom_datapath: config.custom_datapath,
+            sau: Rc::new(RefCell::new(SecurityAttributionUnit::default())),
+            idau: None,
+            stack_limit_violation: false,
             events: Vec::new(),
         };
 
-        processor.map_iface(0xe000e000, system_control).unwrap();
+        processor
+            .map_iface(0xe000e000, system_control.clone())
+            .unwrap();
+        if processor.version != ArmVersion::V6M {
+            let stir = Rc::new(RefCell::new(SoftwareTriggerInterrupt(
+                system_control.clone(),
+            )));
+            processor.map_iface(0xe000ef00, stir).unwrap();
+            let dwt = Rc::new(RefCell::new(DataWatchpointTrace(processor.debug.clone())));
+            processor.map_iface(0xe0001000, dwt).unwrap();
+            let itm = processor.itm.clone();
+            processor.map_iface(0xe0000000, itm).unwrap();
+        }
+        let dcb = Rc::new(RefCell::new(DebugControlBlock(processor.debug.clone())));
+        processor.map_iface(0xe000edf0, dcb).unwrap();
         match processor.version {
             ArmVersion::V6M => {}
-            ArmVersion::V7M | ArmVersion::V7EM => processor
-                .map_iface(0xe000ed90, Rc::new(RefCell::new(MpuV7M::new())))
-                .unwrap(),
-            ArmVersion::V8M => {
-                processor
-                    .map_iface(
-                        0xe000ed90,
-                        Rc::new(RefCell::new(MemoryProtectionUnitV8M::new(16))),
-                    )
-                    .unwrap();
+            ArmVersion::V7M | ArmVersion::V7EM => {
+                let mpu = Rc::new(RefCell::new(MpuV7M::new()));
+                processor.map_iface(0xe000ed90, mpu.clone()).unwrap();
+                processor.mpu = Some(mpu);
+                let fpb = processor.fpb.clone();
+                processor.map_iface(0xe0002000, fpb).unwrap();
             }
+            ArmVersion::V8M | ArmVersion::V81M => {
+                let mpu = Rc::new(RefCell::new(MemoryProtectionUnitV8M::new(16)));
+                processor.map_iface(0xe000ed90, mpu.clone()).unwrap();
+                processor.mpu = Some(mpu);
+            }
+        }
+        if processor.version == ArmVersion::V81M {
+            // Tail predication is disabled out of low-overhead loops.
+            processor.registers.fpscr.set_ltpsize(4);
+        }
+        if processor.fpu.is_some() {
+            let fp_control = processor.fp_control.clone();
+            processor.map_iface(0xe000ef34, fp_control).unwrap();
+        }
+        if processor.security_extension {
+            let sau = processor.sau.clone();
+            processor.map_iface(0xe000edd0, sau).unwrap();
+            let alias = Rc::new(RefCell::new(SystemControlNonSecureAlias(system_control)));
+            processor.map_iface(0xe002e000, alias).unwrap();
         }
         processor
     }
@@ -331,10 +563,13 @@ impl Processor {
             return Err(MapConflict);
         }
 
+        let every_cycle = !iface.borrow().scheduled_updates();
         self.memory_mappings.0.push(MemoryMap {
             address,
             size,
             iface,
+            next_update: Some(self.cycles),
+            every_cycle,
         });
         Ok(())
     }
@@ -365,26 +600,138 @@ impl Processor {
         self.coprocessors[index] = Some(coprocessor)
     }
 
+    /// Defines the implementation of one of the 8 possible Custom Datapath Extension
+    /// coprocessors.
+    ///
+    /// Panics if the coprocessor has not been declared as a CDE coprocessor with
+    /// [Config::custom_datapath].
+    pub fn set_custom_datapath(&mut self, index: usize, datapath: Rc<RefCell<dyn CustomDatapath>>) {
+        assert!(
+            index < 8 && self.custom_datapath & (1 << index) != 0,
+            "Coprocessor {index} is not a Custom Datapath Extension coprocessor"
+        );
+        self.custom_datapaths[index] = Some(datapath)
+    }
+
     pub fn hook_code(&mut self, range: Range<usize>) {
         self.code_hooks.push(CodeHook { range })
     }
 
+    /// Removes a hook previously defined with [Processor::hook_code] with the same `range`.
+    pub fn unhook_code(&mut self, range: Range<usize>) {
+        if let Some(i) = self.code_hooks.iter().position(|h| h.range == range) {
+            self.code_hooks.remove(i);
+        }
+    }
+
+    /// Emits [Event::DataHook] when an instruction reads or writes data in `range`, depending on
+    /// `kind`. Instruction fetches and accesses made with `*_iface` methods are not matched.
+    pub fn hook_data(&mut self, range: Range<usize>, kind: DataHookKind) {
+        self.data_hooks.push(DataHook { range, kind })
+    }
+
+    /// Removes a hook previously defined with [Processor::hook_data] with the same `range` and
+    /// `kind`.
+    pub fn unhook_data(&mut self, range: Range<usize>, kind: DataHookKind) {
+        if let Some(i) = self
+            .data_hooks
+            .iter()
+            .position(|h| h.range == range && h.kind == kind)
+        {
+            self.data_hooks.remove(i);
+        }
+    }
+
+    /// Records a data access of `size` bytes at `address` if it matches a data hook.
+    fn check_data_hooks(&mut self, address: u32, size: usize, write: bool) {
+        if self.data_hook_event.is_some() {
+            return;
+        }
+        let access = address as usize..address as usize + size;
+        if self.data_hooks.iter().any(|h| {
+            h.kind.matches(write) && h.range.start < access.end && access.start < h.range.end
+        }) {
+            self.data_hook_event = Some(Event::DataHook { address, write });
+        }
+    }
+
+    /// Records the first DWT comparator generating a debug event for a data access of `size`
+    /// bytes at `address`. `value` is the data read or written.
+    fn check_watchpoints(&mut self, address: u32, size: usize, write: bool, value: u32) {
+        let mut debug = self.debug.borrow_mut();
+        if debug.is_watching() {
+            let event = debug.match_data(address, size, write, value);
+            self.watchpoint_event = self.watchpoint_event.or(event);
+        }
+    }
+
+    /// Handles a debug event generated by DWT comparator `n`, by halting the execution when
+    /// halting debug is enabled, or by pending the DebugMonitor exception.
+    fn watchpoint_debug_event(&mut self, n: u8) {
+        // DFSR.DWTTRAP
+        self.system_control.borrow_mut().dfsr.value.set_bit(2, true);
+        let debug = self.debug.borrow();
+        if debug.halting_debug {
+            self.events.push(Event::Watchpoint(n));
+        } else if debug.monitor_enabled() {
+            drop(debug);
+            self.set_pending(Irq::DebugMonitor);
+        }
+    }
+
     /// If given `address` is not aligned to `size`, set `UNALIGNED` bit in CFSR register and take
     /// usage fault exception. This method is used by memory access calls performed by
     /// instructions.
     fn usage_fault_if_unaligned(&mut self, address: u32, size: usize) -> Result<(), RunError> {
         if !address.is_aligned(size) {
             self.system_control.borrow_mut().cfsr.set_unaligned(true);
-            self.exception_taken(Irq::UsageFault)?;
+            return Err(RunError::Fault(Irq::UsageFault));
         }
         Ok(())
     }
 
+    /// When bus faults are enabled, converts the memory access error of a data access into a
+    /// precise BusFault, reporting the faulting address in BFAR. Otherwise the error is returned
+    /// unchanged and aborts emulation.
+    fn data_bus_error(&mut self, error: RunError) -> RunError {
+        match error {
+            RunError::MemRead { address, .. } | RunError::MemWrite { address, .. }
+                if self.bus_faults =>
+            {
+                let mut system_control = self.system_control.borrow_mut();
+                system_control.cfsr.set_preciserr(true);
+                system_control.cfsr.set_bfarvalid(true);
+                system_control.bfar = address;
+                RunError::Fault(Irq::BusFault)
+            }
+            _ => error,
+        }
+    }
+
+    /// When bus faults are enabled, converts the memory access error of an instruction fetch into
+    /// a BusFault. Otherwise the error is returned unchanged and aborts emulation.
+    fn instruction_bus_error(&mut self, error: RunError) -> RunError {
+        match error {
+            RunError::MemRead { .. } if self.bus_faults => {
+                self.system_control.borrow_mut().cfsr.set_ibuserr(true);
+                RunError::Fault(Irq::BusFault)
+            }
+            _ => error,
+        }
+    }
+
     /// Implements `MemA_with_priv` and `MemU_with_priv` from Arm Architecture Reference Manual,
     /// for 8 bit read accesses.
     pub fn read_u8_with_priv(&mut self, address: u32, privileged: bool) -> Result<u8, RunError> {
-        self.validate_address(address, privileged, false, false);
-        self.read_u8_iface(address)
+        self.validate_address(address, privileged, false, false)?;
+        self.check_data_hooks(address, 1, false);
+        // Literal loads may be remapped by the Flash Patch and Breakpoint unit.
+        let remapped = self.fpb.borrow().remap_read(address);
+        let value = self
+            .read_u8_iface(remapped)
+            .map_err(|e| self.data_bus_error(e))?;
+        self.check_watchpoints(address, 1, false, value as u32);
+        Ok(value)
     }
 
     /// Implements `MemA_with_priv` and `MemU_with_priv` from Arm Architecture Reference Manual,
@@ -395,8 +742,11 @@ impl Processor {
         value: u8,
         privileged: bool,
     ) -> Result<(), RunError> {
-        self.validate_address(address, privileged, false, false);
+        self.validate_address(address, privileged, true, false)?;
+        self.check_data_hooks(address, 1, true);
+        self.check_watchpoints(address, 1, true, value as u32);
         self.write_u8_iface(address, value)
+            .map_err(|e| self.data_bus_error(e))
     }
 
     /// Implements `MemA_with_priv` from Arm Architecture Reference Manual, for 16 bit read
@@ -407,11 +757,16 @@ impl Processor {
         privileged: bool,
     ) -> Result<u16, RunError> {
         self.usage_fault_if_unaligned(address, 2)?;
-        self.validate_address(address, privileged, false, false);
-        let mut value = self.read_u16le_iface(address)?;
+        self.validate_address(address, privileged, false, false)?;
+        self.check_data_hooks(address, 2, false);
+        let remapped = self.fpb.borrow().remap_read(address);
+        let mut value = self
+            .read_u16le_iface(remapped)
+            .map_err(|e| self.data_bus_error(e))?;
         if self.system_control.borrow_mut().aircr.endianess() {
             value = value.swap_bytes()
         }
+        self.check_watchpoints(address, 2, false, value as u32);
         Ok(value)
     }
 
@@ -424,11 +779,14 @@ impl Processor {
         privileged: bool,
     ) -> Result<(), RunError> {
         self.usage_fault_if_unaligned(address, 2)?;
-        self.validate_address(address, privileged, false, false);
+        self.validate_address(address, privileged, true, false)?;
+        self.check_data_hooks(address, 2, true);
+        self.check_watchpoints(address, 2, true, value as u32);
         if self.system_control.borrow_mut().aircr.endianess() {
             value = value.swap_bytes()
         }
         self.write_u16le_iface(address, value)
+            .map_err(|e| self.data_bus_error(e))
     }
 
     /// Implements `MemA_with_priv` from Arm Architecture Reference Manual, for 32 bit read
@@ -439,11 +797,16 @@ impl Processor {
         privileged: bool,
     ) -> Result<u32, RunError> {
         self.usage_fault_if_unaligned(address, 4)?;
-        self.validate_address(address, privileged, false, false);
-        let mut value = self.read_u32le_iface(address)?;
+        self.validate_address(address, privileged, false, false)?;
+        self.check_data_hooks(address, 4, false);
+        let remapped = self.fpb.borrow().remap_read(address);
+        let mut value = self
+            .read_u32le_iface(remapped)
+            .map_err(|e| self.data_bus_error(e))?;
         if self.system_control.borrow_mut().aircr.endianess() {
             value = value.swap_bytes()
         }
+        self.check_watchpoints(address, 4, false, value);
         Ok(value)
     }
 
@@ -456,11 +819,14 @@ impl Processor {
         privileged: bool,
     ) -> Result<(), RunError> {
         self.usage_fault_if_unaligned(address, 4)?;
-        self.validate_address(address, privileged, false, false);
+        self.validate_address(address, privileged, true, false)?;
+        self.check_data_hooks(address, 4, true);
+        self.check_watchpoints(address, 4, true, value);
         if self.system_control.borrow_mut().aircr.endianess() {
             value = value.swap_bytes()
         }
         self.write_u32le_iface(address, value)
+            .map_err(|e| self.data_bus_error(e))
     }
 
     /// Implements `MemA` and `MemU` from Arm Architecture Reference Manual, for 8 bit read
@@ -506,8 +872,7 @@ impl Processor {
             self.read_u16_aligned_with_priv(address, privileged)
         } else if self.system_control.borrow().ccr.unalign_trp() {
             self.system_control.borrow_mut().cfsr.set_unaligned(true);
-            self.exception_taken(Irq::UsageFault)?;
-            Ok(0)
+            Err(RunError::Fault(Irq::UsageFault))
         } else {
             // Unaligned access
             let v0 = self.read_u8_with_priv(address, privileged)?;
@@ -532,8 +897,7 @@ impl Processor {
             self.write_u16_aligned_with_priv(address, value, privileged)
         } else if self.system_control.borrow().ccr.unalign_trp() {
             self.system_control.borrow_mut().cfsr.set_unaligned(true);
-            self.exception_taken(Irq::UsageFault)?;
-            Ok(())
+            Err(RunError::Fault(Irq::UsageFault))
         } else {
             // Unaligned access
             let v0 = value as u8;
@@ -560,8 +924,7 @@ impl Processor {
             self.read_u32_aligned_with_priv(address, privileged)
         } else if self.system_control.borrow().ccr.unalign_trp() {
             self.system_control.borrow_mut().cfsr.set_unaligned(true);
-            self.exception_taken(Irq::UsageFault)?;
-            Ok(0)
+            Err(RunError::Fault(Irq::UsageFault))
         } else {
             // Unaligned access
             let v0 = self.read_u8_with_priv(address, privileged)?;
@@ -588,8 +951,7 @@ impl Processor {
             self.write_u32_aligned_with_priv(address, value, privileged)
         } else if self.system_control.borrow().ccr.unalign_trp() {
             self.system_control.borrow_mut().cfsr.set_unaligned(true);
-            self.exception_taken(Irq::UsageFault)?;
-            Ok(())
+            Err(RunError::Fault(Irq::UsageFault))
         } else {
             // Unaligned access
             let v0 = value as u8;
@@ -631,9 +993,16 @@ impl Processor {
         self.write_u32_unaligned_with_priv(address, value, self.is_privileged())
     }
 
+    /// Returns the environment passed to memory interfaces for accesses made by the processor.
+    fn env(&self) -> Env {
+        let mut env = Env::new(self.cycles, self.is_privileged());
+        env.secure = self.is_secure();
+        env
+    }
+
     /// Reads a byte at `address` without checking for privileges.
     pub fn read_u8_iface(&mut self, address: u32) -> Result<u8, RunError> {
-        let mut env = Env::new(self.cycles, self.is_privileged());
+        let mut env = self.env();
         let mapping = self
             .memory_mappings
             .get_mut(address)
@@ -646,7 +1015,7 @@ impl Processor {
             .iface
             .borrow_mut()
             .read_u8(address - mapping.address, &mut env);
-        self.memory_op_actions.extend(env.actions);
+        mapping.collect_actions(env, &mut self.memory_op_actions);
         match read {
             Ok(val) => Ok(val),
             Err(e) => Err(RunError::MemRead {
@@ -659,7 +1028,7 @@ impl Processor {
 
     /// Write byte `value` at `address` without checking for privileges.
     pub fn write_u8_iface(&mut self, address: u32, value: u8) -> Result<(), RunError> {
-        let mut env = Env::new(self.cycles, self.is_privileged());
+        let mut env = self.env();
         let mapping = self
             .memory_mappings
             .get_mut(address)
@@ -673,7 +1042,7 @@ impl Processor {
             .iface
             .borrow_mut()
             .write_u8(address - mapping.address, value, &mut env);
-        self.memory_op_actions.extend(env.actions);
+        mapping.collect_actions(env, &mut self.memory_op_actions);
         match write {
             Ok(()) => Ok(()),
             Err(e) => Err(RunError::MemWrite {
@@ -687,7 +1056,7 @@ impl Processor {
 
     /// Write halfword `value` at `address` without checking for privileges or alignment.
     pub fn write_u16le_iface(&mut self, address: u32, value: u16) -> Result<(), RunError> {
-        let mut env = Env::new(self.cycles, self.is_privileged());
+        let mut env = self.env();
         let mapping = self
             .memory_mappings
             .get_mut(address)
@@ -702,7 +1071,7 @@ impl Processor {
                 .iface
                 .borrow_mut()
                 .write_u16le(address - mapping.address, value, &mut env);
-        self.memory_op_actions.extend(env.actions);
+        mapping.collect_actions(env, &mut self.memory_op_actions);
         match write {
             Ok(()) => Ok(()),
             Err(e) => Err(RunError::MemWrite {
@@ -716,13 +1085,13 @@ impl Processor {
 
     /// Read halfword at `address` without checking for privileges or alignment.
     pub fn read_u16le_iface(&mut self, address: u32) -> Result<u16, RunError> {
-        let mut env = Env::new(self.cycles, self.is_privileged());
+        let mut env = self.env();
         if let Some(mapping) = self.memory_mappings.get_mut(address) {
             let read = mapping
                 .iface
                 .borrow_mut()
                 .read_u16le(address - mapping.address, &mut env);
-            self.memory_op_actions.extend(env.actions);
+            mapping.collect_actions(env, &mut self.memory_op_actions);
             match read {
                 Ok(val) => Ok(val),
                 Err(e) => Err(RunError::MemRead {
@@ -742,7 +1111,7 @@ impl Processor {
 
     /// Reads 32-bit word at `address` without checking for privileges or alignment.
     pub fn read_u32le_iface(&mut self, address: u32) -> Result<u32, RunError> {
-        let mut env = Env::new(self.cycles, self.is_privileged());
+        let mut env = self.env();
         let mapping = self
             .memory_mappings
             .get_mut(address)
@@ -755,7 +1124,7 @@ impl Processor {
             .iface
             .borrow_mut()
             .read_u32le(address - mapping.address, &mut env);
-        self.memory_op_actions.extend(env.actions);
+        mapping.collect_actions(env, &mut self.memory_op_actions);
         match read {
             Ok(val) => Ok(val),
             Err(e) => Err(RunError::MemRead {
@@ -768,7 +1137,7 @@ impl Processor {
 
     /// Writes 32-bit word at `address` without checking for privileges or alignment.
     pub fn write_u32le_iface(&mut self, address: u32, value: u32) -> Result<(), RunError> {
-        let mut env = Env::new(self.cycles, self.is_privileged());
+        let mut env = self.env();
         let mapping = self
             .memory_mappings
             .get_mut(address)
@@ -783,7 +1152,7 @@ impl Processor {
                 .iface
                 .borrow_mut()
                 .write_u32le(address - mapping.address, value, &mut env);
-        self.memory_op_actions.extend(env.actions);
+        mapping.collect_actions(env, &mut self.memory_op_actions);
         match write {
             Ok(()) => Ok(()),
             Err(e) => Err(RunError::MemWrite {
@@ -795,16 +1164,78 @@ impl Processor {
         }
     }
 
+    /// Returns `true` if the MPU permits the given access, or if the default memory map permits
+    /// it when there is no MPU.
+    fn mpu_permits(
+        &self,
+        address: u32,
+        is_priv: bool,
+        is_write: bool,
+        is_instr_fetch: bool,
+    ) -> bool {
+        let access = Access {
+            address,
+            privileged: is_priv,
+            write: is_write,
+            instruction_fetch: is_instr_fetch,
+            negative_priority: self.execution_priority < 0,
+        };
+        match &self.mpu {
+            Some(mpu) => mpu.borrow().check(&access),
+            None => mpu::check_default(&access),
+        }
+    }
+
+    /// Returns the security attribution of `address`, as given by the SAU and the IDAU.
+    ///
+    /// Corresponds to `SecurityCheck()` in the Arm Architecture Reference Manual.
+    pub fn security_attributes(&self, address: u32) -> SecurityAttributes {
+        let idau = self
+            .idau
+            .as_ref()
+            .map(|idau| idau.borrow().check(address))
+            .unwrap_or_default();
+        self.sau.borrow().check(address, idau, self.is_secure())
+    }
+
+    /// Verifies that a memory access is permitted by the security attribution and the MPU.
+    ///
+    /// If not, the SecureFault or MemManage fault status is updated and [RunError::Fault] is
+    /// returned to abort the current instruction.
+    ///
     /// Corresponds to `ValidateAddress()` in the Arm Architecture Reference Manual.
-    /// Currently not implemented, MPU is not enforced yet.
     fn validate_address(
-        &self,
-        _address: u32,
-        _is_priv: bool,
-        _is_write: bool,
-        _is_instr_fetch: bool,
-    ) {
-        // TODO
+        &mut self,
+        address: u32,
+        is_priv: bool,
+        is_write: bool,
+        is_instr_fetch: bool,
+    ) -> Result<(), RunError> {
+        // Instruction fetches are checked by Self::validate_fetch_security, since it depends on
+        // the fetched instruction.
+        if self.security_extension
+            && !is_instr_fetch
+            && !self.is_secure()
+            && !self.security_attributes(address).non_secure
+        {
+            let mut sau = self.sau.borrow_mut();
+            sau.sfsr.set_auviol(true);
+            sau.sfsr.set_sfarvalid(true);
+            sau.sfar = address;
+            return Err(RunError::Fault(Irq::SecureFault));
+        }
+        if self.mpu_permits(address, is_priv, is_write, is_instr_fetch) {
+            return Ok(());
+        }
+        let mut system_control = self.system_control.borrow_mut();
+        if is_instr_fetch {
+            system_control.cfsr.set_iaccviol(true);
+        } else {
+            system_control.cfsr.set_daccviol(true);
+            system_control.cfsr.set_mmarvalid(true);
+            system_control.mmfar = address;
+        }
+        Err(RunError::Fault(Irq::MemManage))
     }
 
     /// Reads `size` successive bytes starting at `address`, without checking for privileges or
@@ -837,18 +1268,50 @@ impl Processor {
 
     /// Sets the value of a register.
     ///
-    /// This is a shorcut to `self.registers.set(r, value)`.
+    /// This is a shorcut to `self.registers.set(r, value)`, excepted for SP whose new value is
+    /// checked against the stack limit. See [Processor::set_sp].
     pub fn set(&mut self, index: RegisterIndex, value: u32) {
-        self.registers.set(index, value)
+        if index.is_sp() {
+            self.set_sp(value)
+        } else {
+            self.registers.set(index, value)
+        }
     }
 
     /// Sets value of the Stack Pointer (r13)
     ///
+    /// On ArmV8-M, if `value` is below the limit of the current stack pointer defined by MSPLIM or
+    /// PSPLIM, the stack pointer is not updated and the instruction being executed raises a
+    /// UsageFault with CFSR.STKOF set.
+    ///
     /// # Arguments
     ///
     /// * `value` - New Stack Pointer value
     pub fn set_sp(&mut self, value: u32) {
-        *self.registers.sp_mut() = value
+        if self.violates_sp_limit(self.registers.translate_sp(), value) {
+            self.stack_limit_violation = true;
+        } else {
+            *self.registers.sp_mut() = value
+        }
+    }
+
+    /// Returns `true` if writing back `value` to base register `rn` violates the stack limit.
+    ///
+    /// Store instructions with SP write-back check this before accessing memory, so nothing is
+    /// written below the limit when the write-back raises the stack overflow UsageFault.
+    pub fn sp_writeback_violates_limit(&self, rn: RegisterIndex, value: u32) -> bool {
+        rn.is_sp() && self.violates_sp_limit(self.registers.translate_sp(), value)
+    }
+
+    /// Returns `true` if `value` is below the stack limit of stack pointer `sp`, which must be
+    /// [RegisterIndex::Msp] or [RegisterIndex::Psp]. Stack limits are ignored at negative
+    /// execution priority when CCR.STKOFHFNMIGN is set.
+    ///
+    /// Corresponds to `ViolatesSPLim()` in the Arm Architecture Reference Manual.
+    fn violates_sp_limit(&self, sp: RegisterIndex, value: u32) -> bool {
+        self.version.includes(ArmVersion::V8M)
+            && value < self.registers.sp_limit(sp)
+            && !(self.execution_priority < 0 && self.system_control.borrow().ccr.stkofhfnmign())
     }
 
     /// Returns current value of the Link Register (r14)
@@ -880,44 +1343,85 @@ impl Processor {
         &mut self,
         address: u32,
     ) -> Result<(InstructionBox, InstructionSize), RunError> {
-        let hw = self.read_u16le_iface(address)?;
+        let privileged = self.is_privileged();
+        self.validate_address(address, privileged, false, true)?;
+        let hw = self.fetch_halfword(address, true)?;
         let it_state = self.registers.psr.it_state();
         let size = InstructionSize::from_halfword(hw);
-        let ins = match size {
-            InstructionSize::Ins16 => {
-                self.instruction_decoder
-                    .try_decode(hw as u32, InstructionSize::Ins16, it_state)?
-            }
+        let value = match size {
+            InstructionSize::Ins16 => hw as u32,
             InstructionSize::Ins32 => {
-                let hw2 = self.read_u16le_iface(address + 2)?;
-                self.instruction_decoder.try_decode(
-                    ((hw as u32) << 16) + hw2 as u32,
-                    InstructionSize::Ins32,
-                    it_state,
-                )?
+                self.validate_address(address + 2, privileged, false, true)?;
+                let hw2 = self.fetch_halfword(address + 2, false)?;
+                ((hw as u32) << 16) + hw2 as u32
             }
         };
+        self.validate_fetch_security(address, value)?;
+        let ins = self.instruction_decoder.try_decode(value, size, it_state)?;
         Ok((ins, size))
     }
 
+    /// Fetches the instruction halfword at `address`, which may be remapped or replaced by a
+    /// breakpoint by the Flash Patch and Breakpoint unit. `first` is `false` for the second
+    /// halfword of 32-bit instructions.
+    fn fetch_halfword(&mut self, address: u32, first: bool) -> Result<u16, RunError> {
+        let address = match self.fpb.borrow().match_fetch(address, first) {
+            // BKPT #0
+            Some(FetchPatch::Breakpoint) => return Ok(0xbe00),
+            Some(FetchPatch::Remap(remapped)) => remapped,
+            None => address,
+        };
+        self.read_u16le_iface(address)
+            .map_err(|e| self.instruction_bus_error(e))
+    }
+
+    /// When the Security Extension is implemented, verifies that instruction `value` at `address`
+    /// can be executed in the current security state.
+    ///
+    /// Non-secure code can only enter Secure memory through a SG instruction located in a
+    /// Non-secure callable region, otherwise SFSR.INVEP is set. Secure code cannot execute
+    /// Non-secure memory without using BXNS or BLXNS, otherwise SFSR.INVTRAN is set.
+    fn validate_fetch_security(&mut self, address: u32, value: u32) -> Result<(), RunError> {
+        if !self.security_extension {
+            return Ok(());
+        }
+        let attributes = self.security_attributes(address);
+        let mut sau = self.sau.borrow_mut();
+        match (self.is_secure(), attributes.non_secure) {
+            (false, false) if attributes.non_secure_callable && value == 0xe97fe97f => Ok(()),
+            (false, false) => {
+                sau.sfsr.set_invep(true);
+                Err(RunError::Fault(Irq::SecureFault))
+            }
+            (true, true) => {
+                sau.sfsr.set_invtran(true);
+                Err(RunError::Fault(Irq::SecureFault))
+            }
+            _ => Ok(()),
+        }
+    }
+
     fn step(&mut self) -> Result<(), RunError> {
-        // Handle interrupt requests
-        if let Some(irq) = self.interrupt_requests.pop_first() {
-            let max_num = self.exception_active.len();
-            let num = irq.number();
-            assert!(
-                (num as usize) < max_num,
-                "Exception number too high: got {}, max is {}",
-                num,
-                max_num - 1
-            );
-            // TODO: not all exceptions may result in a WFI wakeup event.
-            if self.state == State::WaitingForInterrupt {
+        // Handle interrupt requests. Only the pending exception with the highest priority is
+        // considered, and it is taken only if it can preempt the current execution priority.
+        // Since all pending requests are evaluated at exception entry, a late-arriving exception
+        // of higher priority is always taken first.
+        self.execution_priority = self.compute_execution_priority();
+        if let Some(irq) = self.pending_exception(self.execution_priority) {
+            self.interrupt_requests.remove(&irq);
+            // Taking an exception wakes up the processor from WFI, and a NMI takes it out of
+            // lockup.
+            if matches!(self.state, State::WaitingForInterrupt | State::Lockup) {
                 self.state = State::Running;
             }
-            if !self.exception_active[irq.number() as usize] {
-                self.exception_entry(irq)?;
-            }
+            self.exception_entry(irq)?;
+        } else if self.state == State::WaitingForInterrupt
+            && self
+                .pending_exception(self.execution_priority_ignoring_primask())
+                .is_some()
+        {
+            // WFI also wakes up on pending exceptions which are only masked by PRIMASK.
+            self.state = State::Running;
         }
 
         // Handle hooks
@@ -932,17 +1436,53 @@ impl Processor {
         }
 
         match self.state {
-            State::Running => {
-                let (ins, effect) = self.execute_next_instruction()?;
-                self.events.push(Event::Instruction { ins });
-                match effect {
-                    Effect::None => {}
-                    Effect::Branch => {}
-                    Effect::Break(i) => self.events.push(Event::Break(i)),
-                    Effect::DebugHint(i) => self.events.push(Event::DebugHint(i)),
-                    Effect::WaitForEvent => self.state = State::WaitingForEvent,
-                    Effect::WaitForInterrupt => self.state = State::WaitingForInterrupt,
+            State::Running => match self.execute_next_instruction() {
+                Ok((ins, effect)) => {
+                    self.events.push(Event::Instruction { ins });
+                    if let Some(event) = self.data_hook_event.take() {
+                        self.events.push(event);
+                    }
+                    let instruction_watchpoint = {
+                        let mut debug = self.debug.borrow_mut();
+                        if debug.is_watching() {
+                            debug.match_instruction(pc)
+                        } else {
+                            None
+                        }
+                    };
+                    if let Some(n) = instruction_watchpoint.or(self.watchpoint_event.take()) {
+                        self.watchpoint_debug_event(n);
+                    }
+                    match effect {
+                        Effect::None => {}
+                        Effect::Branch => {}
+                        Effect::Break(i) => self.events.push(Event::Break(i)),
+                        Effect::DebugHint(i) => self.events.push(Event::DebugHint(i)),
+                        Effect::WaitForEvent => self.sleep(State::WaitingForEvent),
+                        Effect::WaitForInterrupt => self.sleep(State::WaitingForInterrupt),
+                        Effect::SendEvent => self.events.push(Event::SendEvent),
+                    }
+                }
+                // Instruction has been aborted by a synchronous fault. The faulting instruction
+                // address is stacked as the return address.
+                Err(RunError::Fault(irq)) => {
+                    self.data_hook_event = None;
+                    self.watchpoint_event = None;
+                    self.take_fault(irq)?
                 }
+                // Emulation is aborted. Hook and watchpoint events of the aborted instruction must
+                // not be reported by a later step.
+                Err(e) => {
+                    self.data_hook_event = None;
+                    self.watchpoint_event = None;
+                    return Err(e);
+                }
+            },
+            State::Lockup => {
+                // Nothing can be executed until a NMI or a reset. Report lockup again so
+                // `next_event` does not loop forever, and let time pass for the peripherals.
+                self.fast_forward();
+                self.events.push(Event::Lockup);
             }
             State::WaitingForEvent => {
                 if self.registers.event {
@@ -952,30 +1492,206 @@ impl Processor {
                     // otherwise...
                     self.registers.event = false;
                     self.state = State::Running;
+                } else {
+                    self.skip_idle_cycles();
                 }
             }
-            State::WaitingForInterrupt => {}
+            State::WaitingForInterrupt => self.skip_idle_cycles(),
         }
 
         // Handle actions that may come from memory accesses.
-        for action in self.memory_op_actions.iter() {
+        for action in std::mem::take(&mut self.memory_op_actions).iter() {
             match action {
                 MemoryOpAction::Reset => self.events.push(Event::Reset),
                 MemoryOpAction::Irq(irq) => {
                     // A peripheral emitted an interrupt request, save it.
-                    self.interrupt_requests.insert(*irq);
+                    self.set_pending(*irq);
                 }
-                MemoryOpAction::Update(_) => panic!(), // This should be filtered prior
+                MemoryOpAction::CancelIrq(irq) => {
+                    self.interrupt_requests.remove(irq);
+                    self.update_status_registers();
+                }
+                MemoryOpAction::Update(_) => panic!(), // Filtered by MemoryMap::collect_actions
             }
         }
-        self.memory_op_actions.clear();
         self.update_peripherals();
         self.cycles += 1;
         Ok(())
     }
 
+    /// Fetches, decodes and executes the instruction at PC.
+    ///
+    /// Undefined instructions raise a UsageFault. If execution is aborted by a fault, PC, PSR and
+    /// VPR are restored to their value before the instruction so the exception return address is
+    /// the faulting instruction, which is executed again from its first beat.
     fn execute_next_instruction(&mut self) -> Result<(InstructionBox, Effect), RunError> {
+        if self
+            .lo_branch_info
+            .is_some_and(|info| info.end_address == self.pc())
+        {
+            self.take_lo_branch()?;
+        }
+        let pc = self.pc();
+        let psr = self.registers.psr;
+        let vpr = self.registers.vpr;
+        let mut result = self.execute_instruction_at_pc();
+        if let Err(RunError::InstructionUndefined) = result {
+            self.system_control.borrow_mut().cfsr.set_undefinstr(true);
+            result = Err(RunError::Fault(Irq::UsageFault));
+        }
+        if let Err(RunError::Fault(_)) = result {
+            self.set_pc(pc);
+            self.registers.psr = psr;
+            self.registers.vpr = vpr;
+        }
+        result
+    }
+
+    /// Performs the branch recorded in the low-overhead branch information cache, once PC has
+    /// reached the end address of the cache. The instruction at the end address is skipped.
+    fn take_lo_branch(&mut self) -> Result<(), RunError> {
+        let Some(info) = self.lo_branch_info else {
+            return Ok(());
+        };
+        match info.branch {
+            LoBranch::LoopEnd {
+                start,
+                decrement,
+                tail_predicated,
+            } => {
+                if tail_predicated {
+                    self.execute_fp_check()?;
+                }
+                if self.loop_end(decrement, tail_predicated) {
+                    self.set_pc(start);
+                } else {
+                    // Loop is finished, execution continues after the LE instruction.
+                    self.lo_branch_info = None;
+                    self.set_pc(info.end_address + 4);
+                }
+            }
+            LoBranch::BranchFuture {
+                target,
+                link,
+                condition,
+            } => {
+                self.lo_branch_info = None;
+                let address = match condition {
+                    Some((cond, else_address)) if !self.registers.psr.test(cond) => else_address,
+                    _ => target,
+                };
+                if let Some(link) = link {
+                    self.set_lr(link);
+                }
+                self.blx_write_pc(address);
+            }
+        }
+        Ok(())
+    }
+
+    /// Updates the loop iteration count in LR at the end of a low-overhead loop, and returns
+    /// `true` if execution must branch back to the start of the loop.
+    ///
+    /// For tail predicated loops, LR is decremented by the number of vector elements processed
+    /// per iteration, and tail predication is disabled when the loop finishes.
+    pub fn loop_end(&mut self, decrement: bool, tail_predicated: bool) -> bool {
+        if !decrement {
+            return true;
+        }
+        let ltpsize = self.registers.fpscr.ltpsize();
+        let elements = if tail_predicated {
+            1 << (4 - ltpsize)
+        } else {
+            1
+        };
+        let lr = self.lr();
+        if lr > elements {
+            self.set_lr(lr - elements);
+            true
+        } else {
+            if tail_predicated {
+                self.registers.fpscr.set_ltpsize(4);
+            }
+            false
+        }
+    }
+
+    /// Returns the mask of the active bytes of a vector for the given beat of an M-profile
+    /// Vector Extension instruction, with one bit per byte of the beat.
+    ///
+    /// Bytes are disabled by VPT predication when the beat is in a VPT block, and by tail
+    /// predication in low-overhead loops when LR is lower than the number of vector elements.
+    ///
+    /// Corresponds to the mask returned by `GetCurInstrBeat()` in the Arm Architecture Reference
+    /// Manual.
+    pub fn beat_mask(&self, beat: usize) -> u8 {
+        let mut mask = 0xf;
+        let ltpsize = self.registers.fpscr.ltpsize();
+        if ltpsize < 4 {
+            let active_bytes = (self.lr() as u64) << ltpsize;
+            for byte in 0..4 {
+                if (beat * 4 + byte) as u64 >= active_bytes {
+                    mask &= !(1 << byte);
+                }
+            }
+        }
+        if self.registers.vpr.mask(beat) != 0 {
+            mask &= self.registers.vpr.beat_p0(beat);
+        }
+        mask
+    }
+
+    /// Updates the VPT block state at the end of a beat of an M-profile Vector Extension
+    /// instruction.
+    ///
+    /// The predicate flags of the beat are inverted for the next instruction of the block when
+    /// the most significant bit of the mask is set. The mask, which is shared by two beats, is
+    /// shifted after the second one.
+    ///
+    /// Corresponds to `VPTAdvance()` in the Arm Architecture Reference Manual.
+    pub fn vpt_advance(&mut self, beat: usize) {
+        let vpr = &mut self.registers.vpr;
+        let mask = vpr.mask(beat);
+        if mask == 0 {
+            return;
+        }
+        if mask & 0b1000 != 0 {
+            vpr.set_beat_p0(beat, !vpr.beat_p0(beat));
+        }
+        if beat % 2 == 1 {
+            vpr.set_mask(beat, (mask << 1) & 0xf);
+        }
+    }
+
+    /// Executes the four beats of an M-profile Vector Extension instruction.
+    ///
+    /// Each beat processes 32 bits of the vectors. `f` is called for each beat with the beat
+    /// number and the mask of its active bytes (see [Processor::beat_mask]), and the VPT block
+    /// state is updated after each beat. Beats are never interleaved with other instructions,
+    /// so an instruction always completes its four beats.
+    pub fn execute_beats<F>(&mut self, mut f: F) -> Result<(), RunError>
+    where
+        F: FnMut(&mut Self, usize, u8) -> Result<(), RunError>,
+    {
+        for beat in 0..4 {
+            let mask = self.beat_mask(beat);
+            f(self, beat, mask)?;
+            self.vpt_advance(beat);
+        }
+        Ok(())
+    }
+
+    fn execute_instruction_at_pc(&mut self) -> Result<(InstructionBox, Effect), RunError> {
         let (ins, size) = self.decode_instruction(self.pc())?;
+        if self.registers.psr.b() {
+            // Previous instruction was an indirect branch with branch target identification
+            // enabled, so this instruction must be a BTI clearing instruction.
+            self.registers.psr.set_b(false);
+            if !ins.is_branch_target() {
+                self.system_control.borrow_mut().cfsr.set_invstate(true);
+                return Err(RunError::Fault(Irq::UsageFault));
+            }
+        }
         // PC is always 4 bytes ahead of currently executed instruction, so we increment PC before
         // applying the effect of the instruction, and we go back 2 bytes if this is a 16-bit
         // instruction.
@@ -994,7 +1710,13 @@ impl Processor {
         self.registers.psr.set_it_state(it_state);
 
         let effect = if self.registers.psr.test(condition) {
-            ins.execute(self)?
+            self.stack_limit_violation = false;
+            let effect = ins.execute(self)?;
+            if self.stack_limit_violation {
+                self.system_control.borrow_mut().cfsr.set_stkof(true);
+                return Err(RunError::Fault(Irq::UsageFault));
+            }
+            effect
         } else {
             Effect::None
         };
@@ -1006,17 +1728,282 @@ impl Processor {
         Ok((ins, effect))
     }
 
-    /// Call `update` on memory mapping which requested an update during a previous operation.
+    /// Call `update` on memory mapping which requested an update during a previous operation, and
+    /// on memory mappings which do not schedule their updates.
     pub fn update_peripherals(&mut self) {
-        let mut env = Env::new(self.cycles, self.is_privileged());
+        let template = self.env();
         for mapping in self.memory_mappings.0.iter_mut() {
-            mapping.iface.borrow_mut().update(&mut env);
+            if mapping.every_cycle || mapping.next_update.is_some_and(|c| c <= self.cycles) {
+                mapping.next_update = None;
+                let mut env = Env {
+                    actions: Vec::new(),
+                    ..template
+                };
+                mapping.iface.borrow_mut().update(&mut env);
+                mapping.collect_actions(env, &mut self.memory_op_actions);
+            }
+        }
+    }
+
+    /// Called on each step while the processor sleeps. Nothing can wake up the processor until
+    /// the next peripheral update or the processing of pending memory actions, so cycles are
+    /// fast-forwarded up to the next scheduled update. Sleep cycles are counted by the DWT.
+    fn skip_idle_cycles(&mut self) {
+        let start = self.cycles;
+        if !self.fast_forward() {
+            self.events.push(Event::Idle);
+        }
+        // Count the skipped cycles and the current one.
+        self.debug
+            .borrow_mut()
+            .count_sleep_cycles(self.cycles - start + 1);
+    }
+
+    /// Advances the cycle counter up to the next scheduled peripheral update, when the processor
+    /// cannot make progress by itself. Returns `false` if nothing is scheduled, in which case
+    /// only a request from outside the processor can change its state.
+    ///
+    /// No cycle is skipped when a peripheral must be updated at every cycle.
+    fn fast_forward(&mut self) -> bool {
+        if !self.memory_op_actions.is_empty() {
+            // Actions of the current step are handled right after.
+            return true;
+        }
+        if self.memory_mappings.0.iter().any(|m| m.every_cycle) {
+            return true;
+        }
+        match self
+            .memory_mappings
+            .0
+            .iter()
+            .filter_map(|m| m.next_update)
+            .min()
+        {
+            Some(next) => {
+                self.cycles = self.cycles.max(next);
+                true
+            }
+            None => false,
         }
-        self.memory_op_actions.extend(env.actions);
     }
 
+    /// Sets an exception pending. It will be taken once its priority is high enough to preempt
+    /// the current execution priority.
+    ///
+    /// Panics if the exception number exceeds the number of exceptions supported by the
+    /// processor configuration.
     pub fn request_interrupt(&mut self, irq: Irq) {
-        self.interrupt_requests.insert(irq);
+        self.set_pending(irq);
+    }
+
+    /// Resets the processor. This is the only way to leave lockup state, apart from taking a NMI.
+    ///
+    /// Core registers, exception states, pending interrupt requests and the local exclusive
+    /// monitor are reset. The main stack pointer and PC are then loaded from the vector table
+    /// pointed by VTOR. Memory mapped peripherals, including the System Control Space registers,
+    /// keep their state.
+    pub fn reset(&mut self) -> Result<(), RunError> {
+        self.registers = CoreRegisters::new();
+        if self.version == ArmVersion::V81M {
+            self.registers.fpscr.set_ltpsize(4);
+        }
+        self.state = State::Running;
+        self.exception_active.iter_mut().for_each(|a| *a = false);
+        self.interrupt_requests.clear();
+        self.lo_branch_info = None;
+        self.data_hook_event = None;
+        self.watchpoint_event = None;
+        self.stack_limit_violation = false;
+        self.clear_exclusive_local();
+        self.execution_priority = self.compute_execution_priority();
+        self.update_status_registers();
+        let vtor = self.system_control.borrow().vtor.offset();
+        let sp = self.read_u32le_iface(vtor)?;
+        let reset_vector = self.read_u32le_iface(vtor + 4)?;
+        *self.registers.sp_mut() = sp & 0xfffffffc;
+        self.registers.lr = 0xffffffff;
+        self.registers.psr.set_t(reset_vector & 1 != 0);
+        self.set_pc(reset_vector & 0xfffffffe);
+        Ok(())
+    }
+
+    /// Sets the pending state of an exception. When SCR.SEVONPEND is set, an exception becoming
+    /// pending is a wakeup event for WFE, even if it is disabled or masked.
+    fn set_pending(&mut self, irq: Irq) {
+        self.check_exception_number(irq);
+        if self.interrupt_requests.insert(irq) && self.system_control.borrow().scr.sevonpend() {
+            self.registers.event = true;
+        }
+        self.update_status_registers();
+    }
+
+    /// Sets the event register, waking up the processor if it is waiting in WFE.
+    ///
+    /// This is the event signaled by the SEV instruction of other processors in multi-processor
+    /// systems (see [Event::SendEvent]), or by an external event input.
+    pub fn signal_event(&mut self) {
+        self.registers.event = true;
+    }
+
+    /// Enters sleep mode, until an event or an interrupt wakes up the processor depending on
+    /// `state`. [Event::DeepSleep] is emitted if SCR.SLEEPDEEP is set.
+    fn sleep(&mut self, state: State) {
+        self.state = state;
+        if self.system_control.borrow().scr.sleepdeep() {
+            self.events.push(Event::DeepSleep);
+        }
+    }
+
+    /// Updates the fields of ICSR and NVIC registers giving the active and pending exceptions.
+    ///
+    /// Corresponds to `SCS_UpdateStatusRegs()` in the Arm Architecture Reference Manual.
+    fn update_status_registers(&self) {
+        let active = self.registers.psr.exception_number();
+        let rettobase = self
+            .exception_active
+            .iter()
+            .enumerate()
+            .all(|(number, is_active)| !is_active || number == active as usize);
+        let pending = self
+            .pending_exception(i16::MAX)
+            .map_or(0, |irq| irq.number());
+        let mut system_control = self.system_control.borrow_mut();
+        system_control.icsr.update(
+            active,
+            pending,
+            rettobase,
+            self.interrupt_requests.iter().copied(),
+        );
+        system_control.update_nvic_status(
+            self.interrupt_requests.iter().copied(),
+            self.exception_active
+                .iter()
+                .enumerate()
+                .filter(|(_, active)| **active)
+                .filter_map(|(number, _)| Irq::try_from(number as u16).ok()),
+        );
+    }
+
+    /// Panics if `irq` exception number is not supported by the processor configuration.
+    fn check_exception_number(&self, irq: Irq) {
+        let max_num = self.exception_active.len();
+        let num = irq.number();
+        assert!(
+            (num as usize) < max_num,
+            "Exception number too high: got {}, max is {}",
+            num,
+            max_num - 1
+        );
+    }
+
+    /// Returns the priority of an exception.
+    ///
+    /// Reset, NMI and HardFault have fixed negative priorities, other exceptions priorities are
+    /// configured in SHPR and NVIC_IPR registers.
+    ///
+    /// Corresponds to `ExceptionPriority()` in the Arm Architecture Reference Manual, without the
+    /// subpriority removal which is done by [Self::group_priority].
+    fn exception_priority(&self, irq: Irq) -> i16 {
+        match irq {
+            Irq::Reset => -3,
+            Irq::Nmi => -2,
+            Irq::HardFault => -1,
+            _ => self.system_control.borrow().exception_priority(irq) as i16,
+        }
+    }
+
+    /// Returns the group priority of a `priority` value, which is the part of the priority used
+    /// for preemption. The subpriority bits are defined by AIRCR.PRIGROUP.
+    fn group_priority(&self, priority: i16) -> i16 {
+        if priority < 0 {
+            return priority;
+        }
+        let subgroup_mask = (2 << self.system_control.borrow().aircr.prigroup()) - 1;
+        priority & !subgroup_mask
+    }
+
+    /// Returns `true` if an exception is enabled, i.e. if it can be taken when pending.
+    fn is_exception_enabled(&self, irq: Irq) -> bool {
+        match irq {
+            Irq::External(n) => self.system_control.borrow().is_interrupt_enabled(n),
+            _ => true,
+        }
+    }
+
+    /// Returns the execution priority resulting from active exceptions, FAULTMASK and BASEPRI
+    /// registers, but without the boosting of PRIMASK.
+    ///
+    /// When no exception is active and no mask is set, this is 256, one more than the lowest
+    /// configurable priority.
+    fn execution_priority_ignoring_primask(&self) -> i16 {
+        let mut priority = self
+            .exception_active
+            .iter()
+            .enumerate()
+            .filter(|(_, active)| **active)
+            .filter_map(|(number, _)| Irq::try_from(number as u16).ok())
+            .map(|irq| self.group_priority(self.exception_priority(irq)))
+            .fold(256, i16::min);
+        for basepri in [self.registers.basepri, self.registers.banked.basepri] {
+            if basepri != 0 {
+                priority = priority.min(self.group_priority(basepri as i16));
+            }
+        }
+        let (secure, non_secure) = self.banked_pm(|r| r.faultmask, |b| b.faultmask);
+        if secure {
+            priority = priority.min(-1);
+        } else if non_secure {
+            // Non-secure FAULTMASK boosts to -1 only when BusFault, HardFault and NMI target the
+            // Non-secure state.
+            let bfhfnmins = self.system_control.borrow().aircr.bfhfnmins();
+            priority = priority.min(if bfhfnmins { -1 } else { 0 });
+        }
+        priority
+    }
+
+    /// Returns the PM bit values of a Secure and Non-secure banked mask register, given
+    /// accessors to the register in the current and banked register sets. Without the Security
+    /// Extension, the Non-secure value is always `false`.
+    fn banked_pm(
+        &self,
+        current: impl Fn(&CoreRegisters) -> MaskRegister,
+        banked: impl Fn(&BankedRegisters) -> MaskRegister,
+    ) -> (bool, bool) {
+        let current = current(&self.registers).pm();
+        let banked = banked(&self.registers.banked).pm();
+        if self.is_secure() {
+            (current, banked)
+        } else {
+            (banked, current)
+        }
+    }
+
+    /// Computes the current execution priority.
+    ///
+    /// Corresponds to `ExecutionPriority()` in the Arm Architecture Reference Manual.
+    fn compute_execution_priority(&self) -> i16 {
+        let priority = self.execution_priority_ignoring_primask();
+        let (secure, non_secure) = self.banked_pm(|r| r.primask, |b| b.primask);
+        if secure || non_secure {
+            priority.min(0)
+        } else {
+            priority
+        }
+    }
+
+    /// Returns the pending exception with the highest priority if it is enabled and can preempt
+    /// the given `execution_priority`.
+    ///
+    /// When several pending exceptions have the same priority, the one with the lowest exception
+    /// number is selected.
+    fn pending_exception(&self, execution_priority: i16) -> Option<Irq> {
+        let (priority, irq) = self
+            .interrupt_requests
+            .iter()
+            .filter(|irq| self.is_exception_enabled(**irq))
+            .map(|irq| (self.exception_priority(*irq), *irq))
+            .min()?;
+        (self.group_priority(priority) < execution_priority).then_some(irq)
     }
 
     /// Enters exception.
@@ -1025,6 +2012,10 @@ impl Processor {
     /// Manual.
     fn exception_entry(&mut self, number: Irq) -> Result<(), RunError> {
         self.push_stack()?;
+        if self.state == State::Lockup {
+            // A fault raised during stacking could not be handled.
+            return Ok(());
+        }
         self.exception_taken(number)?;
         Ok(())
     }
@@ -1035,62 +2026,175 @@ impl Processor {
     /// Manual.
     fn exception_return(&mut self, exc_return: u32) -> Result<(), RunError> {
         assert_eq!(self.registers.mode, Mode::Handler);
-        ////unpredictable(address & 0xf0000000 != 0xf0000000)?;
+        self.lo_branch_info = None;
+        // Bit 4 selects the extended frame when the floating-point extension is implemented.
+        let mut ones = if self.fpu.is_some() {
+            0x0fffffe0
+        } else {
+            0x0ffffff0
+        };
+        // With the Security Extension, bit 6 (S) selects the returned security state, bit 5
+        // (DCRS) indicates the callee-saved registers have been stacked and bit 0 (ES) is the
+        // security state of the returning exception.
+        let mut mode_bits = exc_return & 0xf;
+        if self.security_extension {
+            ones &= !0x60;
+            mode_bits |= 1;
+        }
+        if exc_return & ones != ones {
+            return Err(RunError::Unpredictable);
+        }
         let number = self.registers.psr.exception_number();
-        let nested_activation = false; // TODO
-        if !self.exception_active[number as usize] {
+        if self.security_extension && !self.is_secure() && exc_return.bit(0) {
+            // Non-secure handlers cannot return as Secure exceptions.
             self.deactivate(number);
-            todo!();
-            //return Ok(());
-        }
-        let frame_ptr = match exc_return & 0xf {
-            0b0001 => {
-                // Return to Handler
-                self.registers.mode = Mode::Handler;
-                self.registers.control.set_spsel(false);
-                self.registers.msp
-            }
-            0b1001 => {
-                // Returning to Thread using Main stack
-                if nested_activation && !self.system_control.borrow().ccr.nonbasethrdena() {
-                    todo!()
-                } else {
-                    self.registers.mode = Mode::Thread;
-                    self.registers.control.set_spsel(false);
-                    self.registers.msp
-                }
-            }
-            0b1101 => {
-                // Returning to Thread using Process stack
-                if nested_activation && !self.system_control.borrow().ccr.nonbasethrdena() {
-                    todo!()
-                } else {
-                    self.registers.mode = Mode::Thread;
-                    self.registers.control.set_spsel(true);
-                    self.registers.psp
-                }
-            }
-            _ => {
-                self.deactivate(number);
-                todo!()
-            }
+            self.sau.borrow_mut().sfsr.set_inver(true);
+            return self.take_derived_fault(Irq::SecureFault, exc_return);
+        }
+        let nested_activation = self.exception_active.iter().filter(|a| **a).count();
+        let nonbasethrdena = self.system_control.borrow().ccr.nonbasethrdena();
+        // Returned mode and stack selection, or `None` if integrity checks fail: returning from an
+        // inactive handler, returning to Thread mode while other exceptions are active, or
+        // illegal EXC_RETURN value.
+        let target = match mode_bits {
+            _ if !self.exception_active[number as usize] => None,
+            0b0001 => Some((Mode::Handler, false)),
+            0b1001 | 0b1101 if nested_activation != 1 && !nonbasethrdena => None,
+            0b1001 => Some((Mode::Thread, false)),
+            0b1101 => Some((Mode::Thread, true)),
+            _ => None,
+        };
+        let Some((mode, spsel)) = target else {
+            self.deactivate(number);
+            return self.invalid_exception_return(exc_return);
         };
         self.deactivate(number);
-        self.pop_stack(frame_ptr, exc_return)?;
+        let to_secure = exc_return.bit(6);
+        if self.security_extension {
+            self.registers.set_security_state(if to_secure {
+                SecurityState::Secure
+            } else {
+                SecurityState::NonSecure
+            });
+        }
+        self.registers.mode = mode;
+        self.registers.control.set_spsel(spsel);
+
+        // Tail-chaining: if a pending exception can preempt the context we are returning to, it
+        // is taken immediately and the stacked frame is kept for it.
+        self.execution_priority = self.compute_execution_priority();
+        if let Some(irq) = self.pending_exception(self.execution_priority) {
+            self.interrupt_requests.remove(&irq);
+            self.set_lr(0xf0000000 | exc_return);
+            return self.exception_taken(irq);
+        }
 
-        if self.registers.mode == Mode::Handler && self.registers.psr.ipsr() == 0 {
-            todo!();
+        // Callee-saved registers are always unstacked when returning from a Non-secure exception
+        // to Secure code, so the integrity signature prevents forged returns.
+        if self.security_extension && to_secure && (!exc_return.bit(5) || !exc_return.bit(0)) {
+            match self.pop_callee_stack(exc_return) {
+                Err(RunError::Fault(irq)) => return self.take_derived_fault(irq, exc_return),
+                result => result?,
+            }
+        }
+
+        let frame_ptr = self.sp();
+        match self.pop_stack(frame_ptr, exc_return) {
+            Err(RunError::Fault(irq)) => return self.take_derived_fault(irq, exc_return),
+            result => result?,
         }
 
-        if self.registers.mode == Mode::Thread && self.registers.psr.ipsr() != 0 {
-            todo!();
+        let ipsr = self.registers.psr.ipsr();
+        if (mode == Mode::Handler && ipsr == 0) || (mode == Mode::Thread && ipsr != 0) {
+            // Returned IPSR is inconsistent with returned mode. Frame is pushed back to negate
+            // the unstacking.
+            self.push_stack()?;
+            return self.invalid_exception_return(exc_return);
         }
 
         // TODO ClearExclusiveLocal()
         self.registers.event = true;
+        self.execution_priority = self.compute_execution_priority();
+        self.update_status_registers();
+        // With SCR.SLEEPONEXIT set, the processor enters sleep when returning to Thread mode
+        // instead of executing Thread mode code.
+        if mode == Mode::Thread && self.system_control.borrow().scr.sleeponexit() {
+            self.sleep(State::WaitingForInterrupt);
+        }
         Ok(())
     }
 
+    /// Raises an INVPC UsageFault when an exception return fails integrity checks.
+    fn invalid_exception_return(&mut self, exc_return: u32) -> Result<(), RunError> {
+        self.system_control.borrow_mut().cfsr.set_invpc(true);
+        self.take_derived_fault(Irq::UsageFault, exc_return)
+    }
+
+    /// Takes a fault raised during exception return. The exception stack frame is left in place
+    /// and LR keeps the EXC_RETURN value, so the fault handler returns where the failed exception
+    /// return was going.
+    fn take_derived_fault(&mut self, irq: Irq, exc_return: u32) -> Result<(), RunError> {
+        self.set_lr(0xf0000000 | exc_return);
+        self.execution_priority = self.compute_execution_priority();
+        match self.escalate_fault(irq) {
+            Some(irq) => self.exception_taken(irq),
+            None => {
+                self.lockup();
+                Ok(())
+            }
+        }
+    }
+
+    /// Takes the exception for a synchronous fault raised by an instruction.
+    fn take_fault(&mut self, irq: Irq) -> Result<(), RunError> {
+        match self.escalate_fault(irq) {
+            Some(irq) => self.exception_entry(irq),
+            None => {
+                self.lockup();
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns the exception to be taken for a synchronous fault.
+    ///
+    /// Configurable faults (MemManage, BusFault and UsageFault) escalate to HardFault if they are
+    /// disabled in SHCSR, or if their priority is not high enough to preempt current execution.
+    /// If HardFault cannot be taken either, `None` is returned and the processor must enter
+    /// lockup state.
+    fn escalate_fault(&mut self, irq: Irq) -> Option<Irq> {
+        let enabled = {
+            let shcsr = &self.system_control.borrow().shcsr;
+            match irq {
+                _ if self.version == ArmVersion::V6M => false,
+                Irq::MemManage => shcsr.memfaultena(),
+                Irq::BusFault => shcsr.busfaultena(),
+                Irq::UsageFault => shcsr.usgfaultena(),
+                Irq::SecureFault => shcsr.securefaultena(),
+                _ => true,
+            }
+        };
+        if enabled && self.group_priority(self.exception_priority(irq)) < self.execution_priority {
+            return Some(irq);
+        }
+        if self.execution_priority <= self.exception_priority(Irq::HardFault) {
+            return None;
+        }
+        if irq != Irq::HardFault && self.version != ArmVersion::V6M {
+            self.system_control.borrow_mut().hfsr.set_forced(true);
+        }
+        Some(Irq::HardFault)
+    }
+
+    /// Enters lockup state.
+    ///
+    /// Execution stops, and PC is set to the architected lockup address `0xeffffffe`.
+    fn lockup(&mut self) {
+        self.state = State::Lockup;
+        self.set_pc(0xeffffffe);
+        self.events.push(Event::Lockup);
+    }
+
     /// Deactivates an exception
     fn deactivate(&mut self, number: u16) {
         self.exception_active[number as usize] = false;
@@ -1104,26 +2208,53 @@ impl Processor {
     ///
     /// Corresponds to `PushStack()` in ARM architecture reference manual.
     fn push_stack(&mut self) -> Result<(), RunError> {
-        let frame_size = 0x20;
-        let force_align = self.system_control.borrow().ccr.stkalign();
+        // An extended frame, including floating-point context, is pushed when it is active.
+        let extended = self.fpu.is_some() && self.registers.control.fpca();
+        let (frame_size, force_align) = if extended {
+            (0x68, true)
+        } else {
+            (0x20, self.system_control.borrow().ccr.stkalign())
+        };
         let sp_mask = !((force_align as u32) << 2);
         let frame_ptr_align = self.sp().bit(2) && force_align;
         let frame_ptr = (self.sp() - frame_size) & sp_mask;
-        self.set_sp(frame_ptr);
+        let limit = self.stacking_set_sp(frame_ptr);
 
         let return_address = self.pc();
-        self.write_u32le_iface(frame_ptr, self.registers.r0)?;
-        self.write_u32le_iface(frame_ptr + 0x04, self.registers.r1)?;
-        self.write_u32le_iface(frame_ptr + 0x08, self.registers.r2)?;
-        self.write_u32le_iface(frame_ptr + 0x0c, self.registers.r3)?;
-        self.write_u32le_iface(frame_ptr + 0x10, self.registers.r12)?;
-        self.write_u32le_iface(frame_ptr + 0x14, self.registers.lr)?;
-        self.write_u32le_iface(frame_ptr + 0x18, return_address)?;
         let mut xpsr = self.registers.psr.get();
         xpsr.set_bit(9, frame_ptr_align);
-        self.write_u32le_iface(frame_ptr + 0x1c, xpsr)?;
+        let mut frame = vec![
+            self.registers.r0,
+            self.registers.r1,
+            self.registers.r2,
+            self.registers.r3,
+            self.registers.r12,
+            self.registers.lr,
+            return_address,
+            xpsr,
+        ];
+        if extended {
+            if self.fp_control.borrow().fpccr.lspen() {
+                // Space is reserved, floating-point registers will be saved only if the handler
+                // uses them.
+                self.update_fpccr(frame_ptr + 0x20);
+            } else {
+                frame.extend_from_slice(&self.registers.s[..16]);
+                frame.push(self.registers.fpscr.get());
+                // VPR is stacked in the word following FPSCR, which is reserved otherwise.
+                if self.mve.is_some() {
+                    frame.push(self.registers.vpr.get());
+                }
+            }
+        }
+        self.stack_words(frame_ptr, limit, &frame)?;
+        if self.version == ArmVersion::V81M && !self.fp_control.borrow().fpccr.lspact() {
+            // Tail predication does not apply to the handler. With lazy state preservation, this
+            // is deferred until the interrupted FPSCR has been saved.
+            self.registers.fpscr.set_ltpsize(4);
+        }
 
-        let lr = match self.registers.mode {
+        let mut lr = match self.registers.mode {
             Mode::Handler => 0xfffffff1,
             Mode::Thread => {
                 if !self.registers.control.spsel() {
@@ -1133,24 +2264,185 @@ impl Processor {
                 }
             }
         };
+        // Bit 4 is cleared to indicate an extended frame.
+        if extended {
+            lr &= !0x10;
+        }
+        // Bit 6 is cleared when the frame has been pushed on a Non-secure stack. Bit 0 is updated
+        // by Self::exception_taken with the security state of the exception.
+        if !self.is_secure() {
+            lr &= !0x40;
+        }
         self.set_lr(lr);
 
         Ok(())
     }
 
+    /// Moves the current stack pointer down to `frame_ptr` before stacking a frame during
+    /// exception entry, and returns the stack limit below which the frame must not be written.
+    ///
+    /// If `frame_ptr` violates the stack limit, the stack pointer is set to the limit instead,
+    /// CFSR.STKOF is set and a derived UsageFault exception is pended. See
+    /// [Self::stacking_fault].
+    fn stacking_set_sp(&mut self, frame_ptr: u32) -> u32 {
+        let sp = self.registers.translate_sp();
+        if self.violates_sp_limit(sp, frame_ptr) {
+            let limit = self.registers.sp_limit(sp);
+            *self.registers.sp_mut() = limit;
+            self.system_control.borrow_mut().cfsr.set_stkof(true);
+            self.stacking_fault(Irq::UsageFault);
+            limit
+        } else {
+            *self.registers.sp_mut() = frame_ptr;
+            0
+        }
+    }
+
+    /// Writes `words` to the stack at `frame_ptr`, during exception entry. Words below `limit`
+    /// are not written, because of a stack limit violation.
+    ///
+    /// Stacking is a privileged access checked by the MPU. On violation, the remaining words are
+    /// not written and a derived MemManage exception is pended. Same for bus errors, when bus
+    /// faults are enabled, with a derived BusFault exception. See [Self::stacking_fault].
+    fn stack_words(&mut self, frame_ptr: u32, limit: u32, words: &[u32]) -> Result<(), RunError> {
+        for (i, value) in words.iter().enumerate() {
+            let address = frame_ptr + 4 * i as u32;
+            if address < limit {
+                continue;
+            }
+            if !self.mpu_permits(address, true, true, false) {
+                self.system_control.borrow_mut().cfsr.set_mstkerr(true);
+                self.stacking_fault(Irq::MemManage);
+                break;
+            }
+            match self.write_u32le_iface(address, *value) {
+                Ok(()) => {}
+                Err(_) if self.bus_faults => {
+                    self.system_control.borrow_mut().cfsr.set_stkerr(true);
+                    self.stacking_fault(Irq::BusFault);
+                    break;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Pends a derived fault raised while stacking during exception entry. Like synchronous
+    /// faults, it escalates to HardFault when disabled or when it cannot preempt the interrupted
+    /// execution, and the processor enters lockup state if HardFault cannot be taken either.
+    fn stacking_fault(&mut self, irq: Irq) {
+        match self.escalate_fault(irq) {
+            Some(irq) => self.request_interrupt(irq),
+            None => self.lockup(),
+        }
+    }
+
+    /// Reads `word_count` words from the stack at `frame_ptr`, during exception return.
+    ///
+    /// Unstacking is a privileged access checked by the MPU over the `frame_size` bytes of the
+    /// frame. On violation, a MemManage fault is returned. Same for bus errors, when bus faults
+    /// are enabled.
+    fn unstack_words(
+        &mut self,
+        frame_ptr: u32,
+        frame_size: u32,
+        word_count: usize,
+    ) -> Result<Vec<u32>, RunError> {
+        if (0..frame_size)
+            .step_by(4)
+            .any(|offset| !self.mpu_permits(frame_ptr + offset, true, false, false))
+        {
+            self.system_control.borrow_mut().cfsr.set_munstker(true);
+            return Err(RunError::Fault(Irq::MemManage));
+        }
+        let mut frame = vec![0; word_count];
+        for (i, word) in frame.iter_mut().enumerate() {
+            *word = match self.read_u32le_iface(frame_ptr + 4 * i as u32) {
+                Ok(value) => value,
+                Err(_) if self.bus_faults => {
+                    self.system_control.borrow_mut().cfsr.set_unstkerr(true);
+                    return Err(RunError::Fault(Irq::BusFault));
+                }
+                Err(e) => return Err(e),
+            };
+        }
+        Ok(frame)
+    }
+
+    /// Integrity signature pushed below the callee-saved registers. Bit 0 is the FType bit of the
+    /// EXC_RETURN value.
+    const INTEGRITY_SIGNATURE: u32 = 0xfefa125a;
+
+    /// Pushes the integrity signature and the callee-saved registers R4-R11 on the current stack,
+    /// when a Non-secure exception preempts Secure code.
+    ///
+    /// Corresponds to `PushCalleeStack()` in the Arm Architecture Reference Manual.
+    fn push_callee_stack(&mut self, exc_return: u32) -> Result<(), RunError> {
+        let frame_ptr = self.sp() - 0x28;
+        let r = &self.registers;
+        let frame = [
+            Self::INTEGRITY_SIGNATURE | exc_return.bit(4) as u32,
+            0,
+            r.r4,
+            r.r5,
+            r.r6,
+            r.r7,
+            r.r8,
+            r.r9,
+            r.r10,
+            r.r11,
+        ];
+        let limit = self.stacking_set_sp(frame_ptr);
+        self.stack_words(frame_ptr, limit, &frame)?;
+        Ok(())
+    }
+
+    /// Verifies the integrity signature and restores the callee-saved registers R4-R11 pushed by
+    /// [Self::push_callee_stack]. A signature mismatch sets SFSR.INVIS and returns a SecureFault.
+    ///
+    /// Corresponds to `PopCalleeStack()` in the Arm Architecture Reference Manual.
+    fn pop_callee_stack(&mut self, exc_return: u32) -> Result<(), RunError> {
+        let frame_ptr = self.sp();
+        let frame = self.unstack_words(frame_ptr, 0x28, 10)?;
+        if frame[0] != Self::INTEGRITY_SIGNATURE | exc_return.bit(4) as u32 {
+            self.sau.borrow_mut().sfsr.set_invis(true);
+            return Err(RunError::Fault(Irq::SecureFault));
+        }
+        let r = &mut self.registers;
+        [r.r4, r.r5, r.r6, r.r7, r.r8, r.r9, r.r10, r.r11] = frame[2..].try_into().unwrap();
+        *self.registers.sp_mut() = frame_ptr + 0x28;
+        Ok(())
+    }
+
     /// On exception return, restore some registers from the stack.
     ///
     /// This corresponds to `PopStack()` from ARM architecture reference manual.
     fn pop_stack(&mut self, frame_ptr: u32, exc_return: u32) -> Result<(), RunError> {
-        let frame_size = 0x20;
-        let force_align = self.system_control.borrow().ccr.stkalign();
-        self.registers.r0 = self.read_u32le_iface(frame_ptr)?;
-        self.registers.r1 = self.read_u32le_iface(frame_ptr + 0x04)?;
-        self.registers.r2 = self.read_u32le_iface(frame_ptr + 0x08)?;
-        self.registers.r3 = self.read_u32le_iface(frame_ptr + 0x0c)?;
-        self.registers.r12 = self.read_u32le_iface(frame_ptr + 0x10)?;
-        self.registers.lr = self.read_u32le_iface(frame_ptr + 0x14)?;
-        let mut pc = self.read_u32le_iface(frame_ptr + 0x18)?;
+        let extended = self.fpu.is_some() && !exc_return.bit(4);
+        let (frame_size, force_align) = if extended {
+            (0x68, true)
+        } else {
+            (0x20, self.system_control.borrow().ccr.stkalign())
+        };
+        // If lazy state preservation is still active, the floating-point registers have not
+        // been modified by the handler and don't need to be restored.
+        let lspact = self.fp_control.borrow().fpccr.lspact();
+        let word_count = match (extended && !lspact, self.mve.is_some()) {
+            (true, true) => 26,
+            (true, false) => 25,
+            (false, _) => 8,
+        };
+        let frame = self.unstack_words(frame_ptr, frame_size, word_count)?;
+        let [r0, r1, r2, r3, r12, lr, mut pc, psr] = frame[..8] else {
+            unreachable!()
+        };
+        self.registers.r0 = r0;
+        self.registers.r1 = r1;
+        self.registers.r2 = r2;
+        self.registers.r3 = r3;
+        self.registers.r12 = r12;
+        self.registers.lr = lr;
         // PC should be halfword aligned, otherwise execution is unpredictable according to the
         // specification. However, some implementations may not respect this and it may still work
         // on hardware, so we have the option `tolerate_pop_stack_unaligned_pc` to tolerate this if
@@ -1163,37 +2455,130 @@ impl Processor {
         };
         self.registers.pc = pc;
 
-        let psr = self.read_u32le_iface(frame_ptr + 0x1c)?;
-        let sp_mask = ((psr.bit(9) && force_align) as u32) << 2;
-        self.registers.psr.set(psr); // Note: this does not copy bit 9
-
-        match exc_return & 0xf {
-            0b0001 | 0b1001 | 0b1101 => {
-                let new_sp = (self.sp() + frame_size) | sp_mask;
-                self.set_sp(new_sp);
+        if extended {
+            if lspact {
+                self.fp_control.borrow_mut().fpccr.set_lspact(false);
+            } else {
+                self.registers.s[..16].copy_from_slice(&frame[8..24]);
+                self.registers.fpscr.set(frame[24]);
+                if self.version == ArmVersion::V81M {
+                    // Resume the interrupted tail predicated loop, if any.
+                    let ltpsize = (frame[24] >> 16 & 7).min(4);
+                    self.registers.fpscr.set_ltpsize(ltpsize as u8);
+                }
+                if self.mve.is_some() {
+                    self.registers.vpr.set(frame[25]);
+                }
             }
-            _ => {}
         }
+        if self.fpu.is_some() {
+            self.registers.control.set_fpca(extended);
+        }
+
+        let sp_mask = ((psr.bit(9) && force_align) as u32) << 2;
+        // APSR.GE flags only exist with the DSP extension.
+        let psr = if self.dsp_extension() {
+            psr
+        } else {
+            psr & 0xfff0ffff
+        };
+        self.registers.psr.set(psr); // Note: this does not copy bit 9
 
-        self.registers.psr.set(psr & 0xfff0ffff); // TODO remove mask if FP extension
+        // EXC_RETURN mode bits have been validated by the caller.
+        let new_sp = (frame_ptr + frame_size) | sp_mask;
+        *self.registers.sp_mut() = new_sp;
         Ok(())
     }
 
+    /// Returns `true` if exception `irq` is taken to the Secure state.
+    ///
+    /// External interrupts target the state configured in NVIC_ITNS, and BusFault, HardFault and
+    /// NMI target the Non-secure state when AIRCR.BFHFNMINS is set. Other exceptions are banked
+    /// between security states, but only one instance of them is implemented which targets the
+    /// current security state.
+    fn exception_targets_secure(&self, irq: Irq) -> bool {
+        if !self.security_extension {
+            return true;
+        }
+        let system_control = self.system_control.borrow();
+        match irq {
+            Irq::External(n) => !system_control.is_interrupt_non_secure(n),
+            Irq::Nmi | Irq::HardFault | Irq::BusFault => !system_control.aircr.bfhfnmins(),
+            Irq::Reset | Irq::SecureFault => true,
+            _ => self.is_secure(),
+        }
+    }
+
     fn exception_taken(&mut self, number: Irq) -> Result<(), RunError> {
-        let vtor = self.system_control.borrow().vtor.offset();
+        self.lo_branch_info = None;
+        let secure = self.exception_targets_secure(number);
+        if self.security_extension {
+            let mut exc_return = self.lr();
+            if !secure && exc_return.bit(6) {
+                // Secure code is preempted by a Non-secure exception: callee-saved registers are
+                // pushed if not done already, and all registers are cleared so no Secure
+                // information leaks to the handler.
+                if exc_return.bit(5) {
+                    self.push_callee_stack(exc_return)?;
+                    if self.state == State::Lockup {
+                        return Ok(());
+                    }
+                    exc_return.set_bit(5, false);
+                }
+                let r = &mut self.registers;
+                [r.r0, r.r1, r.r2, r.r3, r.r4, r.r5, r.r6] = [0; 7];
+                [r.r7, r.r8, r.r9, r.r10, r.r11, r.r12] = [0; 6];
+            }
+            exc_return.set_bit(0, secure);
+            self.set_lr(exc_return);
+            self.registers.set_security_state(if secure {
+                SecurityState::Secure
+            } else {
+                SecurityState::NonSecure
+            });
+        }
+        let vtor = {
+            let system_control = self.system_control.borrow();
+            if secure {
+                system_control.vtor.offset()
+            } else {
+                system_control.vtor_ns.offset()
+            }
+        };
         let vector_address = number.number() as u32 * 4 + vtor;
-        let jump_address = self.read_u32le_iface(vector_address)?;
+        let jump_address = match self.read_u32le_iface(vector_address) {
+            Ok(address) => address,
+            Err(_) if self.bus_faults => {
+                // The exception stays pending and HardFault is taken instead, unless the HardFault
+                // vector itself cannot be fetched.
+                self.system_control.borrow_mut().hfsr.set_vecttbl(true);
+                return match self.escalate_fault(Irq::HardFault) {
+                    Some(irq) if number != Irq::HardFault => {
+                        self.set_pending(number);
+                        self.exception_taken(irq)
+                    }
+                    _ => {
+                        self.lockup();
+                        Ok(())
+                    }
+                };
+            }
+            Err(e) => return Err(e),
+        };
         self.set_pc(jump_address & 0xfffffffe);
         self.registers.mode = Mode::Handler;
         self.registers
             .psr
             .set_exception_number(number.number())
             .set_t(jump_address & 1 != 0)
-            .set_ici_it(0);
-        // TODO: Set CONTROL.FPCA to 1 if FP available
+            .set_ici_it(0)
+            .set_b(false);
+        // The handler starts without floating-point context.
+        self.registers.control.set_fpca(false);
         self.registers.control.set_spsel(false);
         self.exception_active[number.number() as usize] = true;
-        // TODO: SCS_UpdateStatusRegs()
+        self.execution_priority = self.compute_execution_priority();
+        self.update_status_registers();
         // TODO: ClearExclusiveLocal()
         self.registers.event = true; // SetEventRegister()
 
@@ -1212,6 +2597,107 @@ impl Processor {
         }
     }
 
+    /// Returns true if the processor executes in Secure state. This is always the case when the
+    /// Security Extension is not implemented.
+    pub fn is_secure(&self) -> bool {
+        self.registers.security_state == SecurityState::Secure
+    }
+
+    /// Returns `true` if the Security Extension is implemented.
+    pub fn security_extension(&self) -> bool {
+        self.security_extension
+    }
+
+    /// Returns the Security Attribution Unit, with the SFSR and SFAR registers.
+    ///
+    /// Those registers are accessible in memory only if the Security Extension is implemented.
+    pub fn sau(&self) -> RefMut<'_, SecurityAttributionUnit> {
+        self.sau.borrow_mut()
+    }
+
+    /// Returns the Debug Control Block and Data Watchpoint and Trace unit registers.
+    pub fn debug(&self) -> RefMut<'_, DebugRegisters> {
+        self.debug.borrow_mut()
+    }
+
+    /// Returns the Flash Patch and Breakpoint unit registers. The unit is only mapped on ArmV7-M.
+    pub fn fpb(&self) -> RefMut<'_, FlashPatchBreakpoint> {
+        self.fpb.borrow_mut()
+    }
+
+    /// Returns the Instrumentation Trace Macrocell, which holds the data written by software to
+    /// the stimulus ports. The ITM is not mapped on ArmV6-M.
+    pub fn itm(&self) -> RefMut<'_, InstrumentationTraceMacrocell> {
+        self.itm.borrow_mut()
+    }
+
+    /// Returns the System Control Space registers, including the NVIC.
+    ///
+    /// Pending and active states of the exceptions are updated when they change, so they can be
+    /// inspected there.
+    pub fn system_control(&self) -> RefMut<'_, SystemControl> {
+        self.system_control.borrow_mut()
+    }
+
+    /// Installs the Implementation Defined Attribution Unit of the platform.
+    ///
+    /// Panics if the Security Extension is not implemented.
+    pub fn set_idau(&mut self, idau: Rc<RefCell<dyn ImplementationDefinedAttributionUnit>>) {
+        assert!(
+            self.security_extension,
+            "Security Extension is not implemented"
+        );
+        self.idau = Some(idau)
+    }
+
+    /// Returns the response of the TT, TTT, TTA and TTAT instructions, giving the security state
+    /// and access permissions of `address`.
+    ///
+    /// When `alternate` is true, the test is made for the Non-secure state, which requires the
+    /// processor to be in Secure state. When `force_unprivileged` is true, the access permissions
+    /// are checked for unprivileged accesses. Only one MPU is implemented, which is used for both
+    /// security states.
+    ///
+    /// Corresponds to `TTResp()` in the Arm Architecture Reference Manual.
+    pub fn test_target(&self, address: u32, alternate: bool, force_unprivileged: bool) -> u32 {
+        let privileged = if force_unprivileged {
+            false
+        } else if alternate {
+            self.registers.mode == Mode::Handler || !self.registers.banked.control.privileged_bit()
+        } else {
+            self.is_privileged()
+        };
+        let mut response = 0;
+        // MPU region is not reported to unprivileged software, unless it is testing the other
+        // security state.
+        if alternate || self.is_privileged() {
+            if let Some(region) = self
+                .mpu
+                .as_ref()
+                .and_then(|mpu| mpu.borrow().region(address))
+            {
+                response |= region as u32 | 1 << 16;
+            }
+        }
+        let read = self.mpu_permits(address, privileged, false, false);
+        let read_write = read && self.mpu_permits(address, privileged, true, false);
+        response.set_bit(18, read);
+        response.set_bit(19, read_write);
+        if self.security_extension && self.is_secure() {
+            let attributes = self.security_attributes(address);
+            if let Some(region) = attributes.sau_region {
+                response |= (region as u32) << 8 | 1 << 17;
+            }
+            if let Some(region) = attributes.idau_region {
+                response |= (region as u32) << 24 | 1 << 23;
+            }
+            response.set_bit(20, read && attributes.non_secure);
+            response.set_bit(21, read_write && attributes.non_secure);
+            response.set_bit(22, !attributes.non_secure);
+        }
+        response
+    }
+
     /// Write value to PC, with interworking for ARM only from ARMv7
     pub fn alu_write_pc(&mut self, address: u32) {
         self.blx_write_pc(address)
@@ -1230,7 +2716,9 @@ impl Processor {
 
     /// Write value to PC, with interworking
     pub fn bx_write_pc(&mut self, address: u32) -> Result<(), RunError> {
-        if self.registers.mode == Mode::Handler && (address >> 28 == 0xf) {
+        if self.security_extension && !self.is_secure() && (address >> 24 == 0xfe) {
+            self.function_return()
+        } else if self.registers.mode == Mode::Handler && (address >> 28 == 0xf) {
             self.exception_return(address & 0x0fffffff)
         } else {
             self.blx_write_pc(address);
@@ -1238,6 +2726,74 @@ impl Processor {
         }
     }
 
+    /// Write value to PC for the BLXNS instruction. If bit 0 of `address` is cleared, the
+    /// processor transitions to the Non-secure state, otherwise this is the same as
+    /// [Self::blx_write_pc].
+    ///
+    /// Corresponds to `BLXWritePC()` in the Arm Architecture Reference Manual, with
+    /// `allowNonSecure` set.
+    pub fn blxns_write_pc(&mut self, address: u32) {
+        if address.bit(0) {
+            self.blx_write_pc(address)
+        } else {
+            self.registers.set_security_state(SecurityState::NonSecure);
+            self.set_pc(address)
+        }
+    }
+
+    /// Write value to PC for the BXNS instruction. This is the same as [Self::blxns_write_pc],
+    /// excepted for exception return values.
+    ///
+    /// Corresponds to `BXWritePC()` in the Arm Architecture Reference Manual, with
+    /// `allowNonSecure` set.
+    pub fn bxns_write_pc(&mut self, address: u32) -> Result<(), RunError> {
+        if self.registers.mode == Mode::Handler && (address >> 28 == 0xf) {
+            self.exception_return(address & 0x0fffffff)
+        } else {
+            self.blxns_write_pc(address);
+            Ok(())
+        }
+    }
+
+    /// Returns from a Non-secure function called by Secure code with BLXNS, when Non-secure code
+    /// branches to the FNC_RETURN value. The return address and partial PSR pushed by BLXNS are
+    /// popped from the Secure stack.
+    ///
+    /// If the stacked IPSR is not consistent with current mode, CFSR.INVPC is set and a
+    /// UsageFault is returned.
+    ///
+    /// Corresponds to `FunctionReturn()` in the Arm Architecture Reference Manual.
+    fn function_return(&mut self) -> Result<(), RunError> {
+        self.registers.set_security_state(SecurityState::Secure);
+        let frame_ptr = self.sp();
+        let frame = self
+            .read_u32_aligned(frame_ptr)
+            .and_then(|address| Ok((address, self.read_u32_aligned(frame_ptr + 4)?)));
+        let (return_address, return_psr) = match frame {
+            Ok(frame) => frame,
+            Err(e) => {
+                self.registers.set_security_state(SecurityState::NonSecure);
+                return Err(e);
+            }
+        };
+        let ipsr = self.registers.psr.exception_number();
+        let return_ipsr = (return_psr & 0x1ff) as u16;
+        if !((ipsr == 0 && return_ipsr == 0) || (ipsr == 1 && return_ipsr != 0)) {
+            self.registers.set_security_state(SecurityState::NonSecure);
+            self.system_control.borrow_mut().cfsr.set_invpc(true);
+            return Err(RunError::Fault(Irq::UsageFault));
+        }
+        *self.registers.sp_mut() = frame_ptr + 8;
+        self.registers
+            .psr
+            .set_exception_number(return_ipsr)
+            .set_ici_it(0)
+            .set_b(false);
+        self.registers.control.set_sfpa(return_psr.bit(20));
+        self.blx_write_pc(return_address);
+        Ok(())
+    }
+
     pub fn condition_passed(&self) -> bool {
         if let Some(condition) = self.registers.psr.it_state().current_condition() {
             self.registers.psr.test(condition)
@@ -1263,17 +2819,256 @@ impl Processor {
         Some(coprocessor)
     }
 
-    /// Raise usage fault exception and sets NOCP fault flag.
-    pub fn generate_coprocessor_exception(&mut self) {
-        self.request_interrupt(Irq::UsageFault);
+    /// Sets NOCP fault flag and returns the UsageFault to be raised by the instruction.
+    ///
+    /// Corresponds to `GenerateCoprocessorException()` in the Arm Architecture Reference Manual.
+    pub fn generate_coprocessor_exception(&mut self) -> RunError {
         self.system_control.borrow_mut().cfsr.set_nocp(true);
+        RunError::Fault(Irq::UsageFault)
+    }
+
+    /// Checks that Custom Datapath Extension coprocessor `cp` can be accessed at the current
+    /// privilege level according to CPACR, and returns its implementation. If access is denied or
+    /// if no implementation has been defined with [Processor::set_custom_datapath], sets the NOCP
+    /// fault flag and returns the UsageFault to be raised by the instruction.
+    ///
+    /// Corresponds to `ExecuteCPCheck()` in the Arm Architecture Reference Manual.
+    pub fn custom_datapath_check(
+        &mut self,
+        cp: u8,
+    ) -> Result<Rc<RefCell<dyn CustomDatapath>>, RunError> {
+        debug_assert!(cp < 8);
+        let enabled = match self.system_control.borrow().cpacr.cp(cp) {
+            0b01 => self.is_privileged(),
+            0b11 => true,
+            _ => false,
+        };
+        match self.custom_datapaths[cp as usize].clone() {
+            Some(datapath) if enabled => Ok(datapath),
+            _ => Err(self.generate_coprocessor_exception()),
+        }
+    }
+
+    /// Returns the floating-point extension implemented by the processor, if any.
+    pub fn fpu(&self) -> Option<FloatingPointExtension> {
+        self.fpu
+    }
+
+    /// Returns the M-profile Vector Extension implemented by the processor, if any.
+    pub fn mve(&self) -> Option<MveExtension> {
+        self.mve
+    }
+
+    /// Returns `true` if the DSP extension is implemented, which adds the APSR.GE flags.
+    pub fn dsp_extension(&self) -> bool {
+        self.version == ArmVersion::V7EM || self.version.includes(ArmVersion::V8M)
+    }
+
+    /// Returns `true` if the Pointer Authentication and Branch Target Identification Extension is
+    /// implemented.
+    pub fn pacbti(&self) -> bool {
+        self.pacbti
+    }
+
+    /// Returns `true` if pointer authentication is enabled for the current privilege level, by
+    /// the PAC_EN or UPAC_EN bit of CONTROL.
+    pub fn is_pac_enabled(&self) -> bool {
+        let control = &self.registers.control;
+        self.pacbti
+            && if self.is_privileged() {
+                control.pac_en()
+            } else {
+                control.upac_en()
+            }
+    }
+
+    /// Returns `true` if branch target identification is enabled for the current privilege level,
+    /// by the BTI_EN or UBTI_EN bit of CONTROL.
+    pub fn is_bti_enabled(&self) -> bool {
+        let control = &self.registers.control;
+        self.pacbti
+            && if self.is_privileged() {
+                control.bti_en()
+            } else {
+                control.ubti_en()
+            }
+    }
+
+    /// Returns the pointer authentication code of `data` with `modifier`, computed with the
+    /// privileged or unprivileged key of the current security state depending on the current
+    /// privilege level.
+    pub fn compute_pac(&self, data: u32, modifier: u32) -> u32 {
+        let key = if self.is_privileged() {
+            self.registers.pac_key_p
+        } else {
+            self.registers.pac_key_u
+        };
+        compute_pac(data, modifier, key)
+    }
+
+    /// Checks that `pac` is the pointer authentication code of `data` with `modifier`. Otherwise,
+    /// sets CFSR.INVSTATE and returns the UsageFault to be raised by the instruction.
+    pub fn authenticate_pac(&mut self, pac: u32, data: u32, modifier: u32) -> Result<(), RunError> {
+        if pac != self.compute_pac(data, modifier) {
+            self.system_control.borrow_mut().cfsr.set_invstate(true);
+            return Err(RunError::Fault(Irq::UsageFault));
+        }
+        Ok(())
+    }
+
+    /// Sets EPSR.B if branch target identification is enabled, so the next instruction executed
+    /// must be a BTI clearing instruction.
+    ///
+    /// This must be called before writing PC by indirect branches which are not function returns:
+    /// BX and BXNS with a register other than LR, BLX and BLXNS, and loads to PC with a base
+    /// register other than SP. Exception returns restore EPSR.B from the stacked xPSR.
+    pub fn require_branch_target(&mut self) {
+        if self.is_bti_enabled() {
+            self.registers.psr.set_b(true);
+        }
+    }
+
+    /// Returns the floating-point context control registers (FPCCR, FPCAR and FPDSCR).
+    ///
+    /// Those registers are accessible in memory only if the floating-point extension is
+    /// implemented.
+    pub fn fp_control(&self) -> RefMut<'_, FloatingPointControl> {
+        self.fp_control.borrow_mut()
+    }
+
+    /// Checks that floating-point instructions can be executed, which requires the FPU to be
+    /// implemented and access to coprocessors CP10 and CP11 to be granted by CPACR for the current
+    /// privilege level. Otherwise, sets NOCP fault flag and returns the UsageFault to be raised by
+    /// the instruction.
+    ///
+    /// When lazy state preservation is active, the floating-point context is saved to the stack
+    /// before the instruction executes. If FPCCR.ASPEN is set and no floating-point context is
+    /// active, a new context is created with FPSCR defaults taken from FPDSCR.
+    ///
+    /// Corresponds to `ExecuteFPCheck()` in the Arm Architecture Reference Manual.
+    pub fn execute_fp_check(&mut self) -> Result<(), RunError> {
+        let enabled = self.fpu.is_some()
+            && match self.system_control.borrow().cpacr.cp(10) {
+                0b01 => self.is_privileged(),
+                0b11 => true,
+                _ => false,
+            };
+        if !enabled {
+            return Err(self.generate_coprocessor_exception());
+        }
+        if self.fp_control.borrow().fpccr.lspact() {
+            self.preserve_fp_state()?;
+        }
+        let fp_control = self.fp_control.borrow();
+        if fp_control.fpccr.aspen() && !self.registers.control.fpca() {
+            let fpscr = self.registers.fpscr.get() & !0x07c00000 | fp_control.fpdscr;
+            self.registers.fpscr.set(fpscr);
+            self.registers.control.set_fpca(true);
+        }
+        Ok(())
+    }
+
+    /// Checks that an M-profile Vector Extension instruction can be executed, before
+    /// [Processor::execute_fp_check] checks.
+    ///
+    /// Returns [RunError::InstructionUndefined] if the processor does not implement the M-profile
+    /// Vector Extension, or if `float` is `true` and only the integer subset is implemented.
+    pub fn execute_mve_check(&mut self, float: bool) -> Result<(), RunError> {
+        match self.mve {
+            None => return Err(RunError::InstructionUndefined),
+            Some(MveExtension::Integer) if float => return Err(RunError::InstructionUndefined),
+            _ => {}
+        }
+        self.execute_fp_check()
+    }
+
+    /// Saves S0-S15, FPSCR and VPR (if the M-profile Vector Extension is implemented) to the
+    /// stack space reserved when lazy state preservation was activated, using the address and
+    /// privilege recorded in FPCAR and FPCCR. On ArmV8.1-M, tail predication is then disabled, as
+    /// this was deferred at exception entry.
+    ///
+    /// Corresponds to `PreserveFPState()` in the Arm Architecture Reference Manual.
+    fn preserve_fp_state(&mut self) -> Result<(), RunError> {
+        let (fpcar, privileged) = {
+            let fp_control = self.fp_control.borrow();
+            (fp_control.fpcar, !fp_control.fpccr.user())
+        };
+        let mut words = self.registers.s[..16].to_vec();
+        words.push(self.registers.fpscr.get());
+        if self.mve.is_some() {
+            words.push(self.registers.vpr.get());
+        }
+        for (i, value) in words.into_iter().enumerate() {
+            let address = fpcar + 4 * i as u32;
+            if !self.mpu_permits(address, privileged, true, false) {
+                self.system_control.borrow_mut().cfsr.set_mlsperr(true);
+                return Err(RunError::Fault(Irq::MemManage));
+            }
+            match self.write_u32le_iface(address, value) {
+                Ok(()) => {}
+                Err(_) if self.bus_faults => {
+                    self.system_control.borrow_mut().cfsr.set_lsperr(true);
+                    return Err(RunError::Fault(Irq::BusFault));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        self.fp_control.borrow_mut().fpccr.set_lspact(false);
+        if self.version == ArmVersion::V81M {
+            self.registers.fpscr.set_ltpsize(4);
+        }
+        Ok(())
+    }
+
+    /// Activates lazy floating-point state preservation, with `address` the location reserved
+    /// for S0 in memory. Privilege, mode and fault readiness of the current context are recorded
+    /// in FPCCR so the state can be preserved later on.
+    ///
+    /// Corresponds to `UpdateFPCCR()` in the Arm Architecture Reference Manual.
+    pub fn update_fpccr(&mut self, address: u32) {
+        let priority = self.execution_priority;
+        let (mmrdy, bfrdy) = {
+            let shcsr = &self.system_control.borrow().shcsr;
+            (shcsr.memfaultena(), shcsr.busfaultena())
+        };
+        let mmrdy = mmrdy && priority > self.exception_priority(Irq::MemManage);
+        let bfrdy = bfrdy && priority > self.exception_priority(Irq::BusFault);
+        let monrdy = self.debug.borrow().monitor_enabled()
+            && priority > self.exception_priority(Irq::DebugMonitor);
+        let mut fp_control = self.fp_control.borrow_mut();
+        fp_control.fpcar = address & !7;
+        let fpccr = &mut fp_control.fpccr;
+        fpccr.set_lspact(true);
+        fpccr.set_user(!self.is_privileged());
+        fpccr.set_thread(self.registers.mode == Mode::Thread);
+        fpccr.set_hfrdy(priority > -1);
+        fpccr.set_mmrdy(mmrdy);
+        fpccr.set_bfrdy(bfrdy);
+        fpccr.set_monrdy(monrdy);
+    }
+
+    /// Returns `true` if division by zero must raise a UsageFault, as configured by
+    /// CCR.DIV_0_TRP.
+    ///
+    /// Corresponds to `IntegerZeroDivideTrappingEnabled()` in the Arm Architecture Reference
+    /// Manual.
+    pub fn integer_zero_divide_trapping_enabled(&self) -> bool {
+        self.system_control.borrow().ccr.div_0_trp()
+    }
+
+    /// Sets DIVBYZERO fault flag and returns the UsageFault to be raised by the instruction.
+    ///
+    /// Corresponds to `GenerateIntegerZeroDivide()` in the Arm Architecture Reference Manual.
+    pub fn generate_integer_zero_divide(&mut self) -> RunError {
+        self.system_control.borrow_mut().cfsr.set_divbyzero(true);
+        RunError::Fault(Irq::UsageFault)
     }
 
     /// Tags a memory address for exclusive access. `size` can be in [1, 4].
     ///
     /// Corresponds to `SetExclusiveMonitors()` in Arm Architecture Reference Manual.
     pub fn set_exclusive_monitors(&mut self, address: u32, size: u32) {
-        debug_assert!((size >= 1) && (size <= 4));
+        debug_assert!((1..=4).contains(&size));
         let granule = self.local_monitor.granule;
         self.local_monitor.state = MonitorState::ExclusiveAccess {
             address: address.align(granule as usize),
@@ -1285,12 +3080,12 @@ impl Processor {
     /// Corresponds to `ExclusiveMonitorsPass()` in the Arm Architecture Reference Manual.
     pub fn exclusive_monitors_pass(&mut self, address: u32, size: u32) -> Result<bool, RunError> {
         self.usage_fault_if_unaligned(address, size as usize)?;
-        // TODO address validation
-        if self.local_monitor.state == (MonitorState::ExclusiveAccess { address: address }) {
+        self.validate_address(address, self.is_privileged(), true, false)?;
+        if self.local_monitor.state == (MonitorState::ExclusiveAccess { address }) {
             self.clear_exclusive_local();
             return Ok(true);
         }
-        return Ok(false);
+        Ok(false)
     }
 
     /// Clears the local exclusive monitor lock.
@@ -1346,9 +3141,15 @@ pub trait Emulator {
             let event = self.next_event()?;
             match event {
                 Event::Hook { address: _ }
+                | Event::DataHook { .. }
+                | Event::Watchpoint(_)
                 | Event::Reset
                 | Event::Break(_)
-                | Event::DebugHint(_) => return Ok(Some(event.clone())),
+                | Event::DebugHint(_)
+                | Event::Lockup
+                | Event::SendEvent
+                | Event::DeepSleep
+                | Event::Idle => return Ok(Some(event.clone())),
                 Event::Instruction { ins: _ } => ins_count += 1,
             }
         }
@@ -1378,6 +3179,7 @@ enum State {
     Running,
     WaitingForEvent,
     WaitingForInterrupt,
+    Lockup,
 }
 
 /// An instruction execution may result in some optional effect that require special treatment
@@ -1399,6 +3201,8 @@ pub enum Effect {
     WaitForEvent,
     /// Instruction requests the halt of the processor execution until an interrupt occurs.
     WaitForInterrupt,
+    /// Returned by the SEV instruction.
+    SendEvent,
 }
 
 /// Error returned when mapping an interface would intersect with an already mapping other
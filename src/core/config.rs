@@ -1,4 +1,30 @@
 use crate::core::ArmVersion;
+#[cfg(doc)]
+use crate::core::{Processor, RunError};
+
+/// Floating-point extension implemented by the processor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloatingPointExtension {
+    /// Single-precision floating-point extension (FPv4-SP), as found in Cortex-M4F or
+    /// Cortex-M33F processors.
+    SinglePrecision,
+    /// Single-precision and double-precision floating-point extension (FPv5-DP), as found in
+    /// Cortex-M7 processors with the `fpv5-d16` FPU.
+    ///
+    /// In addition to double-precision operations, this adds the VCVTA, VCVTN, VCVTP, VCVTM,
+    /// VMAXNM, VMINNM, VRINT and VSEL instructions.
+    DoublePrecision,
+}
+
+/// M-profile Vector Extension (MVE, also known as Helium) implemented by the processor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MveExtension {
+    /// Integer vector instructions only (MVE-I).
+    Integer,
+    /// Integer and half-precision and single-precision floating-point vector instructions
+    /// (MVE-F), as found in Cortex-M55 or Cortex-M85 processors.
+    FloatingPoint,
+}
 
 /// Configuration builder used to build instances of [`Processor`].
 pub struct Config {
@@ -8,6 +34,19 @@ pub struct Config {
     pub(crate) external_exceptions: usize,
     /// Reservation granule for the local monitor dealing with exclusive accesses.
     pub(crate) exclusives_reservation_granule: u32,
+    /// Whether memory access errors raise BusFault exceptions.
+    pub(crate) bus_faults: bool,
+    /// Floating-point extension, if any.
+    pub(crate) fpu: Option<FloatingPointExtension>,
+    /// Whether the ArmV8-M Security Extension is implemented.
+    pub(crate) security_extension: bool,
+    /// M-profile Vector Extension, if any.
+    pub(crate) mve: Option<MveExtension>,
+    /// Whether the Pointer Authentication and Branch Target Identification Extension is
+    /// implemented.
+    pub(crate) pacbti: bool,
+    /// Coprocessors implementing the Custom Datapath Extension, as a bit mask.
+    pub(crate) custom_datapath: u8,
 }
 
 impl Config {
@@ -16,6 +55,12 @@ impl Config {
             version: ArmVersion::V6M,
             external_exceptions: 0,
             exclusives_reservation_granule: 4,
+            bus_faults: false,
+            fpu: None,
+            security_extension: false,
+            mve: None,
+            pacbti: false,
+            custom_datapath: 0,
         }
     }
 
@@ -40,6 +85,13 @@ impl Config {
         }
     }
 
+    pub fn v81m() -> Self {
+        Self {
+            version: ArmVersion::V81M,
+            ..Self::v6m()
+        }
+    }
+
     /// Sets the number of platform specific exceptions.
     pub fn external_exceptions(mut self, count: usize) -> Self {
         self.external_exceptions = count;
@@ -53,4 +105,93 @@ impl Config {
         self.exclusives_reservation_granule = granule;
         self
     }
+
+    /// Enables or disables bus faults.
+    ///
+    /// By default, any memory access error returned by a memory interface aborts emulation with a
+    /// [`RunError::MemRead`] or [`RunError::MemWrite`] error, which is convenient for fuzzing.
+    /// When bus faults are enabled, such errors raise a BusFault exception instead, as they would
+    /// on hardware: precise data bus errors set CFSR.PRECISERR and BFAR, instruction fetch errors
+    /// set CFSR.IBUSERR, and stacking or unstacking errors set CFSR.STKERR or CFSR.UNSTKERR.
+    /// Vector table read errors raise a HardFault and set HFSR.VECTTBL.
+    pub fn bus_faults(mut self, enable: bool) -> Self {
+        self.bus_faults = enable;
+        self
+    }
+
+    /// Adds a floating-point extension to the processor.
+    ///
+    /// By default, the processor has no FPU and floating-point instructions raise a UsageFault
+    /// with CFSR.NOCP set. When the FPU is present, software must still grant access to
+    /// coprocessors CP10 and CP11 in CPACR before executing floating-point instructions, as on
+    /// hardware.
+    ///
+    /// ArmV6-M does not support the floating-point extension.
+    pub fn fpu(mut self, fpu: FloatingPointExtension) -> Self {
+        self.fpu = Some(fpu);
+        self
+    }
+
+    /// Enables or disables the ArmV8-M Security Extension (TrustZone).
+    ///
+    /// When enabled, the processor starts in Secure state and its stack pointers, PRIMASK,
+    /// FAULTMASK, CONTROL and VTOR registers are banked between Secure and Non-secure states.
+    /// Memory is attributed to either state by the Security Attribution Unit, optionally
+    /// completed by an Implementation Defined Attribution Unit installed with
+    /// [`Processor::set_idau`]. Violations of the security boundary raise SecureFault exceptions.
+    ///
+    /// By default, the Security Extension is not implemented and the processor always executes in
+    /// Secure state. Only ArmV8-M supports the Security Extension.
+    pub fn security_extension(mut self, enable: bool) -> Self {
+        self.security_extension = enable;
+        self
+    }
+
+    /// Adds the M-profile Vector Extension (Helium) to the processor.
+    ///
+    /// Vector registers Q0 to Q7 alias the floating-point extension registers, and vector
+    /// instructions are enabled by the CP10 and CP11 fields of CPACR like floating-point
+    /// instructions. A floating-point extension must therefore be configured with
+    /// [`Config::fpu`] as well.
+    ///
+    /// Only ArmV8.1-M supports the M-profile Vector Extension.
+    pub fn mve(mut self, mve: MveExtension) -> Self {
+        self.mve = Some(mve);
+        self
+    }
+
+    /// Enables or disables the ArmV8.1-M Pointer Authentication and Branch Target Identification
+    /// Extension (PACBTI).
+    ///
+    /// When enabled, the PAC_KEY_P and PAC_KEY_U keys can be written with MSR, and the PAC_EN,
+    /// UPAC_EN, BTI_EN and UBTI_EN bits of CONTROL enable pointer authentication and branch target
+    /// identification for privileged and unprivileged code. Authentication failures, as well as
+    /// indirect branches to an instruction other than BTI, PACBTI or SG while branch target
+    /// identification is enabled, raise a UsageFault with CFSR.INVSTATE set.
+    ///
+    /// When disabled, PAC, PACBTI, AUT and BTI execute as NOPs, and PACG, AUTG and BXAUT are
+    /// UNDEFINED. Only ArmV8.1-M supports the PACBTI extension.
+    pub fn pacbti(mut self, enable: bool) -> Self {
+        self.pacbti = enable;
+        self
+    }
+
+    /// Declares coprocessor `coprocessor`, from 0 to 7, as a Custom Datapath Extension (CDE)
+    /// coprocessor. This can be called several times to declare multiple coprocessors.
+    ///
+    /// Instructions in the coprocessor space of CDE coprocessors are decoded as CX1, CX2, CX3,
+    /// VCX1, VCX2 and VCX3 instructions instead of generic coprocessor instructions, and are
+    /// executed by the accelerator attached with [`Processor::set_custom_datapath`]. As for other
+    /// coprocessors, software must grant access to the coprocessor in CPACR, otherwise a
+    /// UsageFault is raised with CFSR.NOCP set.
+    ///
+    /// Only ArmV8-M supports the Custom Datapath Extension.
+    pub fn custom_datapath(mut self, coprocessor: u8) -> Self {
+        assert!(
+            coprocessor < 8,
+            "CDE coprocessor index must be lower than 8"
+        );
+        self.custom_datapath |= 1 << coprocessor;
+        self
+    }
 }
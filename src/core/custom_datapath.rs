@@ -0,0 +1,75 @@
+/// Custom Datapath Extension instruction operation, as passed to [CustomDatapath] methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CustomInstruction {
+    /// Coprocessor index, from 0 to 7.
+    pub coproc: u8,
+    /// Instruction class, from 1 to 3. Class 1 instructions have no source operand, class 2
+    /// instructions have one and class 3 instructions have two.
+    pub class: u8,
+    /// `true` for accumulating variants (CX1A, VCX2A, ...), for which the destination register
+    /// is also an input operand.
+    pub accumulate: bool,
+    /// Immediate value encoded in the instruction, which usually selects the operation.
+    pub imm: u32,
+}
+
+/// Arm Custom Datapath Extension (CDE) accelerator.
+///
+/// The Custom Datapath Extension of ArmV8-M allows silicon vendors to add accelerator
+/// instructions in the coprocessor instruction space. The coprocessors which are CDE
+/// coprocessors must be declared with [crate::core::Config::custom_datapath], and their
+/// implementation is attached to the processor using
+/// [crate::core::Processor::set_custom_datapath].
+///
+/// Each method receives the destination register value before execution and the values of the
+/// source operands, and returns the value to be written to the destination register. Unused
+/// source operands are passed as zero. When APSR_nzcv is an operand, the N, Z, C and V flags are
+/// given in bits 31 to 28, and when APSR_nzcv is the destination only bits 31 to 28 of the result
+/// are used.
+///
+/// Returning [None] means the instruction is not supported by the accelerator, and a UsageFault
+/// is raised with CFSR.UNDEFINSTR set. This is the default behavior of all methods.
+pub trait CustomDatapath {
+    /// Executes CX1, CX2 or CX3 instructions, operating on general-purpose registers.
+    fn cx(&mut self, ins: &CustomInstruction, d: u32, n: u32, m: u32) -> Option<u32> {
+        let _ = (ins, d, n, m);
+        None
+    }
+
+    /// Executes CX1D, CX2D or CX3D instructions, whose destination is a pair of general-purpose
+    /// registers. `d` is the value of the pair, with the odd register in the most significant
+    /// word.
+    fn cx_dual(&mut self, ins: &CustomInstruction, d: u64, n: u32, m: u32) -> Option<u64> {
+        let _ = (ins, d, n, m);
+        None
+    }
+
+    /// Executes VCX1, VCX2 or VCX3 instructions operating on single-precision registers.
+    fn vcx_single(&mut self, ins: &CustomInstruction, d: u32, n: u32, m: u32) -> Option<u32> {
+        let _ = (ins, d, n, m);
+        None
+    }
+
+    /// Executes VCX1, VCX2 or VCX3 instructions operating on double-precision registers.
+    fn vcx_double(&mut self, ins: &CustomInstruction, d: u64, n: u64, m: u64) -> Option<u64> {
+        let _ = (ins, d, n, m);
+        None
+    }
+
+    /// Executes one beat of VCX1, VCX2 or VCX3 instructions operating on vector registers.
+    ///
+    /// `d`, `n` and `m` are the words of the vector registers for the given `beat`. Only the
+    /// bytes of the result enabled by predication are written to the destination register, and
+    /// this method is not called for beats which are entirely disabled.
+    fn vcx_vector(
+        &mut self,
+        ins: &CustomInstruction,
+        beat: usize,
+        d: u32,
+        n: u32,
+        m: u32,
+    ) -> Option<u32> {
+        let _ = (ins, beat, d, n, m);
+        None
+    }
+}
@@ -21,7 +21,7 @@ impl LocalMonitor {
             "Exclusives reservation granule must be a power of two."
         );
         assert!(
-            (granule >= 4) && (granule <= 512),
+            (4..=512).contains(&granule),
             "Exclusive reservation granule must be in [4, 512]."
         );
         Self {
@@ -4,7 +4,7 @@
 ///
 /// Some interrupt may be specific to the platform running the ARM core (specific peripheral
 /// interrupts for instance), those are defined as [Irq::External] interrupts.
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 pub enum Irq {
     Reset,
     Nmi,
@@ -12,6 +12,8 @@ pub enum Irq {
     MemManage,
     BusFault,
     UsageFault,
+    /// Only exists when the ArmV8-M Security Extension is implemented.
+    SecureFault,
     SVCall,
     DebugMonitor,
     PendSV,
@@ -31,6 +33,7 @@ impl Irq {
             Irq::MemManage => 4,
             Irq::BusFault => 5,
             Irq::UsageFault => 6,
+            Irq::SecureFault => 7,
             Irq::SVCall => 11,
             Irq::DebugMonitor => 12,
             Irq::PendSV => 14,
@@ -39,3 +42,27 @@ impl Irq {
         }
     }
 }
+
+impl TryFrom<u16> for Irq {
+    type Error = ();
+
+    /// Returns the exception corresponding to an exception number, or an error if the number is
+    /// reserved.
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        Ok(match value {
+            1 => Irq::Reset,
+            2 => Irq::Nmi,
+            3 => Irq::HardFault,
+            4 => Irq::MemManage,
+            5 => Irq::BusFault,
+            6 => Irq::UsageFault,
+            7 => Irq::SecureFault,
+            11 => Irq::SVCall,
+            12 => Irq::DebugMonitor,
+            14 => Irq::PendSV,
+            15 => Irq::SysTick,
+            16.. => Irq::External(value - 16),
+            _ => return Err(()),
+        })
+    }
+}
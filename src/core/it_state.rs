@@ -15,6 +15,7 @@ impl ItState {
 
     /// Try to create a new IT state, fails if any condition combination lead to the invalid
     /// condition code 0xf.
+    #[allow(clippy::result_unit_err)]
     pub fn try_new(value: u8) -> Result<Self, ()> {
         let first_cond = value >> 4;
         let mask = value & 0xf;
@@ -151,25 +152,25 @@ mod tests {
     #[test]
     fn test_in_it_block() {
         let mut state = ItState::try_new(0b00100001).unwrap();
-        assert_eq!(state.in_it_block(), true);
-        assert_eq!(state.last_in_it_block(), false);
-        assert_eq!(state.in_it_block_not_last(), true);
+        assert!(state.in_it_block());
+        assert!(!state.last_in_it_block());
+        assert!(state.in_it_block_not_last());
         state.advance();
-        assert_eq!(state.in_it_block(), true);
-        assert_eq!(state.last_in_it_block(), false);
-        assert_eq!(state.in_it_block_not_last(), true);
+        assert!(state.in_it_block());
+        assert!(!state.last_in_it_block());
+        assert!(state.in_it_block_not_last());
         state.advance();
-        assert_eq!(state.in_it_block(), true);
-        assert_eq!(state.last_in_it_block(), false);
-        assert_eq!(state.in_it_block_not_last(), true);
+        assert!(state.in_it_block());
+        assert!(!state.last_in_it_block());
+        assert!(state.in_it_block_not_last());
         state.advance();
-        assert_eq!(state.in_it_block(), true);
-        assert_eq!(state.last_in_it_block(), true);
-        assert_eq!(state.in_it_block_not_last(), false);
+        assert!(state.in_it_block());
+        assert!(state.last_in_it_block());
+        assert!(!state.in_it_block_not_last());
         state.advance();
-        assert_eq!(state.in_it_block(), false);
-        assert_eq!(state.last_in_it_block(), false);
-        assert_eq!(state.in_it_block_not_last(), false);
+        assert!(!state.in_it_block());
+        assert!(!state.last_in_it_block());
+        assert!(!state.in_it_block_not_last());
     }
 
     #[test]
@@ -0,0 +1,42 @@
+//! Low-overhead branch information cache of ArmV8.1-M.
+
+use super::Condition;
+
+/// Branch recorded in the low-overhead branch information cache.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LoBranch {
+    /// End of a low-overhead loop, recorded by LE and LETP instructions.
+    LoopEnd {
+        /// Address of the first instruction of the loop.
+        start: u32,
+        /// `true` if the loop iteration count in LR is decremented at each iteration. `false` for
+        /// loops without iteration count, which never end.
+        decrement: bool,
+        /// `true` for tail predicated loops (LETP).
+        tail_predicated: bool,
+    },
+    /// Branch future, recorded by BF, BFX, BFL, BFLX and BFCSEL instructions.
+    BranchFuture {
+        /// Branch target address. Bit 0 gives the Thumb state bit.
+        target: u32,
+        /// Value written to LR when the branch is taken, for BFL and BFLX.
+        link: Option<u32>,
+        /// For BFCSEL, the branch condition and the address where execution continues when the
+        /// condition fails. Bit 0 of the address gives the Thumb state bit.
+        condition: Option<(Condition, u32)>,
+    },
+}
+
+/// Low-overhead branch information cache (LO_BRANCH_INFO).
+///
+/// Loop end and branch future instructions record a branch which is performed when execution
+/// reaches [LoBranchInfo::end_address]. The instruction at that address, which is expected to be
+/// an equivalent branch instruction, is then skipped. The cache is invalidated on exception entry
+/// and return.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoBranchInfo {
+    /// Address of the instruction replaced by the recorded branch.
+    pub end_address: u32,
+    /// Recorded branch.
+    pub branch: LoBranch,
+}
@@ -4,14 +4,20 @@ mod arm;
 mod condition;
 mod config;
 mod coprocessor;
+mod custom_datapath;
 mod exclusive_monitor;
 mod irq;
 mod it_state;
+mod lob;
 
-pub use arm::{ArmVersion, Effect, Emulator, Event, MapConflict, Processor, RunError, RunOptions};
+pub use arm::{
+    ArmVersion, DataHookKind, Effect, Emulator, Event, MapConflict, Processor, RunError, RunOptions,
+};
 pub use condition::Condition;
-pub use config::Config;
+pub use config::{Config, FloatingPointExtension, MveExtension};
 pub use coprocessor::Coprocessor;
+pub use custom_datapath::{CustomDatapath, CustomInstruction};
 pub use exclusive_monitor::{LocalMonitor, MonitorState};
 pub use irq::Irq;
 pub use it_state::{ItState, ItThenElse};
+pub use lob::{LoBranch, LoBranchInfo};
@@ -0,0 +1,651 @@
+//! Debug Control Block and Data Watchpoint and Trace unit.
+//!
+//! The Debug Control Block (DCB) is mapped in the System Control Space at address `0xe000edf0`.
+//! It holds DHCSR, which reports whether a debugger is attached, and DEMCR which globally enables
+//! the DWT (TRCENA) and the DebugMonitor exception (MON_EN).
+//!
+//! The Data Watchpoint and Trace unit (DWT) is mapped at address `0xe0001000` and provides the
+//! cycle counter, profiling counters and comparators. The comparators act as watchpoints on
+//! instruction addresses, data addresses or data values. When a comparator configured to generate
+//! debug events matches, DFSR.DWTTRAP is set and:
+//! - if halting debug is enabled (see [DebugRegisters::halting_debug]), the processor emits
+//!   [crate::core::Event::Watchpoint],
+//! - otherwise, if DEMCR.MON_EN is set, the DebugMonitor exception is pended,
+//! - otherwise the debug event is ignored.
+//!
+//! Since all instructions take one cycle in Armagnac, the CPI, exception overhead, load-store and
+//! folded instruction counters never increment. The sleep counter counts the cycles spent in
+//! WFI or WFE. Trace packets, PC sampling and cycle count matching are not implemented.
+
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    core::{ArmVersion, Irq},
+    helpers::BitAccess,
+    memory::{
+        Env, MemoryAccessError, MemoryReadResult, MemoryWriteResult, RegistersMemoryInterface,
+    },
+};
+
+/// Number of DWT comparators.
+const COMPARATOR_COUNT: usize = 4;
+
+/// DEMCR bit enabling the DebugMonitor exception.
+const DEMCR_MON_EN: usize = 16;
+/// DEMCR bit setting the DebugMonitor exception pending.
+const DEMCR_MON_PEND: usize = 17;
+/// DEMCR bit globally enabling the DWT and ITM.
+const DEMCR_TRCENA: usize = 24;
+
+/// DWT_CTRL bit enabling the cycle counter.
+const CTRL_CYCCNTENA: usize = 0;
+/// DWT_CTRL bit enabling the sleep counter.
+const CTRL_SLEEPEVTENA: usize = 19;
+
+/// DWT_FUNCTION bit set when the comparator matched.
+const FUNCTION_MATCHED: usize = 24;
+
+pub enum DebugControlRegister {
+    Dhcsr,
+    Dcrsr,
+    Dcrdr,
+    Demcr,
+}
+
+impl TryFrom<u32> for DebugControlRegister {
+    type Error = ();
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0x0 => DebugControlRegister::Dhcsr,
+            0x4 => DebugControlRegister::Dcrsr,
+            0x8 => DebugControlRegister::Dcrdr,
+            0xc => DebugControlRegister::Demcr,
+            _ => return Err(()),
+        })
+    }
+}
+
+pub enum DwtRegister {
+    Ctrl,
+    Cyccnt,
+    Cpicnt,
+    Exccnt,
+    Sleepcnt,
+    Lsucnt,
+    Foldcnt,
+    Pcsr,
+    Comp(u8),
+    Mask(u8),
+    Function(u8),
+}
+
+impl TryFrom<u32> for DwtRegister {
+    type Error = ();
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        if !value.is_multiple_of(4) {
+            return Err(());
+        }
+        Ok(match value {
+            0x00 => DwtRegister::Ctrl,
+            0x04 => DwtRegister::Cyccnt,
+            0x08 => DwtRegister::Cpicnt,
+            0x0c => DwtRegister::Exccnt,
+            0x10 => DwtRegister::Sleepcnt,
+            0x14 => DwtRegister::Lsucnt,
+            0x18 => DwtRegister::Foldcnt,
+            0x1c => DwtRegister::Pcsr,
+            0x20.. if value < 0x20 + 16 * COMPARATOR_COUNT as u32 => {
+                let n = ((value - 0x20) / 16) as u8;
+                match value % 16 {
+                    0x0 => DwtRegister::Comp(n),
+                    0x4 => DwtRegister::Mask(n),
+                    0x8 => DwtRegister::Function(n),
+                    _ => return Err(()),
+                }
+            }
+            _ => return Err(()),
+        })
+    }
+}
+
+/// Accesses watched by a comparator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WatchKind {
+    Instruction,
+    Read,
+    Write,
+    Access,
+}
+
+impl WatchKind {
+    fn matches_data(&self, write: bool) -> bool {
+        match self {
+            WatchKind::Instruction => false,
+            WatchKind::Read => !write,
+            WatchKind::Write => write,
+            WatchKind::Access => true,
+        }
+    }
+}
+
+/// Comparator configuration, decoded from the DWT registers.
+struct Watchpoint {
+    kind: WatchKind,
+    /// First and last watched addresses.
+    range: (u32, u32),
+    /// Watched data value and access size, for data value comparators.
+    value: Option<(u32, usize)>,
+    /// Whether a match generates a debug event.
+    debug_event: bool,
+}
+
+impl Watchpoint {
+    /// Returns `true` if an access of `size` bytes at `address` hits the watched address range.
+    fn contains(&self, address: u32, size: usize) -> bool {
+        let last = address.saturating_add(size as u32 - 1);
+        address <= self.range.1 && self.range.0 <= last
+    }
+}
+
+/// One DWT comparator.
+#[derive(Default)]
+struct Comparator {
+    comp: u32,
+    mask: u32,
+    function: u32,
+}
+
+/// State shared by the Debug Control Block and the Data Watchpoint and Trace unit.
+///
+/// This state is mapped into the processor memory space through [DebugControlBlock] and
+/// [DataWatchpointTrace], and can be accessed with [crate::core::Processor::debug].
+pub struct DebugRegisters {
+    version: ArmVersion,
+    /// DHCSR.C_DEBUGEN, set when a debugger is attached to the processor. In that case DWT debug
+    /// events halt the execution by emitting [crate::core::Event::Watchpoint], and the
+    /// DebugMonitor exception is never taken. This bit cannot be changed by software.
+    pub halting_debug: bool,
+    demcr: u32,
+    dcrdr: u32,
+    ctrl: u32,
+    /// Value of CYCCNT when it was last written, enabled or disabled.
+    cyccnt: u32,
+    /// Cycle at which `cyccnt` was saved.
+    cyccnt_cycles: u64,
+    cpicnt: u8,
+    exccnt: u8,
+    sleepcnt: u8,
+    lsucnt: u8,
+    foldcnt: u8,
+    comparators: [Comparator; COMPARATOR_COUNT],
+    /// Cached result of [DebugRegisters::is_watching].
+    watching: bool,
+}
+
+impl DebugRegisters {
+    pub fn new(version: ArmVersion) -> Self {
+        Self {
+            version,
+            halting_debug: false,
+            demcr: 0,
+            dcrdr: 0,
+            ctrl: 0,
+            cyccnt: 0,
+            cyccnt_cycles: 0,
+            cpicnt: 0,
+            exccnt: 0,
+            sleepcnt: 0,
+            lsucnt: 0,
+            foldcnt: 0,
+            comparators: Default::default(),
+            watching: false,
+        }
+    }
+
+    /// Returns DEMCR.TRCENA, which enables the DWT and ITM.
+    pub fn trcena(&self) -> bool {
+        self.demcr.bit(DEMCR_TRCENA)
+    }
+
+    /// Returns DEMCR.MON_EN, which enables the DebugMonitor exception.
+    pub fn monitor_enabled(&self) -> bool {
+        self.demcr.bit(DEMCR_MON_EN)
+    }
+
+    /// Returns the value of CYCCNT at time `cycles`.
+    pub fn cyccnt(&self, cycles: u64) -> u32 {
+        if self.is_counting_cycles() {
+            self.cyccnt
+                .wrapping_add(cycles.wrapping_sub(self.cyccnt_cycles) as u32)
+        } else {
+            self.cyccnt
+        }
+    }
+
+    /// Returns the value of SLEEPCNT.
+    pub fn sleepcnt(&self) -> u8 {
+        self.sleepcnt
+    }
+
+    fn is_counting_cycles(&self) -> bool {
+        self.trcena() && self.ctrl.bit(CTRL_CYCCNTENA)
+    }
+
+    /// Freezes the current value of CYCCNT, before its value or enable state changes.
+    fn save_cyccnt(&mut self, cycles: u64) {
+        self.cyccnt = self.cyccnt(cycles);
+        self.cyccnt_cycles = cycles;
+    }
+
+    /// Counts `cycles` cycles spent in sleep mode.
+    pub(crate) fn count_sleep_cycles(&mut self, cycles: u64) {
+        if self.trcena() && self.ctrl.bit(CTRL_SLEEPEVTENA) {
+            self.sleepcnt = self.sleepcnt.wrapping_add(cycles as u8);
+        }
+    }
+
+    /// Returns `true` if the DWT is enabled and at least one comparator is configured, in which
+    /// case the processor must check its accesses with [DebugRegisters::match_instruction] and
+    /// [DebugRegisters::match_data].
+    pub(crate) fn is_watching(&self) -> bool {
+        self.watching
+    }
+
+    fn update_watching(&mut self) {
+        self.watching =
+            self.trcena() && (0..COMPARATOR_COUNT).any(|n| self.watchpoint(n).is_some());
+    }
+
+    /// Decodes the configuration of comparator `n`, or returns `None` if it is disabled or
+    /// configured for an unsupported function.
+    fn watchpoint(&self, n: usize) -> Option<Watchpoint> {
+        let c = &self.comparators[n];
+        match self.version {
+            ArmVersion::V6M | ArmVersion::V7M | ArmVersion::V7EM => {
+                // Address range given by the comparator COMP and MASK registers.
+                let range = |c: &Comparator| {
+                    let mask = ((1u64 << c.mask) - 1) as u32;
+                    (c.comp & !mask, c.comp | mask)
+                };
+                if c.function.bit(7) {
+                    // CYCMATCH
+                    return None;
+                }
+                let kind = match c.function & 0xf {
+                    0b0100 => WatchKind::Instruction,
+                    0b0101 => WatchKind::Read,
+                    0b0110 => WatchKind::Write,
+                    0b0111 => WatchKind::Access,
+                    _ => return None,
+                };
+                if !c.function.bit(8) {
+                    return Some(Watchpoint {
+                        kind,
+                        range: range(c),
+                        value: None,
+                        debug_event: true,
+                    });
+                }
+                if kind == WatchKind::Instruction {
+                    return None;
+                }
+                let size = 1 << ((c.function >> 10) & 3);
+                // Data value matching may be linked to the address of another comparator.
+                let link = ((c.function >> 12) & 0xf) as usize;
+                let range = if link != n && link < COMPARATOR_COUNT {
+                    range(&self.comparators[link])
+                } else {
+                    (0, u32::MAX)
+                };
+                Some(Watchpoint {
+                    kind,
+                    range,
+                    value: Some((c.comp, size)),
+                    debug_event: true,
+                })
+            }
+            ArmVersion::V8M | ArmVersion::V81M => {
+                let size = 1u32 << ((c.function >> 10) & 3);
+                let debug_event = (c.function >> 4) & 3 == 0b01;
+                // An address comparator followed by a limit comparator watches an address range.
+                let limit = |limit_match| {
+                    self.comparators
+                        .get(n + 1)
+                        .filter(|next| next.function & 0xf == limit_match)
+                        .map(|next| next.comp)
+                };
+                let (kind, range, value) = match c.function & 0xf {
+                    0b0010 => {
+                        let start = c.comp & !1;
+                        let range = (start, limit(0b0011).unwrap_or(start | 1));
+                        (WatchKind::Instruction, range, None)
+                    }
+                    m @ 0b0100..=0b0110 => {
+                        let range = (
+                            c.comp,
+                            limit(0b0111).unwrap_or(c.comp.saturating_add(size - 1)),
+                        );
+                        let kind = [WatchKind::Access, WatchKind::Write, WatchKind::Read]
+                            [m as usize - 0b0100];
+                        (kind, range, None)
+                    }
+                    m @ 0b1000..=0b1010 => {
+                        let kind = [WatchKind::Access, WatchKind::Write, WatchKind::Read]
+                            [m as usize - 0b1000];
+                        (kind, (0, u32::MAX), Some((c.comp, size as usize)))
+                    }
+                    _ => return None,
+                };
+                Some(Watchpoint {
+                    kind,
+                    range,
+                    value,
+                    debug_event,
+                })
+            }
+        }
+    }
+
+    /// Sets the MATCHED bit of the comparators matching the access, and returns the first
+    /// comparator generating a debug event, if any.
+    fn check(&mut self, matches: impl Fn(&Watchpoint) -> bool) -> Option<u8> {
+        let mut result = None;
+        for n in 0..COMPARATOR_COUNT {
+            if let Some(watchpoint) = self.watchpoint(n).filter(&matches) {
+                self.comparators[n].function.set_bit(FUNCTION_MATCHED, true);
+                if watchpoint.debug_event && result.is_none() {
+                    result = Some(n as u8);
+                }
+            }
+        }
+        result
+    }
+
+    /// Checks the execution of the instruction at `address` against the comparators. Returns
+    /// the first comparator generating a debug event, if any.
+    pub(crate) fn match_instruction(&mut self, address: u32) -> Option<u8> {
+        self.check(|w| w.kind == WatchKind::Instruction && w.contains(address, 2))
+    }
+
+    /// Checks a data access of `size` bytes at `address` against the comparators. `value` is
+    /// the data read or written. Returns the first comparator generating a debug event, if any.
+    pub(crate) fn match_data(
+        &mut self,
+        address: u32,
+        size: usize,
+        write: bool,
+        value: u32,
+    ) -> Option<u8> {
+        self.check(|w| {
+            w.kind.matches_data(write)
+                && w.contains(address, size)
+                && w.value.is_none_or(|(expected, expected_size)| {
+                    let mask = (u64::MAX >> (64 - 8 * expected_size)) as u32;
+                    size == expected_size && value & mask == expected & mask
+                })
+        })
+    }
+}
+
+/// Debug Control Block, mapped at `0xe000edf0`.
+///
+/// DHCSR and DCRSR are controlled by the debugger: software writes to those registers are
+/// ignored.
+pub struct DebugControlBlock(pub Rc<RefCell<DebugRegisters>>);
+
+impl RegistersMemoryInterface for DebugControlBlock {
+    type Register = DebugControlRegister;
+
+    fn read32(&mut self, reg: Self::Register, env: &mut Env) -> MemoryReadResult<u32> {
+        if !env.privileged {
+            return Err(MemoryAccessError::PrivilegedOnly);
+        }
+        let debug = self.0.borrow();
+        Ok(match reg {
+            DebugControlRegister::Dhcsr => debug.halting_debug as u32,
+            DebugControlRegister::Dcrsr => 0,
+            DebugControlRegister::Dcrdr => debug.dcrdr,
+            DebugControlRegister::Demcr => debug.demcr,
+        })
+    }
+
+    fn write32(&mut self, reg: Self::Register, value: u32, env: &mut Env) -> MemoryWriteResult {
+        if !env.privileged {
+            return Err(MemoryAccessError::PrivilegedOnly);
+        }
+        let mut debug = self.0.borrow_mut();
+        match reg {
+            DebugControlRegister::Dhcsr | DebugControlRegister::Dcrsr => {}
+            DebugControlRegister::Dcrdr => debug.dcrdr = value,
+            DebugControlRegister::Demcr => {
+                let mask = match debug.version {
+                    // DWTENA, VC_HARDERR and VC_CORERESET
+                    ArmVersion::V6M => 0x01000401,
+                    ArmVersion::V7M | ArmVersion::V7EM | ArmVersion::V8M | ArmVersion::V81M => {
+                        0x010d07f1
+                    }
+                };
+                if debug.version != ArmVersion::V6M && value.bit(DEMCR_MON_PEND) {
+                    env.request_interrupt(Irq::DebugMonitor);
+                }
+                debug.save_cyccnt(env.cycles);
+                debug.demcr = value & mask;
+                debug.update_watching();
+            }
+        }
+        Ok(())
+    }
+
+    fn size(&self) -> u32 {
+        0x10
+    }
+
+    fn scheduled_updates(&self) -> bool {
+        true
+    }
+}
+
+/// Data Watchpoint and Trace unit, mapped at `0xe0001000`.
+pub struct DataWatchpointTrace(pub Rc<RefCell<DebugRegisters>>);
+
+impl RegistersMemoryInterface for DataWatchpointTrace {
+    type Register = DwtRegister;
+
+    fn read32(&mut self, reg: Self::Register, env: &mut Env) -> MemoryReadResult<u32> {
+        if !env.privileged {
+            return Err(MemoryAccessError::PrivilegedOnly);
+        }
+        let mut debug = self.0.borrow_mut();
+        Ok(match reg {
+            // NUMCOMP, NOTRCPKT and NOEXTTRIG
+            DwtRegister::Ctrl => ((COMPARATOR_COUNT as u32) << 28) | 0x0c000000 | debug.ctrl,
+            DwtRegister::Cyccnt => debug.cyccnt(env.cycles),
+            DwtRegister::Cpicnt => debug.cpicnt as u32,
+            DwtRegister::Exccnt => debug.exccnt as u32,
+            DwtRegister::Sleepcnt => debug.sleepcnt as u32,
+            DwtRegister::Lsucnt => debug.lsucnt as u32,
+            DwtRegister::Foldcnt => debug.foldcnt as u32,
+            // PC sampling is not implemented.
+            DwtRegister::Pcsr => 0xffffffff,
+            DwtRegister::Comp(n) => debug.comparators[n as usize].comp,
+            DwtRegister::Mask(n) => debug.comparators[n as usize].mask,
+            DwtRegister::Function(n) => {
+                // MATCHED is cleared when read.
+                let function = &mut debug.comparators[n as usize].function;
+                let value = *function;
+                function.set_bit(FUNCTION_MATCHED, false);
+                value
+            }
+        })
+    }
+
+    fn write32(&mut self, reg: Self::Register, value: u32, env: &mut Env) -> MemoryWriteResult {
+        if !env.privileged {
+            return Err(MemoryAccessError::PrivilegedOnly);
+        }
+        let mut debug = self.0.borrow_mut();
+        let v8m = debug.version.includes(ArmVersion::V8M);
+        match reg {
+            DwtRegister::Ctrl => {
+                let mask = if v8m { 0x00ff1fff } else { 0x007f1fff };
+                if !debug.ctrl.bit(CTRL_SLEEPEVTENA) && value.bit(CTRL_SLEEPEVTENA) {
+                    // The counter is cleared when enabled.
+                    debug.sleepcnt = 0;
+                }
+                debug.save_cyccnt(env.cycles);
+                debug.ctrl = value & mask;
+            }
+            DwtRegister::Cyccnt => {
+                debug.cyccnt = value;
+                debug.cyccnt_cycles = env.cycles;
+            }
+            DwtRegister::Cpicnt => debug.cpicnt = value as u8,
+            DwtRegister::Exccnt => debug.exccnt = value as u8,
+            DwtRegister::Sleepcnt => debug.sleepcnt = value as u8,
+            DwtRegister::Lsucnt => debug.lsucnt = value as u8,
+            DwtRegister::Foldcnt => debug.foldcnt = value as u8,
+            DwtRegister::Pcsr => {}
+            DwtRegister::Comp(n) => debug.comparators[n as usize].comp = value,
+            DwtRegister::Mask(n) => {
+                // ArmV8-M comparators match address ranges with limit comparators instead.
+                if !v8m {
+                    debug.comparators[n as usize].mask = value & 0x1f
+                }
+            }
+            DwtRegister::Function(n) => {
+                let mask = if v8m { 0x00000c3f } else { 0x000ffdaf };
+                let function = &mut debug.comparators[n as usize].function;
+                *function = (*function & !mask) | (value & mask);
+            }
+        }
+        debug.update_watching();
+        Ok(())
+    }
+
+    fn size(&self) -> u32 {
+        0x1000
+    }
+
+    fn scheduled_updates(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DataWatchpointTrace, DebugControlBlock, DebugControlRegister, DebugRegisters, DwtRegister,
+    };
+    use crate::{
+        core::ArmVersion,
+        memory::{Env, RegistersMemoryInterface},
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    #[test]
+    fn test_dwt() {
+        let debug = Rc::new(RefCell::new(DebugRegisters::new(ArmVersion::V7M)));
+        let mut dcb = DebugControlBlock(debug.clone());
+        let mut dwt = DataWatchpointTrace(debug.clone());
+        let mut env = Env::new(100, true);
+
+        // Cycle counter only counts when TRCENA and CYCCNTENA are set.
+        dwt.write32(DwtRegister::Ctrl, 1, &mut env).unwrap();
+        env.cycles = 200;
+        assert_eq!(dwt.read32(DwtRegister::Cyccnt, &mut env), Ok(0));
+        dcb.write32(DebugControlRegister::Demcr, 1 << 24, &mut env)
+            .unwrap();
+        env.cycles = 250;
+        assert_eq!(dwt.read32(DwtRegister::Cyccnt, &mut env), Ok(50));
+        dwt.write32(DwtRegister::Cyccnt, 0xfffffff0, &mut env)
+            .unwrap();
+        env.cycles = 270;
+        assert_eq!(dwt.read32(DwtRegister::Cyccnt, &mut env), Ok(4));
+        dwt.write32(DwtRegister::Ctrl, 0, &mut env).unwrap();
+        env.cycles = 300;
+        assert_eq!(dwt.read32(DwtRegister::Cyccnt, &mut env), Ok(4));
+        assert_eq!(
+            dwt.read32(DwtRegister::Ctrl, &mut env),
+            Ok(0x4c000000),
+            "NUMCOMP"
+        );
+
+        // Comparator 1 watches writes to 0x20000100..0x20000104.
+        assert!(!debug.borrow().is_watching());
+        dwt.write32(DwtRegister::Comp(1), 0x20000100, &mut env)
+            .unwrap();
+        dwt.write32(DwtRegister::Mask(1), 2, &mut env).unwrap();
+        dwt.write32(DwtRegister::Function(1), 0b0110, &mut env)
+            .unwrap();
+        assert!(debug.borrow().is_watching());
+        assert_eq!(debug.borrow_mut().match_data(0x20000100, 4, false, 0), None);
+        assert_eq!(debug.borrow_mut().match_data(0x200000fc, 4, true, 0), None);
+        assert_eq!(
+            debug.borrow_mut().match_data(0x20000103, 1, true, 0),
+            Some(1)
+        );
+        assert_eq!(
+            dwt.read32(DwtRegister::Function(1), &mut env),
+            Ok(0x01000006)
+        );
+        assert_eq!(dwt.read32(DwtRegister::Function(1), &mut env), Ok(6));
+
+        // Comparator 2 watches the value 0x1234 written or read as halfword at the address of
+        // comparator 1.
+        dwt.write32(DwtRegister::Comp(2), 0x1234, &mut env).unwrap();
+        dwt.write32(
+            DwtRegister::Function(2),
+            0b0111 | 1 << 8 | 1 << 10 | 1 << 12,
+            &mut env,
+        )
+        .unwrap();
+        assert_eq!(
+            debug.borrow_mut().match_data(0x20000102, 2, false, 0x1234),
+            Some(2)
+        );
+        assert_eq!(
+            debug.borrow_mut().match_data(0x20000200, 2, false, 0x1234),
+            None
+        );
+        assert_eq!(
+            debug.borrow_mut().match_data(0x20000102, 2, false, 0x1235),
+            None
+        );
+
+        // Disabling the DWT disables the comparators.
+        dcb.write32(DebugControlRegister::Demcr, 0, &mut env)
+            .unwrap();
+        assert!(!debug.borrow().is_watching());
+    }
+
+    #[test]
+    fn test_dwt_v8m() {
+        let debug = Rc::new(RefCell::new(DebugRegisters::new(ArmVersion::V8M)));
+        let mut dwt = DataWatchpointTrace(debug.clone());
+        let mut env = Env::new(0, true);
+        debug.borrow_mut().demcr = 1 << 24;
+
+        // Instruction address range, generating debug events.
+        dwt.write32(DwtRegister::Comp(0), 0x1000, &mut env).unwrap();
+        dwt.write32(DwtRegister::Function(0), 0b010010, &mut env)
+            .unwrap();
+        dwt.write32(DwtRegister::Comp(1), 0x1010, &mut env).unwrap();
+        dwt.write32(DwtRegister::Function(1), 0b0011, &mut env)
+            .unwrap();
+        assert_eq!(debug.borrow_mut().match_instruction(0x0ffe), None);
+        assert_eq!(debug.borrow_mut().match_instruction(0x1008), Some(0));
+        assert_eq!(debug.borrow_mut().match_instruction(0x1012), None);
+
+        // Data value, without debug event.
+        dwt.write32(DwtRegister::Comp(2), 0xcafe, &mut env).unwrap();
+        dwt.write32(DwtRegister::Function(2), 0b1010 | 1 << 10, &mut env)
+            .unwrap();
+        assert_eq!(debug.borrow_mut().match_data(0x100, 2, false, 0xcafe), None);
+        assert_eq!(
+            dwt.read32(DwtRegister::Function(2), &mut env),
+            Ok(0x0100040a)
+        );
+    }
+}
@@ -15,6 +15,8 @@ use lru::LruCache;
 use crate::{
     arith::ArithError,
     core::ArmVersion,
+    core::Config,
+    core::FloatingPointExtension,
     core::ItState,
     instructions::{self, Encoding, Instruction, InstructionSize},
 };
@@ -221,6 +223,22 @@ impl InstructionPattern {
         Ok(true)
     }
 
+    /// Returns the pattern with the argument bit at `index` (0 being the least significant bit)
+    /// turned into an opcode bit, which must be equal to `value` for an instruction to match.
+    pub fn with_fixed_bit(mut self, index: usize, value: bool) -> Self {
+        let position = self.bits.len() - 1 - index;
+        self.bits[position] = if value {
+            InstructionPatternBit::OpcodeOne
+        } else {
+            InstructionPatternBit::OpcodeZero
+        };
+        self.test_mask |= 1 << index;
+        self.test_value = self.test_value & !(1 << index) | (value as u32) << index;
+        self.unp_mask &= !(1 << index);
+        self.unp_value &= !(1 << index);
+        self
+    }
+
     /// Number of bits of the instruction code this pattern is supposed to match.
     pub fn size(&self) -> InstructionSize {
         match self.bits.len() {
@@ -279,10 +297,60 @@ fn rc_decoder<T: 'static + Instruction>(
 }
 
 impl BasicInstructionDecoder {
-    pub fn new(version: ArmVersion) -> Self {
+    /// Creates a decoder for the architecture version and extensions of the given processor
+    /// configuration.
+    ///
+    /// Single-precision floating-point instructions are always decoded, and raise a UsageFault
+    /// when executed on a processor without FPU. Double-precision floating-point instructions are
+    /// only decoded with the [FloatingPointExtension::DoublePrecision] extension, and are
+    /// UNDEFINED otherwise.
+    ///
+    /// Vector instructions are only decoded when the M-profile Vector Extension is implemented.
+    /// Floating-point vector instructions are decoded for both [crate::core::MveExtension] variants, and are
+    /// UNDEFINED when executed on a processor implementing only the integer subset.
+    ///
+    /// Instructions in the coprocessor space of the coprocessors which implement the Custom
+    /// Datapath Extension are decoded as CDE instructions instead of generic coprocessor
+    /// instructions.
+    pub fn new(config: &Config) -> Self {
+        let version = config.version;
+        let custom_datapath = config.custom_datapath;
+        let double = config.fpu == Some(FloatingPointExtension::DoublePrecision);
+        // VCVTA, VMAXNM, VRINT and VSEL instructions are part of the ArmV8-M floating-point
+        // extension, but on ArmV7-M they are only implemented by FPv5 FPUs.
+        let fpv5 = double || version.includes(ArmVersion::V8M);
         let mut dec = Self {
             entries: Vec::new(),
         };
+        if config.mve.is_some() {
+            // Vector instructions are encoded in the coprocessor instructions space, so they must
+            // take precedence over coprocessor instructions.
+            dec.insert::<instructions::vadd::VaddVector>(version);
+            dec.insert::<instructions::vcmp::VcmpVector>(version);
+            dec.insert::<instructions::vctp::Vctp>(version);
+            dec.insert::<instructions::vdup::Vdup>(version);
+            dec.insert::<instructions::vldr::VldrGather>(version);
+            dec.insert::<instructions::vldr::VldrVector>(version);
+            dec.insert::<instructions::vmladav::Vmladav>(version);
+            dec.insert::<instructions::vmul::VmulVector>(version);
+            dec.insert::<instructions::vorr::Vorr>(version);
+            dec.insert::<instructions::vpnot::Vpnot>(version);
+            dec.insert::<instructions::vpst::Vpst>(version);
+            dec.insert::<instructions::vpt::Vpt>(version);
+            dec.insert::<instructions::vstr::VstrScatter>(version);
+            dec.insert::<instructions::vstr::VstrVector>(version);
+            dec.insert::<instructions::vsub::VsubVector>(version);
+        }
+        if custom_datapath != 0 {
+            // Custom Datapath Extension instructions are encoded in the coprocessor instructions
+            // space as well.
+            dec.insert_cde::<instructions::cx::Cx1>(version, custom_datapath);
+            dec.insert_cde::<instructions::cx::Cx2>(version, custom_datapath);
+            dec.insert_cde::<instructions::cx::Cx3>(version, custom_datapath);
+            dec.insert_cde::<instructions::vcx::Vcx1>(version, custom_datapath);
+            dec.insert_cde::<instructions::vcx::Vcx2>(version, custom_datapath);
+            dec.insert_cde::<instructions::vcx::Vcx3>(version, custom_datapath);
+        }
         dec.insert::<instructions::adc::AdcImm>(version);
         dec.insert::<instructions::adc::AdcReg>(version);
         dec.insert::<instructions::add::AddImm>(version);
@@ -294,15 +362,24 @@ impl BasicInstructionDecoder {
         dec.insert::<instructions::and::AndReg>(version);
         dec.insert::<instructions::asr::AsrImm>(version);
         dec.insert::<instructions::asr::AsrReg>(version);
+        dec.insert::<instructions::aut::Aut>(version);
+        dec.insert::<instructions::autg::Autg>(version);
         dec.insert::<instructions::b::B>(version);
+        dec.insert::<instructions::bf::Bf>(version);
         dec.insert::<instructions::bfc::Bfc>(version);
+        dec.insert::<instructions::bfcsel::Bfcsel>(version);
         dec.insert::<instructions::bfi::Bfi>(version);
+        dec.insert::<instructions::bfl::Bfl>(version);
+        dec.insert::<instructions::bflx::Bflx>(version);
+        dec.insert::<instructions::bfx::Bfx>(version);
         dec.insert::<instructions::bic::BicImm>(version);
         dec.insert::<instructions::bic::BicReg>(version);
         dec.insert::<instructions::bkpt::Bkpt>(version);
         dec.insert::<instructions::bl::Bl>(version);
         dec.insert::<instructions::blx::Blx>(version);
+        dec.insert::<instructions::bti::Bti>(version);
         dec.insert::<instructions::bx::Bx>(version);
+        dec.insert::<instructions::bxaut::Bxaut>(version);
         dec.insert::<instructions::cbnz::Cbnz>(version);
         dec.insert::<instructions::cdp::Cdp>(version);
         dec.insert::<instructions::clrex::Clrex>(version);
@@ -314,12 +391,20 @@ impl BasicInstructionDecoder {
         dec.insert::<instructions::cps::Cps>(version);
         dec.insert::<instructions::csdb::Csdb>(version);
         dec.insert::<instructions::dbg::Dbg>(version);
+        dec.insert::<instructions::dls::Dls>(version);
         dec.insert::<instructions::dmb::Dmb>(version);
         dec.insert::<instructions::dsb::Dsb>(version);
         dec.insert::<instructions::eor::EorImm>(version);
         dec.insert::<instructions::eor::EorReg>(version);
         dec.insert::<instructions::isb::Isb>(version);
         dec.insert::<instructions::it::It>(version);
+        dec.insert::<instructions::lctp::Lctp>(version);
+        dec.insert::<instructions::lda::Lda>(version);
+        dec.insert::<instructions::ldab::Ldab>(version);
+        dec.insert::<instructions::ldaex::Ldaex>(version);
+        dec.insert::<instructions::ldaexb::Ldaexb>(version);
+        dec.insert::<instructions::ldaexh::Ldaexh>(version);
+        dec.insert::<instructions::ldah::Ldah>(version);
         dec.insert::<instructions::ldc::LdcImm>(version);
         dec.insert::<instructions::ldc::LdcLit>(version);
         dec.insert::<instructions::ldm::Ldm>(version);
@@ -350,6 +435,7 @@ impl BasicInstructionDecoder {
         dec.insert::<instructions::ldrsh::LdrshReg>(version);
         dec.insert::<instructions::ldrsht::Ldrsht>(version);
         dec.insert::<instructions::ldrt::Ldrt>(version);
+        dec.insert::<instructions::le::Le>(version);
         dec.insert::<instructions::lsl::LslImm>(version);
         dec.insert::<instructions::lsl::LslReg>(version);
         dec.insert::<instructions::lsr::LsrImm>(version);
@@ -374,6 +460,10 @@ impl BasicInstructionDecoder {
         dec.insert::<instructions::orn::OrnReg>(version);
         dec.insert::<instructions::orr::OrrImm>(version);
         dec.insert::<instructions::orr::OrrReg>(version);
+        dec.insert::<instructions::pac::Pac>(version);
+        dec.insert::<instructions::pacbti::Pacbti>(version);
+        dec.insert::<instructions::pacg::Pacg>(version);
+        dec.insert::<instructions::pkhbt::Pkhbt>(version);
         dec.insert::<instructions::pop::Pop>(version);
         dec.insert::<instructions::pld::PldImm>(version);
         dec.insert::<instructions::pld::PldLit>(version);
@@ -385,8 +475,10 @@ impl BasicInstructionDecoder {
         dec.insert::<instructions::qadd16::Qadd16>(version);
         dec.insert::<instructions::qadd8::Qadd8>(version);
         dec.insert::<instructions::qadd::Qadd>(version);
+        dec.insert::<instructions::qasx::Qasx>(version);
         dec.insert::<instructions::qdadd::Qdadd>(version);
         dec.insert::<instructions::qdsub::Qdsub>(version);
+        dec.insert::<instructions::qsax::Qsax>(version);
         dec.insert::<instructions::qsub16::Qsub16>(version);
         dec.insert::<instructions::qsub8::Qsub8>(version);
         dec.insert::<instructions::qsub::Qsub>(version);
@@ -402,15 +494,48 @@ impl BasicInstructionDecoder {
         dec.insert::<instructions::rsb::RsbReg>(version);
         dec.insert::<instructions::sadd16::Sadd16>(version);
         dec.insert::<instructions::sadd8::Sadd8>(version);
+        dec.insert::<instructions::sasx::Sasx>(version);
         dec.insert::<instructions::sbc::SbcImm>(version);
         dec.insert::<instructions::sbc::SbcReg>(version);
         dec.insert::<instructions::sbfx::Sbfx>(version);
         dec.insert::<instructions::sdiv::Sdiv>(version);
+        dec.insert::<instructions::sel::Sel>(version);
         dec.insert::<instructions::sev::Sev>(version);
+        dec.insert::<instructions::sg::Sg>(version);
+        dec.insert::<instructions::shadd16::Shadd16>(version);
+        dec.insert::<instructions::shadd8::Shadd8>(version);
+        dec.insert::<instructions::shasx::Shasx>(version);
+        dec.insert::<instructions::shsax::Shsax>(version);
+        dec.insert::<instructions::shsub16::Shsub16>(version);
+        dec.insert::<instructions::shsub8::Shsub8>(version);
+        dec.insert::<instructions::smlabb::Smlabb>(version);
+        dec.insert::<instructions::smlad::Smlad>(version);
         dec.insert::<instructions::smlal::Smlal>(version);
+        dec.insert::<instructions::smlalbb::Smlalbb>(version);
+        dec.insert::<instructions::smlald::Smlald>(version);
+        dec.insert::<instructions::smlawb::Smlawb>(version);
+        dec.insert::<instructions::smlsd::Smlsd>(version);
+        dec.insert::<instructions::smlsld::Smlsld>(version);
+        dec.insert::<instructions::smmla::Smmla>(version);
+        dec.insert::<instructions::smmls::Smmls>(version);
+        dec.insert::<instructions::smmul::Smmul>(version);
+        dec.insert::<instructions::smuad::Smuad>(version);
+        dec.insert::<instructions::smulbb::Smulbb>(version);
         dec.insert::<instructions::smull::Smull>(version);
+        dec.insert::<instructions::smulwb::Smulwb>(version);
+        dec.insert::<instructions::smusd::Smusd>(version);
+        dec.insert::<instructions::ssat16::Ssat16>(version);
         dec.insert::<instructions::ssat::Ssat>(version);
+        dec.insert::<instructions::ssax::Ssax>(version);
+        dec.insert::<instructions::ssub16::Ssub16>(version);
+        dec.insert::<instructions::ssub8::Ssub8>(version);
         dec.insert::<instructions::stc::Stc>(version);
+        dec.insert::<instructions::stl::Stl>(version);
+        dec.insert::<instructions::stlb::Stlb>(version);
+        dec.insert::<instructions::stlex::Stlex>(version);
+        dec.insert::<instructions::stlexb::Stlexb>(version);
+        dec.insert::<instructions::stlexh::Stlexh>(version);
+        dec.insert::<instructions::stlh::Stlh>(version);
         dec.insert::<instructions::stm::Stm>(version);
         dec.insert::<instructions::stmdb::Stmdb>(version);
         dec.insert::<instructions::str::StrImm>(version);
@@ -432,6 +557,10 @@ impl BasicInstructionDecoder {
         dec.insert::<instructions::sub::SubSpMinusReg>(version);
         dec.insert::<instructions::ssbb::Ssbb>(version);
         dec.insert::<instructions::svc::Svc>(version);
+        dec.insert::<instructions::sxtab16::Sxtab16>(version);
+        dec.insert::<instructions::sxtab::Sxtab>(version);
+        dec.insert::<instructions::sxtah::Sxtah>(version);
+        dec.insert::<instructions::sxtb16::Sxtb16>(version);
         dec.insert::<instructions::sxtb::Sxtb>(version);
         dec.insert::<instructions::sxth::Sxth>(version);
         dec.insert::<instructions::tbb::Tbb>(version);
@@ -439,30 +568,151 @@ impl BasicInstructionDecoder {
         dec.insert::<instructions::teq::TeqReg>(version);
         dec.insert::<instructions::tst::TstImm>(version);
         dec.insert::<instructions::tst::TstReg>(version);
+        dec.insert::<instructions::tt::Tt>(version);
+        dec.insert::<instructions::uadd16::Uadd16>(version);
+        dec.insert::<instructions::uadd8::Uadd8>(version);
+        dec.insert::<instructions::uasx::Uasx>(version);
         dec.insert::<instructions::ubfx::Ubfx>(version);
         dec.insert::<instructions::udf::Udf>(version);
         dec.insert::<instructions::udiv::Udiv>(version);
+        dec.insert::<instructions::uhadd16::Uhadd16>(version);
+        dec.insert::<instructions::uhadd8::Uhadd8>(version);
+        dec.insert::<instructions::uhasx::Uhasx>(version);
+        dec.insert::<instructions::uhsax::Uhsax>(version);
+        dec.insert::<instructions::uhsub16::Uhsub16>(version);
+        dec.insert::<instructions::uhsub8::Uhsub8>(version);
+        dec.insert::<instructions::umaal::Umaal>(version);
         dec.insert::<instructions::umlal::Umlal>(version);
         dec.insert::<instructions::umull::Umull>(version);
+        dec.insert::<instructions::uqadd16::Uqadd16>(version);
+        dec.insert::<instructions::uqadd8::Uqadd8>(version);
+        dec.insert::<instructions::uqasx::Uqasx>(version);
+        dec.insert::<instructions::uqsax::Uqsax>(version);
+        dec.insert::<instructions::uqsub16::Uqsub16>(version);
+        dec.insert::<instructions::uqsub8::Uqsub8>(version);
+        dec.insert::<instructions::usad8::Usad8>(version);
+        dec.insert::<instructions::usada8::Usada8>(version);
         dec.insert::<instructions::usat16::Usat16>(version);
         dec.insert::<instructions::usat::Usat>(version);
+        dec.insert::<instructions::usax::Usax>(version);
+        dec.insert::<instructions::usub16::Usub16>(version);
+        dec.insert::<instructions::usub8::Usub8>(version);
+        dec.insert::<instructions::uxtab16::Uxtab16>(version);
+        dec.insert::<instructions::uxtab::Uxtab>(version);
+        dec.insert::<instructions::uxtah::Uxtah>(version);
+        dec.insert::<instructions::uxtb16::Uxtb16>(version);
         dec.insert::<instructions::uxtb::Uxtb>(version);
         dec.insert::<instructions::uxth::Uxth>(version);
+        dec.insert_fp::<instructions::vabs::Vabs>(version, double);
+        dec.insert_fp::<instructions::vadd::Vadd>(version, double);
+        dec.insert_fp::<instructions::vcmp::Vcmp>(version, double);
+        if double {
+            dec.insert::<instructions::vcvt::VcvtDouble>(version);
+        }
+        if fpv5 {
+            dec.insert_fp::<instructions::vcvt::VcvtDirected>(version, double);
+        }
+        dec.insert_fp::<instructions::vcvt::VcvtFixed>(version, double);
+        dec.insert_fp::<instructions::vcvt::VcvtHalf>(version, double);
+        dec.insert_fp::<instructions::vcvt::VcvtInt>(version, double);
+        dec.insert_fp::<instructions::vdiv::Vdiv>(version, double);
+        dec.insert_fp::<instructions::vfma::Vfma>(version, double);
+        dec.insert_fp::<instructions::vfnma::Vfnma>(version, double);
+        dec.insert::<instructions::vldm::Vldm>(version);
+        dec.insert::<instructions::vldr::Vldr>(version);
+        dec.insert::<instructions::vlldm::Vlldm>(version);
+        dec.insert::<instructions::vlstm::Vlstm>(version);
+        if fpv5 {
+            dec.insert_fp::<instructions::vmaxnm::Vmaxnm>(version, double);
+        }
+        dec.insert_fp::<instructions::vmla::Vmla>(version, double);
+        dec.insert::<instructions::vmov::VmovCoreDouble>(version);
+        dec.insert::<instructions::vmov::VmovCoreSingle>(version);
+        dec.insert::<instructions::vmov::VmovCoreToScalar>(version);
+        dec.insert::<instructions::vmov::VmovCoreTwoSingles>(version);
+        dec.insert_fp::<instructions::vmov::VmovImm>(version, double);
+        dec.insert_fp::<instructions::vmov::VmovReg>(version, double);
+        dec.insert::<instructions::vmov::VmovScalarToCore>(version);
+        dec.insert::<instructions::vmrs::Vmrs>(version);
+        dec.insert::<instructions::vmsr::Vmsr>(version);
+        dec.insert_fp::<instructions::vmul::Vmul>(version, double);
+        dec.insert_fp::<instructions::vneg::Vneg>(version, double);
+        dec.insert_fp::<instructions::vnmla::Vnmla>(version, double);
+        dec.insert::<instructions::vpop::Vpop>(version);
+        dec.insert::<instructions::vpush::Vpush>(version);
+        if fpv5 {
+            dec.insert_fp::<instructions::vrint::Vrint>(version, double);
+            dec.insert_fp::<instructions::vsel::Vsel>(version, double);
+        }
+        dec.insert_fp::<instructions::vsqrt::Vsqrt>(version, double);
+        dec.insert::<instructions::vstm::Vstm>(version);
+        dec.insert::<instructions::vstr::Vstr>(version);
+        dec.insert_fp::<instructions::vsub::Vsub>(version, double);
         dec.insert::<instructions::wfe::Wfe>(version);
         dec.insert::<instructions::wfi::Wfi>(version);
+        dec.insert::<instructions::wls::Wls>(version);
         dec
     }
 
     pub fn insert<T: 'static + Instruction>(&mut self, version: ArmVersion) {
-        let mut patterns = Vec::new();
-        for pattern in T::patterns().iter() {
-            if pattern.versions.iter().any(|&v| v == version) {
-                patterns.push((
+        let patterns = T::patterns()
+            .iter()
+            .filter(|pattern| pattern.versions.iter().any(|v| version.includes(*v)))
+            .map(|pattern| {
+                (
                     pattern.encoding,
                     InstructionPattern::new(pattern.expression),
-                ));
-            }
-        }
+                )
+            })
+            .collect();
+        self.push_entry::<T>(patterns);
+    }
+
+    /// Inserts a floating-point instruction whose `sz` bit (bit 8) selects double-precision
+    /// operation. When `double` is `false`, the patterns are restricted to single-precision
+    /// operation so the double-precision encodings are not decoded.
+    pub fn insert_fp<T: 'static + Instruction>(&mut self, version: ArmVersion, double: bool) {
+        let patterns = T::patterns()
+            .iter()
+            .filter(|pattern| pattern.versions.iter().any(|v| version.includes(*v)))
+            .map(|pattern| {
+                let matcher = InstructionPattern::new(pattern.expression);
+                let matcher = if double {
+                    matcher
+                } else {
+                    matcher.with_fixed_bit(8, false)
+                };
+                (pattern.encoding, matcher)
+            })
+            .collect();
+        self.push_entry::<T>(patterns);
+    }
+
+    /// Inserts a Custom Datapath Extension instruction, whose coprocessor index is encoded in
+    /// bits 10 to 8. One pattern is inserted for each coprocessor of the `coprocessors` bit mask,
+    /// so the coprocessor space of other coprocessors is left to generic coprocessor
+    /// instructions.
+    pub fn insert_cde<T: 'static + Instruction>(&mut self, version: ArmVersion, coprocessors: u8) {
+        let patterns = T::patterns()
+            .iter()
+            .filter(|pattern| pattern.versions.iter().any(|v| version.includes(*v)))
+            .flat_map(|pattern| {
+                (0..8).filter(|cp| coprocessors & (1 << cp) != 0).map(|cp| {
+                    let matcher = (0..3)
+                        .fold(InstructionPattern::new(pattern.expression), |matcher, i| {
+                            matcher.with_fixed_bit(8 + i, cp & (1 << i) != 0)
+                        });
+                    (pattern.encoding, matcher)
+                })
+            })
+            .collect();
+        self.push_entry::<T>(patterns);
+    }
+
+    fn push_entry<T: 'static + Instruction>(
+        &mut self,
+        patterns: Vec<(Encoding, InstructionPattern)>,
+    ) {
         if !patterns.is_empty() {
             self.entries.push(BasicDecoderEntry {
                 patterns,
@@ -508,8 +758,10 @@ pub struct Lut16InstructionDecoder {
 }
 
 impl Lut16InstructionDecoder {
-    pub fn new(version: ArmVersion) -> Self {
-        let base_decoder = BasicInstructionDecoder::new(version);
+    /// Creates a decoder for the architecture version and extensions of the given processor
+    /// configuration. See [BasicInstructionDecoder::new].
+    pub fn new(config: &Config) -> Self {
+        let base_decoder = BasicInstructionDecoder::new(config);
         let lut16 = (0..=u16::MAX)
             .map(|i| base_decoder.try_decode(i as u32, InstructionSize::Ins16, ItState::new()))
             .collect();
@@ -567,10 +819,10 @@ impl GroupedInstructionDecoder {
 
     pub fn try_from_basic_decoder(
         head_bit_count: u8,
-        version: ArmVersion,
+        config: &Config,
     ) -> Result<Self, GroupingError> {
         let mut result = Self::new(head_bit_count);
-        let basic_decoder = BasicInstructionDecoder::new(version);
+        let basic_decoder = BasicInstructionDecoder::new(config);
         for entry in basic_decoder.entries {
             result.try_insert_from_decoder_entry(&entry)?;
         }
@@ -648,8 +900,10 @@ pub struct Lut16AndGrouped32InstructionDecoder {
 }
 
 impl Lut16AndGrouped32InstructionDecoder {
-    pub fn new(version: ArmVersion) -> Self {
-        let lut_decoder = Lut16InstructionDecoder::new(version);
+    /// Creates a decoder for the architecture version and extensions of the given processor
+    /// configuration. See [BasicInstructionDecoder::new].
+    pub fn new(config: &Config) -> Self {
+        let lut_decoder = Lut16InstructionDecoder::new(config);
         let mut group_decoder = GroupedInstructionDecoder::new(5);
         for entry in lut_decoder.base_decoder.entries.iter() {
             for (tn, pattern) in entry
@@ -690,11 +944,13 @@ struct DecodeCacheKey {
     state: ItState,
 }
 
+/// Cache storage used by [LruCachedInstuctionDecoder].
+type DecodeCache = LruCache<DecodeCacheKey, Result<Rc<dyn Instruction>, InstructionDecodeError>>;
+
 /// A decoder caching decode with an LRU cache of it's inner decoder.
 pub struct LruCachedInstuctionDecoder<D: InstructionDecode> {
     decoder: D,
-    decode_cache:
-        RefCell<LruCache<DecodeCacheKey, Result<Rc<dyn Instruction>, InstructionDecodeError>>>,
+    decode_cache: RefCell<DecodeCache>,
 }
 
 impl<D> LruCachedInstuctionDecoder<D>
@@ -742,8 +998,8 @@ mod tests {
     };
     use crate::{
         core::ItState,
-        core::{ArmVersion::V7EM, Config, Processor},
-        decoder::InstructionDecode,
+        core::{Config, FloatingPointExtension, MveExtension, Processor},
+        decoder::{InstructionDecode, InstructionDecodeError},
         instructions::{InstructionSize, Mnemonic},
     };
     use rand::Rng;
@@ -756,11 +1012,43 @@ mod tests {
 
     #[test]
     fn test_dissassembly() {
-        let file = File::open("src/test_decoder.txt").unwrap();
+        check_disassembly(
+            "src/test_decoder.txt",
+            Config::v7em().fpu(FloatingPointExtension::DoublePrecision),
+        );
+    }
+
+    /// Same as [test_dissassembly] for instructions which are only available in ArmV8-M and
+    /// ArmV8.1-M.
+    #[test]
+    fn test_dissassembly_v8m() {
+        check_disassembly(
+            "src/test_decoder_v8m.txt",
+            (0..8).fold(
+                Config::v81m()
+                    .fpu(FloatingPointExtension::DoublePrecision)
+                    .mve(MveExtension::FloatingPoint),
+                |config, cp| config.custom_datapath(cp),
+            ),
+        );
+    }
+
+    /// Decodes all the instructions of test vector file `path` and checks their mnemonics match
+    /// the ones given by llvm-objdump.
+    fn check_disassembly(path: &str, config: Config) {
+        let file = File::open(path).unwrap();
         let buf_reader = BufReader::new(file);
-        let decoder = BasicInstructionDecoder::new(V7EM);
-        let mut proc = Processor::new(Config::v7em());
+        let decoder = BasicInstructionDecoder::new(&config);
+        let mut proc = Processor::new(config);
         let mut pc = 0x1000;
+        // Vector instructions must be executed for VPT blocks to be tracked, which requires
+        // access to the floating-point extension.
+        if proc.fpu().is_some() {
+            proc.write_u32le_iface(0xe000ed88, 0x00f00000).unwrap();
+        }
+        // Predicate sense of the current instruction in a VPT block: `true` for then, `false`
+        // for else.
+        let mut vpt_then = true;
 
         for line in buf_reader.lines().map(|l| l.unwrap()) {
             // Skip comment lines
@@ -797,7 +1085,23 @@ mod tests {
                 println!("  Mnemonic: {mnemonic}");
                 panic!();
             };
-            let got_mnemonic = ins.mnemonic(pc, cond);
+            // llvm-objdump adds the then/else suffix to the name of instructions in VPT blocks.
+            let vpt_mask = proc.registers.vpr.mask(0);
+            let got_mnemonic = if vpt_mask != 0 {
+                let mut name = ins.name();
+                name.insert(
+                    name.find('.').unwrap_or(name.len()),
+                    if vpt_then { 't' } else { 'e' },
+                );
+                let args = ins.args(pc);
+                if args.is_empty() {
+                    name
+                } else {
+                    format!("{name:<8} {args}")
+                }
+            } else {
+                ins.mnemonic(pc, cond)
+            };
             if got_mnemonic != mnemonic {
                 println!("Mnemonic generation failed:");
                 println!("  Expected: {mnemonic}");
@@ -812,11 +1116,59 @@ mod tests {
             // llvm-objdump does set the condition in the name of instruction following IT
             // instructions, we need to do the same.
             let _result = ins.execute(&mut proc);
+            if vpt_mask & 0b1000 != 0 {
+                vpt_then = !vpt_then;
+            } else if vpt_mask == 0 {
+                vpt_then = true;
+            }
 
             pc += size.byte_count() as u32;
         }
     }
 
+    /// Checks that double-precision and FPv5 instructions are only decoded when the
+    /// floating-point extension implements them.
+    #[test]
+    fn test_double_precision_decoding() {
+        let it = ItState::new();
+        let size = InstructionSize::Ins32;
+        let single = BasicInstructionDecoder::new(
+            &Config::v7em().fpu(FloatingPointExtension::SinglePrecision),
+        );
+        let double = BasicInstructionDecoder::new(
+            &Config::v7em().fpu(FloatingPointExtension::DoublePrecision),
+        );
+        // vadd.f32 s0, s2, s30
+        assert_eq!(
+            single.try_decode(0xee310a0f, size, it).unwrap().name(),
+            "vadd.f32"
+        );
+        // vadd.f64 d0, d1, d15
+        assert!(matches!(
+            single.try_decode(0xee310b0f, size, it),
+            Err(InstructionDecodeError::Unknown)
+        ));
+        assert_eq!(
+            double.try_decode(0xee310b0f, size, it).unwrap().name(),
+            "vadd.f64"
+        );
+        // vseleq.f32 s0, s1, s2
+        assert!(single.try_decode(0xfe000a81, size, it).is_err());
+        assert_eq!(
+            double.try_decode(0xfe000a81, size, it).unwrap().name(),
+            "vseleq.f32"
+        );
+        // Only D0 to D15 exist, so encodings with D16 to D31 operands are undefined:
+        // vadd.f64 d16, d1, d15
+        // vmul.f64 d0, d17, d1
+        // vsqrt.f64 d0, d16
+        // vmov.f64 d16, d0
+        // vadd.f64 d25, d22, d8
+        for ins in [0xee710b0f, 0xee210b81, 0xeeb10be0, 0xeef00b40, 0xee769b88] {
+            assert!(double.try_decode(ins, size, it).is_err());
+        }
+    }
+
     fn test_decoder(
         a: &dyn InstructionDecode,
         b: &dyn InstructionDecode,
@@ -840,19 +1192,19 @@ mod tests {
     /// Checks that [Lut16InstructionDecoder] always decodes the same as [BasicInstructionDecoder].
     #[test]
     fn test_instruction_decoders() {
-        let dec_a = BasicInstructionDecoder::new(V7EM);
-        let dec_b = Lut16InstructionDecoder::new(V7EM);
-        let dec_c = Lut16AndGrouped32InstructionDecoder::new(V7EM);
+        let dec_a = BasicInstructionDecoder::new(&Config::v7em());
+        let dec_b = Lut16InstructionDecoder::new(&Config::v7em());
+        let dec_c = Lut16AndGrouped32InstructionDecoder::new(&Config::v7em());
         let dec_a_cached = LruCachedInstuctionDecoder::new(
-            BasicInstructionDecoder::new(V7EM),
+            BasicInstructionDecoder::new(&Config::v7em()),
             NonZeroUsize::new(1000).unwrap(),
         );
         let dec_b_cached = LruCachedInstuctionDecoder::new(
-            Lut16InstructionDecoder::new(V7EM),
+            Lut16InstructionDecoder::new(&Config::v7em()),
             NonZeroUsize::new(1000).unwrap(),
         );
         let dec_c_cached = LruCachedInstuctionDecoder::new(
-            Lut16AndGrouped32InstructionDecoder::new(V7EM),
+            Lut16AndGrouped32InstructionDecoder::new(&Config::v7em()),
             NonZeroUsize::new(1000).unwrap(),
         );
         let it = ItState::new();
@@ -0,0 +1,1244 @@
+//! Floating-point arithmetic emulation.
+//!
+//! Operations are implemented in software following the pseudocode of the Arm Architecture
+//! Reference Manual, so that results, NaN propagation, rounding and cumulative exception flags
+//! stored in FPSCR match the hardware exactly, whatever the host platform is. Floating-point
+//! values are manipulated as raw bits, using `u16`, `u32` and `u64` for half, single and double
+//! precision formats respectively.
+
+use crate::registers::{FloatingPointStatusControlRegister as Fpscr, RoundingMode};
+use std::cmp::Ordering;
+
+/// Raw bits of a floating-point value, which defines the format of the value.
+pub trait FloatBits: Copy {
+    /// Total number of bits.
+    const N: u32;
+    /// Number of bits of the exponent field.
+    const E: u32;
+    /// Number of bits of the fraction field.
+    const F: u32;
+
+    /// Returns the bits zero-extended to 64 bits.
+    fn to_u64(self) -> u64;
+
+    /// Returns the `N` least significant bits of `value`.
+    fn from_u64(value: u64) -> Self;
+}
+
+impl FloatBits for u16 {
+    const N: u32 = 16;
+    const E: u32 = 5;
+    const F: u32 = 10;
+
+    fn to_u64(self) -> u64 {
+        self as u64
+    }
+
+    fn from_u64(value: u64) -> Self {
+        value as u16
+    }
+}
+
+impl FloatBits for u32 {
+    const N: u32 = 32;
+    const E: u32 = 8;
+    const F: u32 = 23;
+
+    fn to_u64(self) -> u64 {
+        self as u64
+    }
+
+    fn from_u64(value: u64) -> Self {
+        value as u32
+    }
+}
+
+impl FloatBits for u64 {
+    const N: u32 = 64;
+    const E: u32 = 11;
+    const F: u32 = 52;
+
+    fn to_u64(self) -> u64 {
+        self
+    }
+
+    fn from_u64(value: u64) -> Self {
+        value
+    }
+}
+
+/// Type of an unpacked floating-point value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FpType {
+    Nonzero,
+    Zero,
+    Infinity,
+    QNaN,
+    SNaN,
+}
+
+/// Unpacked floating-point value.
+///
+/// For [FpType::Nonzero] values, the real value is `mant * 2^exp`.
+#[derive(Debug, Clone, Copy)]
+struct Unpacked {
+    kind: FpType,
+    sign: bool,
+    mant: u64,
+    exp: i32,
+    /// Original value bits, required for NaN propagation.
+    bits: u64,
+}
+
+impl Unpacked {
+    fn is_nan(&self) -> bool {
+        matches!(self.kind, FpType::QNaN | FpType::SNaN)
+    }
+}
+
+/// Returns the exponent bias of the format.
+fn bias<T: FloatBits>() -> i32 {
+    (1 << (T::E - 1)) - 1
+}
+
+/// Returns the value with all exponent bits set.
+fn exponent_ones<T: FloatBits>() -> u64 {
+    ((1 << T::E) - 1) << T::F
+}
+
+/// Returns the sign bit of the format at its position.
+fn sign_bit<T: FloatBits>(sign: bool) -> u64 {
+    (sign as u64) << (T::N - 1)
+}
+
+/// Corresponds to `FPZero()` in the Arm Architecture Reference Manual.
+fn zero<T: FloatBits>(sign: bool) -> T {
+    T::from_u64(sign_bit::<T>(sign))
+}
+
+/// Corresponds to `FPInfinity()` in the Arm Architecture Reference Manual.
+fn infinity<T: FloatBits>(sign: bool) -> T {
+    T::from_u64(sign_bit::<T>(sign) | exponent_ones::<T>())
+}
+
+/// Corresponds to `FPMaxNormal()` in the Arm Architecture Reference Manual.
+fn max_normal<T: FloatBits>(sign: bool) -> T {
+    T::from_u64(sign_bit::<T>(sign) | (exponent_ones::<T>() - 1))
+}
+
+/// Corresponds to `FPDefaultNaN()` in the Arm Architecture Reference Manual.
+fn default_nan<T: FloatBits>() -> T {
+    T::from_u64(exponent_ones::<T>() | (1 << (T::F - 1)))
+}
+
+/// Returns `true` if the format is half-precision and FPSCR selects the alternative
+/// half-precision format, which has no infinity or NaN.
+fn alternative_half<T: FloatBits>(fpscr: &Fpscr) -> bool {
+    T::N == 16 && fpscr.ahp()
+}
+
+/// Unpacks a floating-point value. Input denormals are flushed to zero when FPSCR.FZ is set,
+/// except for half-precision values.
+///
+/// Corresponds to `FPUnpack()` in the Arm Architecture Reference Manual.
+fn unpack<T: FloatBits>(fpval: T, fpscr: &mut Fpscr) -> Unpacked {
+    let bits = fpval.to_u64();
+    let sign = (bits >> (T::N - 1)) & 1 != 0;
+    let exp = ((bits >> T::F) & ((1 << T::E) - 1)) as i32;
+    let frac = bits & ((1 << T::F) - 1);
+    let (kind, mant, exp) = if exp == 0 {
+        if frac == 0 {
+            (FpType::Zero, 0, 0)
+        } else if fpscr.fz() && T::N != 16 {
+            fpscr.set_idc(true);
+            (FpType::Zero, 0, 0)
+        } else {
+            (FpType::Nonzero, frac, 1 - bias::<T>() - T::F as i32)
+        }
+    } else if exp == (1 << T::E) - 1 && !alternative_half::<T>(fpscr) {
+        if frac == 0 {
+            (FpType::Infinity, 0, 0)
+        } else if (frac >> (T::F - 1)) & 1 != 0 {
+            (FpType::QNaN, 0, 0)
+        } else {
+            (FpType::SNaN, 0, 0)
+        }
+    } else {
+        (
+            FpType::Nonzero,
+            frac | (1 << T::F),
+            exp - bias::<T>() - T::F as i32,
+        )
+    };
+    Unpacked {
+        kind,
+        sign,
+        mant,
+        exp,
+        bits,
+    }
+}
+
+/// Shifts `value` right by `shift` bits, and returns the shifted value, the most significant
+/// discarded bit and whether any other discarded bit is set.
+fn shift_right(value: u128, shift: u32) -> (u128, bool, bool) {
+    debug_assert!(shift > 0);
+    if shift > 128 {
+        (0, false, value != 0)
+    } else if shift == 128 {
+        (0, value >> 127 != 0, value & !(1 << 127) != 0)
+    } else {
+        let half = (value >> (shift - 1)) & 1 != 0;
+        let sticky = value & ((1 << (shift - 1)) - 1) != 0;
+        (value >> shift, half, sticky)
+    }
+}
+
+/// Shifts `value` right by `shift` bits, setting the least significant bit of the result if any
+/// discarded bit is set, so the result remains inexact.
+fn shift_right_jam(value: u128, shift: u32) -> u128 {
+    if shift == 0 {
+        return value;
+    }
+    let (result, half, sticky) = shift_right(value, shift);
+    result | (half || sticky) as u128
+}
+
+/// Shifts the non-zero `mant * 2^exp` value so the most significant bit of the mantissa is at
+/// bit 125, leaving room for additions without overflow.
+fn normalize(mant: u128, exp: i32) -> (u128, i32) {
+    debug_assert!(mant != 0);
+    let msb = 127 - mant.leading_zeros() as i32;
+    if msb <= 125 {
+        (mant << (125 - msb), exp - (125 - msb))
+    } else {
+        let shift = (msb - 125) as u32;
+        (shift_right_jam(mant, shift), exp + shift as i32)
+    }
+}
+
+/// Rounds the non-zero real value `mant * 2^exp` with the given sign to the format `T`, using the
+/// `rounding` mode. If the least significant bit of `mant` is set, it may represent any non-zero
+/// fraction of that bit, as long as it is far below the rounding position.
+///
+/// Corresponds to `FPRound()` in the Arm Architecture Reference Manual.
+fn round<T: FloatBits>(
+    sign: bool,
+    mant: u128,
+    exp: i32,
+    rounding: RoundingMode,
+    fpscr: &mut Fpscr,
+) -> T {
+    debug_assert!(mant != 0);
+    let f = T::F as i32;
+    let minimum_exp = 2 - (1 << (T::E - 1));
+    // Exponent such that 1.0 <= value / 2^exponent < 2.0.
+    let exponent = exp + 127 - mant.leading_zeros() as i32;
+    if fpscr.fz() && T::N != 16 && exponent < minimum_exp {
+        fpscr.set_ufc(true);
+        return zero(sign);
+    }
+    let mut biased_exp = (exponent - minimum_exp + 1).max(0) as u64;
+    // Denormalized values are truncated at the same position as the smallest normalized values.
+    let shift = exponent.max(minimum_exp) - f - exp;
+    let (int_mant, half, sticky) = if shift <= 0 {
+        (mant << -shift, false, false)
+    } else {
+        shift_right(mant, shift as u32)
+    };
+    let mut int_mant = int_mant as u64;
+    let mut inexact = half || sticky;
+    if biased_exp == 0 && inexact {
+        fpscr.set_ufc(true);
+    }
+    let (round_up, overflow_to_inf) = match rounding {
+        RoundingMode::Nearest => (half && (sticky || int_mant & 1 != 0), true),
+        RoundingMode::PlusInfinity => (inexact && !sign, !sign),
+        RoundingMode::MinusInfinity => (inexact && sign, sign),
+        RoundingMode::Zero => (false, false),
+        RoundingMode::TiesAway => (half, true),
+    };
+    if round_up {
+        int_mant += 1;
+        if int_mant == 1 << f {
+            // Rounded up from denormalized to normalized.
+            biased_exp = 1;
+        }
+        if int_mant == 1 << (f + 1) {
+            // Rounded up to next exponent.
+            biased_exp += 1;
+            int_mant >>= 1;
+        }
+    }
+    let result = if !alternative_half::<T>(fpscr) {
+        if biased_exp >= (1 << T::E) - 1 {
+            fpscr.set_ofc(true);
+            // Overflow always produces an inexact result.
+            inexact = true;
+            return_overflow::<T>(sign, overflow_to_inf)
+        } else {
+            sign_bit::<T>(sign) | (biased_exp << T::F) | (int_mant & ((1 << T::F) - 1))
+        }
+    } else if biased_exp >= 1 << T::E {
+        fpscr.set_ioc(true);
+        inexact = false;
+        sign_bit::<T>(sign) | ((1 << (T::N - 1)) - 1)
+    } else {
+        sign_bit::<T>(sign) | (biased_exp << T::F) | (int_mant & ((1 << T::F) - 1))
+    };
+    if inexact {
+        fpscr.set_ixc(true);
+    }
+    T::from_u64(result)
+}
+
+/// Returns the bits of the result of an overflow, which is either an infinity or the maximum
+/// normal value depending on the rounding mode.
+fn return_overflow<T: FloatBits>(sign: bool, overflow_to_inf: bool) -> u64 {
+    if overflow_to_inf {
+        infinity::<T>(sign).to_u64()
+    } else {
+        max_normal::<T>(sign).to_u64()
+    }
+}
+
+/// Rounds the signed sum of two non-zero values. Returns [None] if the exact result is zero.
+fn add_values(
+    (sign1, mant1, exp1): (bool, u128, i32),
+    (sign2, mant2, exp2): (bool, u128, i32),
+) -> Option<(bool, u128, i32)> {
+    let (mut a, mut b) = (normalize(mant1, exp1), normalize(mant2, exp2));
+    let (mut sign_a, mut sign_b) = (sign1, sign2);
+    if a.1 < b.1 {
+        (a, b) = (b, a);
+        (sign_a, sign_b) = (sign_b, sign_a);
+    }
+    // Exponents difference may be very large, which is not an issue since shifting by more than
+    // 128 bits is handled.
+    let b_mant = shift_right_jam(b.0, (a.1 - b.1).min(200) as u32);
+    if sign_a == sign_b {
+        Some((sign_a, a.0 + b_mant, a.1))
+    } else {
+        match a.0.cmp(&b_mant) {
+            Ordering::Greater => Some((sign_a, a.0 - b_mant, a.1)),
+            Ordering::Less => Some((sign_b, b_mant - a.0, a.1)),
+            Ordering::Equal => None,
+        }
+    }
+}
+
+/// Returns the zero resulting from the exact cancellation of an addition.
+fn cancellation_zero<T: FloatBits>(rounding: RoundingMode) -> T {
+    zero(rounding == RoundingMode::MinusInfinity)
+}
+
+/// Corresponds to `FPProcessNaN()` in the Arm Architecture Reference Manual.
+fn process_nan<T: FloatBits>(op: &Unpacked, fpscr: &mut Fpscr) -> T {
+    debug_assert!(op.is_nan());
+    let mut result = op.bits;
+    if op.kind == FpType::SNaN {
+        result |= 1 << (T::F - 1);
+        fpscr.set_ioc(true);
+    }
+    if fpscr.dn() {
+        default_nan()
+    } else {
+        T::from_u64(result)
+    }
+}
+
+/// Returns the processed NaN result of an operation if any of the operands is a NaN. Signaling
+/// NaNs take precedence over quiet NaNs, and then the first operand takes precedence.
+///
+/// Corresponds to `FPProcessNaNs()` and `FPProcessNaNs3()` in the Arm Architecture Reference
+/// Manual.
+fn process_nans<T: FloatBits>(ops: &[Unpacked], fpscr: &mut Fpscr) -> Option<T> {
+    let nan = ops
+        .iter()
+        .find(|op| op.kind == FpType::SNaN)
+        .or_else(|| ops.iter().find(|op| op.kind == FpType::QNaN))?;
+    Some(process_nan(nan, fpscr))
+}
+
+/// Marks an invalid operation and returns the default NaN.
+fn invalid_operation<T: FloatBits>(fpscr: &mut Fpscr) -> T {
+    fpscr.set_ioc(true);
+    default_nan()
+}
+
+/// Returns the absolute value of `op`.
+///
+/// Corresponds to `FPAbs()` in the Arm Architecture Reference Manual.
+pub fn fp_abs<T: FloatBits>(op: T) -> T {
+    T::from_u64(op.to_u64() & !sign_bit::<T>(true))
+}
+
+/// Returns the negation of `op`.
+///
+/// Corresponds to `FPNeg()` in the Arm Architecture Reference Manual.
+pub fn fp_neg<T: FloatBits>(op: T) -> T {
+    T::from_u64(op.to_u64() ^ sign_bit::<T>(true))
+}
+
+/// Floating-point addition.
+///
+/// Corresponds to `FPAdd()` in the Arm Architecture Reference Manual.
+pub fn fp_add<T: FloatBits>(op1: T, op2: T, fpscr: &mut Fpscr) -> T {
+    let a = unpack(op1, fpscr);
+    let b = unpack(op2, fpscr);
+    add_unpacked(a, b, fpscr)
+}
+
+/// Floating-point subtraction.
+///
+/// Corresponds to `FPSub()` in the Arm Architecture Reference Manual.
+pub fn fp_sub<T: FloatBits>(op1: T, op2: T, fpscr: &mut Fpscr) -> T {
+    let a = unpack(op1, fpscr);
+    let mut b = unpack(op2, fpscr);
+    if let Some(result) = process_nans(&[a, b], fpscr) {
+        return result;
+    }
+    b.sign = !b.sign;
+    add_unpacked(a, b, fpscr)
+}
+
+fn add_unpacked<T: FloatBits>(a: Unpacked, b: Unpacked, fpscr: &mut Fpscr) -> T {
+    use FpType::{Infinity, Zero};
+    if let Some(result) = process_nans(&[a, b], fpscr) {
+        return result;
+    }
+    let rounding = fpscr.rmode();
+    match (a.kind, b.kind) {
+        (Infinity, Infinity) if a.sign != b.sign => invalid_operation(fpscr),
+        (Infinity, _) => infinity(a.sign),
+        (_, Infinity) => infinity(b.sign),
+        (Zero, Zero) if a.sign == b.sign => zero(a.sign),
+        (Zero, Zero) => cancellation_zero(rounding),
+        (Zero, _) => round(b.sign, b.mant as u128, b.exp, rounding, fpscr),
+        (_, Zero) => round(a.sign, a.mant as u128, a.exp, rounding, fpscr),
+        _ => match add_values(
+            (a.sign, a.mant as u128, a.exp),
+            (b.sign, b.mant as u128, b.exp),
+        ) {
+            Some((sign, mant, exp)) => round(sign, mant, exp, rounding, fpscr),
+            None => cancellation_zero(rounding),
+        },
+    }
+}
+
+/// Floating-point multiplication.
+///
+/// Corresponds to `FPMul()` in the Arm Architecture Reference Manual.
+pub fn fp_mul<T: FloatBits>(op1: T, op2: T, fpscr: &mut Fpscr) -> T {
+    use FpType::{Infinity, Zero};
+    let a = unpack(op1, fpscr);
+    let b = unpack(op2, fpscr);
+    if let Some(result) = process_nans(&[a, b], fpscr) {
+        return result;
+    }
+    let sign = a.sign != b.sign;
+    match (a.kind, b.kind) {
+        (Infinity, Zero) | (Zero, Infinity) => invalid_operation(fpscr),
+        (Infinity, _) | (_, Infinity) => infinity(sign),
+        (Zero, _) | (_, Zero) => zero(sign),
+        _ => {
+            let rounding = fpscr.rmode();
+            round(
+                sign,
+                a.mant as u128 * b.mant as u128,
+                a.exp + b.exp,
+                rounding,
+                fpscr,
+            )
+        }
+    }
+}
+
+/// Floating-point division.
+///
+/// Corresponds to `FPDiv()` in the Arm Architecture Reference Manual.
+pub fn fp_div<T: FloatBits>(op1: T, op2: T, fpscr: &mut Fpscr) -> T {
+    use FpType::{Infinity, Zero};
+    let a = unpack(op1, fpscr);
+    let b = unpack(op2, fpscr);
+    if let Some(result) = process_nans(&[a, b], fpscr) {
+        return result;
+    }
+    let sign = a.sign != b.sign;
+    match (a.kind, b.kind) {
+        (Infinity, Infinity) | (Zero, Zero) => invalid_operation(fpscr),
+        (Infinity, _) => infinity(sign),
+        (_, Zero) => {
+            fpscr.set_dzc(true);
+            infinity(sign)
+        }
+        (Zero, _) | (_, Infinity) => zero(sign),
+        _ => {
+            // Dividend is shifted left as much as possible to get enough quotient bits for
+            // rounding. Remainder makes the result inexact.
+            let (dividend, exp) = normalize(a.mant as u128, a.exp);
+            let divisor = b.mant as u128;
+            let quotient = dividend / divisor;
+            let inexact = dividend % divisor != 0;
+            let rounding = fpscr.rmode();
+            round(
+                sign,
+                quotient | inexact as u128,
+                exp - b.exp,
+                rounding,
+                fpscr,
+            )
+        }
+    }
+}
+
+/// Floating-point square root.
+///
+/// Corresponds to `FPSqrt()` in the Arm Architecture Reference Manual.
+pub fn fp_sqrt<T: FloatBits>(op: T, fpscr: &mut Fpscr) -> T {
+    let a = unpack(op, fpscr);
+    match a.kind {
+        FpType::QNaN | FpType::SNaN => process_nan(&a, fpscr),
+        FpType::Zero => zero(a.sign),
+        FpType::Infinity if !a.sign => infinity(false),
+        _ if a.sign => invalid_operation(fpscr),
+        _ => {
+            // Mantissa is shifted left as much as possible with an even exponent, so the integer
+            // square root has enough bits for rounding.
+            let (mut mant, mut exp) = normalize(a.mant as u128, a.exp);
+            if exp % 2 != 0 {
+                mant >>= 1;
+                exp += 1;
+            }
+            let root = mant.isqrt();
+            let inexact = root * root != mant;
+            let rounding = fpscr.rmode();
+            round(false, root | inexact as u128, exp / 2, rounding, fpscr)
+        }
+    }
+}
+
+/// Fused floating-point multiply-add, computing `addend + op1 * op2` with a single rounding.
+///
+/// Corresponds to `FPMulAdd()` in the Arm Architecture Reference Manual.
+pub fn fp_mul_add<T: FloatBits>(addend: T, op1: T, op2: T, fpscr: &mut Fpscr) -> T {
+    use FpType::{Infinity, Zero};
+    let c = unpack(addend, fpscr);
+    let a = unpack(op1, fpscr);
+    let b = unpack(op2, fpscr);
+    let invalid_product =
+        (a.kind == Infinity && b.kind == Zero) || (a.kind == Zero && b.kind == Infinity);
+    if c.kind == FpType::QNaN && invalid_product {
+        return invalid_operation(fpscr);
+    }
+    if let Some(result) = process_nans(&[c, a, b], fpscr) {
+        return result;
+    }
+    let sign_p = a.sign != b.sign;
+    let inf_p = a.kind == Infinity || b.kind == Infinity;
+    let zero_p = a.kind == Zero || b.kind == Zero;
+    let rounding = fpscr.rmode();
+    if invalid_product || (c.kind == Infinity && inf_p && c.sign != sign_p) {
+        invalid_operation(fpscr)
+    } else if c.kind == Infinity {
+        infinity(c.sign)
+    } else if inf_p {
+        infinity(sign_p)
+    } else if c.kind == Zero && zero_p {
+        if c.sign == sign_p {
+            zero(c.sign)
+        } else {
+            cancellation_zero(rounding)
+        }
+    } else if zero_p {
+        round(c.sign, c.mant as u128, c.exp, rounding, fpscr)
+    } else {
+        let product = (sign_p, a.mant as u128 * b.mant as u128, a.exp + b.exp);
+        if c.kind == Zero {
+            return round(product.0, product.1, product.2, rounding, fpscr);
+        }
+        match add_values((c.sign, c.mant as u128, c.exp), product) {
+            Some((sign, mant, exp)) => round(sign, mant, exp, rounding, fpscr),
+            None => cancellation_zero(rounding),
+        }
+    }
+}
+
+/// Compares two non-NaN unpacked values.
+fn compare_values(a: &Unpacked, b: &Unpacked) -> Ordering {
+    if a.kind == FpType::Zero && b.kind == FpType::Zero {
+        return Ordering::Equal;
+    }
+    if a.sign != b.sign {
+        return if a.sign {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        };
+    }
+    let magnitude = |u: &Unpacked| match u.kind {
+        FpType::Zero => (i32::MIN, 0),
+        FpType::Infinity => (i32::MAX, 0),
+        _ => {
+            let lz = u.mant.leading_zeros();
+            (u.exp + 63 - lz as i32, u.mant << lz)
+        }
+    };
+    let ordering = magnitude(a).cmp(&magnitude(b));
+    if a.sign {
+        ordering.reverse()
+    } else {
+        ordering
+    }
+}
+
+/// Floating-point comparison. Returns the N, Z, C and V flags, N being the most significant bit.
+///
+/// Comparing with a signaling NaN is an invalid operation. If `quiet_nan_exc` is `true`,
+/// comparing with a quiet NaN is also an invalid operation.
+///
+/// Corresponds to `FPCompare()` in the Arm Architecture Reference Manual.
+pub fn fp_compare<T: FloatBits>(op1: T, op2: T, quiet_nan_exc: bool, fpscr: &mut Fpscr) -> u8 {
+    let a = unpack(op1, fpscr);
+    let b = unpack(op2, fpscr);
+    if a.is_nan() || b.is_nan() {
+        if a.kind == FpType::SNaN || b.kind == FpType::SNaN || quiet_nan_exc {
+            fpscr.set_ioc(true);
+        }
+        return 0b0011;
+    }
+    match compare_values(&a, &b) {
+        Ordering::Equal => 0b0110,
+        Ordering::Less => 0b1000,
+        Ordering::Greater => 0b0010,
+    }
+}
+
+/// Rounds the magnitude of the real value `mant * 2^exp` with the given sign to an integer, using
+/// the `rounding` mode. Returns the rounded magnitude and whether the result is inexact.
+fn round_to_integer(sign: bool, mant: u128, exp: i32, rounding: RoundingMode) -> (u128, bool) {
+    let (int_mant, half, sticky) = if exp >= 0 {
+        (mant << exp.min(64), false, false)
+    } else {
+        shift_right(mant, (-exp) as u32)
+    };
+    let inexact = half || sticky;
+    let round_up = match rounding {
+        RoundingMode::Nearest => half && (sticky || int_mant & 1 != 0),
+        RoundingMode::PlusInfinity => inexact && !sign,
+        RoundingMode::MinusInfinity => inexact && sign,
+        RoundingMode::Zero => false,
+        RoundingMode::TiesAway => half,
+    };
+    (int_mant + round_up as u128, inexact)
+}
+
+/// Converts a floating-point value to a `size`-bit fixed-point value with `fraction_bits`
+/// fractional bits. Integer conversions have zero fractional bits. Out of range values saturate
+/// and raise an invalid operation.
+///
+/// Result is zero-extended or sign-extended to 32 bits.
+///
+/// Corresponds to `FPToFixed()` in the Arm Architecture Reference Manual.
+pub fn fp_to_fixed<T: FloatBits>(
+    op: T,
+    size: u32,
+    fraction_bits: u32,
+    unsigned: bool,
+    rounding: RoundingMode,
+    fpscr: &mut Fpscr,
+) -> u32 {
+    debug_assert!(size == 16 || size == 32);
+    let a = unpack(op, fpscr);
+    // Infinities are given a value large enough to saturate.
+    let (mant, exp) = match a.kind {
+        FpType::Nonzero => (a.mant as u128, a.exp + fraction_bits as i32),
+        FpType::Infinity => (1, 64),
+        _ => (0, 0),
+    };
+    if a.is_nan() {
+        fpscr.set_ioc(true);
+    }
+    let (magnitude, inexact) = round_to_integer(a.sign, mant, exp, rounding);
+    let magnitude = magnitude as i128;
+    let int_result = if a.sign { -magnitude } else { magnitude };
+    let (min, max) = if unsigned {
+        (0, (1i128 << size) - 1)
+    } else {
+        (-(1i128 << (size - 1)), (1i128 << (size - 1)) - 1)
+    };
+    if int_result < min || int_result > max {
+        fpscr.set_ioc(true);
+    } else if inexact {
+        fpscr.set_ixc(true);
+    }
+    int_result.clamp(min, max) as u32
+}
+
+/// Converts the `size`-bit fixed-point value `op` with `fraction_bits` fractional bits to a
+/// floating-point value. Integer conversions have zero fractional bits.
+///
+/// Corresponds to `FixedToFP()` in the Arm Architecture Reference Manual.
+pub fn fixed_to_fp<T: FloatBits>(
+    op: u32,
+    size: u32,
+    fraction_bits: u32,
+    unsigned: bool,
+    rounding: RoundingMode,
+    fpscr: &mut Fpscr,
+) -> T {
+    debug_assert!(size == 16 || size == 32);
+    let value = if unsigned {
+        (op as u64 & ((1 << size) - 1)) as i64
+    } else {
+        ((op as i64) << (64 - size)) >> (64 - size)
+    };
+    if value == 0 {
+        return zero(false);
+    }
+    round(
+        value < 0,
+        value.unsigned_abs() as u128,
+        -(fraction_bits as i32),
+        rounding,
+        fpscr,
+    )
+}
+
+/// Rounds a floating-point value to an integral floating-point value, using the `rounding` mode.
+/// If `exact` is `true`, an inexact result raises the Inexact exception.
+///
+/// Corresponds to `FPRoundInt()` in the Arm Architecture Reference Manual.
+pub fn fp_round_int<T: FloatBits>(
+    op: T,
+    rounding: RoundingMode,
+    exact: bool,
+    fpscr: &mut Fpscr,
+) -> T {
+    let a = unpack(op, fpscr);
+    match a.kind {
+        FpType::QNaN | FpType::SNaN => process_nan(&a, fpscr),
+        FpType::Infinity => infinity(a.sign),
+        FpType::Zero => zero(a.sign),
+        // Values with a non-negative exponent are already integral.
+        FpType::Nonzero if a.exp >= 0 => op,
+        FpType::Nonzero => {
+            let (int_mant, inexact) = round_to_integer(a.sign, a.mant as u128, a.exp, rounding);
+            if inexact && exact {
+                fpscr.set_ixc(true);
+            }
+            if int_mant == 0 {
+                zero(a.sign)
+            } else {
+                // Integral values are always representable, so this rounding is exact.
+                round(a.sign, int_mant, 0, rounding, fpscr)
+            }
+        }
+    }
+}
+
+/// Returns the maximum or minimum of two floating-point values, following the IEEE 754-2008
+/// maxNum and minNum operations: when exactly one of the operands is a quiet NaN, the other
+/// operand is returned.
+///
+/// Corresponds to `FPMaxNum()` and `FPMinNum()` in the Arm Architecture Reference Manual.
+pub fn fp_max_min_num<T: FloatBits>(op1: T, op2: T, minimum: bool, fpscr: &mut Fpscr) -> T {
+    let mut a = unpack(op1, fpscr);
+    let mut b = unpack(op2, fpscr);
+    // A single quiet NaN is replaced by an infinity which can never be selected.
+    let replacement = |op: &mut Unpacked| {
+        op.kind = FpType::Infinity;
+        op.sign = !minimum;
+    };
+    match (a.kind, b.kind) {
+        (FpType::QNaN, kind) if kind != FpType::QNaN && kind != FpType::SNaN => replacement(&mut a),
+        (kind, FpType::QNaN) if kind != FpType::QNaN && kind != FpType::SNaN => replacement(&mut b),
+        _ => {}
+    }
+    if let Some(result) = process_nans(&[a, b], fpscr) {
+        return result;
+    }
+    let select_a = match compare_values(&a, &b) {
+        // Zeros of opposite signs: +0 is the maximum and -0 the minimum.
+        Ordering::Equal if a.kind == FpType::Zero => a.sign == minimum,
+        Ordering::Greater => !minimum,
+        Ordering::Less => minimum,
+        Ordering::Equal => true,
+    };
+    let r = if select_a { a } else { b };
+    match r.kind {
+        FpType::Infinity => infinity(r.sign),
+        FpType::Zero => zero(r.sign),
+        _ => {
+            let rounding = fpscr.rmode();
+            round(r.sign, r.mant as u128, r.exp, rounding, fpscr)
+        }
+    }
+}
+
+/// Converts between floating-point formats.
+///
+/// Corresponds to `FPHalfToSingle()`, `FPSingleToHalf()`, `FPSingleToDouble()` and
+/// `FPDoubleToSingle()` in the Arm Architecture Reference Manual.
+pub fn fp_convert<S: FloatBits, D: FloatBits>(op: S, fpscr: &mut Fpscr) -> D {
+    let a = unpack(op, fpscr);
+    let alternative = alternative_half::<D>(fpscr);
+    match a.kind {
+        FpType::QNaN | FpType::SNaN => {
+            if a.kind == FpType::SNaN || alternative {
+                fpscr.set_ioc(true);
+            }
+            if alternative {
+                zero(a.sign)
+            } else if fpscr.dn() {
+                default_nan()
+            } else {
+                // Fraction most significant bits are kept, and the NaN is made quiet.
+                let frac = a.bits & ((1 << S::F) - 1);
+                let frac = if D::F >= S::F {
+                    frac << (D::F - S::F)
+                } else {
+                    frac >> (S::F - D::F)
+                };
+                D::from_u64(sign_bit::<D>(a.sign) | exponent_ones::<D>() | (1 << (D::F - 1)) | frac)
+            }
+        }
+        FpType::Infinity if alternative => {
+            fpscr.set_ioc(true);
+            D::from_u64(sign_bit::<D>(a.sign) | ((1 << (D::N - 1)) - 1))
+        }
+        FpType::Infinity => infinity(a.sign),
+        FpType::Zero => zero(a.sign),
+        FpType::Nonzero => {
+            let rounding = fpscr.rmode();
+            round(a.sign, a.mant as u128, a.exp, rounding, fpscr)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        fixed_to_fp, fp_add, fp_compare, fp_convert, fp_div, fp_max_min_num, fp_mul, fp_mul_add,
+        fp_round_int, fp_sqrt, fp_sub, fp_to_fixed,
+    };
+    use crate::registers::{FloatingPointStatusControlRegister as Fpscr, RoundingMode};
+    use rand::Rng;
+
+    /// Generates a random single-precision value, with a bias towards special values, small
+    /// exponents differences and denormals.
+    fn random_f32(rng: &mut impl Rng) -> u32 {
+        match rng.random_range(0..10) {
+            0 => [
+                0x00000000, 0x80000000, 0x7f800000, 0xff800000, 0x7f7fffff, 0x00000001, 0x3f800000,
+            ][rng.random_range(0..7)],
+            1 => rng.random::<u32>() & 0x807fffff,
+            2..=5 => (rng.random::<u32>() & 0x83ffffff) | 0x3c000000,
+            _ => rng.random(),
+        }
+    }
+
+    /// Generates a random double-precision value, with a bias towards special values, small
+    /// exponents differences and denormals.
+    fn random_f64(rng: &mut impl Rng) -> u64 {
+        match rng.random_range(0..10) {
+            0 => 0x7ff0000000000000,
+            1 => rng.random::<u64>() & 0x800fffffffffffff,
+            2..=5 => (rng.random::<u64>() & 0x803fffffffffffff) | 0x3fc0000000000000,
+            _ => rng.random(),
+        }
+    }
+
+    /// Compares with host results, which are rounded to nearest. NaN results are skipped since
+    /// hosts may have different default NaN values.
+    fn check_f32(name: &str, inputs: &[u32], got: u32, expected: f32) {
+        if !expected.is_nan() {
+            assert_eq!(
+                got,
+                expected.to_bits(),
+                "{name} {inputs:08x?}: got {got:08x}, expected {:08x}",
+                expected.to_bits()
+            );
+        } else {
+            assert_eq!(got, 0x7fc00000, "{name} {inputs:08x?}");
+        }
+    }
+
+    fn check_f64(name: &str, inputs: &[u64], got: u64, expected: f64) {
+        if !expected.is_nan() {
+            assert_eq!(
+                got,
+                expected.to_bits(),
+                "{name} {inputs:016x?}: got {got:016x}, expected {:016x}",
+                expected.to_bits()
+            );
+        } else {
+            assert_eq!(got, 0x7ff8000000000000, "{name} {inputs:016x?}");
+        }
+    }
+
+    #[test]
+    fn test_against_host_f32() {
+        let mut rng = rand::rng();
+        // Default NaN mode makes the NaN results predictable.
+        let mut fpscr = Fpscr::new();
+        fpscr.set_dn(true);
+        for _ in 0..100000 {
+            let (a, b, c) = (
+                random_f32(&mut rng),
+                random_f32(&mut rng),
+                random_f32(&mut rng),
+            );
+            let (fa, fb, fc) = (f32::from_bits(a), f32::from_bits(b), f32::from_bits(c));
+            check_f32("add", &[a, b], fp_add(a, b, &mut fpscr), fa + fb);
+            check_f32("sub", &[a, b], fp_sub(a, b, &mut fpscr), fa - fb);
+            check_f32("mul", &[a, b], fp_mul(a, b, &mut fpscr), fa * fb);
+            check_f32("div", &[a, b], fp_div(a, b, &mut fpscr), fa / fb);
+            check_f32("sqrt", &[a], fp_sqrt(a, &mut fpscr), fa.sqrt());
+            check_f32(
+                "fma",
+                &[c, a, b],
+                fp_mul_add(c, a, b, &mut fpscr),
+                fa.mul_add(fb, fc),
+            );
+            check_f32(
+                "cvt",
+                &[a],
+                fp_convert(fp_convert::<u32, u64>(a, &mut fpscr), &mut fpscr),
+                fa,
+            );
+        }
+    }
+
+    #[test]
+    fn test_against_host_f64() {
+        let mut rng = rand::rng();
+        let mut fpscr = Fpscr::new();
+        fpscr.set_dn(true);
+        for _ in 0..100000 {
+            let (a, b, c) = (
+                random_f64(&mut rng),
+                random_f64(&mut rng),
+                random_f64(&mut rng),
+            );
+            let (fa, fb, fc) = (f64::from_bits(a), f64::from_bits(b), f64::from_bits(c));
+            check_f64("add", &[a, b], fp_add(a, b, &mut fpscr), fa + fb);
+            check_f64("sub", &[a, b], fp_sub(a, b, &mut fpscr), fa - fb);
+            check_f64("mul", &[a, b], fp_mul(a, b, &mut fpscr), fa * fb);
+            check_f64("div", &[a, b], fp_div(a, b, &mut fpscr), fa / fb);
+            check_f64("sqrt", &[a], fp_sqrt(a, &mut fpscr), fa.sqrt());
+            check_f64(
+                "fma",
+                &[c, a, b],
+                fp_mul_add(c, a, b, &mut fpscr),
+                fa.mul_add(fb, fc),
+            );
+            check_f32("cvt", &[], fp_convert(a, &mut fpscr), fa as f32);
+        }
+    }
+
+    #[test]
+    fn test_exception_flags() {
+        let mut fpscr = Fpscr::new();
+        // 1.0 / 0.0
+        assert_eq!(fp_div(0x3f800000u32, 0, &mut fpscr), 0x7f800000);
+        assert!(fpscr.dzc() && !fpscr.ioc() && !fpscr.ixc());
+
+        // 0.0 / 0.0
+        let mut fpscr = Fpscr::new();
+        assert_eq!(fp_div(0u32, 0, &mut fpscr), 0x7fc00000);
+        assert!(fpscr.ioc() && !fpscr.dzc());
+
+        // Signaling NaN is made quiet.
+        let mut fpscr = Fpscr::new();
+        assert_eq!(fp_add(0x3f800000u32, 0xff800001, &mut fpscr), 0xffc00001);
+        assert!(fpscr.ioc());
+
+        // Quiet NaN propagation has no exception.
+        let mut fpscr = Fpscr::new();
+        assert_eq!(fp_mul(0x7fc00123u32, 0x3f800000, &mut fpscr), 0x7fc00123);
+        assert_eq!(fpscr.get(), 0);
+
+        // Overflow: max * 2.0
+        let mut fpscr = Fpscr::new();
+        assert_eq!(fp_mul(0x7f7fffffu32, 0x40000000, &mut fpscr), 0x7f800000);
+        assert!(fpscr.ofc() && fpscr.ixc());
+
+        // Underflow: smallest normal / 3.0
+        let mut fpscr = Fpscr::new();
+        assert_eq!(fp_div(0x00800000u32, 0x40400000, &mut fpscr), 0x002aaaab);
+        assert!(fpscr.ufc() && fpscr.ixc());
+
+        // Exact denormal result does not underflow.
+        let mut fpscr = Fpscr::new();
+        assert_eq!(fp_div(0x00800000u32, 0x40000000, &mut fpscr), 0x00400000);
+        assert_eq!(fpscr.get(), 0);
+
+        // Inexact: 1.0 / 3.0
+        let mut fpscr = Fpscr::new();
+        assert_eq!(fp_div(0x3f800000u32, 0x40400000, &mut fpscr), 0x3eaaaaab);
+        assert!(fpscr.ixc() && !fpscr.ufc());
+
+        // Flush-to-zero of inputs and outputs.
+        let mut fpscr = Fpscr::new();
+        fpscr.set_fz(true);
+        assert_eq!(fp_add(0x00000001u32, 0x3f800000, &mut fpscr), 0x3f800000);
+        assert!(fpscr.idc() && !fpscr.ixc());
+        let mut fpscr = Fpscr::new();
+        fpscr.set_fz(true);
+        assert_eq!(fp_div(0x00800000u32, 0x40400000, &mut fpscr), 0);
+        assert!(fpscr.ufc() && !fpscr.ixc());
+    }
+
+    #[test]
+    fn test_rounding_modes() {
+        // 1.0 + 2^-24 is exactly halfway between 1.0 and its successor.
+        // 1.0 + 3 * 2^-25 is above halfway.
+        // -1.0 - 2^-25 is below halfway, negative.
+        struct Test {
+            a: u32,
+            b: u32,
+            rmode: RoundingMode,
+            expected: u32,
+        }
+        let vectors = [
+            (0x33800000, RoundingMode::Nearest, 0x3f800000),
+            (0x33800000, RoundingMode::PlusInfinity, 0x3f800001),
+            (0x33800000, RoundingMode::MinusInfinity, 0x3f800000),
+            (0x33800000, RoundingMode::Zero, 0x3f800000),
+            (0x33c00000, RoundingMode::Nearest, 0x3f800001),
+            (0x33c00000, RoundingMode::Zero, 0x3f800000),
+        ]
+        .into_iter()
+        .map(|(b, rmode, expected)| Test {
+            a: 0x3f800000,
+            b,
+            rmode,
+            expected,
+        })
+        .chain([
+            Test {
+                a: 0xbf800000,
+                b: 0xb3000000,
+                rmode: RoundingMode::MinusInfinity,
+                expected: 0xbf800001,
+            },
+            Test {
+                a: 0xbf800000,
+                b: 0xb3000000,
+                rmode: RoundingMode::PlusInfinity,
+                expected: 0xbf800000,
+            },
+            // Exact cancellation gives -0 when rounding towards minus infinity only.
+            Test {
+                a: 0x3f800000,
+                b: 0xbf800000,
+                rmode: RoundingMode::MinusInfinity,
+                expected: 0x80000000,
+            },
+            Test {
+                a: 0x3f800000,
+                b: 0xbf800000,
+                rmode: RoundingMode::Nearest,
+                expected: 0x00000000,
+            },
+            // Overflow gives the maximum normal value when rounding towards zero.
+            Test {
+                a: 0x7f7fffff,
+                b: 0x7f7fffff,
+                rmode: RoundingMode::Zero,
+                expected: 0x7f7fffff,
+            },
+        ]);
+
+        for v in vectors {
+            let mut fpscr = Fpscr::new();
+            fpscr.set_rmode(v.rmode);
+            assert_eq!(fp_add(v.a, v.b, &mut fpscr), v.expected);
+            assert_eq!(fpscr.ixc(), v.expected & 0x7fffffff != 0);
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let mut fpscr = Fpscr::new();
+        assert_eq!(
+            fp_compare(0x3f800000u32, 0x40000000, false, &mut fpscr),
+            0b1000
+        );
+        assert_eq!(
+            fp_compare(0x40000000u32, 0x3f800000, false, &mut fpscr),
+            0b0010
+        );
+        assert_eq!(
+            fp_compare(0x80000000u32, 0x00000000, false, &mut fpscr),
+            0b0110
+        );
+        assert_eq!(
+            fp_compare(0xff800000u32, 0xbf800000, false, &mut fpscr),
+            0b1000
+        );
+        assert_eq!(
+            fp_compare(0x7fc00000u32, 0x3f800000, false, &mut fpscr),
+            0b0011
+        );
+        assert!(!fpscr.ioc());
+        assert_eq!(
+            fp_compare(0x7fc00000u32, 0x3f800000, true, &mut fpscr),
+            0b0011
+        );
+        assert!(fpscr.ioc());
+    }
+
+    #[test]
+    fn test_fixed_conversions() {
+        let mut fpscr = Fpscr::new();
+        // 2.5 rounds to 2 with ties to even, and is truncated to 2.
+        assert_eq!(
+            fp_to_fixed(
+                0x40200000u32,
+                32,
+                0,
+                false,
+                RoundingMode::Nearest,
+                &mut fpscr
+            ),
+            2
+        );
+        assert_eq!(
+            fp_to_fixed(0xc0200000u32, 32, 0, false, RoundingMode::Zero, &mut fpscr),
+            -2i32 as u32
+        );
+        assert_eq!(
+            fp_to_fixed(
+                0xc0200000u32,
+                32,
+                0,
+                false,
+                RoundingMode::MinusInfinity,
+                &mut fpscr
+            ),
+            -3i32 as u32
+        );
+        assert!(fpscr.ixc() && !fpscr.ioc());
+        // Saturation.
+        let mut fpscr = Fpscr::new();
+        assert_eq!(
+            fp_to_fixed(0xbf800000u32, 32, 0, true, RoundingMode::Zero, &mut fpscr),
+            0
+        );
+        assert!(fpscr.ioc() && !fpscr.ixc());
+        assert_eq!(
+            fp_to_fixed(0x7f800000u32, 32, 0, false, RoundingMode::Zero, &mut fpscr),
+            0x7fffffff
+        );
+        assert_eq!(
+            fp_to_fixed(0x7fc00000u32, 32, 0, false, RoundingMode::Zero, &mut fpscr),
+            0
+        );
+        // 1.5 with 16 fraction bits, 16-bit sign-extended result.
+        let mut fpscr = Fpscr::new();
+        assert_eq!(
+            fp_to_fixed(0xbfc00000u32, 16, 8, false, RoundingMode::Zero, &mut fpscr),
+            0xfffffe80
+        );
+        assert_eq!(
+            fixed_to_fp::<u32>(0xfe80, 16, 8, false, RoundingMode::Nearest, &mut fpscr),
+            0xbfc00000
+        );
+        assert_eq!(
+            fixed_to_fp::<u32>(0xffffffff, 32, 0, true, RoundingMode::Nearest, &mut fpscr),
+            0x4f800000
+        );
+        assert!(fpscr.ixc());
+    }
+
+    #[test]
+    fn test_half_conversions() {
+        let mut fpscr = Fpscr::new();
+        assert_eq!(fp_convert::<u32, u16>(0x3f800000, &mut fpscr), 0x3c00);
+        assert_eq!(fp_convert::<u16, u32>(0x3c00, &mut fpscr), 0x3f800000);
+        assert_eq!(fp_convert::<u32, u16>(0x7f7fffff, &mut fpscr), 0x7c00);
+        assert!(fpscr.ofc());
+        assert_eq!(fp_convert::<u16, u32>(0x7e01, &mut fpscr), 0x7fc02000);
+        // Alternative half-precision format has no infinity.
+        let mut fpscr = Fpscr::new();
+        fpscr.set_ahp(true);
+        assert_eq!(fp_convert::<u16, u32>(0x7c00, &mut fpscr), 0x47800000);
+        assert_eq!(fp_convert::<u32, u16>(0x7f800000, &mut fpscr), 0x7fff);
+        assert!(fpscr.ioc());
+    }
+
+    #[test]
+    fn test_round_int() {
+        let vectors = [
+            (2.5f64, RoundingMode::Nearest, 2.0),
+            (3.5, RoundingMode::Nearest, 4.0),
+            (2.5, RoundingMode::TiesAway, 3.0),
+            (-2.5, RoundingMode::TiesAway, -3.0),
+            (2.25, RoundingMode::PlusInfinity, 3.0),
+            (-2.25, RoundingMode::PlusInfinity, -2.0),
+            (-2.25, RoundingMode::MinusInfinity, -3.0),
+            (-2.75, RoundingMode::Zero, -2.0),
+            (-0.25, RoundingMode::Nearest, -0.0),
+            (1e300, RoundingMode::Zero, 1e300),
+        ];
+        for (value, rounding, expected) in vectors {
+            let mut fpscr = Fpscr::new();
+            assert_eq!(
+                fp_round_int(value.to_bits(), rounding, false, &mut fpscr),
+                f64::to_bits(expected)
+            );
+            assert_eq!(
+                fp_round_int((value as f32).to_bits(), rounding, false, &mut fpscr),
+                (expected as f32).to_bits()
+            );
+            assert_eq!(fpscr.get(), 0);
+        }
+
+        // Inexact exception is only raised when requested.
+        let mut fpscr = Fpscr::new();
+        assert_eq!(
+            fp_round_int(0x3fc00000u32, RoundingMode::Nearest, true, &mut fpscr),
+            0x40000000
+        );
+        assert!(fpscr.ixc());
+        let mut fpscr = Fpscr::new();
+        assert_eq!(
+            fp_round_int(0x40000000u32, RoundingMode::Nearest, true, &mut fpscr),
+            0x40000000
+        );
+        assert!(!fpscr.ixc());
+    }
+
+    #[test]
+    fn test_max_min_num() {
+        let mut fpscr = Fpscr::new();
+        let one = 1.0f64.to_bits();
+        let two = 2.0f64.to_bits();
+        let qnan = 0x7ff8000000000000u64;
+        assert_eq!(fp_max_min_num(one, two, false, &mut fpscr), two);
+        assert_eq!(fp_max_min_num(one, two, true, &mut fpscr), one);
+        // A single quiet NaN operand is ignored.
+        assert_eq!(fp_max_min_num(qnan, one, false, &mut fpscr), one);
+        assert_eq!(fp_max_min_num(one, qnan, true, &mut fpscr), one);
+        assert_eq!(fp_max_min_num(qnan, qnan, true, &mut fpscr), qnan);
+        // +0 is greater than -0.
+        assert_eq!(
+            fp_max_min_num(0x8000000000000000u64, 0, false, &mut fpscr),
+            0
+        );
+        assert_eq!(
+            fp_max_min_num(0u64, 0x8000000000000000, true, &mut fpscr),
+            0x8000000000000000
+        );
+        assert_eq!(fpscr.get(), 0);
+
+        // Signaling NaNs are not ignored.
+        assert_eq!(
+            fp_max_min_num(0x7f800001u32, 0x3f800000, false, &mut fpscr),
+            0x7fc00001
+        );
+        assert!(fpscr.ioc());
+    }
+}
@@ -0,0 +1,214 @@
+//! Flash Patch and Breakpoint unit.
+//!
+//! The FPB is mapped at address `0xe0002000` on ArmV7-M processors. Its comparators match
+//! addresses in the Code region (`0x00000000` to `0x1fffffff`):
+//! - Instruction address comparators either remap instruction fetches to a table in SRAM, or
+//!   replace the instruction by a `BKPT #0` instruction, which emits [crate::core::Event::Break].
+//! - Literal address comparators remap the data reads to the same table in SRAM.
+//!
+//! The remap table address is given by FP_REMAP, and comparator `n` remaps the matching word to
+//! the word at offset `4 * n` in the table. Instruction fetches and data reads made with
+//! `*_iface` methods are not affected.
+
+use crate::{
+    helpers::BitAccess,
+    memory::{
+        Env, MemoryAccessError, MemoryReadResult, MemoryWriteResult, RegistersMemoryInterface,
+    },
+};
+
+/// Number of instruction address comparators.
+const CODE_COMPARATOR_COUNT: usize = 6;
+/// Number of literal address comparators.
+const LITERAL_COMPARATOR_COUNT: usize = 2;
+
+pub enum Register {
+    Ctrl,
+    Remap,
+    Comp(u8),
+}
+
+impl TryFrom<u32> for Register {
+    type Error = ();
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        if !value.is_multiple_of(4) {
+            return Err(());
+        }
+        let count = (CODE_COMPARATOR_COUNT + LITERAL_COMPARATOR_COUNT) as u32;
+        Ok(match value {
+            0x00 => Register::Ctrl,
+            0x04 => Register::Remap,
+            0x08.. if value < 0x08 + 4 * count => Register::Comp(((value - 0x08) / 4) as u8),
+            _ => return Err(()),
+        })
+    }
+}
+
+/// Result of the matching of an instruction fetch by the FPB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FetchPatch {
+    /// Instruction is fetched from the given address instead.
+    Remap(u32),
+    /// Instruction is replaced by a `BKPT #0` instruction.
+    Breakpoint,
+}
+
+/// Flash Patch and Breakpoint unit registers.
+#[derive(Default)]
+pub struct FlashPatchBreakpoint {
+    /// FP_CTRL.ENABLE
+    enable: bool,
+    /// FP_REMAP.REMAP, address of the remap table.
+    remap: u32,
+    /// FP_COMPn registers. Instruction address comparators come first.
+    comp: [u32; CODE_COMPARATOR_COUNT + LITERAL_COMPARATOR_COUNT],
+}
+
+impl FlashPatchBreakpoint {
+    /// Returns FP_CTRL.ENABLE.
+    pub fn enabled(&self) -> bool {
+        self.enable
+    }
+
+    /// Returns the address of the remap table. Bits 31:29 are always `001`, so the table is in
+    /// the SRAM region.
+    pub fn remap_address(&self) -> u32 {
+        0x20000000 | self.remap
+    }
+
+    /// Returns the index of the first enabled comparator in `range` matching the word at
+    /// `address`, along with its REPLACE field.
+    fn find(&self, range: std::ops::Range<usize>, address: u32) -> Option<(usize, u32)> {
+        if !self.enable || address >= 0x20000000 {
+            return None;
+        }
+        range
+            .map(|n| (n, self.comp[n]))
+            .find(|(_, comp)| comp.bit(0) && comp & 0x1ffffffc == address & !3)
+            .map(|(n, comp)| (n, comp >> 30))
+    }
+
+    /// Returns the address of the remap table entry for comparator `n`, matching `address`.
+    fn remapped(&self, n: usize, address: u32) -> u32 {
+        self.remap_address() + 4 * n as u32 + (address & 3)
+    }
+
+    /// Matches the fetch of the instruction halfword at `address`. `first` is `false` for the
+    /// second halfword of 32-bit instructions, which cannot be replaced by breakpoints.
+    pub(crate) fn match_fetch(&self, address: u32, first: bool) -> Option<FetchPatch> {
+        let (n, replace) = self.find(0..CODE_COMPARATOR_COUNT, address)?;
+        let upper = address.bit(1);
+        match replace {
+            0b00 => Some(FetchPatch::Remap(self.remapped(n, address))),
+            0b01 if first && !upper => Some(FetchPatch::Breakpoint),
+            0b10 if first && upper => Some(FetchPatch::Breakpoint),
+            0b11 if first => Some(FetchPatch::Breakpoint),
+            _ => None,
+        }
+    }
+
+    /// Returns the address to be read by a data read at `address`, which is remapped if a literal
+    /// address comparator matches.
+    pub(crate) fn remap_read(&self, address: u32) -> u32 {
+        let range = CODE_COMPARATOR_COUNT..CODE_COMPARATOR_COUNT + LITERAL_COMPARATOR_COUNT;
+        match self.find(range, address) {
+            Some((n, _)) => self.remapped(n, address),
+            None => address,
+        }
+    }
+}
+
+impl RegistersMemoryInterface for FlashPatchBreakpoint {
+    type Register = Register;
+
+    fn read32(&mut self, reg: Self::Register, env: &mut Env) -> MemoryReadResult<u32> {
+        if !env.privileged {
+            return Err(MemoryAccessError::PrivilegedOnly);
+        }
+        Ok(match reg {
+            Register::Ctrl => {
+                (LITERAL_COMPARATOR_COUNT << 8 | CODE_COMPARATOR_COUNT << 4) as u32
+                    | self.enable as u32
+            }
+            // RMPSPT: remapping is supported.
+            Register::Remap => 1 << 29 | self.remap,
+            Register::Comp(n) => self.comp[n as usize],
+        })
+    }
+
+    fn write32(&mut self, reg: Self::Register, value: u32, env: &mut Env) -> MemoryWriteResult {
+        if !env.privileged {
+            return Err(MemoryAccessError::PrivilegedOnly);
+        }
+        match reg {
+            Register::Ctrl => {
+                // Writes are ignored if KEY is not set.
+                if value.bit(1) {
+                    self.enable = value.bit(0)
+                }
+            }
+            Register::Remap => self.remap = value & 0x1fffffe0,
+            Register::Comp(n) => {
+                let n = n as usize;
+                // REPLACE is reserved for literal comparators.
+                let mask = if n < CODE_COMPARATOR_COUNT {
+                    0xdffffffd
+                } else {
+                    0x1ffffffd
+                };
+                self.comp[n] = value & mask
+            }
+        }
+        Ok(())
+    }
+
+    fn size(&self) -> u32 {
+        0x1000
+    }
+
+    fn scheduled_updates(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FetchPatch, FlashPatchBreakpoint, Register};
+    use crate::memory::{Env, RegistersMemoryInterface};
+
+    #[test]
+    fn test_fpb() {
+        let mut fpb = FlashPatchBreakpoint::default();
+        let mut env = Env::new(0, true);
+        assert_eq!(fpb.read32(Register::Ctrl, &mut env), Ok(0x260));
+        fpb.write32(Register::Remap, 0xffffffff, &mut env).unwrap();
+        assert_eq!(fpb.read32(Register::Remap, &mut env), Ok(0x3fffffe0));
+        fpb.write32(Register::Remap, 0x20001000, &mut env).unwrap();
+        fpb.write32(Register::Comp(0), 0x00000801, &mut env)
+            .unwrap();
+        fpb.write32(Register::Comp(1), 0x80000c01, &mut env)
+            .unwrap();
+        fpb.write32(Register::Comp(7), 0xc0000401, &mut env)
+            .unwrap();
+        assert_eq!(fpb.read32(Register::Comp(7), &mut env), Ok(0x00000401));
+
+        // Nothing matches until the unit is enabled, which requires KEY.
+        assert_eq!(fpb.match_fetch(0x800, true), None);
+        fpb.write32(Register::Ctrl, 1, &mut env).unwrap();
+        assert!(!fpb.enabled());
+        fpb.write32(Register::Ctrl, 3, &mut env).unwrap();
+        assert!(fpb.enabled());
+
+        assert_eq!(
+            fpb.match_fetch(0x802, false),
+            Some(FetchPatch::Remap(0x20001002))
+        );
+        assert_eq!(fpb.match_fetch(0x804, true), None);
+        assert_eq!(fpb.match_fetch(0xc00, true), None);
+        assert_eq!(fpb.match_fetch(0xc02, false), None);
+        assert_eq!(fpb.match_fetch(0xc02, true), Some(FetchPatch::Breakpoint));
+        assert_eq!(fpb.remap_read(0x402), 0x2000101e);
+        assert_eq!(fpb.remap_read(0x800), 0x800);
+    }
+}
@@ -0,0 +1,247 @@
+//! Floating-point extension system registers.
+//!
+//! FPCCR, FPCAR and FPDSCR control the floating-point context preservation on exception entry,
+//! and the MVFR registers describe the implemented floating-point features. They are mapped in
+//! the System Control Space at address `0xe000ef34`.
+
+use crate::{
+    core::MveExtension,
+    helpers::BitAccess,
+    memory::{
+        Env, MemoryAccessError, MemoryReadResult, MemoryWriteResult, RegistersMemoryInterface,
+    },
+};
+use num_enum::TryFromPrimitive;
+
+#[derive(TryFromPrimitive)]
+#[repr(u32)]
+pub enum Register {
+    Fpccr = 0x00,
+    Fpcar = 0x04,
+    Fpdscr = 0x08,
+    Mvfr0 = 0x0c,
+    Mvfr1 = 0x10,
+    Mvfr2 = 0x14,
+}
+
+/// Floating-point extension control registers.
+pub struct FloatingPointControl {
+    /// Floating-point Context Control Register.
+    pub fpccr: Fpccr,
+    /// Floating-point Context Address Register.
+    ///
+    /// Holds the address of the space reserved on the stack for S0 when lazy floating-point
+    /// context preservation is active. Bits 2:0 are always zero.
+    pub fpcar: u32,
+    /// Floating-point Default Status Control Register.
+    ///
+    /// Holds the AHP, DN, FZ and RMode values copied to FPSCR when a new floating-point context
+    /// is created.
+    pub fpdscr: u32,
+    /// Whether the floating-point extension supports double-precision operations, which is
+    /// reported by the MVFR registers.
+    double_precision: bool,
+    /// M-profile Vector Extension, if any, which is reported by MVFR1.
+    mve: Option<MveExtension>,
+}
+
+impl FloatingPointControl {
+    /// Mask of the writable bits in FPDSCR.
+    const FPDSCR_MASK: u32 = 0x07c00000;
+
+    /// Creates the registers of a single-precision floating-point extension, or of a
+    /// single-precision and double-precision extension if `double_precision` is `true`, with the
+    /// given M-profile Vector Extension.
+    pub fn new(double_precision: bool, mve: Option<MveExtension>) -> Self {
+        Self {
+            fpccr: Fpccr::default(),
+            fpcar: 0,
+            fpdscr: 0,
+            double_precision,
+            mve,
+        }
+    }
+
+    /// Value of MVFR1.
+    fn mvfr1(&self) -> u32 {
+        // FMA, default NaN and flush-to-zero. Half-precision conversions are supported, and
+        // extended to double-precision values in FPv5.
+        let fp = if self.double_precision {
+            0x12000011
+        } else {
+            0x11000011
+        };
+        // MVE support is in bits 11:8.
+        let mve = match self.mve {
+            None => 0,
+            Some(MveExtension::Integer) => 0b0001,
+            Some(MveExtension::FloatingPoint) => 0b0010,
+        };
+        fp | mve << 8
+    }
+}
+
+impl Default for FloatingPointControl {
+    fn default() -> Self {
+        Self::new(false, None)
+    }
+}
+
+impl RegistersMemoryInterface for FloatingPointControl {
+    type Register = Register;
+
+    fn read32(&mut self, reg: Self::Register, _env: &mut Env) -> MemoryReadResult<u32> {
+        Ok(match reg {
+            Register::Fpccr => self.fpccr.0,
+            Register::Fpcar => self.fpcar,
+            Register::Fpdscr => self.fpdscr,
+            // All rounding modes, square root, divide, short vectors not supported and 16
+            // double-word registers. Double-precision support is in bits 11:8.
+            Register::Mvfr0 if self.double_precision => 0x10110221,
+            Register::Mvfr0 => 0x10110021,
+            Register::Mvfr1 => self.mvfr1(),
+            // VRINT, VSEL, VMAXNM, VMINNM and directed rounding conversions, only implemented
+            // by FPv5.
+            Register::Mvfr2 if self.double_precision => 0x00000040,
+            Register::Mvfr2 => 0,
+        })
+    }
+
+    fn write32(&mut self, reg: Self::Register, value: u32, _env: &mut Env) -> MemoryWriteResult {
+        match reg {
+            Register::Fpccr => self.fpccr.write(value)?,
+            Register::Fpcar => {
+                if value & 7 != 0 {
+                    return Err(MemoryAccessError::InvalidValue);
+                }
+                self.fpcar = value
+            }
+            Register::Fpdscr => {
+                if value & !Self::FPDSCR_MASK != 0 {
+                    return Err(MemoryAccessError::InvalidValue);
+                }
+                self.fpdscr = value
+            }
+            // MVFR registers are read-only.
+            Register::Mvfr0 | Register::Mvfr1 | Register::Mvfr2 => {}
+        }
+        Ok(())
+    }
+
+    fn size(&self) -> u32 {
+        4 * 6
+    }
+
+    fn scheduled_updates(&self) -> bool {
+        true
+    }
+}
+
+/// FPCCR (Floating-point Context Control Register).
+///
+/// Reset value has ASPEN and LSPEN set: floating-point context is automatically preserved on
+/// exception entry, using lazy state preservation.
+pub struct Fpccr(u32);
+
+impl Fpccr {
+    /// Sets new register value.
+    /// Returns [MemoryAccessError::InvalidValue] when attempting to write a reserved bit.
+    pub fn write(&mut self, value: u32) -> MemoryWriteResult {
+        if value & !0xc000017b != 0 {
+            return Err(MemoryAccessError::InvalidValue);
+        }
+        self.0 = value;
+        Ok(())
+    }
+
+    /// Returns LSPACT bit value, set when lazy state preservation is active: space has been
+    /// reserved on the stack for the floating-point context but it has not been saved yet.
+    pub fn lspact(&self) -> bool {
+        self.0.bit(0)
+    }
+
+    /// Sets LSPACT bit value.
+    pub fn set_lspact(&mut self, value: bool) {
+        self.0.set_bit(0, value)
+    }
+
+    /// Returns USER bit value, set if the processor was unprivileged when the floating-point
+    /// stack frame was allocated.
+    pub fn user(&self) -> bool {
+        self.0.bit(1)
+    }
+
+    /// Sets USER bit value.
+    pub fn set_user(&mut self, value: bool) {
+        self.0.set_bit(1, value)
+    }
+
+    /// Returns THREAD bit value, set if the processor was in Thread mode when the floating-point
+    /// stack frame was allocated.
+    pub fn thread(&self) -> bool {
+        self.0.bit(3)
+    }
+
+    /// Sets THREAD bit value.
+    pub fn set_thread(&mut self, value: bool) {
+        self.0.set_bit(3, value)
+    }
+
+    /// Returns HFRDY bit value.
+    pub fn hfrdy(&self) -> bool {
+        self.0.bit(4)
+    }
+
+    /// Sets HFRDY bit value.
+    pub fn set_hfrdy(&mut self, value: bool) {
+        self.0.set_bit(4, value)
+    }
+
+    /// Returns MMRDY bit value.
+    pub fn mmrdy(&self) -> bool {
+        self.0.bit(5)
+    }
+
+    /// Sets MMRDY bit value.
+    pub fn set_mmrdy(&mut self, value: bool) {
+        self.0.set_bit(5, value)
+    }
+
+    /// Returns BFRDY bit value.
+    pub fn bfrdy(&self) -> bool {
+        self.0.bit(6)
+    }
+
+    /// Sets BFRDY bit value.
+    pub fn set_bfrdy(&mut self, value: bool) {
+        self.0.set_bit(6, value)
+    }
+
+    /// Returns MONRDY bit value.
+    pub fn monrdy(&self) -> bool {
+        self.0.bit(8)
+    }
+
+    /// Sets MONRDY bit value.
+    pub fn set_monrdy(&mut self, value: bool) {
+        self.0.set_bit(8, value)
+    }
+
+    /// Returns LSPEN bit value, which enables lazy floating-point context preservation.
+    pub fn lspen(&self) -> bool {
+        self.0.bit(30)
+    }
+
+    /// Returns ASPEN bit value, which enables automatic setting of CONTROL.FPCA when a
+    /// floating-point instruction is executed, and therefore automatic floating-point context
+    /// preservation on exception entry.
+    pub fn aspen(&self) -> bool {
+        self.0.bit(31)
+    }
+}
+
+impl Default for Fpccr {
+    fn default() -> Self {
+        Self(0xc0000000)
+    }
+}
@@ -0,0 +1,809 @@
+//! GDB Remote Serial Protocol server.
+//!
+//! [GdbServer] drives any [Emulator] which gives access to its [Processor], and allows debugging
+//! the emulated firmware with GDB the same way as on hardware through a debug probe.
+//!
+//! ```no_run
+//! use armagnac::{
+//!     core::{Config, Processor},
+//!     gdb::GdbServer,
+//! };
+//!
+//! let mut proc = Processor::new(Config::v7m());
+//! // Map the firmware and peripherals, set initial SP and PC...
+//! GdbServer::new(&mut proc).listen_tcp("127.0.0.1:3333").unwrap();
+//! ```
+//!
+//! GDB can then connect to the server:
+//!
+//! ```text
+//! arm-none-eabi-gdb firmware.elf -ex "target remote :3333"
+//! ```
+//!
+//! Registers and memory can be read and written, and execution can be continued, single-stepped
+//! or interrupted with Ctrl-C. Breakpoints are implemented with [Processor::hook_code] and
+//! watchpoints with [Processor::hook_data].
+
+use crate::{
+    core::{ArmVersion, DataHookKind, Emulator, Event, Processor, RunError},
+    registers::RegisterIndex,
+};
+use std::{
+    borrow::BorrowMut,
+    collections::VecDeque,
+    io::{self, ErrorKind, Read, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    ops::Range,
+};
+
+/// Number of events processed between two checks for an interrupt request from GDB when
+/// execution is continued.
+const INTERRUPT_POLL_PERIOD: u32 = 0x10000;
+
+/// Maximum packet size accepted by the server.
+const PACKET_SIZE: usize = 0x4000;
+
+/// Signal numbers reported to GDB in stop replies.
+const SIGINT: u8 = 2;
+const SIGILL: u8 = 4;
+const SIGTRAP: u8 = 5;
+const SIGSEGV: u8 = 11;
+
+/// Stream over which [GdbServer] communicates with GDB.
+pub trait Connection: Read + Write {
+    /// Sets the stream in blocking or non-blocking mode. Non-blocking mode is used to check for
+    /// an interrupt request while the emulator is running.
+    fn set_nonblocking(&mut self, nonblocking: bool) -> io::Result<()>;
+}
+
+impl Connection for TcpStream {
+    fn set_nonblocking(&mut self, nonblocking: bool) -> io::Result<()> {
+        TcpStream::set_nonblocking(self, nonblocking)
+    }
+}
+
+#[cfg(unix)]
+impl Connection for std::os::unix::net::UnixStream {
+    fn set_nonblocking(&mut self, nonblocking: bool) -> io::Result<()> {
+        std::os::unix::net::UnixStream::set_nonblocking(self, nonblocking)
+    }
+}
+
+/// A watchpoint inserted by GDB.
+struct Watchpoint {
+    range: Range<usize>,
+    kind: DataHookKind,
+}
+
+/// GDB Remote Serial Protocol server.
+///
+/// The server controls an [Emulator] which also gives access to its [Processor] for register and
+/// memory accesses. [Processor] itself can be used, as well as any device struct built by
+/// composition which implements `BorrowMut<Processor>`.
+pub struct GdbServer<'a, E: Emulator + BorrowMut<Processor>> {
+    emulator: &'a mut E,
+    /// Bytes received and not processed yet.
+    input: VecDeque<u8>,
+    /// Set when GDB disabled acknowledgments with `QStartNoAckMode` packet.
+    no_ack: bool,
+    /// Last sent packet, sent again if GDB does not acknowledge it.
+    last_packet: Vec<u8>,
+    /// Reply to the `?` packet, which is the reason of the last stop.
+    stop_reply: String,
+    /// Breakpoints addresses.
+    breakpoints: Vec<u32>,
+    watchpoints: Vec<Watchpoint>,
+}
+
+impl<'a, E: Emulator + BorrowMut<Processor>> GdbServer<'a, E> {
+    pub fn new(emulator: &'a mut E) -> Self {
+        Self {
+            emulator,
+            input: VecDeque::new(),
+            no_ack: false,
+            last_packet: Vec::new(),
+            stop_reply: format!("S{SIGTRAP:02x}"),
+            breakpoints: Vec::new(),
+            watchpoints: Vec::new(),
+        }
+    }
+
+    fn proc(&mut self) -> &mut Processor {
+        <E as BorrowMut<Processor>>::borrow_mut(self.emulator)
+    }
+
+    /// Waits for GDB to connect on TCP `address`, then serves the connection until GDB detaches
+    /// or kills the session.
+    pub fn listen_tcp(&mut self, address: impl ToSocketAddrs) -> io::Result<()> {
+        let listener = TcpListener::bind(address)?;
+        let (mut stream, _) = listener.accept()?;
+        stream.set_nodelay(true)?;
+        self.serve(&mut stream)
+    }
+
+    /// Waits for GDB to connect on Unix socket `path`, then serves the connection until GDB
+    /// detaches or kills the session.
+    #[cfg(unix)]
+    pub fn listen_unix(&mut self, path: impl AsRef<std::path::Path>) -> io::Result<()> {
+        let listener = std::os::unix::net::UnixListener::bind(path)?;
+        let (mut stream, _) = listener.accept()?;
+        self.serve(&mut stream)
+    }
+
+    /// Processes GDB requests received from `conn` until GDB detaches, kills the session or
+    /// closes the connection.
+    ///
+    /// Breakpoints and watchpoints inserted by GDB are removed when the session ends.
+    pub fn serve<C: Connection>(&mut self, conn: &mut C) -> io::Result<()> {
+        let result = self.serve_packets(conn);
+        for address in std::mem::take(&mut self.breakpoints) {
+            self.proc()
+                .unhook_code(address as usize..address as usize + 1);
+        }
+        for watch in std::mem::take(&mut self.watchpoints) {
+            self.proc().unhook_data(watch.range, watch.kind);
+        }
+        result
+    }
+
+    fn serve_packets<C: Connection>(&mut self, conn: &mut C) -> io::Result<()> {
+        while let Some(packet) = self.receive(conn)? {
+            match packet.first() {
+                Some(b'D') => {
+                    self.send(conn, b"OK")?;
+                    return Ok(());
+                }
+                Some(b'k') => return Ok(()),
+                _ => {
+                    let reply = self.process(conn, &packet)?;
+                    self.send(conn, &reply)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the next byte received from GDB. Returns `None` if the connection has been closed.
+    fn read_byte<C: Connection>(&mut self, conn: &mut C) -> io::Result<Option<u8>> {
+        if self.input.is_empty() {
+            let mut buffer = [0; 4096];
+            let n = loop {
+                match conn.read(&mut buffer) {
+                    Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                    result => break result?,
+                }
+            };
+            self.input.extend(&buffer[..n]);
+        }
+        Ok(self.input.pop_front())
+    }
+
+    /// Waits for the next packet and returns its unescaped payload, or `None` if the connection
+    /// has been closed. Packets with an invalid checksum are rejected.
+    fn receive<C: Connection>(&mut self, conn: &mut C) -> io::Result<Option<Vec<u8>>> {
+        loop {
+            match self.read_byte(conn)? {
+                None => return Ok(None),
+                Some(b'$') => {}
+                Some(b'-') => {
+                    let packet = self.last_packet.clone();
+                    conn.write_all(&packet)?;
+                    continue;
+                }
+                // Acknowledgments, and interrupt requests received while the target is already
+                // stopped, are ignored.
+                Some(_) => continue,
+            }
+            let mut payload = Vec::new();
+            let mut checksum = 0u8;
+            loop {
+                match self.read_byte(conn)? {
+                    None => return Ok(None),
+                    Some(b'#') => break,
+                    Some(b) => {
+                        checksum = checksum.wrapping_add(b);
+                        payload.push(b);
+                    }
+                }
+            }
+            let mut expected = [0; 2];
+            for c in expected.iter_mut() {
+                match self.read_byte(conn)? {
+                    None => return Ok(None),
+                    Some(b) => *c = b,
+                }
+            }
+            if self.no_ack {
+                return Ok(Some(unescape(&payload)));
+            }
+            if parse_hex(&expected) == Some(checksum as u64) {
+                conn.write_all(b"+")?;
+                return Ok(Some(unescape(&payload)));
+            }
+            conn.write_all(b"-")?;
+        }
+    }
+
+    /// Sends a packet with the given payload.
+    fn send<C: Connection>(&mut self, conn: &mut C, payload: &[u8]) -> io::Result<()> {
+        let mut packet = vec![b'$'];
+        let mut checksum = 0u8;
+        for &b in payload {
+            if matches!(b, b'#' | b'$' | b'}' | b'*') {
+                packet.extend([b'}', b ^ 0x20]);
+                checksum = checksum.wrapping_add(b'}').wrapping_add(b ^ 0x20);
+            } else {
+                packet.push(b);
+                checksum = checksum.wrapping_add(b);
+            }
+        }
+        packet.extend(format!("#{checksum:02x}").as_bytes());
+        conn.write_all(&packet)?;
+        conn.flush()?;
+        self.last_packet = packet;
+        Ok(())
+    }
+
+    /// Processes a packet and returns the reply payload. An empty reply indicates an unsupported
+    /// request.
+    fn process<C: Connection>(&mut self, conn: &mut C, packet: &[u8]) -> io::Result<Vec<u8>> {
+        let Some((&command, args)) = packet.split_first() else {
+            return Ok(Vec::new());
+        };
+        let reply = match command {
+            b'?' => self.stop_reply.clone().into_bytes(),
+            b'g' => self.read_registers(),
+            b'G' => self.write_registers(args),
+            b'p' => self.read_register(args),
+            b'P' => self.write_register(args),
+            b'm' => self.read_memory(args),
+            b'M' => self.write_memory(args, true),
+            b'X' => self.write_memory(args, false),
+            b'c' | b's' | b'C' | b'S' => {
+                // Signal numbers of `C` and `S` packets are ignored.
+                let address = match command {
+                    b'c' | b's' => args,
+                    _ => args.splitn(2, |&b| b == b';').nth(1).unwrap_or_default(),
+                };
+                if !address.is_empty() {
+                    let Some(address) = parse_hex(address) else {
+                        return Ok(b"E01".to_vec());
+                    };
+                    self.proc().set_pc(address as u32);
+                }
+                self.stop_reply = self.resume(conn, matches!(command, b's' | b'S'))?;
+                self.stop_reply.clone().into_bytes()
+            }
+            b'Z' => self.insert_point(args),
+            b'z' => self.remove_point(args),
+            b'H' | b'T' => b"OK".to_vec(),
+            b'q' | b'Q' => self.query(packet),
+            _ => Vec::new(),
+        };
+        Ok(reply)
+    }
+
+    /// Processes general query and set packets.
+    fn query(&mut self, packet: &[u8]) -> Vec<u8> {
+        let packet = String::from_utf8_lossy(packet);
+        let (name, args) = packet.split_once(':').unwrap_or((&packet, ""));
+        match name {
+            "qSupported" => {
+                format!("PacketSize={PACKET_SIZE:x};qXfer:features:read+;QStartNoAckMode+")
+                    .into_bytes()
+            }
+            "QStartNoAckMode" => {
+                self.no_ack = true;
+                b"OK".to_vec()
+            }
+            "qAttached" => b"1".to_vec(),
+            "qC" => b"QC1".to_vec(),
+            "qfThreadInfo" => b"m1".to_vec(),
+            "qsThreadInfo" => b"l".to_vec(),
+            "qSymbol" => b"OK".to_vec(),
+            "qXfer" => {
+                let Some(("features", "read", annex, range)) = split4(args) else {
+                    return Vec::new();
+                };
+                if annex != "target.xml" {
+                    return b"E00".to_vec();
+                }
+                let Some((offset, length)) = range
+                    .split_once(',')
+                    .and_then(|(o, l)| Some((parse_hex(o.as_bytes())?, parse_hex(l.as_bytes())?)))
+                else {
+                    return b"E01".to_vec();
+                };
+                let xml = target_xml(self.proc());
+                let data = xml.as_bytes();
+                let start = (offset as usize).min(data.len());
+                let end = start.saturating_add(length as usize).min(data.len());
+                let mut reply = vec![if end == data.len() { b'l' } else { b'm' }];
+                reply.extend(&data[start..end]);
+                reply
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Runs the emulator until an event stops execution, or until the first instruction is
+    /// executed if `step` is `true`. Returns the stop reply packet.
+    fn resume<C: Connection>(&mut self, conn: &mut C, step: bool) -> io::Result<String> {
+        let mut count = 0u32;
+        let mut stop = None;
+        loop {
+            let event = self.emulator.next_event();
+            // Poll whatever the event is, so GDB can still interrupt a sleeping or locked up
+            // processor.
+            count = count.wrapping_add(1);
+            if stop.is_none()
+                && count.is_multiple_of(INTERRUPT_POLL_PERIOD)
+                && self.interrupted(conn)?
+            {
+                return Ok(format!("S{SIGINT:02x}"));
+            }
+            match event {
+                Ok(Event::Instruction { .. }) => {
+                    // Some events are returned before the instruction which caused them. Stop
+                    // once that instruction is completed.
+                    if let Some(stop) = stop {
+                        return Ok(stop);
+                    }
+                    if step {
+                        return Ok(format!("S{SIGTRAP:02x}"));
+                    }
+                }
+                Ok(Event::Hook { .. }) => return Ok(format!("S{SIGTRAP:02x}")),
+                Ok(Event::Lockup) => return Ok(format!("S{SIGSEGV:02x}")),
+                Ok(Event::Break(_) | Event::Watchpoint(_)) => {
+                    stop.get_or_insert(format!("S{SIGTRAP:02x}"));
+                }
+                Ok(Event::DataHook { address, write }) => {
+                    if stop.is_none() {
+                        stop = Some(self.watch_reply(address, write));
+                    }
+                }
+                Ok(
+                    Event::Reset
+                    | Event::DebugHint(_)
+                    | Event::SendEvent
+                    | Event::DeepSleep
+                    | Event::Idle,
+                ) => {}
+                Err(RunError::MemRead { .. } | RunError::MemWrite { .. }) => {
+                    return Ok(format!("S{SIGSEGV:02x}"))
+                }
+                Err(_) => return Ok(format!("S{SIGILL:02x}")),
+            }
+        }
+    }
+
+    /// Returns `true` if GDB requested to interrupt execution.
+    fn interrupted<C: Connection>(&mut self, conn: &mut C) -> io::Result<bool> {
+        conn.set_nonblocking(true)?;
+        let mut buffer = [0; 64];
+        let result = conn.read(&mut buffer);
+        conn.set_nonblocking(false)?;
+        match result {
+            // Connection closed: stop to terminate the session.
+            Ok(0) => Ok(true),
+            Ok(n) => Ok(buffer[..n].contains(&0x03)),
+            Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::Interrupted) => {
+                Ok(false)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Returns the stop reply for a data access at `address` which matched a watchpoint.
+    fn watch_reply(&self, address: u32, write: bool) -> String {
+        let watch = self
+            .watchpoints
+            .iter()
+            .find(|w| w.kind.matches(write) && w.range.contains(&(address as usize)))
+            .or_else(|| self.watchpoints.iter().find(|w| w.kind.matches(write)));
+        let Some(watch) = watch else {
+            return format!("S{SIGTRAP:02x}");
+        };
+        let name = match watch.kind {
+            DataHookKind::Read => "rwatch",
+            DataHookKind::Write => "watch",
+            DataHookKind::Access => "awatch",
+        };
+        let address = (address as usize).max(watch.range.start);
+        format!("T{SIGTRAP:02x}{name}:{address:x};")
+    }
+
+    /// Processes `Z` packets, inserting breakpoints and watchpoints.
+    fn insert_point(&mut self, args: &[u8]) -> Vec<u8> {
+        let Some((kind, address, size)) = parse_point(args) else {
+            return b"E01".to_vec();
+        };
+        let range = address as usize..address as usize + size as usize;
+        match kind {
+            0 | 1 => {
+                self.proc()
+                    .hook_code(address as usize..address as usize + 1);
+                self.breakpoints.push(address);
+            }
+            2..=4 => {
+                let kind = watch_kind(kind);
+                self.proc().hook_data(range.clone(), kind);
+                self.watchpoints.push(Watchpoint { range, kind });
+            }
+            _ => return Vec::new(),
+        }
+        b"OK".to_vec()
+    }
+
+    /// Processes `z` packets, removing breakpoints and watchpoints.
+    fn remove_point(&mut self, args: &[u8]) -> Vec<u8> {
+        let Some((kind, address, size)) = parse_point(args) else {
+            return b"E01".to_vec();
+        };
+        let range = address as usize..address as usize + size as usize;
+        match kind {
+            0 | 1 => {
+                self.proc()
+                    .unhook_code(address as usize..address as usize + 1);
+                if let Some(i) = self.breakpoints.iter().position(|&b| b == address) {
+                    self.breakpoints.remove(i);
+                }
+            }
+            2..=4 => {
+                let kind = watch_kind(kind);
+                self.proc().unhook_data(range.clone(), kind);
+                if let Some(i) = self
+                    .watchpoints
+                    .iter()
+                    .position(|w| w.range == range && w.kind == kind)
+                {
+                    self.watchpoints.remove(i);
+                }
+            }
+            _ => return Vec::new(),
+        }
+        b"OK".to_vec()
+    }
+
+    fn read_registers(&mut self) -> Vec<u8> {
+        let proc = self.proc();
+        let mut reply = String::new();
+        for index in registers(proc) {
+            reply += &encode_hex(&proc.registers[index].to_le_bytes());
+        }
+        reply.into_bytes()
+    }
+
+    fn write_registers(&mut self, args: &[u8]) -> Vec<u8> {
+        let Some(data) = decode_hex(args) else {
+            return b"E01".to_vec();
+        };
+        let proc = self.proc();
+        for (index, value) in registers(proc).into_iter().zip(data.chunks_exact(4)) {
+            let value = u32::from_le_bytes(value.try_into().unwrap());
+            proc.registers.set(index, value);
+        }
+        b"OK".to_vec()
+    }
+
+    fn read_register(&mut self, args: &[u8]) -> Vec<u8> {
+        let proc = self.proc();
+        match parse_hex(args).and_then(|n| registers(proc).get(n as usize).copied()) {
+            Some(index) => encode_hex(&proc.registers[index].to_le_bytes()).into_bytes(),
+            None => b"E01".to_vec(),
+        }
+    }
+
+    fn write_register(&mut self, args: &[u8]) -> Vec<u8> {
+        let proc = self.proc();
+        let Some((n, value)) = split_at_byte(args, b'=') else {
+            return b"E01".to_vec();
+        };
+        let index = parse_hex(n).and_then(|n| registers(proc).get(n as usize).copied());
+        let value = decode_hex(value).and_then(|v| <[u8; 4]>::try_from(v).ok());
+        match (index, value) {
+            (Some(index), Some(value)) => {
+                proc.registers.set(index, u32::from_le_bytes(value));
+                b"OK".to_vec()
+            }
+            _ => b"E01".to_vec(),
+        }
+    }
+
+    fn read_memory(&mut self, args: &[u8]) -> Vec<u8> {
+        let Some((address, length)) = parse_address_length(args) else {
+            return b"E01".to_vec();
+        };
+        match self.proc().read_bytes_iface(address, length) {
+            Ok(data) => encode_hex(&data).into_bytes(),
+            Err(_) => b"E01".to_vec(),
+        }
+    }
+
+    /// Processes `M` packets, with hex encoded data, or `X` packets, with binary data.
+    fn write_memory(&mut self, args: &[u8], hex: bool) -> Vec<u8> {
+        let Some((range, data)) = split_at_byte(args, b':') else {
+            return b"E01".to_vec();
+        };
+        let data = if hex {
+            decode_hex(data)
+        } else {
+            Some(data.to_vec())
+        };
+        match (parse_address_length(range), data) {
+            (Some((address, length)), Some(data)) if length as usize == data.len() => {
+                match self.proc().write_bytes_iface(address, &data) {
+                    Ok(()) => b"OK".to_vec(),
+                    Err(_) => b"E01".to_vec(),
+                }
+            }
+            _ => b"E01".to_vec(),
+        }
+    }
+}
+
+/// Returns the data hook kind corresponding to `Z2`, `Z3` and `Z4` packets.
+fn watch_kind(kind: u8) -> DataHookKind {
+    match kind {
+        2 => DataHookKind::Write,
+        3 => DataHookKind::Read,
+        _ => DataHookKind::Access,
+    }
+}
+
+/// Returns the registers exposed to GDB, in the order of the register numbers given in the
+/// target description.
+fn registers(proc: &Processor) -> Vec<RegisterIndex> {
+    let mut result: Vec<RegisterIndex> = (0..16).map(RegisterIndex::new_main).collect();
+    result.extend([RegisterIndex::Xpsr, RegisterIndex::Msp, RegisterIndex::Psp]);
+    if proc.version.includes(ArmVersion::V8M) {
+        result.extend([RegisterIndex::Msplim, RegisterIndex::Psplim]);
+    }
+    result.push(RegisterIndex::Primask);
+    if proc.version != ArmVersion::V6M {
+        result.extend([RegisterIndex::Basepri, RegisterIndex::FaultMask]);
+    }
+    result.push(RegisterIndex::Control);
+    result
+}
+
+/// Returns the target description of the processor.
+///
+/// Core registers are described in the `org.gnu.gdb.arm.m-profile` feature, and the special
+/// registers in the `org.gnu.gdb.arm.m-system` feature.
+fn target_xml(proc: &Processor) -> String {
+    let mut xml = String::from(concat!(
+        "<?xml version=\"1.0\"?>\n",
+        "<!DOCTYPE target SYSTEM \"gdb-target.dtd\">\n",
+        "<target version=\"1.0\">\n",
+        "<architecture>arm</architecture>\n",
+        "<feature name=\"org.gnu.gdb.arm.m-profile\">\n"
+    ));
+    for (n, index) in registers(proc).into_iter().enumerate() {
+        if index == RegisterIndex::Msp {
+            xml += "</feature>\n<feature name=\"org.gnu.gdb.arm.m-system\">\n";
+        }
+        let kind = match index {
+            RegisterIndex::Sp | RegisterIndex::Msp | RegisterIndex::Psp => "data_ptr",
+            RegisterIndex::Pc => "code_ptr",
+            _ => "uint32",
+        };
+        let group = if n > 16 { " group=\"system\"" } else { "" };
+        xml += &format!(
+            "<reg name=\"{index}\" bitsize=\"32\" regnum=\"{n}\" type=\"{kind}\"{group}/>\n"
+        );
+    }
+    xml += "</feature>\n</target>\n";
+    xml
+}
+
+/// Splits `s` in four parts separated by `:`. The last part may contain `:`.
+fn split4(s: &str) -> Option<(&str, &str, &str, &str)> {
+    let mut parts = s.splitn(4, ':');
+    Some((parts.next()?, parts.next()?, parts.next()?, parts.next()?))
+}
+
+/// Splits `data` at the first occurrence of `separator`, which is excluded.
+fn split_at_byte(data: &[u8], separator: u8) -> Option<(&[u8], &[u8])> {
+    let i = data.iter().position(|&b| b == separator)?;
+    Some((&data[..i], &data[i + 1..]))
+}
+
+/// Parses `addr,length` arguments. Returns `None` if the range overflows the address space.
+fn parse_address_length(args: &[u8]) -> Option<(u32, u32)> {
+    let (address, length) = split_at_byte(args, b',')?;
+    let address = u32::try_from(parse_hex(address)?).ok()?;
+    let length = u32::try_from(parse_hex(length)?).ok()?;
+    address.checked_add(length)?;
+    Some((address, length))
+}
+
+/// Parses `type,addr,kind` arguments of `Z` and `z` packets. For watchpoints, `kind` is the
+/// number of watched bytes.
+fn parse_point(args: &[u8]) -> Option<(u8, u32, u32)> {
+    let (kind, rest) = split_at_byte(args, b',')?;
+    let kind = u8::try_from(parse_hex(kind)?).ok()?;
+    // Breakpoints may be followed by conditions, which are not supported.
+    let rest = rest.split(|&b| b == b';').next()?;
+    let (address, size) = parse_address_length(rest)?;
+    Some((kind, address, size.max(1)))
+}
+
+/// Parses an hexadecimal number.
+fn parse_hex(s: &[u8]) -> Option<u64> {
+    if s.is_empty() || s.len() > 16 {
+        return None;
+    }
+    u64::from_str_radix(std::str::from_utf8(s).ok()?, 16).ok()
+}
+
+fn encode_hex(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn decode_hex(s: &[u8]) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    s.chunks_exact(2)
+        .map(|c| u8::try_from(parse_hex(c)?).ok())
+        .collect()
+}
+
+/// Removes the escaping of `#`, `$`, `}` and `*` characters in packet payloads.
+fn unescape(payload: &[u8]) -> Vec<u8> {
+    let mut result = Vec::with_capacity(payload.len());
+    let mut iter = payload.iter();
+    while let Some(&b) = iter.next() {
+        if b == b'}' {
+            if let Some(&next) = iter.next() {
+                result.push(next ^ 0x20);
+            }
+        } else {
+            result.push(b);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Connection, GdbServer};
+    use crate::core::{Config, Processor};
+    use std::io::{self, ErrorKind, Read, Write};
+
+    /// Connection replaying GDB packets and recording the server output.
+    struct MockConnection {
+        input: Vec<u8>,
+        output: Vec<u8>,
+        nonblocking: bool,
+    }
+
+    impl Read for MockConnection {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.input.is_empty() && self.nonblocking {
+                return Err(ErrorKind::WouldBlock.into());
+            }
+            // Interrupt requests are received alone, while the server is running the emulator.
+            let available = match self.input.iter().position(|b| *b == 0x03) {
+                Some(i) if self.nonblocking => i + 1,
+                Some(i) => i,
+                None => self.input.len(),
+            };
+            let n = buf.len().min(available);
+            buf[..n].copy_from_slice(&self.input[..n]);
+            self.input.drain(..n);
+            Ok(n)
+        }
+    }
+
+    impl Write for MockConnection {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.output.extend(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Connection for MockConnection {
+        fn set_nonblocking(&mut self, nonblocking: bool) -> io::Result<()> {
+            self.nonblocking = nonblocking;
+            Ok(())
+        }
+    }
+
+    /// Sends all the `packets` to a server and returns the payloads of the replies. `"\x03"` is
+    /// sent as an interrupt request.
+    fn session(proc: &mut Processor, packets: &[&str]) -> Vec<String> {
+        let mut input = String::new();
+        for packet in packets {
+            if *packet == "\x03" {
+                input += packet;
+                continue;
+            }
+            let checksum = packet.bytes().fold(0u8, |a, b| a.wrapping_add(b));
+            input += &format!("${packet}#{checksum:02x}+");
+        }
+        let mut conn = MockConnection {
+            input: input.into_bytes(),
+            output: Vec::new(),
+            nonblocking: false,
+        };
+        GdbServer::new(proc).serve(&mut conn).unwrap();
+        let output = String::from_utf8(conn.output).unwrap();
+        output
+            .split('$')
+            .skip(1)
+            .map(|p| p.split_once('#').unwrap().0.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_gdb_session() {
+        let mut proc = Processor::new(Config::v7m());
+        // movs r0, #1; adds r0, #1; str r0, [r1]; b 0x102
+        proc.map(0x100, &[0x01, 0x20, 0x01, 0x30, 0x08, 0x60, 0xfc, 0xe7])
+            .unwrap();
+        proc.map_ram(0x20000000, 0x100).unwrap();
+        proc.registers.r1 = 0x20000000;
+        proc.set_pc(0x100);
+
+        let replies = session(
+            &mut proc,
+            &[
+                "qSupported:multiprocess+;swbreak+",
+                "qXfer:features:read:target.xml:0,4000",
+                "Z0,104,2",
+                "c",
+                "pf",
+                "z0,104,2",
+                "s",
+                "Z2,20000000,4",
+                "c",
+                "m20000000,4",
+                "M20000004,2:abcd",
+                "g",
+                "P0=78563412",
+                "D",
+            ],
+        );
+        assert!(replies[0].contains("qXfer:features:read+"));
+        assert!(replies[1].starts_with('l'));
+        assert!(replies[1].contains("<feature name=\"org.gnu.gdb.arm.m-profile\">"));
+        assert!(replies[1].contains("<reg name=\"basepri\" bitsize=\"32\" regnum=\"20\""));
+        assert_eq!(replies[2], "OK");
+        assert_eq!(replies[3], "S05");
+        assert_eq!(replies[4], "04010000");
+        assert_eq!(replies[5], "OK");
+        assert_eq!(replies[6], "S05");
+        assert_eq!(replies[7], "OK");
+        assert_eq!(replies[8], "T05watch:20000000;");
+        assert_eq!(replies[9], "03000000");
+        assert_eq!(replies[10], "OK");
+        // r0 to r15, xPSR, MSP, PSP, PRIMASK, BASEPRI, FAULTMASK and CONTROL.
+        assert_eq!(replies[11].len(), 23 * 8);
+        assert_eq!(replies[12], "OK");
+        assert_eq!(replies[13], "OK");
+
+        assert_eq!(proc.pc(), 0x106);
+        assert_eq!(proc.registers.r0, 0x12345678);
+        assert_eq!(proc.read_u16le_iface(0x20000004).unwrap(), 0xcdab);
+    }
+
+    #[test]
+    fn test_gdb_interrupt_sleep() {
+        let mut proc = Processor::new(Config::v7m());
+        // wfi; b 0x100
+        proc.map(0x100, &[0x30, 0xbf, 0xfd, 0xe7]).unwrap();
+        proc.set_pc(0x100);
+
+        // Nothing can wake up the processor, but GDB can still interrupt it.
+        let replies = session(&mut proc, &["c", "\x03", "D"]);
+        assert_eq!(replies, ["S02", "OK"]);
+        assert_eq!(proc.pc(), 0x102);
+    }
+}
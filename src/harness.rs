@@ -1,6 +1,9 @@
 //! Helpers for running methods from an ELF file.
 
-use crate::core::{Config, Emulator, Processor};
+use crate::{
+    core::{Config, Emulator, Event, Processor},
+    semihosting::{Semihosting, SEMIHOSTING_BKPT},
+};
 use object::{File, Object, ObjectSection, ObjectSymbol};
 use std::collections::BTreeMap;
 
@@ -13,10 +16,19 @@ pub struct ElfHarness {
     pub proc: Processor,
     /// All symbols and their address, extracted from the ELF file.
     pub symbols: BTreeMap<String, u32>,
+    /// Semihosting implementation used by [Self::run_semihosted].
+    pub semihosting: Semihosting,
 }
 
 impl ElfHarness {
+    /// Creates a harness with an ArmV7-M processor.
     pub fn new(elf: &[u8]) -> Self {
+        Self::with_config(elf, Config::v7m())
+    }
+
+    /// Creates a harness with a processor built from the given configuration. This allows for
+    /// instance running firmware compiled for a Cortex-M7 with a double-precision FPU.
+    pub fn with_config(elf: &[u8], config: Config) -> Self {
         // Parse ELF file to extract all symbols.
         // Keep the symbols in a BTreeMap to simplify and discard object::File.
         let object = File::parse(elf).unwrap();
@@ -25,7 +37,7 @@ impl ElfHarness {
             .map(|s| (s.name().unwrap().into(), s.address() as u32))
             .collect();
 
-        let mut proc = Processor::new(Config::v7m());
+        let mut proc = Processor::new(config);
         proc.map_ram(ADDR_RAM, 1024).unwrap();
 
         // Map program section as read-only RAM memories
@@ -39,7 +51,11 @@ impl ElfHarness {
             }
         }
 
-        Self { proc, symbols }
+        Self {
+            proc,
+            symbols,
+            semihosting: Semihosting::new(),
+        }
     }
 
     /// Sets PC at given method entry and execute instructions until the function returns (or a
@@ -61,6 +77,32 @@ impl ElfHarness {
         }
     }
 
+    /// Sets PC at given method entry and executes instructions, processing semihosting calls,
+    /// until the program exits with SYS_EXIT or SYS_EXIT_EXTENDED, or returns. Returns the exit
+    /// status, or R0 if the method returned.
+    ///
+    /// This allows running programs linked with newlib `--specs=rdimon.specs`, whose console
+    /// outputs are then available in [Semihosting::stdout] and [Semihosting::stderr].
+    pub fn run_semihosted(&mut self, method: &str) -> i32 {
+        let address = self.symbols[method];
+        self.proc.set_pc(address & 0xfffffffe);
+        self.proc.registers.lr = 0xfffffffe;
+        self.proc.set_sp(ADDR_RAM + STACK_SIZE);
+        self.semihosting.exit = None;
+
+        loop {
+            if let Event::Break(SEMIHOSTING_BKPT) = self.proc.next_event().unwrap() {
+                self.semihosting.call(&mut self.proc).unwrap();
+                if let Some(exit) = self.semihosting.exit {
+                    return exit.status();
+                }
+            }
+            if self.proc.pc() == 0xfffffffe {
+                return self.proc.registers.r0 as i32;
+            }
+        }
+    }
+
     /// Similar to [Self::call], with passing a function argument and returning function result
     /// using R0 register.
     pub fn call1(&mut self, method: &str, arg0: u32) -> u32 {
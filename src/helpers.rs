@@ -112,12 +112,37 @@ impl MaskedRegister {
         }
     }
 
+    /// Writes `value` to the register. Bits out of the write mask keep their previous value,
+    /// unless they are set by writing one (set mask) or cleared by writing one (clear at one
+    /// mask).
+    ///
+    /// Returns [MemoryAccessError::InvalidValue] if a reserved bit is written to one.
     pub fn write(&mut self, value: u32) -> Result<(), MemoryAccessError> {
-        self.value = ((value & self.write_mask) | (value & self.set_mask))
-            & !(value & self.clear_at_one_mask);
+        self.value =
+            (self.value & !self.write_mask) | (value & self.write_mask) | (value & self.set_mask);
+        self.value &= !(value & self.clear_at_one_mask);
         if value & self.reserved_mask != 0 {
             return Err(MemoryAccessError::InvalidValue);
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::MaskedRegister;
+    use crate::memory::MemoryAccessError;
+
+    #[test]
+    fn test_masked_register() {
+        let mut reg = MaskedRegister::new(0x13)
+            .write_mask_reserved(0xff)
+            .write_mask(0xf0)
+            .set_mask(0x04)
+            .clear_at_one(0x03);
+        assert_eq!(reg.write(0xa5), Ok(()));
+        assert_eq!(reg.value, 0xa6);
+        assert_eq!(reg.write(0x100), Err(MemoryAccessError::InvalidValue));
+        assert_eq!(reg.value, 0x06);
+    }
+}
@@ -256,7 +256,7 @@ mod tests {
             let rn = RegisterIndex::new_general_random();
             proc.registers.psr.set_c(v.carry_in);
             proc.set(rn, 100);
-            let mut expected = proc.registers.clone();
+            let mut expected = proc.registers;
             expected.set(rd, v.expected_r0);
             expected.psr.set_flags(v.expected_flags);
             AdcImm {
@@ -346,7 +346,7 @@ mod tests {
             proc.set(rn, 100);
             proc.set(rm, v.initial_r2);
             proc.registers.psr.set_c(v.carry_in);
-            let mut expected = proc.registers.clone();
+            let mut expected = proc.registers;
             expected.set(rd, v.expected_r0);
             expected.psr.set_flags(v.expected_flags);
             AdcReg {
@@ -590,7 +590,7 @@ mod tests {
             let rn = RegisterIndex::new_general_random();
             let rd = RegisterIndex::new_general_random();
             proc.set(rn, v.rn_value);
-            let mut expected_registers = proc.registers.clone();
+            let mut expected_registers = proc.registers;
             AddImm {
                 rd,
                 rn,
@@ -683,7 +683,7 @@ mod tests {
             proc.set(rd, 0);
             proc.set(rn, v.rn_value);
             proc.set(rm, v.rm_value);
-            let mut expected_registers = proc.registers.clone();
+            let mut expected_registers = proc.registers;
             AddReg {
                 rd,
                 rn,
@@ -752,7 +752,7 @@ mod tests {
             let mut proc = Processor::new(Config::v8m());
             let rd = RegisterIndex::new_general_random();
             proc.registers.msp = v.sp_value;
-            let mut expected_registers = proc.registers.clone();
+            let mut expected_registers = proc.registers;
             AddSpPlusImm {
                 rd,
                 imm32: v.imm32,
@@ -827,7 +827,7 @@ mod tests {
             let rm = RegisterIndex::new_general_random();
             proc.registers.msp = v.sp_value;
             proc.set(rm, v.rm_value);
-            let mut expected_registers = proc.registers.clone();
+            let mut expected_registers = proc.registers;
             AddSpPlusReg {
                 rd,
                 rm,
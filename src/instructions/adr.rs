@@ -103,7 +103,7 @@ mod tests {
         }
         .execute(proc)
         .unwrap();
-        assert_eq!(proc[rd], (0x1000 as i32 + offset) as u32);
+        assert_eq!(proc[rd], (0x1000_i32 + offset) as u32);
     }
 
     #[test]
@@ -242,7 +242,7 @@ mod tests {
             let rd = RegisterIndex::new_general_random();
             let rn = RegisterIndex::new_general_random();
             proc.set(rn, v.initial_rn);
-            let mut expected = proc.registers.clone();
+            let mut expected = proc.registers;
             expected.set(rd, v.expected_rd);
             expected.psr.set_flags(v.expected_flags);
             AndImm {
@@ -310,7 +310,7 @@ mod tests {
             let (rn, rm) = RegisterIndex::pick_two_general_distinct();
             proc.set(rn, v.initial_rn);
             proc.set(rm, v.initial_rm);
-            let mut expected = proc.registers.clone();
+            let mut expected = proc.registers;
             expected.set(rd, v.expected_rd);
             expected.psr.set_flags(v.expected_flags);
             AndReg {
@@ -0,0 +1,45 @@
+//! Implements AUT (Authenticate link register) instruction.
+
+use super::Encoding::{self, T1};
+use crate::{
+    core::{ArmVersion::V81M, Effect, ItState, Processor, RunError},
+    decoder::DecodeError,
+    instructions::{Instruction, Pattern},
+};
+
+/// AUT instruction.
+///
+/// Authenticate link register. Checks that R12 holds the authentication code of LR with SP as
+/// modifier, as computed by PAC or PACBTI, and raises an INVSTATE UsageFault otherwise. Executes
+/// as a NOP when pointer authentication is not enabled.
+pub struct Aut {}
+
+impl Instruction for Aut {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V81M],
+            expression: "111100111010(1)(1)(1)(1)10(0)0(0)00000101101",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, _ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        Ok(Self {})
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        if proc.is_pac_enabled() {
+            proc.authenticate_pac(proc.registers.r12, proc.lr(), proc.sp())?;
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "aut".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        "r12, lr, sp".into()
+    }
+}
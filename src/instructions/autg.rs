@@ -0,0 +1,57 @@
+//! Implements AUTG (Authenticate general value) instruction.
+
+use super::Encoding::{self, T1};
+use crate::{
+    core::{ArmVersion::V81M, Effect, ItState, Processor, RunError},
+    decoder::DecodeError,
+    instructions::{unpredictable, DecodeHelper, Instruction, Pattern},
+    registers::RegisterIndex,
+};
+
+/// AUTG instruction.
+///
+/// Authenticate general value. Checks that a register holds the authentication code of a value,
+/// as computed by PACG, and raises an INVSTATE UsageFault otherwise.
+pub struct Autg {
+    /// Register holding the authentication code.
+    rd: RegisterIndex,
+    /// Register holding the value to be authenticated.
+    rn: RegisterIndex,
+    /// Register holding the modifier.
+    rm: RegisterIndex,
+}
+
+impl Instruction for Autg {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V81M],
+            expression: "111110110101xxxxxxxx11110000xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(12);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        unpredictable(rd.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_pc())?;
+        Ok(Self { rd, rn, rm })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        if !proc.pacbti() {
+            return Err(RunError::InstructionUndefined);
+        }
+        proc.authenticate_pac(proc[self.rd], proc[self.rn], proc[self.rm])?;
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "autg".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}, {}", self.rd, self.rn, self.rm)
+    }
+}
@@ -0,0 +1,66 @@
+//! Implements BF (Branch Future) instruction.
+
+use super::Encoding::{self, T1};
+use super::{other, unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    arith::sign_extend,
+    core::{ArmVersion::V81M, Effect, ItState, LoBranch, LoBranchInfo, Processor, RunError},
+    decoder::DecodeError,
+};
+
+/// BF instruction.
+///
+/// Branch Future.
+pub struct Bf {
+    /// Offset from PC to the branch point.
+    boff: u32,
+    /// Branch offset.
+    imm32: i32,
+}
+
+impl Instruction for Bf {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V81M],
+            expression: "11110xxxx10xxxxx1110xxxxxxxxxxx1",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let boff = ins.imm4(23);
+        other(boff == 0)?; // DLS
+        unpredictable(state.in_it_block())?;
+        let imm17 = (ins.imm5(16) << 12) | (((ins >> 1) & 0x3ff) << 2) | (ins.imm1(11) << 1);
+        Ok(Self {
+            boff: boff << 1,
+            imm32: sign_extend(imm17, 17),
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let target = (proc.pc() as i32 + self.imm32) as u32;
+        proc.lo_branch_info = Some(LoBranchInfo {
+            end_address: proc.pc() + self.boff,
+            branch: LoBranch::BranchFuture {
+                target: target | 1,
+                link: None,
+                condition: None,
+            },
+        });
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "bf".into()
+    }
+
+    fn args(&self, pc: u32) -> String {
+        format!(
+            "0x{:x}, 0x{:x}",
+            pc + 4 + self.boff,
+            (pc as i32 + 4 + self.imm32) as u32
+        )
+    }
+}
@@ -0,0 +1,81 @@
+//! Implements BFCSEL (Branch Future Conditional Select) instruction.
+
+use super::Encoding::{self, T1};
+use super::{other, unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    arith::sign_extend,
+    core::{
+        ArmVersion::V81M, Condition, Effect, ItState, LoBranch, LoBranchInfo, Processor, RunError,
+    },
+    decoder::DecodeError,
+};
+
+/// BFCSEL instruction.
+///
+/// Branch Future Conditional Select.
+pub struct Bfcsel {
+    /// Offset from PC to the branch point.
+    boff: u32,
+    /// Branch offset.
+    imm32: i32,
+    /// Size in bytes of the branch instruction at the branch point. Execution continues after
+    /// this instruction when the condition fails.
+    else_offset: u32,
+    /// Branch condition.
+    cond: Condition,
+}
+
+impl Instruction for Bfcsel {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V81M],
+            expression: "11110xxxx0xxxxxx1110xxxxxxxxxxx1",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let boff = ins.imm4(23);
+        other(boff == 0)?; // DLSTP, LCTP
+        unpredictable(state.in_it_block())?;
+        let cond = Condition::try_from(ins.imm4(18)).map_err(|_| DecodeError::Unpredictable)?;
+        unpredictable(cond == Condition::Always)?;
+        let imm13 = (ins.imm1(16) << 12) | (((ins >> 1) & 0x3ff) << 2) | (ins.imm1(11) << 1);
+        Ok(Self {
+            boff: boff << 1,
+            imm32: sign_extend(imm13, 13),
+            else_offset: if ins.imm1(17) == 1 { 4 } else { 2 },
+            cond,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let target = (proc.pc() as i32 + self.imm32) as u32;
+        let end_address = proc.pc() + self.boff;
+        proc.lo_branch_info = Some(LoBranchInfo {
+            end_address,
+            branch: LoBranch::BranchFuture {
+                target: target | 1,
+                link: None,
+                condition: Some((self.cond, (end_address + self.else_offset) | 1)),
+            },
+        });
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "bfcsel".into()
+    }
+
+    fn args(&self, pc: u32) -> String {
+        let b_label = pc + 4 + self.boff;
+        format!(
+            "0x{:x}, 0x{:x}, 0x{:x}, {}",
+            b_label,
+            (pc as i32 + 4 + self.imm32) as u32,
+            b_label + self.else_offset,
+            self.cond
+        )
+    }
+}
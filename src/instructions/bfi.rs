@@ -99,7 +99,7 @@ mod tests {
             let (rd, rn) = RegisterIndex::pick_two_general_distinct();
             proc.set(rd, 0x12b456f8);
             proc.set(rn, 0x87654321);
-            let mut expected_registers = proc.registers.clone();
+            let mut expected_registers = proc.registers;
             Bfi {
                 rd,
                 rn,
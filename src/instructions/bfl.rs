@@ -0,0 +1,68 @@
+//! Implements BFL (Branch Future and Link) instruction.
+
+use super::Encoding::{self, T1};
+use super::{other, unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    arith::sign_extend,
+    core::{ArmVersion::V81M, Effect, ItState, LoBranch, LoBranchInfo, Processor, RunError},
+    decoder::DecodeError,
+};
+
+/// BFL instruction.
+///
+/// Branch Future and Link.
+pub struct Bfl {
+    /// Offset from PC to the branch point.
+    boff: u32,
+    /// Branch offset.
+    imm32: i32,
+}
+
+impl Instruction for Bfl {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V81M],
+            expression: "11110xxxxxxxxxxx1100xxxxxxxxxxx1",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let boff = ins.imm4(23);
+        other(boff == 0)?; // WLS, WLSTP, LE, LETP
+        unpredictable(state.in_it_block())?;
+        let imm19 = (ins.imm7(16) << 12) | (((ins >> 1) & 0x3ff) << 2) | (ins.imm1(11) << 1);
+        Ok(Self {
+            boff: boff << 1,
+            imm32: sign_extend(imm19, 19),
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let target = (proc.pc() as i32 + self.imm32) as u32;
+        let end_address = proc.pc() + self.boff;
+        // The branch point is expected to be a 32-bit BL instruction.
+        proc.lo_branch_info = Some(LoBranchInfo {
+            end_address,
+            branch: LoBranch::BranchFuture {
+                target: target | 1,
+                link: Some((end_address + 4) | 1),
+                condition: None,
+            },
+        });
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "bfl".into()
+    }
+
+    fn args(&self, pc: u32) -> String {
+        format!(
+            "0x{:x}, 0x{:x}",
+            pc + 4 + self.boff,
+            (pc as i32 + 4 + self.imm32) as u32
+        )
+    }
+}
@@ -0,0 +1,64 @@
+//! Implements BFLX (Branch Future, Link and Exchange) instruction.
+
+use super::Encoding::{self, T1};
+use super::{other, unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    core::{ArmVersion::V81M, Effect, ItState, LoBranch, LoBranchInfo, Processor, RunError},
+    decoder::DecodeError,
+    registers::RegisterIndex,
+};
+
+/// BFLX instruction.
+///
+/// Branch Future, Link and Exchange.
+pub struct Bflx {
+    /// Offset from PC to the branch point.
+    boff: u32,
+    /// Register holding the branch target address.
+    rn: RegisterIndex,
+}
+
+impl Instruction for Bflx {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V81M],
+            expression: "11110xxxx111xxxx1110000000000001",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let boff = ins.imm4(23);
+        other(boff == 0)?;
+        let rn = ins.reg4(16);
+        unpredictable(rn.is_sp_or_pc())?;
+        unpredictable(state.in_it_block())?;
+        Ok(Self {
+            boff: boff << 1,
+            rn,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let end_address = proc.pc() + self.boff;
+        // The branch point is expected to be a 16-bit BLX instruction.
+        proc.lo_branch_info = Some(LoBranchInfo {
+            end_address,
+            branch: LoBranch::BranchFuture {
+                target: proc[self.rn],
+                link: Some((end_address + 2) | 1),
+                condition: None,
+            },
+        });
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "bflx".into()
+    }
+
+    fn args(&self, pc: u32) -> String {
+        format!("0x{:x}, {}", pc + 4 + self.boff, self.rn)
+    }
+}
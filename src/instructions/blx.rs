@@ -12,14 +12,14 @@ use crate::{
     decoder::DecodeError,
     helpers::BitAccess,
     instructions::{unpredictable, DecodeHelper},
-    registers::RegisterIndex,
+    registers::{Mode, RegisterIndex},
 };
 
 /// BLX (register) instruction.
 pub struct Blx {
     /// Branch target address register.
     rm: RegisterIndex,
-    /// Non secure bit.
+    /// Non secure bit, true for BLXNS which can call Non-secure functions.
     /// Only for ArmV8-M, false for other architectures.
     ns: bool,
 }
@@ -49,12 +49,34 @@ impl Instruction for Blx {
     }
 
     fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let target = proc[self.rm];
+        let next_instr_addr = proc.pc().wrapping_sub(2);
         if self.ns {
-            // ArmV8-M only, to be implemented.
-            todo!()
+            if !proc.security_extension() || !proc.is_secure() {
+                return Err(RunError::InstructionUndefined);
+            }
+            if !target.bit(0) {
+                // Call to Non-secure state: return address and partial PSR are saved on the
+                // Secure stack, and LR receives the FNC_RETURN value so the Non-secure function
+                // does not know where it returns.
+                let address = proc.sp().wrapping_sub(8);
+                let mut partial_psr = proc.registers.psr.exception_number() as u32;
+                partial_psr.set_bit(20, proc.registers.control.sfpa());
+                proc.write_u32_aligned(address, next_instr_addr | 1)?;
+                proc.write_u32_aligned(address + 4, partial_psr)?;
+                proc.set_sp(address);
+                proc.set_lr(0xfeffffff);
+                if proc.registers.mode == Mode::Handler {
+                    proc.registers.psr.set_exception_number(1);
+                }
+                proc.registers.control.set_sfpa(false);
+                proc.require_branch_target();
+                proc.blxns_write_pc(target);
+                return Ok(Effect::Branch);
+            }
         }
-        let target = proc[self.rm];
-        proc.set_lr((proc.pc().wrapping_sub(2)) | 1);
+        proc.set_lr(next_instr_addr | 1);
+        proc.require_branch_target();
         proc.blx_write_pc(target);
         Ok(Effect::Branch)
     }
@@ -0,0 +1,45 @@
+//! Implements BTI (Branch Target Identification) instruction.
+
+use super::Encoding::{self, T1};
+use crate::{
+    core::{ArmVersion::V81M, Effect, ItState, Processor, RunError},
+    decoder::DecodeError,
+    instructions::{Instruction, Pattern},
+};
+
+/// BTI instruction.
+///
+/// Branch Target Identification. Marks a valid target of indirect branches when branch target
+/// identification is enabled, and otherwise executes as a NOP.
+pub struct Bti {}
+
+impl Instruction for Bti {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V81M],
+            expression: "111100111010(1)(1)(1)(1)10(0)0(0)00000001111",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, _ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        Ok(Self {})
+    }
+
+    fn is_branch_target(&self) -> bool {
+        true
+    }
+
+    fn execute(&self, _proc: &mut Processor) -> Result<Effect, RunError> {
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "bti".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        "".into()
+    }
+}
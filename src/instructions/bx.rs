@@ -19,7 +19,7 @@ use crate::{
 pub struct Bx {
     /// Branch target register.
     rm: RegisterIndex,
-    /// Non secure bit.
+    /// Non secure bit, true for BXNS which can branch to Non-secure state.
     /// Only for ArmV8-M, false for other architectures.
     ns: bool,
 }
@@ -50,12 +50,18 @@ impl Instruction for Bx {
     }
 
     fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let target = proc[self.rm];
+        if self.rm != RegisterIndex::Lr {
+            proc.require_branch_target();
+        }
         if self.ns {
-            // ArmV8-M only, to be implemented.
-            todo!()
+            if !proc.security_extension() || !proc.is_secure() {
+                return Err(RunError::InstructionUndefined);
+            }
+            proc.bxns_write_pc(target)?;
+        } else {
+            proc.bx_write_pc(target)?;
         }
-        let target = proc[self.rm];
-        proc.bx_write_pc(target)?;
         Ok(Effect::Branch)
     }
 
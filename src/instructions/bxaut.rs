@@ -0,0 +1,64 @@
+//! Implements BXAUT (Branch Exchange after Authentication) instruction.
+
+use super::Encoding::{self, T1};
+use crate::{
+    core::{ArmVersion::V81M, Effect, ItState, Processor, RunError},
+    decoder::DecodeError,
+    instructions::{unpredictable, DecodeHelper, Instruction, Pattern},
+    registers::RegisterIndex,
+};
+
+/// BXAUT instruction.
+///
+/// Branch Exchange after Authentication. Checks that a register holds the authentication code of
+/// the branch target address, and raises an INVSTATE UsageFault otherwise. It is typically used
+/// to return from functions whose entry point used PAC or PACBTI.
+pub struct Bxaut {
+    /// Register holding the authentication code.
+    rd: RegisterIndex,
+    /// Register holding the branch target address.
+    rn: RegisterIndex,
+    /// Register holding the modifier.
+    rm: RegisterIndex,
+}
+
+impl Instruction for Bxaut {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V81M],
+            expression: "111110110101xxxxxxxx11110001xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(12);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        unpredictable(rd.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_pc())?;
+        unpredictable(state.in_it_block_not_last())?;
+        Ok(Self { rd, rn, rm })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        if !proc.pacbti() {
+            return Err(RunError::InstructionUndefined);
+        }
+        let target = proc[self.rn];
+        proc.authenticate_pac(proc[self.rd], target, proc[self.rm])?;
+        if self.rn != RegisterIndex::Lr {
+            proc.require_branch_target();
+        }
+        proc.bx_write_pc(target)?;
+        Ok(Effect::Branch)
+    }
+
+    fn name(&self) -> String {
+        "bxaut".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}, {}", self.rd, self.rn, self.rm)
+    }
+}
@@ -1,6 +1,7 @@
 //! Implements CDP and CDP2 (Coprocessor Data Processing) instructions.
 
 use super::{
+    other,
     Encoding::{self, T1, T2},
     Instruction, Pattern,
 };
@@ -55,6 +56,7 @@ impl Instruction for Cdp {
 
     fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
         debug_assert!((encoding == T1) || (encoding == T2));
+        other(ins.imm3(9) == 0b101)?; // Floating-point extension
         Ok(Self {
             coproc: ins.imm4(8) as u8,
             opc1: ins.imm4(20) as u8,
@@ -69,8 +71,7 @@ impl Instruction for Cdp {
 
     fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
         let Some(coprocessor) = proc.coproc_accepted(self.coproc, self.ins) else {
-            proc.generate_coprocessor_exception();
-            return Ok(Effect::None);
+            return Err(proc.generate_coprocessor_exception());
         };
 
         coprocessor.borrow_mut().internal_operation(self.ins);
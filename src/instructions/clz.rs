@@ -85,7 +85,7 @@ mod tests {
             let rm = RegisterIndex::new_general_random();
             let rd = RegisterIndex::new_general_random();
             proc.set(rm, v.0);
-            let mut expected = proc.registers.clone();
+            let mut expected = proc.registers;
             expected.set(rd, v.1);
             Clz { rd, rm }.execute(&mut proc).unwrap();
             assert_eq!(proc.registers, expected);
@@ -151,8 +151,6 @@ impl Instruction for CmnReg {
 
 #[cfg(test)]
 mod tests {
-    use std::i32;
-
     use crate::{
         arith::Shift,
         core::{Config, Processor},
@@ -0,0 +1,340 @@
+//! Implements CX1, CX2 and CX3 (Custom Instruction) instructions of the Custom Datapath
+//! Extension, including their accumulating and dual variants.
+
+use super::Encoding::{self, T1};
+use super::{undefined, unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    core::{ArmVersion::V8M, CustomInstruction, Effect, ItState, Processor, RunError},
+    decoder::DecodeError,
+    helpers::BitAccess,
+    registers::RegisterIndex,
+};
+
+/// Operands of CX1, CX2 and CX3 instructions.
+struct CxOperation {
+    /// Coprocessor index, class, accumulation and immediate value.
+    ins: CustomInstruction,
+    /// Destination register. PC means APSR_nzcv. For dual variants, this is the first register of
+    /// the destination pair.
+    rd: RegisterIndex,
+    /// Second register of the destination pair for dual variants.
+    rd2: Option<RegisterIndex>,
+    /// Source operand registers. PC means APSR_nzcv.
+    sources: Vec<RegisterIndex>,
+}
+
+impl CxOperation {
+    /// Decodes the operands common to all classes, with the destination register at `rd_lsb` and
+    /// the source registers at `sources` bit positions.
+    fn decode(
+        ins: u32,
+        class: u8,
+        imm: u32,
+        rd_lsb: u8,
+        sources: &[u8],
+    ) -> Result<Self, DecodeError> {
+        let rd = ins.reg4(rd_lsb);
+        let rd2 = if ins.bit(6) {
+            undefined(ins.bit(rd_lsb as usize))?;
+            unpredictable(ins.imm4(rd_lsb) > 10)?;
+            Some(RegisterIndex::new_main(ins.imm4(rd_lsb) + 1))
+        } else {
+            None
+        };
+        let sources: Vec<RegisterIndex> = sources.iter().map(|lsb| ins.reg4(*lsb)).collect();
+        unpredictable(rd.is_sp() || sources.iter().any(|r| r.is_sp()))?;
+        Ok(Self {
+            ins: CustomInstruction {
+                coproc: ins.imm3(8) as u8,
+                class,
+                accumulate: ins.bit(28),
+                imm,
+            },
+            rd,
+            rd2,
+            sources,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let datapath = proc.custom_datapath_check(self.ins.coproc)?;
+        let n = self.source(proc, 0);
+        let m = self.source(proc, 1);
+        if let Some(rd2) = self.rd2 {
+            let d = (proc[rd2] as u64) << 32 | proc[self.rd] as u64;
+            let Some(result) = datapath.borrow_mut().cx_dual(&self.ins, d, n, m) else {
+                return Err(RunError::InstructionUndefined);
+            };
+            proc.set(self.rd, result as u32);
+            proc.set(rd2, (result >> 32) as u32);
+        } else {
+            let d = read_register_or_flags(proc, self.rd);
+            let Some(result) = datapath.borrow_mut().cx(&self.ins, d, n, m) else {
+                return Err(RunError::InstructionUndefined);
+            };
+            if self.rd.is_pc() {
+                proc.registers
+                    .psr
+                    .set_n(result.bit(31))
+                    .set_z(result.bit(30))
+                    .set_c(result.bit(29))
+                    .set_v(result.bit(28));
+            } else {
+                proc.set(self.rd, result);
+            }
+        }
+        Ok(Effect::None)
+    }
+
+    /// Returns the value of source operand `index`, or zero if the instruction class does not
+    /// have this operand.
+    fn source(&self, proc: &Processor, index: usize) -> u32 {
+        self.sources
+            .get(index)
+            .map_or(0, |r| read_register_or_flags(proc, *r))
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "cx{}{}{}",
+            self.ins.class,
+            if self.rd2.is_some() { "d" } else { "" },
+            if self.ins.accumulate { "a" } else { "" }
+        )
+    }
+
+    fn args(&self) -> String {
+        let mut registers = vec![register_or_flags_name(self.rd)];
+        registers.extend(self.rd2.map(|r| r.to_string()));
+        registers.extend(self.sources.iter().map(|r| register_or_flags_name(*r)));
+        format!(
+            "p{}, {}, #{}",
+            self.ins.coproc,
+            registers.join(", "),
+            self.ins.imm
+        )
+    }
+}
+
+/// Returns the value of register `r`, or APSR condition flags if `r` is PC.
+fn read_register_or_flags(proc: &Processor, r: RegisterIndex) -> u32 {
+    if r.is_pc() {
+        proc.registers.psr.get() & 0xf0000000
+    } else {
+        proc[r]
+    }
+}
+
+/// Returns the name of register `r` in disassembly, PC being APSR_nzcv.
+fn register_or_flags_name(r: RegisterIndex) -> String {
+    if r.is_pc() {
+        "apsr_nzcv".into()
+    } else {
+        r.to_string()
+    }
+}
+
+/// CX1, CX1A, CX1D or CX1DA instruction.
+///
+/// Custom Instruction Class 1.
+pub struct Cx1 {
+    op: CxOperation,
+}
+
+impl Instruction for Cx1 {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V8M],
+            expression: "111x111000xxxxxxxxxx0xxxxxxxxxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let imm = (((ins >> 16) & 0x3f) << 7) | (ins.imm1(7) << 6) | (ins & 0x3f);
+        Ok(Self {
+            op: CxOperation::decode(ins, 1, imm, 12, &[])?,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        self.op.execute(proc)
+    }
+
+    fn name(&self) -> String {
+        self.op.name()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        self.op.args()
+    }
+}
+
+/// CX2, CX2A, CX2D or CX2DA instruction.
+///
+/// Custom Instruction Class 2.
+pub struct Cx2 {
+    op: CxOperation,
+}
+
+impl Instruction for Cx2 {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V8M],
+            expression: "111x111001xxxxxxxxxx0xxxxxxxxxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let imm = (ins.imm2(20) << 7) | (ins.imm1(7) << 6) | (ins & 0x3f);
+        Ok(Self {
+            op: CxOperation::decode(ins, 2, imm, 12, &[16])?,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        self.op.execute(proc)
+    }
+
+    fn name(&self) -> String {
+        self.op.name()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        self.op.args()
+    }
+}
+
+/// CX3, CX3A, CX3D or CX3DA instruction.
+///
+/// Custom Instruction Class 3.
+pub struct Cx3 {
+    op: CxOperation,
+}
+
+impl Instruction for Cx3 {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V8M],
+            expression: "111x11101xxxxxxxxxxx0xxxxxxxxxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let imm = (ins.imm3(20) << 3) | (ins.imm1(7) << 2) | ins.imm2(4);
+        Ok(Self {
+            op: CxOperation::decode(ins, 3, imm, 0, &[16, 12])?,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        self.op.execute(proc)
+    }
+
+    fn name(&self) -> String {
+        self.op.name()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        self.op.args()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cx1, Cx2, Cx3};
+    use crate::{
+        core::{Config, CustomDatapath, CustomInstruction, Irq, ItState, Processor, RunError},
+        instructions::{Encoding::T1, Instruction},
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    struct TestDatapath;
+
+    impl CustomDatapath for TestDatapath {
+        fn cx(&mut self, ins: &CustomInstruction, d: u32, n: u32, m: u32) -> Option<u32> {
+            (ins.coproc != 7 || ins.imm == 5).then_some(d + n * ins.imm + m + 0xa0000000)
+        }
+
+        fn cx_dual(&mut self, ins: &CustomInstruction, d: u64, n: u32, m: u32) -> Option<u64> {
+            Some(d + ((n as u64) << 32 | m as u64) + ins.imm as u64)
+        }
+    }
+
+    fn processor() -> Processor {
+        let mut proc = Processor::new(
+            Config::v8m()
+                .custom_datapath(0)
+                .custom_datapath(2)
+                .custom_datapath(7),
+        );
+        let datapath = Rc::new(RefCell::new(TestDatapath));
+        proc.set_custom_datapath(0, datapath.clone());
+        proc.set_custom_datapath(2, datapath);
+        proc
+    }
+
+    #[test]
+    fn test_cx() {
+        // cx2a p2, r4, r5, #3
+        let ins = Cx2::try_decode(T1, 0xfe454203, ItState::new()).unwrap();
+        let mut proc = processor();
+        proc.registers.r4 = 10;
+        proc.registers.r5 = 7;
+
+        // Access to coprocessor 2 not granted.
+        assert_eq!(
+            ins.execute(&mut proc),
+            Err(RunError::Fault(Irq::UsageFault))
+        );
+
+        proc.write_u32le_iface(0xe000ed88, 0x00000030).unwrap();
+        ins.execute(&mut proc).unwrap();
+        assert_eq!(proc.registers.r4, 0xa0000000 + 10 + 21);
+
+        // cx1 p7, apsr_nzcv, #5
+        // Coprocessor 7 has no implementation.
+        let ins = Cx1::try_decode(T1, 0xee00f705, ItState::new()).unwrap();
+        proc.write_u32le_iface(0xe000ed88, 0x0000c000).unwrap();
+        assert_eq!(
+            ins.execute(&mut proc),
+            Err(RunError::Fault(Irq::UsageFault))
+        );
+        proc.set_custom_datapath(7, Rc::new(RefCell::new(TestDatapath)));
+        ins.execute(&mut proc).unwrap();
+        let psr = &proc.registers.psr;
+        assert!(psr.n() && !psr.z() && psr.c() && !psr.v());
+    }
+
+    #[test]
+    fn test_cx_dual() {
+        // cx3d p0, r0, r1, r2, r3, #0
+        let ins = Cx3::try_decode(T1, 0xee823040, ItState::new()).unwrap();
+        assert_eq!(ins.name(), "cx3d");
+        let mut proc = processor();
+        proc.write_u32le_iface(0xe000ed88, 0x00000003).unwrap();
+        proc.registers.r0 = 0xffffffff;
+        proc.registers.r1 = 1;
+        proc.registers.r2 = 1;
+        proc.registers.r3 = 2;
+        ins.execute(&mut proc).unwrap();
+        assert_eq!(proc.registers.r0, 1);
+        assert_eq!(proc.registers.r1, 3);
+
+        // cx1da p0, r2, r3, #2
+        let ins = Cx1::try_decode(T1, 0xfe002042, ItState::new()).unwrap();
+        proc.registers.r2 = 0xffffffff;
+        proc.registers.r3 = 1;
+        ins.execute(&mut proc).unwrap();
+        assert_eq!(proc.registers.r2, 1);
+        assert_eq!(proc.registers.r3, 2);
+
+        // cx2d p0, r0, r1, r2, #0 with an odd destination register is UNDEFINED.
+        assert!(Cx2::try_decode(T1, 0xee421040, ItState::new()).is_err());
+    }
+}
@@ -0,0 +1,70 @@
+//! Implements DLS (Do Loop Start) and DLSTP (Do Loop Start with Tail Predication) instructions.
+
+use super::Encoding::{self, T1, T2};
+use super::{other, unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    core::{ArmVersion::V81M, Effect, ItState, Processor, RunError},
+    decoder::DecodeError,
+    registers::RegisterIndex,
+};
+
+/// DLS or DLSTP instruction.
+///
+/// Do Loop Start, with optional Tail Predication.
+pub struct Dls {
+    /// Register holding the loop iteration count, or the number of vector elements to be processed
+    /// for tail predicated loops.
+    rn: RegisterIndex,
+    /// Element size for tail predicated loops, as a power of two number of bytes (DLSTP).
+    size: Option<u8>,
+}
+
+impl Instruction for Dls {
+    fn patterns() -> &'static [Pattern] {
+        &[
+            Pattern {
+                encoding: T1,
+                versions: &[V81M],
+                expression: "111100000100xxxx1110000000000001",
+            },
+            Pattern {
+                encoding: T2,
+                versions: &[V81M],
+                expression: "1111000000xxxxxx1110000000000001",
+            },
+        ]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        let rn = ins.reg4(16);
+        other(encoding == T2 && rn.is_pc())?; // LCTP
+        unpredictable(rn.is_sp_or_pc())?;
+        unpredictable(state.in_it_block())?;
+        Ok(Self {
+            rn,
+            size: (encoding == T2).then_some(ins.imm2(20) as u8),
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        if self.size.is_some() {
+            proc.execute_mve_check(false)?;
+        }
+        proc.set_lr(proc[self.rn]);
+        if let Some(size) = self.size {
+            proc.registers.fpscr.set_ltpsize(size);
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        match self.size {
+            Some(size) => format!("dlstp.{}", 8 << size),
+            None => "dls".into(),
+        }
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("lr, {}", self.rn)
+    }
+}
@@ -69,7 +69,7 @@ mod tests {
     fn test_dmb() {
         // Check that the instruction does nothing.
         let mut proc = Processor::new(Config::v7m());
-        let expected = proc.registers.clone();
+        let expected = proc.registers;
         for option in 0..=0xf {
             Dmb { option }.execute(&mut proc).unwrap();
             assert_eq!(proc.registers, expected)
@@ -0,0 +1,43 @@
+//! Implements LCTP (Loop Clear with Tail Predication) instruction.
+
+use super::Encoding::{self, T1};
+use super::{unpredictable, Instruction, Pattern};
+use crate::{
+    core::{ArmVersion::V81M, Effect, ItState, Processor, RunError},
+    decoder::DecodeError,
+};
+
+/// LCTP instruction.
+///
+/// Loop Clear with Tail Predication.
+pub struct Lctp {}
+
+impl Instruction for Lctp {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V81M],
+            expression: "11110000000011111110000000000001",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, _ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        unpredictable(state.in_it_block())?;
+        Ok(Self {})
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_mve_check(false)?;
+        proc.registers.fpscr.set_ltpsize(4);
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "lctp".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        "".into()
+    }
+}
@@ -0,0 +1,52 @@
+//! Implements LDAB (Load-Acquire Byte) instruction.
+
+use super::{Encoding::T1, Pattern};
+use crate::{
+    core::{ArmVersion::V8M, Effect, ItState, Processor, RunError},
+    decoder::DecodeError,
+    instructions::{unpredictable, DecodeHelper, Encoding, Instruction},
+    registers::RegisterIndex,
+};
+
+/// LDAB instruction.
+///
+/// Load-Acquire Byte.
+pub struct Ldab {
+    /// Destination register.
+    rt: RegisterIndex,
+    /// Base register.
+    rn: RegisterIndex,
+}
+
+impl Instruction for Ldab {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V8M],
+            expression: "111010001101xxxxxxxx(1)(1)(1)(1)1000(1)(1)(1)(1)",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rt = ins.reg4(12);
+        let rn = ins.reg4(16);
+        unpredictable(rt.is_sp_or_pc() || rn.is_pc())?;
+        Ok(Self { rt, rn })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let address = proc[self.rn];
+        let value = proc.read_u8(address)? as u32;
+        proc.set(self.rt, value);
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "ldab".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, [{}]", self.rt, self.rn)
+    }
+}
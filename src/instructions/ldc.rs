@@ -64,6 +64,7 @@ impl Instruction for LdcImm {
 
     fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
         debug_assert!((encoding == T1) || (encoding == T2));
+        other(encoding == T1 && ins.imm3(9) == 0b101)?; // Floating-point extension
         let rn = ins.reg4(16);
         other(rn.is_pc())?; // LDC (literal)
         let index = ins.bit(24);
@@ -88,8 +89,7 @@ impl Instruction for LdcImm {
 
     fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
         let Some(coprocessor) = proc.coproc_accepted(self.coproc, self.ins) else {
-            proc.generate_coprocessor_exception();
-            return Ok(Effect::None);
+            return Err(proc.generate_coprocessor_exception());
         };
 
         let rn = proc[self.rn];
@@ -172,6 +172,7 @@ impl Instruction for LdcLit {
 
     fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
         debug_assert!((encoding == T1) || (encoding == T2));
+        other(encoding == T1 && ins.imm3(9) == 0b101)?; // Floating-point extension
         let index = ins.bit(24);
         let add = ins.bit(23);
         let d = ins.bit(22);
@@ -193,8 +194,7 @@ impl Instruction for LdcLit {
 
     fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
         let Some(coprocessor) = proc.coproc_accepted(self.coproc, self.ins) else {
-            proc.generate_coprocessor_exception();
-            return Ok(Effect::None);
+            return Err(proc.generate_coprocessor_exception());
         };
 
         let pc = proc.pc().align(4);
@@ -313,7 +313,7 @@ mod tests {
         )
         .unwrap();
         proc.set(rn, 0x1000 - 100);
-        let mut expected = proc.registers.clone();
+        let mut expected = proc.registers;
         expected.set(rn, 0x1000);
         LdcImm {
             coproc: cp,
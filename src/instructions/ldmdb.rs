@@ -61,6 +61,9 @@ impl Instruction for Ldmdb {
         for reg in self.registers.iter() {
             let value = proc.read_u32_aligned(address)?;
             if reg.is_pc() {
+                if !self.rn.is_sp() {
+                    proc.require_branch_target();
+                }
                 proc.bx_write_pc(value)?;
                 action = Effect::Branch;
             } else {
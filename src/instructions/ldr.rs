@@ -133,7 +133,11 @@ impl Instruction for LdrImm {
         }
         if self.rt.is_pc() {
             if addr & 3 == 0 {
-                todo!();
+                if !self.rn.is_sp() {
+                    proc.require_branch_target();
+                }
+                proc.load_write_pc(data)?;
+                return Ok(Effect::Branch);
             } else {
                 return Err(RunError::InstructionUnpredictable);
             }
@@ -216,7 +220,9 @@ impl Instruction for LdrLit {
         let data = proc.read_u32_unaligned(addr)?;
         if self.rt.is_pc() {
             if addr & 3 == 0 {
-                todo!();
+                proc.require_branch_target();
+                proc.load_write_pc(data)?;
+                return Ok(Effect::Branch);
             } else {
                 return Err(RunError::InstructionUnpredictable);
             }
@@ -324,6 +330,9 @@ impl Instruction for LdrReg {
         }
         if self.rt.is_pc() {
             if address & 3 == 0 {
+                if !self.rn.is_sp() {
+                    proc.require_branch_target();
+                }
                 proc.bx_write_pc(data)?;
                 return Ok(Effect::Branch);
             } else {
@@ -122,6 +122,7 @@ impl Instruction for LdrdLit {
         let rt = ins.reg4(12);
         let rt2 = ins.reg4(8);
         other(!index && !wback)?;
+        other(ins == 0xe97fe97f)?; // SG
         unpredictable(rt.is_sp_or_pc() || rt2.is_sp_or_pc() || rt == rt2)?;
         unpredictable(wback)?;
         Ok(Self {
@@ -133,7 +134,7 @@ impl Instruction for LdrdLit {
     }
 
     fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
-        if proc.pc() % 4 != 0 {
+        if !proc.pc().is_multiple_of(4) {
             return Err(RunError::InstructionUnpredictable);
         }
         let address = proc.pc().wrapping_add_or_sub(self.imm32, self.add);
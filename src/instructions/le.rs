@@ -0,0 +1,89 @@
+//! Implements LE (Loop End) and LETP (Loop End with Tail Predication) instructions.
+
+use super::Encoding::{self, T1, T2, T3};
+use super::{unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    core::{ArmVersion::V81M, Effect, ItState, LoBranch, LoBranchInfo, Processor, RunError},
+    decoder::DecodeError,
+};
+
+/// LE or LETP instruction.
+///
+/// Loop End, with optional Tail Predication.
+pub struct Le {
+    /// Offset back to the first instruction of the loop.
+    imm32: u32,
+    /// `true` if the iteration count in LR is decremented. `false` for loops which never end
+    /// (LE without LR).
+    decrement: bool,
+    /// `true` for LETP.
+    tail_predicated: bool,
+}
+
+impl Instruction for Le {
+    fn patterns() -> &'static [Pattern] {
+        &[
+            Pattern {
+                encoding: T1,
+                versions: &[V81M],
+                expression: "11110000000011111100xxxxxxxxxxx1",
+            },
+            Pattern {
+                encoding: T2,
+                versions: &[V81M],
+                expression: "11110000001011111100xxxxxxxxxxx1",
+            },
+            Pattern {
+                encoding: T3,
+                versions: &[V81M],
+                expression: "11110000000111111100xxxxxxxxxxx1",
+            },
+        ]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        unpredictable(state.in_it_block())?;
+        Ok(Self {
+            imm32: (ins.imm1(11) << 1) | (((ins >> 1) & 0x3ff) << 2),
+            decrement: encoding != T2,
+            tail_predicated: encoding == T3,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        if self.tail_predicated {
+            proc.execute_mve_check(false)?;
+        }
+        let end_address = proc.pc().wrapping_sub(4);
+        let start = proc.pc().wrapping_sub(self.imm32);
+        if !proc.loop_end(self.decrement, self.tail_predicated) {
+            proc.lo_branch_info = None;
+            return Ok(Effect::None);
+        }
+        // Next iterations can branch back to the start of the loop without executing this
+        // instruction.
+        proc.lo_branch_info = Some(LoBranchInfo {
+            end_address,
+            branch: LoBranch::LoopEnd {
+                start,
+                decrement: self.decrement,
+                tail_predicated: self.tail_predicated,
+            },
+        });
+        proc.set_pc(start);
+        Ok(Effect::Branch)
+    }
+
+    fn name(&self) -> String {
+        if self.tail_predicated { "letp" } else { "le" }.into()
+    }
+
+    fn args(&self, pc: u32) -> String {
+        let label = pc + 4 - self.imm32;
+        if self.decrement {
+            format!("lr, 0x{:x}", label)
+        } else {
+            format!("0x{:x}", label)
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! Implements MCR and MCR2 (Move to Coprocessor from Arm Register) instructions.
 
 use super::{
+    other,
     Encoding::{self, T1, T2},
     Instruction, Pattern,
 };
@@ -56,6 +57,7 @@ impl Instruction for Mcr {
 
     fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
         debug_assert!((encoding == T1) || (encoding == T2));
+        other(encoding == T1 && ins.imm3(9) == 0b101)?; // Floating-point extension
         let rt = ins.reg4(12);
         unpredictable(rt.is_sp_or_pc())?;
         Ok(Self {
@@ -72,8 +74,7 @@ impl Instruction for Mcr {
 
     fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
         let Some(coprocessor) = proc.coproc_accepted(self.coproc, self.ins) else {
-            proc.generate_coprocessor_exception();
-            return Ok(Effect::None);
+            return Err(proc.generate_coprocessor_exception());
         };
 
         coprocessor
@@ -10,7 +10,8 @@ use crate::{
     core::ItState,
     core::{ArmVersion, Effect, Processor, RunError},
     decoder::DecodeError,
-    registers::RegisterIndex,
+    float::FloatBits,
+    registers::{CoreRegisters, RegisterIndex},
 };
 use std::rc::Rc;
 
@@ -19,14 +20,23 @@ pub mod add;
 pub mod adr;
 pub mod and;
 pub mod asr;
+pub mod aut;
+pub mod autg;
 pub mod b;
+pub mod bf;
 pub mod bfc;
+pub mod bfcsel;
 pub mod bfi;
+pub mod bfl;
+pub mod bflx;
+pub mod bfx;
 pub mod bic;
 pub mod bkpt;
 pub mod bl;
 pub mod blx;
+pub mod bti;
 pub mod bx;
+pub mod bxaut;
 pub mod cbnz;
 pub mod cdp;
 pub mod clrex;
@@ -35,12 +45,21 @@ pub mod cmn;
 pub mod cmp;
 pub mod cps;
 pub mod csdb;
+pub mod cx;
 pub mod dbg;
+pub mod dls;
 pub mod dmb;
 pub mod dsb;
 pub mod eor;
 pub mod isb;
 pub mod it;
+pub mod lctp;
+pub mod lda;
+pub mod ldab;
+pub mod ldaex;
+pub mod ldaexb;
+pub mod ldaexh;
+pub mod ldah;
 pub mod ldc;
 pub mod ldm;
 pub mod ldmdb;
@@ -58,6 +77,7 @@ pub mod ldrsbt;
 pub mod ldrsh;
 pub mod ldrsht;
 pub mod ldrt;
+pub mod le;
 pub mod lsl;
 pub mod lsr;
 pub mod mcr;
@@ -75,6 +95,10 @@ pub mod mvn;
 pub mod nop;
 pub mod orn;
 pub mod orr;
+pub mod pac;
+pub mod pacbti;
+pub mod pacg;
+pub mod pkhbt;
 pub mod pld;
 pub mod pli;
 pub mod pop;
@@ -83,8 +107,10 @@ pub mod push;
 pub mod qadd;
 pub mod qadd16;
 pub mod qadd8;
+pub mod qasx;
 pub mod qdadd;
 pub mod qdsub;
+pub mod qsax;
 pub mod qsub;
 pub mod qsub16;
 pub mod qsub8;
@@ -97,15 +123,48 @@ pub mod rrx;
 pub mod rsb;
 pub mod sadd16;
 pub mod sadd8;
+pub mod sasx;
 pub mod sbc;
 pub mod sbfx;
 pub mod sdiv;
+pub mod sel;
 pub mod sev;
+pub mod sg;
+pub mod shadd16;
+pub mod shadd8;
+pub mod shasx;
+pub mod shsax;
+pub mod shsub16;
+pub mod shsub8;
+pub mod smlabb;
+pub mod smlad;
 pub mod smlal;
+pub mod smlalbb;
+pub mod smlald;
+pub mod smlawb;
+pub mod smlsd;
+pub mod smlsld;
+pub mod smmla;
+pub mod smmls;
+pub mod smmul;
+pub mod smuad;
+pub mod smulbb;
 pub mod smull;
+pub mod smulwb;
+pub mod smusd;
 pub mod ssat;
+pub mod ssat16;
+pub mod ssax;
 pub mod ssbb;
+pub mod ssub16;
+pub mod ssub8;
 pub mod stc;
+pub mod stl;
+pub mod stlb;
+pub mod stlex;
+pub mod stlexb;
+pub mod stlexh;
+pub mod stlh;
 pub mod stm;
 pub mod stmdb;
 pub mod str;
@@ -120,22 +179,88 @@ pub mod strht;
 pub mod strt;
 pub mod sub;
 pub mod svc;
+pub mod sxtab;
+pub mod sxtab16;
+pub mod sxtah;
 pub mod sxtb;
+pub mod sxtb16;
 pub mod sxth;
 pub mod tbb;
 pub mod teq;
 pub mod tst;
+pub mod tt;
+pub mod uadd16;
+pub mod uadd8;
+pub mod uasx;
 pub mod ubfx;
 pub mod udf;
 pub mod udiv;
+pub mod uhadd16;
+pub mod uhadd8;
+pub mod uhasx;
+pub mod uhsax;
+pub mod uhsub16;
+pub mod uhsub8;
+pub mod umaal;
 pub mod umlal;
 pub mod umull;
+pub mod uqadd16;
+pub mod uqadd8;
+pub mod uqasx;
+pub mod uqsax;
+pub mod uqsub16;
+pub mod uqsub8;
+pub mod usad8;
+pub mod usada8;
 pub mod usat;
 pub mod usat16;
+pub mod usax;
+pub mod usub16;
+pub mod usub8;
+pub mod uxtab;
+pub mod uxtab16;
+pub mod uxtah;
 pub mod uxtb;
+pub mod uxtb16;
 pub mod uxth;
+pub mod vabs;
+pub mod vadd;
+pub mod vcmp;
+pub mod vctp;
+pub mod vcvt;
+pub mod vcx;
+pub mod vdiv;
+pub mod vdup;
+pub mod vfma;
+pub mod vfnma;
+pub mod vldm;
+pub mod vldr;
+pub mod vlldm;
+pub mod vlstm;
+pub mod vmaxnm;
+pub mod vmla;
+pub mod vmladav;
+pub mod vmov;
+pub mod vmrs;
+pub mod vmsr;
+pub mod vmul;
+pub mod vneg;
+pub mod vnmla;
+pub mod vorr;
+pub mod vpnot;
+pub mod vpop;
+pub mod vpst;
+pub mod vpt;
+pub mod vpush;
+pub mod vrint;
+pub mod vsel;
+pub mod vsqrt;
+pub mod vstm;
+pub mod vstr;
+pub mod vsub;
 pub mod wfe;
 pub mod wfi;
+pub mod wls;
 pub mod r#yield;
 
 /// Defines how to match an instruction encoding.
@@ -219,6 +344,14 @@ pub trait Instruction {
         None
     }
 
+    /// Returns `true` if the instruction is a BTI clearing instruction, which is a valid target of
+    /// indirect branches when branch target identification is enabled. Only BTI, PACBTI and SG
+    /// instructions should implement this, all other instructions rely on the blanket
+    /// implementation which returns `false`.
+    fn is_branch_target(&self) -> bool {
+        false
+    }
+
     /// Execute the instruction and updates given `proc` processor state.
     ///
     /// The instruction may request special treatment by returning some effect. In particular,
@@ -389,6 +522,20 @@ pub trait DecodeHelper {
 
     /// Extracts `index`, `add` and `wback` flags respectively from bits 10, 9 and 8.
     fn puw(&self) -> (bool, bool, bool);
+
+    /// Extracts a single-precision register index from a 4-bit field at `lsb_index`, extended
+    /// with the bit at `bit_index` as least significant bit (`Vd:D` in the Arm Architecture
+    /// Reference Manual).
+    fn sreg(&self, lsb_index: u8, bit_index: u8) -> u8;
+
+    /// Extracts a double-precision register index from a 4-bit field at `lsb_index`, extended
+    /// with the bit at `bit_index` as most significant bit (`D:Vd` in the Arm Architecture
+    /// Reference Manual).
+    fn dreg(&self, lsb_index: u8, bit_index: u8) -> u8;
+
+    /// Extracts a double-precision register index if `double` is `true`, or a single-precision
+    /// register index otherwise. See [DecodeHelper::sreg] and [DecodeHelper::dreg].
+    fn vreg(&self, lsb_index: u8, bit_index: u8, double: bool) -> u8;
 }
 
 impl DecodeHelper for u32 {
@@ -439,6 +586,22 @@ impl DecodeHelper for u32 {
             self & (1 << 8) != 0,
         )
     }
+
+    fn sreg(&self, lsb_index: u8, bit_index: u8) -> u8 {
+        (self.imm4(lsb_index) << 1 | self.imm1(bit_index)) as u8
+    }
+
+    fn dreg(&self, lsb_index: u8, bit_index: u8) -> u8 {
+        (self.imm1(bit_index) << 4 | self.imm4(lsb_index)) as u8
+    }
+
+    fn vreg(&self, lsb_index: u8, bit_index: u8, double: bool) -> u8 {
+        if double {
+            self.dreg(lsb_index, bit_index)
+        } else {
+            self.sreg(lsb_index, bit_index)
+        }
+    }
 }
 
 #[macro_export]
@@ -497,6 +660,67 @@ pub fn indexing_args(
     }
 }
 
+/// Returns a floating-point extension registers list string, such as "{s0, s1, s2}".
+///
+/// # Arguments
+///
+/// * `single` - `true` for single-precision registers, `false` for double-precision registers.
+/// * `first` - Index of the first register of the list.
+/// * `count` - Number of registers in the list.
+pub fn extension_registers_args(single: bool, first: u8, count: u8) -> String {
+    let prefix = if single { "s" } else { "d" };
+    let list: Vec<String> = (first..first + count)
+        .map(|i| format!("{prefix}{i}"))
+        .collect();
+    format!("{{{}}}", list.join(", "))
+}
+
+/// Returns a floating-point extension register name, such as "s3" or "d3".
+pub fn extension_register(double: bool, index: u8) -> String {
+    let prefix = if double { "d" } else { "s" };
+    format!("{prefix}{index}")
+}
+
+/// Returns the floating-point data type of an instruction operating on single-precision or
+/// double-precision values, used as instruction name suffix.
+pub fn fp_data_type(double: bool) -> &'static str {
+    if double {
+        "f64"
+    } else {
+        "f32"
+    }
+}
+
+/// Access to the floating-point extension registers as single-precision or double-precision
+/// values, so floating-point instructions can be implemented once for both precisions.
+pub(crate) trait ExtensionRegister: FloatBits {
+    /// Returns the value of register `Sn` or `Dn`.
+    fn read(regs: &CoreRegisters, n: u8) -> Self;
+
+    /// Sets the value of register `Sn` or `Dn`.
+    fn write(regs: &mut CoreRegisters, n: u8, value: Self);
+}
+
+impl ExtensionRegister for u32 {
+    fn read(regs: &CoreRegisters, n: u8) -> Self {
+        regs.s[n as usize]
+    }
+
+    fn write(regs: &mut CoreRegisters, n: u8, value: Self) {
+        regs.s[n as usize] = value
+    }
+}
+
+impl ExtensionRegister for u64 {
+    fn read(regs: &CoreRegisters, n: u8) -> Self {
+        regs.d(n as usize)
+    }
+
+    fn write(regs: &mut CoreRegisters, n: u8, value: Self) {
+        regs.set_d(n as usize, value)
+    }
+}
+
 /// Utility trait to call either addition or subtraction between two values depending on a
 /// condition.
 ///
@@ -558,7 +782,9 @@ impl Mnemonic for Rc<dyn Instruction> {
 #[cfg(test)]
 mod tests {
     use crate::{
-        instructions::{indexing_args, rdn_args_string, DecodeHelper, InstructionSize},
+        instructions::{
+            extension_registers_args, indexing_args, rdn_args_string, DecodeHelper, InstructionSize,
+        },
         registers::RegisterIndex,
     };
 
@@ -634,6 +860,14 @@ mod tests {
                 (i & 0b100 != 0, i & 0b010 != 0, i & 0b001 != 0)
             )
         }
+
+        // Test sreg and dreg
+        for i in 0..=15 {
+            for b in 0..=1 {
+                assert_eq!(((i << 12) | (b << 22)).sreg(12, 22), (i * 2 + b) as u8);
+                assert_eq!(((i << 12) | (b << 22)).dreg(12, 22), (i + b * 16) as u8);
+            }
+        }
     }
 
     #[test]
@@ -669,6 +903,12 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_extension_registers_args() {
+        assert_eq!(extension_registers_args(true, 4, 1), "{s4}");
+        assert_eq!(extension_registers_args(false, 8, 3), "{d8, d9, d10}");
+    }
+
     #[test]
     fn test_indexing_args() {
         assert_eq!(
@@ -76,7 +76,7 @@ mod tests {
         for v in vectors {
             let mut proc = Processor::new(Config::v7m());
             proc.set(v.0, 0x87654321);
-            let mut expected = proc.registers.clone();
+            let mut expected = proc.registers;
             Movt {
                 rd: v.0,
                 imm16: v.1,
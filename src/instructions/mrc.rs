@@ -1,6 +1,7 @@
 //! Implements MRC and MRC2 (Move to Arm Register from Coprocessor) instructions.
 
 use super::{
+    other,
     Encoding::{self, T1, T2},
     Instruction, Pattern,
 };
@@ -57,6 +58,7 @@ impl Instruction for Mrc {
 
     fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
         debug_assert!((encoding == T1) || (encoding == T2));
+        other(encoding == T1 && ins.imm3(9) == 0b101)?; // Floating-point extension
         let rt = ins.reg4(12);
         unpredictable(rt.is_sp())?;
         Ok(Self {
@@ -73,8 +75,7 @@ impl Instruction for Mrc {
 
     fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
         let Some(coprocessor) = proc.coproc_accepted(self.coproc, self.ins) else {
-            proc.generate_coprocessor_exception();
-            return Ok(Effect::None);
+            return Err(proc.generate_coprocessor_exception());
         };
 
         let value = coprocessor.borrow_mut().get_one_word(self.ins);
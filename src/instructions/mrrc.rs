@@ -1,6 +1,7 @@
 //! Implements MRRC and MRRC2 (Move to two Arm Registers from Coprocessor) instructions.
 
 use super::{
+    other,
     Encoding::{self, T1, T2},
     Instruction, Pattern,
 };
@@ -54,6 +55,7 @@ impl Instruction for Mrrc {
 
     fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
         debug_assert!((encoding == T1) || (encoding == T2));
+        other(encoding == T1 && ins.imm3(9) == 0b101)?; // Floating-point extension
         let rt = ins.reg4(12);
         let rt2 = ins.reg4(16);
         unpredictable(rt.is_sp_or_pc() || rt2.is_sp_or_pc() || rt == rt2)?;
@@ -70,8 +72,7 @@ impl Instruction for Mrrc {
 
     fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
         let Some(coprocessor) = proc.coproc_accepted(self.coproc, self.ins) else {
-            proc.generate_coprocessor_exception();
-            return Ok(Effect::None);
+            return Err(proc.generate_coprocessor_exception());
         };
 
         let (rt2, rt) = coprocessor.borrow_mut().get_two_words(self.ins);
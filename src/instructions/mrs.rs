@@ -34,7 +34,20 @@ impl Instruction for Mrs {
         debug_assert_eq!(encoding, T1);
         let rd = ins.reg4(8);
         let sysm = ins & 0xff;
-        let good_sysm = matches!(sysm, 0..=3 | 5..=9 | 16..=20);
+        let good_sysm = matches!(
+            sysm,
+            0..=3
+                | 5..=11
+                | 16..=20
+                | 0x20..=0x27
+                | 0x88..=0x8b
+                | 0x90
+                | 0x91
+                | 0x93
+                | 0x94
+                | 0x98
+                | 0xa0..=0xa7
+        );
         unpredictable(rd.is_sp_or_pc() || !good_sysm)?;
         Ok(Self {
             rd,
@@ -51,8 +64,12 @@ impl Instruction for Mrs {
                     rd |= proc.registers.psr.ipsr();
                 }
                 if sysm & 4 == 0 {
-                    rd |= proc.registers.psr.apsr() & 0xf8000000;
-                    // TODO DSP extension
+                    let mask = if proc.dsp_extension() {
+                        0xf80f0000
+                    } else {
+                        0xf8000000
+                    };
+                    rd |= proc.registers.psr.apsr() & mask;
                 }
             }
             0b00001 => {
@@ -60,22 +77,53 @@ impl Instruction for Mrs {
                     match sysm & 7 {
                         0 => rd = proc.registers.msp,
                         1 => rd = proc.registers.psp,
+                        2 => rd = proc.registers.msplim,
+                        3 => rd = proc.registers.psplim,
                         _ => {}
                     }
                 }
             }
             0b00010 => match sysm & 7 {
-                0b000 => {
-                    if proc.is_privileged() {
-                        rd = proc.registers.primask.pm() as u32;
+                0b000 if proc.is_privileged() => {
+                    rd = proc.registers.primask.pm() as u32;
+                }
+                // BASEPRI and FAULTMASK are not present in ArmV6-M.
+                0b001 | 0b010 if proc.is_privileged() && proc.version != V6M => {
+                    rd = proc.registers.basepri & 0xff;
+                }
+                0b011 if proc.is_privileged() && proc.version != V6M => {
+                    rd = proc.registers.faultmask.pm() as u32;
+                }
+                0b100 => {
+                    let mut mask = if proc.fpu().is_some() { 7 } else { 3 };
+                    if proc.pacbti() {
+                        mask |= 0xf0;
                     }
+                    rd = proc.registers.control.read() & mask
                 }
-                0b001 => todo!(),
-                0b010 => todo!(),
-                0b011 => todo!(),
-                0b100 => rd = proc.registers.control.read() & 3,
                 _ => {}
             },
+            // Pointer authentication keys.
+            0b00100 => {
+                if proc.pacbti() && proc.is_privileged() {
+                    rd = proc[self.sysm]
+                }
+            }
+            // Non-secure banked registers, only accessible from Secure state.
+            0b10001..=0b10100 if proc.security_extension() && proc.is_secure() => {
+                rd = match self.sysm {
+                    RegisterIndex::ControlNs if proc.pacbti() => proc[self.sysm] & 0xf3,
+                    RegisterIndex::ControlNs => proc[self.sysm] & 3,
+                    RegisterIndex::PacKeyPNs(_) | RegisterIndex::PacKeyUNs(_) if !proc.pacbti() => {
+                        0
+                    }
+                    _ if !proc.is_privileged() => 0,
+                    RegisterIndex::PrimaskNs | RegisterIndex::FaultMaskNs => proc[self.sysm] & 1,
+                    RegisterIndex::BasepriNs => proc[self.sysm] & 0xff,
+                    _ => proc[self.sysm],
+                }
+            }
+            0b10001..=0b10100 => {}
             _ => panic!(),
         }
         proc.set(self.rd, rd);
@@ -23,6 +23,9 @@ pub struct Msr {
     sysm: RegisterIndex,
     /// Source register.
     rn: RegisterIndex,
+    /// Selects the APSR bits to be written. Bit 0 selects GE flags, bit 1 selects N, Z, C, V and
+    /// Q flags.
+    mask: u8,
 }
 
 impl Instruction for Msr {
@@ -51,24 +54,46 @@ impl Instruction for Msr {
         // For ArmV7-M and following, mask can be different.
         let mask = ins.imm2(10);
         unpredictable((mask == 0) || ((mask != 2) && !matches!(sysm, 0..=3)))?;
-        let good_sysm = matches!(sysm, 0..=3 | 5..=9 | 16..=20);
+        let good_sysm = matches!(
+            sysm,
+            0..=3
+                | 5..=11
+                | 16..=20
+                | 0x20..=0x27
+                | 0x88..=0x8b
+                | 0x90
+                | 0x91
+                | 0x93
+                | 0x94
+                | 0x98
+                | 0xa0..=0xa7
+        );
         unpredictable(rn.is_sp_or_pc() || !good_sysm)?;
         Ok(Self {
             sysm: RegisterIndex::new_sys(sysm),
             rn,
+            mask: mask as u8,
         })
     }
 
     fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
         let val = proc[self.rn];
         match self.sysm {
-            RegisterIndex::Apsr => todo!(),
-            RegisterIndex::Iapsr => todo!(),
-            RegisterIndex::Eapsr => todo!(),
-            RegisterIndex::Xpsr => todo!(),
-            RegisterIndex::Ipsr => todo!(),
-            RegisterIndex::Epsr => {} // Writes are ignored for EPSR with MSR
-            RegisterIndex::Iepsr => todo!(),
+            RegisterIndex::Apsr
+            | RegisterIndex::Iapsr
+            | RegisterIndex::Eapsr
+            | RegisterIndex::Xpsr => {
+                let ge = proc.dsp_extension() && self.mask.bit(0);
+                let psr = &mut proc.registers.psr;
+                if self.mask.bit(1) {
+                    psr.set_apsr(psr.apsr() & 0x000f0000 | val & 0xf8000000);
+                }
+                if ge {
+                    psr.set_ge(val.imm4(16) as u8);
+                }
+            }
+            // Writes are ignored for IPSR and EPSR with MSR
+            RegisterIndex::Ipsr | RegisterIndex::Epsr | RegisterIndex::Iepsr => {}
             RegisterIndex::Msp => {
                 if proc.is_privileged() {
                     proc.registers.msp = val
@@ -79,20 +104,99 @@ impl Instruction for Msr {
                     proc.registers.psp = val
                 }
             }
+            RegisterIndex::Msplim | RegisterIndex::Psplim => {
+                // Stack limits are 8 bytes aligned.
+                if proc.version.includes(V8M) && proc.is_privileged() {
+                    proc.set(self.sysm, val & !7)
+                }
+            }
             RegisterIndex::Primask => {
                 if proc.is_privileged() {
                     proc.registers.primask.set_pm(val.bit(0));
                 }
-            },
-            RegisterIndex::Basepri => todo!(),
-            RegisterIndex::BasepriMax => todo!(),
-            RegisterIndex::FaultMask => todo!(),
+            }
+            RegisterIndex::Basepri => {
+                if proc.version != V6M && proc.is_privileged() {
+                    proc.registers.basepri = val & 0xff;
+                }
+            }
+            RegisterIndex::BasepriMax => {
+                // BASEPRI is only updated if the new value raises the execution priority.
+                let val = val & 0xff;
+                let basepri = proc.registers.basepri;
+                if proc.version != V6M
+                    && proc.is_privileged()
+                    && val != 0
+                    && (val < basepri || basepri == 0)
+                {
+                    proc.registers.basepri = val;
+                }
+            }
+            RegisterIndex::FaultMask => {
+                // FAULTMASK cannot be set when execution priority is already negative.
+                if proc.version != V6M
+                    && proc.is_privileged()
+                    && (proc.execution_priority > -1 || !val.bit(0))
+                {
+                    proc.registers.faultmask.set_pm(val.bit(0));
+                }
+            }
             RegisterIndex::Control => {
                 if proc.is_privileged() {
                     proc.registers.control.set_privileged_bit(val.bit(0));
                     if proc.registers.mode != Mode::Handler {
                         proc.registers.control.set_spsel(val.bit(1))
                     }
+                    if proc.fpu().is_some() {
+                        proc.registers.control.set_fpca(val.bit(2))
+                    }
+                    if proc.pacbti() {
+                        let control = &mut proc.registers.control;
+                        control.set_bti_en(val.bit(4));
+                        control.set_ubti_en(val.bit(5));
+                        control.set_pac_en(val.bit(6));
+                        control.set_upac_en(val.bit(7));
+                    }
+                }
+            }
+            RegisterIndex::PacKeyP(_) | RegisterIndex::PacKeyU(_) => {
+                if proc.pacbti() && proc.is_privileged() {
+                    proc.set(self.sysm, val)
+                }
+            }
+            // Non-secure banked registers, only accessible from Secure state.
+            RegisterIndex::MspNs
+            | RegisterIndex::PspNs
+            | RegisterIndex::MsplimNs
+            | RegisterIndex::PsplimNs
+            | RegisterIndex::SpNs
+            | RegisterIndex::PrimaskNs
+            | RegisterIndex::BasepriNs
+            | RegisterIndex::FaultMaskNs
+            | RegisterIndex::ControlNs => {
+                if proc.security_extension() && proc.is_secure() && proc.is_privileged() {
+                    // PACBTI enable bits are banked as well.
+                    let control_mask = if proc.pacbti() { 0xf1 } else { 1 };
+                    let val = match self.sysm {
+                        RegisterIndex::PrimaskNs | RegisterIndex::FaultMaskNs => val & 1,
+                        RegisterIndex::BasepriNs => val & 0xff,
+                        RegisterIndex::MsplimNs | RegisterIndex::PsplimNs => val & !7,
+                        RegisterIndex::ControlNs if proc.registers.mode == Mode::Handler => {
+                            proc[self.sysm] & 2 | val & control_mask
+                        }
+                        RegisterIndex::ControlNs => val & (control_mask | 2),
+                        _ => val,
+                    };
+                    proc.set(self.sysm, val)
+                }
+            }
+            RegisterIndex::PacKeyPNs(_) | RegisterIndex::PacKeyUNs(_) => {
+                if proc.pacbti()
+                    && proc.security_extension()
+                    && proc.is_secure()
+                    && proc.is_privileged()
+                {
+                    proc.set(self.sysm, val)
                 }
             }
             _ => panic!(),
@@ -105,6 +209,18 @@ impl Instruction for Msr {
     }
 
     fn args(&self, _pc: u32) -> String {
-        format!("{}, {}", self.sysm, self.rn)
+        // Writes to APSR have a suffix indicating which flags are written.
+        let suffix = match self.sysm {
+            RegisterIndex::Apsr
+            | RegisterIndex::Iapsr
+            | RegisterIndex::Eapsr
+            | RegisterIndex::Xpsr => match self.mask {
+                1 => "_g",
+                2 => "_nzcvq",
+                _ => "_nzcvqg",
+            },
+            _ => "",
+        };
+        format!("{}{}, {}", self.sysm, suffix, self.rn)
     }
 }
@@ -174,9 +174,9 @@ mod tests {
         .execute(&mut proc)
         .unwrap();
         assert_eq!(proc.registers.r0, 0xff34ff78);
-        assert_eq!(proc.registers.psr.n(), true);
-        assert_eq!(proc.registers.psr.z(), false);
-        assert_eq!(proc.registers.psr.z(), false);
+        assert!(proc.registers.psr.n());
+        assert!(!proc.registers.psr.z());
+        assert!(!proc.registers.psr.z());
     }
 
     #[test]
@@ -194,9 +194,9 @@ mod tests {
         .execute(&mut proc)
         .unwrap();
         assert_eq!(proc.registers.r0, 0xff34ff78);
-        assert_eq!(proc.registers.psr.n(), true);
-        assert_eq!(proc.registers.psr.z(), false);
-        assert_eq!(proc.registers.psr.z(), false);
+        assert!(proc.registers.psr.n());
+        assert!(!proc.registers.psr.z());
+        assert!(!proc.registers.psr.z());
 
         OrnReg {
             rd: RegisterIndex::R0,
@@ -208,8 +208,8 @@ mod tests {
         .execute(&mut proc)
         .unwrap();
         assert_eq!(proc.registers.r0, 0x12ff56ff);
-        assert_eq!(proc.registers.psr.n(), true);
-        assert_eq!(proc.registers.psr.z(), false);
-        assert_eq!(proc.registers.psr.z(), false);
+        assert!(proc.registers.psr.n());
+        assert!(!proc.registers.psr.z());
+        assert!(!proc.registers.psr.z());
     }
 }
@@ -0,0 +1,90 @@
+//! Implements PAC (Pointer Authentication Code for the link register) instruction.
+
+use super::Encoding::{self, T1};
+use crate::{
+    core::{ArmVersion::V81M, Effect, ItState, Processor, RunError},
+    decoder::DecodeError,
+    instructions::{Instruction, Pattern},
+};
+
+/// PAC instruction.
+///
+/// Pointer Authentication Code for the link register. Computes the authentication code of LR
+/// with SP as modifier and writes it to R12. Executes as a NOP when pointer authentication is not
+/// enabled.
+pub struct Pac {}
+
+impl Instruction for Pac {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V81M],
+            expression: "111100111010(1)(1)(1)(1)10(0)0(0)00000011101",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, _ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        Ok(Self {})
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        if proc.is_pac_enabled() {
+            proc.registers.r12 = proc.compute_pac(proc.lr(), proc.sp());
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "pac".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        "r12, lr, sp".into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pac;
+    use crate::{
+        core::{Config, Processor},
+        instructions::{aut::Aut, Instruction},
+    };
+
+    #[test]
+    fn test_pac_aut() {
+        let mut proc = Processor::new(Config::v81m().pacbti(true));
+        proc.registers.pac_key_p = [1, 2, 3, 4];
+        proc.set_lr(0x08000123);
+        proc.set_sp(0x20000ff0);
+        proc.registers.r12 = 0xcafe;
+
+        // Disabled by default, PAC and AUT are NOPs.
+        Pac {}.execute(&mut proc).unwrap();
+        assert_eq!(proc.registers.r12, 0xcafe);
+        Aut {}.execute(&mut proc).unwrap();
+
+        proc.registers.control.set_pac_en(true);
+        Pac {}.execute(&mut proc).unwrap();
+        let pac = proc.registers.r12;
+        assert_eq!(pac, proc.compute_pac(0x08000123, 0x20000ff0));
+        Aut {}.execute(&mut proc).unwrap();
+
+        // The code depends on the key and the modifier.
+        proc.registers.pac_key_p = [1, 2, 3, 5];
+        assert!(Aut {}.execute(&mut proc).is_err());
+        proc.registers.pac_key_p = [1, 2, 3, 4];
+        proc.set_sp(0x20000fe8);
+        assert!(Aut {}.execute(&mut proc).is_err());
+        // CFSR.INVSTATE
+        assert_eq!(proc.read_u32le_iface(0xe000ed28).unwrap(), 1 << 17);
+
+        // Unprivileged code uses its own key and enable bit.
+        proc.set_sp(0x20000ff0);
+        proc.registers.control.set_privileged_bit(true);
+        Aut {}.execute(&mut proc).unwrap();
+        proc.registers.control.set_upac_en(true);
+        assert!(Aut {}.execute(&mut proc).is_err());
+    }
+}
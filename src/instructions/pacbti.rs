@@ -0,0 +1,49 @@
+//! Implements PACBTI (Pointer Authentication Code for the link register with BTI clearing)
+//! instruction.
+
+use super::Encoding::{self, T1};
+use crate::{
+    core::{ArmVersion::V81M, Effect, ItState, Processor, RunError},
+    decoder::DecodeError,
+    instructions::{Instruction, Pattern},
+};
+
+/// PACBTI instruction.
+///
+/// Pointer Authentication Code for the link register with BTI clearing. Same as PAC, and also a
+/// valid target of indirect branches as BTI, so it can be placed at function entry points.
+pub struct Pacbti {}
+
+impl Instruction for Pacbti {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V81M],
+            expression: "111100111010(1)(1)(1)(1)10(0)0(0)00000001101",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, _ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        Ok(Self {})
+    }
+
+    fn is_branch_target(&self) -> bool {
+        true
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        if proc.is_pac_enabled() {
+            proc.registers.r12 = proc.compute_pac(proc.lr(), proc.sp());
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "pacbti".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        "r12, lr, sp".into()
+    }
+}
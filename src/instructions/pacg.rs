@@ -0,0 +1,57 @@
+//! Implements PACG (Pointer Authentication Code for a general value) instruction.
+
+use super::Encoding::{self, T1};
+use crate::{
+    core::{ArmVersion::V81M, Effect, ItState, Processor, RunError},
+    decoder::DecodeError,
+    instructions::{unpredictable, DecodeHelper, Instruction, Pattern},
+    registers::RegisterIndex,
+};
+
+/// PACG instruction.
+///
+/// Pointer Authentication Code for a general value.
+pub struct Pacg {
+    /// Destination register, receiving the authentication code.
+    rd: RegisterIndex,
+    /// Register holding the value to be authenticated.
+    rn: RegisterIndex,
+    /// Register holding the modifier.
+    rm: RegisterIndex,
+}
+
+impl Instruction for Pacg {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V81M],
+            expression: "111110110110xxxx1111xxxx0000xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        unpredictable(rd.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_pc())?;
+        Ok(Self { rd, rn, rm })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        if !proc.pacbti() {
+            return Err(RunError::InstructionUndefined);
+        }
+        let pac = proc.compute_pac(proc[self.rn], proc[self.rm]);
+        proc.set(self.rd, pac);
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "pacg".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}, {}", self.rd, self.rn, self.rm)
+    }
+}
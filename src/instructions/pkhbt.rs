@@ -0,0 +1,126 @@
+//! Implements PKHBT and PKHTB (Pack Halfword) instructions.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{Instruction, Pattern};
+use crate::{
+    arith::{shift_c, Shift},
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    helpers::BitAccess,
+    instructions::{unpredictable, DecodeHelper},
+    registers::RegisterIndex,
+};
+
+/// PKHBT or PKHTB instruction.
+///
+/// Pack Halfword.
+pub struct Pkhbt {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+    /// Shift applied to Rm.
+    /// LSL for PKHBT, ASR for PKHTB.
+    shift: Shift,
+    /// True for PKHTB: top halfword is taken from Rn and bottom halfword from shifted Rm.
+    /// False for PKHBT: bottom halfword is taken from Rn and top halfword from shifted Rm.
+    tbform: bool,
+}
+
+impl Instruction for Pkhbt {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111010101100xxxx(0)xxxxxxxxxx0xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        unpredictable(rd.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_sp_or_pc())?;
+        let tbform = ins.bit(5);
+        let imm5 = (ins.imm3(12) << 2) | ins.imm2(6);
+        Ok(Self {
+            rd,
+            rn,
+            rm,
+            shift: Shift::from_bits((tbform as u32) << 1, imm5),
+            tbform,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let rn = proc[self.rn];
+        let operand2 = shift_c(proc[self.rm], self.shift, false).0;
+        let result = if self.tbform {
+            (rn & 0xffff0000) | (operand2 & 0xffff)
+        } else {
+            (operand2 & 0xffff0000) | (rn & 0xffff)
+        };
+        proc.set(self.rd, result);
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        if self.tbform { "pkhtb" } else { "pkhbt" }.into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!(
+            "{}, {}, {}{}",
+            self.rd,
+            self.rn,
+            self.rm,
+            self.shift.arg_string()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pkhbt;
+    use crate::{
+        arith::Shift,
+        core::{Config, Processor},
+        instructions::Instruction,
+        registers::RegisterIndex,
+    };
+
+    #[test]
+    fn test_pkhbt() {
+        let vectors = [
+            (false, Shift::lsl(0), 0x12345678, 0x9abcdef0, 0x9abc5678),
+            (false, Shift::lsl(8), 0x12345678, 0x9abcdef0, 0xbcde5678),
+            (true, Shift::asr(16), 0x12345678, 0x9abcdef0, 0x12349abc),
+            (true, Shift::asr(32), 0x12345678, 0x9abcdef0, 0x1234ffff),
+        ];
+
+        for v in vectors {
+            let mut proc = Processor::new(Config::v7em());
+            let rd = RegisterIndex::new_general_random();
+            let (rn, rm) = RegisterIndex::pick_two_general_distinct();
+            proc.set(rn, v.2);
+            proc.set(rm, v.3);
+            let mut expected = proc.registers;
+            expected.set(rd, v.4);
+            Pkhbt {
+                rd,
+                rn,
+                rm,
+                shift: v.1,
+                tbform: v.0,
+            }
+            .execute(&mut proc)
+            .unwrap();
+            assert_eq!(proc.registers, expected);
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! Implements QASX (Saturating Add and Subtract with Exchange) instruction.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{Instruction, Pattern};
+use crate::{
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    instructions::{unpredictable, DecodeHelper},
+    registers::RegisterIndex,
+};
+
+/// QASX instruction.
+///
+/// Saturating Add and Subtract with Exchange.
+pub struct Qasx {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+}
+
+impl Instruction for Qasx {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111110101010xxxx1111xxxx0001xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        unpredictable(rd.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_sp_or_pc())?;
+        Ok(Self { rd, rn, rm })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let rn = proc[self.rn];
+        let rm = proc[self.rm];
+        let diff = (rn as i16).saturating_sub((rm >> 16) as i16);
+        let sum = ((rn >> 16) as i16).saturating_add(rm as i16);
+        proc.set(self.rd, (diff as u16 as u32) | ((sum as u16 as u32) << 16));
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "qasx".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}, {}", self.rd, self.rn, self.rm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Qasx;
+    use crate::core::{Config, Processor};
+    use crate::instructions::Instruction;
+    use crate::registers::RegisterIndex;
+
+    #[test]
+    fn test_qasx() {
+        let vectors = [
+            (0x7fff8000, 0x00017fff, 0x7fff8000),
+            (0x80008000, 0x00010001, 0x80018000),
+            (0x12345678, 0x87654321, 0x55557fff),
+            (0xffff0001, 0x0001ffff, 0xfffe0000),
+            (0x807f01fe, 0x7f8001ff, 0x827e827e),
+            (0xa4517d6c, 0x07c15471, 0xf8c275ab),
+            (0x7589ca4a, 0x7cc661e9, 0x7fff8000),
+        ];
+
+        for v in vectors {
+            let mut proc = Processor::new(Config::v7em());
+            let rd = RegisterIndex::new_general_random();
+            let (rn, rm) = RegisterIndex::pick_two_general_distinct();
+            proc.set(rn, v.0);
+            proc.set(rm, v.1);
+            let mut expected = proc.registers;
+            expected.set(rd, v.2);
+            Qasx { rd, rn, rm }.execute(&mut proc).unwrap();
+            assert_eq!(proc.registers, expected)
+        }
+    }
+}
@@ -0,0 +1,60 @@
+//! Implements QSAX (Saturating Subtract and Add with Exchange) instruction.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{Instruction, Pattern};
+use crate::{
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    instructions::{unpredictable, DecodeHelper},
+    registers::RegisterIndex,
+};
+
+/// QSAX instruction.
+///
+/// Saturating Subtract and Add with Exchange.
+pub struct Qsax {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+}
+
+impl Instruction for Qsax {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111110101110xxxx1111xxxx0001xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        unpredictable(rd.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_sp_or_pc())?;
+        Ok(Self { rd, rn, rm })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let rn = proc[self.rn];
+        let rm = proc[self.rm];
+        let sum = (rn as i16).saturating_add((rm >> 16) as i16);
+        let diff = ((rn >> 16) as i16).saturating_sub(rm as i16);
+        proc.set(self.rd, (sum as u16 as u32) | ((diff as u16 as u32) << 16));
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "qsax".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}, {}", self.rd, self.rn, self.rm)
+    }
+}
@@ -136,7 +136,7 @@ mod tests {
             proc.set(rm, v.initial_rm);
             proc.set(rn, v.initial_rn);
             proc.registers.psr.set_q(v.initial_q);
-            let mut expected = proc.registers.clone();
+            let mut expected = proc.registers;
             expected.psr.set_q(v.expected_q);
             expected.set(rd, v.expected_rd);
             Qsub { rd, rm, rn }.execute(&mut proc).unwrap();
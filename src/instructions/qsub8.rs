@@ -118,7 +118,7 @@ mod tests {
             let (rm, rn) = RegisterIndex::pick_two_general_distinct();
             proc.set(rm, v.initial_rm);
             proc.set(rn, v.initial_rn);
-            let mut expected = proc.registers.clone();
+            let mut expected = proc.registers;
             expected.set(rd, v.expected_rd);
             Qsub8 { rd, rm, rn }.execute(&mut proc).unwrap();
             assert_eq!(proc.registers, expected);
@@ -101,7 +101,7 @@ mod tests {
             let mut proc = Processor::new(Config::v7m());
             let (rd, rm) = RegisterIndex::pick_two_general_distinct();
             proc.set(rm, v.0);
-            let mut expected = proc.registers.clone();
+            let mut expected = proc.registers;
             expected.set(rd, v.1);
             Revsh {
                 rd,
@@ -201,13 +201,13 @@ mod tests {
         };
         ins.execute(&mut proc).unwrap();
         assert_eq!(proc.registers.r0, 0x091a2b3c);
-        assert_eq!(proc.registers.psr.c(), false);
-        assert_eq!(proc.registers.psr.n(), false);
+        assert!(!proc.registers.psr.c());
+        assert!(!proc.registers.psr.n());
         ins.shift.n = 4;
         ins.execute(&mut proc).unwrap();
         assert_eq!(proc.registers.r0, 0x81234567);
-        assert_eq!(proc.registers.psr.c(), true);
-        assert_eq!(proc.registers.psr.n(), true);
+        assert!(proc.registers.psr.c());
+        assert!(proc.registers.psr.n());
     }
 
     #[test]
@@ -224,12 +224,12 @@ mod tests {
         };
         ins.execute(&mut proc).unwrap();
         assert_eq!(proc.registers.r0, 0x091a2b3c);
-        assert_eq!(proc.registers.psr.c(), false);
-        assert_eq!(proc.registers.psr.n(), false);
+        assert!(!proc.registers.psr.c());
+        assert!(!proc.registers.psr.n());
         proc.registers.r2 = 4;
         ins.execute(&mut proc).unwrap();
         assert_eq!(proc.registers.r0, 0x81234567);
-        assert_eq!(proc.registers.psr.c(), true);
-        assert_eq!(proc.registers.psr.n(), true);
+        assert!(proc.registers.psr.c());
+        assert!(proc.registers.psr.n());
     }
 }
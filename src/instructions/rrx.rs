@@ -128,7 +128,7 @@ mod tests {
             let rm = RegisterIndex::new_general_random();
             proc.set(rm, v.initial_rm);
             proc.registers.psr.set_c(v.carry_in);
-            let mut expected = proc.registers.clone();
+            let mut expected = proc.registers;
             expected.psr.set_flags(v.expected_flags);
             expected.set(rd, v.expected_rd);
             Rrx {
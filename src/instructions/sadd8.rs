@@ -94,7 +94,7 @@ mod tests {
             let (rn, rm) = RegisterIndex::pick_two_general_distinct();
             proc.set(rn, v.0);
             proc.set(rm, v.1);
-            let mut expected = proc.registers.clone();
+            let mut expected = proc.registers;
             expected.set(rd, v.2);
             expected.psr.set_ge(v.3);
             Sadd8 { rd, rn, rm }.execute(&mut proc).unwrap();
@@ -0,0 +1,97 @@
+//! Implements SASX (Signed Add and Subtract with Exchange) instruction.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{Instruction, Pattern};
+use crate::{
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    instructions::{unpredictable, DecodeHelper},
+    registers::RegisterIndex,
+};
+
+/// SASX instruction.
+///
+/// Signed Add and Subtract with Exchange.
+pub struct Sasx {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+}
+
+impl Instruction for Sasx {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111110101010xxxx1111xxxx0000xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        unpredictable(rd.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_sp_or_pc())?;
+        Ok(Self { rd, rn, rm })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let rn = proc[self.rn];
+        let rm = proc[self.rm];
+        let diff = (rn as i16 as i32) - ((rm >> 16) as i16 as i32);
+        let sum = ((rn >> 16) as i16 as i32) + (rm as i16 as i32);
+        proc.set(self.rd, (diff as u16 as u32) | ((sum as u16 as u32) << 16));
+        let ge10 = if diff >= 0 { 0b0011 } else { 0 };
+        let ge32 = if sum >= 0 { 0b1100 } else { 0 };
+        proc.registers.psr.set_ge(ge10 | ge32);
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "sasx".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}, {}", self.rd, self.rn, self.rm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Sasx;
+    use crate::core::{Config, Processor};
+    use crate::instructions::Instruction;
+    use crate::registers::RegisterIndex;
+
+    #[test]
+    fn test_sasx() {
+        let vectors = [
+            (0x7fff8000, 0x00017fff, 0xfffe7fff, 0b1100),
+            (0x80008000, 0x00010001, 0x80017fff, 0b0000),
+            (0x12345678, 0x87654321, 0x5555cf13, 0b1111),
+            (0xffff0001, 0x0001ffff, 0xfffe0000, 0b0011),
+            (0x807f01fe, 0x7f8001ff, 0x827e827e, 0b0000),
+            (0x3a096533, 0x5ed34fe5, 0x89ee0660, 0b1111),
+            (0xf658f7a7, 0x6018366c, 0x2cc4978f, 0b1100),
+        ];
+
+        for v in vectors {
+            let mut proc = Processor::new(Config::v7em());
+            let rd = RegisterIndex::new_general_random();
+            let (rn, rm) = RegisterIndex::pick_two_general_distinct();
+            proc.set(rn, v.0);
+            proc.set(rm, v.1);
+            let mut expected = proc.registers;
+            expected.set(rd, v.2);
+            expected.psr.set_ge(v.3);
+            Sasx { rd, rn, rm }.execute(&mut proc).unwrap();
+            assert_eq!(proc.registers, expected)
+        }
+    }
+}
@@ -210,9 +210,9 @@ mod tests {
         .execute(&mut proc)
         .unwrap();
         assert_eq!(proc.registers.r0, 900);
-        assert_eq!(proc.registers.psr.z(), false);
-        assert_eq!(proc.registers.psr.c(), true);
-        assert_eq!(proc.registers.psr.v(), false);
+        assert!(!proc.registers.psr.z());
+        assert!(proc.registers.psr.c());
+        assert!(!proc.registers.psr.v());
 
         proc.registers.psr.set_c(true);
         SbcImm {
@@ -224,9 +224,9 @@ mod tests {
         .execute(&mut proc)
         .unwrap();
         assert_eq!(proc.registers.r0, 0);
-        assert_eq!(proc.registers.psr.z(), true);
-        assert_eq!(proc.registers.psr.c(), true);
-        assert_eq!(proc.registers.psr.v(), false);
+        assert!(proc.registers.psr.z());
+        assert!(proc.registers.psr.c());
+        assert!(!proc.registers.psr.v());
     }
 
     #[test]
@@ -245,9 +245,9 @@ mod tests {
         .execute(&mut proc)
         .unwrap();
         assert_eq!(proc.registers.r0, 900);
-        assert_eq!(proc.registers.psr.z(), false);
-        assert_eq!(proc.registers.psr.c(), true);
-        assert_eq!(proc.registers.psr.v(), false);
+        assert!(!proc.registers.psr.z());
+        assert!(proc.registers.psr.c());
+        assert!(!proc.registers.psr.v());
 
         proc.registers.psr.set_c(true);
         proc.registers.r2 = 250;
@@ -262,8 +262,8 @@ mod tests {
         .execute(&mut proc)
         .unwrap();
         assert_eq!(proc.registers.r0, 0);
-        assert_eq!(proc.registers.psr.z(), true);
-        assert_eq!(proc.registers.psr.c(), true);
-        assert_eq!(proc.registers.psr.v(), false);
+        assert!(proc.registers.psr.z());
+        assert!(proc.registers.psr.c());
+        assert!(!proc.registers.psr.v());
     }
 }
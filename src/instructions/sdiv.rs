@@ -44,9 +44,13 @@ impl Instruction for Sdiv {
     fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
         let rm = proc[self.rm];
         let result = if rm == 0 {
-            todo!()
+            if proc.integer_zero_divide_trapping_enabled() {
+                return Err(proc.generate_integer_zero_divide());
+            }
+            0
         } else {
-            (proc[self.rn] as i32 / rm as i32) as u32
+            // Division of 0x80000000 by -1 overflows and gives 0x80000000.
+            (proc[self.rn] as i32).wrapping_div(rm as i32) as u32
         };
         proc.set(self.rd, result);
         Ok(Effect::None)
@@ -0,0 +1,96 @@
+//! Implements SEL (Select Bytes) instruction.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{Instruction, Pattern};
+use crate::{
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    helpers::BitAccess,
+    instructions::{unpredictable, DecodeHelper},
+    registers::RegisterIndex,
+};
+
+/// SEL instruction.
+///
+/// Select Bytes.
+pub struct Sel {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+}
+
+impl Instruction for Sel {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111110101010xxxx1111xxxx1000xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        unpredictable(rd.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_sp_or_pc())?;
+        Ok(Self { rd, rn, rm })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let rn = proc[self.rn];
+        let rm = proc[self.rm];
+        let ge = proc.registers.psr.ge() as u32;
+        let mut result = 0;
+        for i in 0..4 {
+            let mask = 0xff << (i * 8);
+            result |= if ge.bit(i) { rn & mask } else { rm & mask };
+        }
+        proc.set(self.rd, result);
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "sel".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}, {}", self.rd, self.rn, self.rm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Sel;
+    use crate::core::{Config, Processor};
+    use crate::instructions::Instruction;
+    use crate::registers::RegisterIndex;
+
+    #[test]
+    fn test_sel() {
+        let vectors = [
+            (0b0000, 0x9abcdef0),
+            (0b1111, 0x12345678),
+            (0b0101, 0x9a34de78),
+            (0b1000, 0x12bcdef0),
+        ];
+
+        for v in vectors {
+            let mut proc = Processor::new(Config::v7em());
+            let rd = RegisterIndex::new_general_random();
+            let (rn, rm) = RegisterIndex::pick_two_general_distinct();
+            proc.set(rn, 0x12345678);
+            proc.set(rm, 0x9abcdef0);
+            proc.registers.psr.set_ge(v.0);
+            let mut expected = proc.registers;
+            expected.set(rd, v.1);
+            Sel { rd, rn, rm }.execute(&mut proc).unwrap();
+            assert_eq!(proc.registers, expected);
+        }
+    }
+}
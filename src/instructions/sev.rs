@@ -36,8 +36,10 @@ impl Instruction for Sev {
         Ok(Self {})
     }
 
-    fn execute(&self, _proc: &mut Processor) -> Result<Effect, RunError> {
-        todo!()
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        // The event is also signaled to the executing processor.
+        proc.registers.event = true;
+        Ok(Effect::SendEvent)
     }
 
     fn name(&self) -> String {
@@ -0,0 +1,57 @@
+//! Implements SG (Secure Gateway) instruction.
+
+use super::Encoding::T1;
+use crate::{
+    core::{ArmVersion::V8M, Effect, ItState, Processor, RunError},
+    decoder::DecodeError,
+    instructions::{Encoding, Instruction, Pattern},
+    registers::SecurityState,
+};
+
+/// SG instruction.
+///
+/// Secure Gateway. Marks a valid entry point to Secure code when placed in a Non-secure callable
+/// region: executed from Non-secure state, the processor transitions to the Secure state and
+/// clears bit 0 of LR, so the Secure function can return to the Non-secure caller with BXNS.
+pub struct Sg {}
+
+impl Instruction for Sg {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V8M],
+            expression: "11101001011111111110100101111111",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, _ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        Ok(Self {})
+    }
+
+    fn is_branch_target(&self) -> bool {
+        true
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        // Behaves as a NOP if the Security Extension is not implemented, or in Secure state, or
+        // if the instruction is not in Secure memory.
+        if proc.security_extension()
+            && !proc.is_secure()
+            && !proc.security_attributes(proc.pc() - 4).non_secure
+        {
+            proc.set_lr(proc.lr() & !1);
+            proc.registers.set_security_state(SecurityState::Secure);
+            proc.registers.control.set_sfpa(false);
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "sg".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        "".into()
+    }
+}
@@ -0,0 +1,63 @@
+//! Implements SHADD16 (Signed Halving Add 16) instruction.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{Instruction, Pattern};
+use crate::{
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    instructions::{unpredictable, DecodeHelper},
+    registers::RegisterIndex,
+};
+
+/// SHADD16 instruction.
+///
+/// Signed Halving Add 16.
+pub struct Shadd16 {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+}
+
+impl Instruction for Shadd16 {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111110101001xxxx1111xxxx0010xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        unpredictable(rd.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_sp_or_pc())?;
+        Ok(Self { rd, rn, rm })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let rn = proc[self.rn];
+        let rm = proc[self.rm];
+        let sum1 = (rn as i16 as i32) + (rm as i16 as i32);
+        let sum2 = ((rn >> 16) as i16 as i32) + ((rm >> 16) as i16 as i32);
+        proc.set(
+            self.rd,
+            ((sum1 >> 1) as u16 as u32) | (((sum2 >> 1) as u16 as u32) << 16),
+        );
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "shadd16".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}, {}", self.rd, self.rn, self.rm)
+    }
+}
@@ -0,0 +1,96 @@
+//! Implements SHADD8 (Signed Halving Add 8) instruction.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{Instruction, Pattern};
+use crate::{
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    instructions::{unpredictable, DecodeHelper},
+    registers::RegisterIndex,
+};
+
+/// SHADD8 instruction.
+///
+/// Signed Halving Add 8.
+pub struct Shadd8 {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+}
+
+impl Instruction for Shadd8 {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111110101000xxxx1111xxxx0010xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        unpredictable(rd.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_sp_or_pc())?;
+        Ok(Self { rd, rn, rm })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let rn = proc[self.rn];
+        let rm = proc[self.rm];
+        let mut result = 0;
+        for i in 0..4 {
+            let sum = ((rn >> (i * 8)) as i8 as i32) + ((rm >> (i * 8)) as i8 as i32);
+            result |= ((sum >> 1) as u8 as u32) << (i * 8);
+        }
+        proc.set(self.rd, result);
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "shadd8".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}, {}", self.rd, self.rn, self.rm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Shadd8;
+    use crate::core::{Config, Processor};
+    use crate::instructions::Instruction;
+    use crate::registers::RegisterIndex;
+
+    #[test]
+    fn test_shadd8() {
+        let vectors = [
+            (0x7fff8000, 0x00017fff, 0x3f00ffff),
+            (0x80008000, 0x00010001, 0xc000c000),
+            (0x12345678, 0x87654321, 0xcc4c4c4c),
+            (0xffff0001, 0x0001ffff, 0xff00ff00),
+            (0x807f01fe, 0x7f8001ff, 0xffff01fe),
+            (0x74007cb4, 0x63f65da8, 0x6bfb6cae),
+            (0x7eb72f82, 0x92b850ad, 0x08b73f97),
+        ];
+
+        for v in vectors {
+            let mut proc = Processor::new(Config::v7em());
+            let rd = RegisterIndex::new_general_random();
+            let (rn, rm) = RegisterIndex::pick_two_general_distinct();
+            proc.set(rn, v.0);
+            proc.set(rm, v.1);
+            let mut expected = proc.registers;
+            expected.set(rd, v.2);
+            Shadd8 { rd, rn, rm }.execute(&mut proc).unwrap();
+            assert_eq!(proc.registers, expected)
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! Implements SHSUB16 (Signed Halving Subtract 16) instruction.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{Instruction, Pattern};
+use crate::{
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    instructions::{unpredictable, DecodeHelper},
+    registers::RegisterIndex,
+};
+
+/// SHSUB16 instruction.
+///
+/// Signed Halving Subtract 16.
+pub struct Shsub16 {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+}
+
+impl Instruction for Shsub16 {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111110101101xxxx1111xxxx0010xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        unpredictable(rd.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_sp_or_pc())?;
+        Ok(Self { rd, rn, rm })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let rn = proc[self.rn];
+        let rm = proc[self.rm];
+        let diff1 = (rn as i16 as i32) - (rm as i16 as i32);
+        let diff2 = ((rn >> 16) as i16 as i32) - ((rm >> 16) as i16 as i32);
+        proc.set(
+            self.rd,
+            ((diff1 >> 1) as u16 as u32) | (((diff2 >> 1) as u16 as u32) << 16),
+        );
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "shsub16".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}, {}", self.rd, self.rn, self.rm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Shsub16;
+    use crate::core::{Config, Processor};
+    use crate::instructions::Instruction;
+    use crate::registers::RegisterIndex;
+
+    #[test]
+    fn test_shsub16() {
+        let vectors = [
+            (0x7fff8000, 0x00017fff, 0x3fff8000),
+            (0x80008000, 0x00010001, 0xbfffbfff),
+            (0x12345678, 0x87654321, 0x456709ab),
+            (0xffff0001, 0x0001ffff, 0xffff0001),
+            (0x807f01fe, 0x7f8001ff, 0x807fffff),
+            (0x16edc5d4, 0x7c2f7ad0, 0xcd5fa582),
+            (0x3bf36a14, 0xc24f6aa8, 0x3cd2ffb6),
+        ];
+
+        for v in vectors {
+            let mut proc = Processor::new(Config::v7em());
+            let rd = RegisterIndex::new_general_random();
+            let (rn, rm) = RegisterIndex::pick_two_general_distinct();
+            proc.set(rn, v.0);
+            proc.set(rm, v.1);
+            let mut expected = proc.registers;
+            expected.set(rd, v.2);
+            Shsub16 { rd, rn, rm }.execute(&mut proc).unwrap();
+            assert_eq!(proc.registers, expected)
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! Implements SMLABB, SMLABT, SMLATB and SMLATT (Signed Multiply Accumulate halfwords)
+//! instructions.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{other, unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    helpers::BitAccess,
+    registers::RegisterIndex,
+};
+
+/// SMLABB, SMLABT, SMLATB or SMLATT instruction.
+///
+/// Signed Multiply Accumulate (halfwords).
+pub struct Smlabb {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+    /// Accumulator register.
+    ra: RegisterIndex,
+    /// True to use the top halfword of Rn, false to use the bottom halfword.
+    n_high: bool,
+    /// True to use the top halfword of Rm, false to use the bottom halfword.
+    m_high: bool,
+}
+
+impl Instruction for Smlabb {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111110110001xxxxxxxxxxxx00xxxxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        let ra = ins.reg4(12);
+        other(ra.is_pc())?; // SMULBB, SMULBT, SMULTB, SMULTT
+        unpredictable(rd.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_sp_or_pc() || ra.is_sp())?;
+        Ok(Self {
+            rd,
+            rn,
+            rm,
+            ra,
+            n_high: ins.bit(5),
+            m_high: ins.bit(4),
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let rn = proc[self.rn];
+        let rm = proc[self.rm];
+        let operand1 = if self.n_high { rn >> 16 } else { rn } as i16 as i64;
+        let operand2 = if self.m_high { rm >> 16 } else { rm } as i16 as i64;
+        let result = operand1 * operand2 + proc[self.ra] as i32 as i64;
+        proc.set(self.rd, result as u32);
+        if result != result as i32 as i64 {
+            proc.registers.psr.set_q(true);
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "smla{}{}",
+            if self.n_high { 't' } else { 'b' },
+            if self.m_high { 't' } else { 'b' }
+        )
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}, {}, {}", self.rd, self.rn, self.rm, self.ra)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Smlabb;
+    use crate::{
+        core::{Config, Processor},
+        instructions::Instruction,
+        registers::RegisterIndex,
+    };
+
+    #[test]
+    fn test_smlabb() {
+        let vectors = [
+            (false, false, 0x00030002, 0xfffd0004, 10, 18, false),
+            (true, false, 0x00030002, 0xfffd0004, 10, 22, false),
+            (false, true, 0x00030002, 0xfffd0004, 10, 4, false),
+            (true, true, 0x00030002, 0xfffd0004, 10, 1, false),
+            (false, false, 0x8000, 0x8000, 0x40000000, 0x80000000, true),
+            (false, false, 0x8000, 0x8000, 0xc0000000, 0x00000000, false),
+        ];
+
+        for v in vectors {
+            let mut proc = Processor::new(Config::v7em());
+            let (rd, rn, rm, ra) = RegisterIndex::pick_four_general_distinct();
+            proc.set(rn, v.2);
+            proc.set(rm, v.3);
+            proc.set(ra, v.4);
+            let mut expected = proc.registers;
+            expected.set(rd, v.5);
+            expected.psr.set_q(v.6);
+            Smlabb {
+                rd,
+                rn,
+                rm,
+                ra,
+                n_high: v.0,
+                m_high: v.1,
+            }
+            .execute(&mut proc)
+            .unwrap();
+            assert_eq!(proc.registers, expected);
+        }
+    }
+}
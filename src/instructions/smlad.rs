@@ -0,0 +1,119 @@
+//! Implements SMLAD and SMLADX (Signed Multiply Accumulate Dual) instructions.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{other, unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    arith::ror,
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    helpers::BitAccess,
+    registers::RegisterIndex,
+};
+
+/// SMLAD or SMLADX instruction.
+///
+/// Signed Multiply Accumulate Dual.
+pub struct Smlad {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+    /// Accumulator register.
+    ra: RegisterIndex,
+    /// True to swap the halfwords of Rm before multiplication (SMLADX).
+    m_swap: bool,
+}
+
+impl Instruction for Smlad {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111110110010xxxxxxxxxxxx000xxxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        let ra = ins.reg4(12);
+        other(ra.is_pc())?; // SMUAD
+        unpredictable(rd.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_sp_or_pc() || ra.is_sp())?;
+        Ok(Self {
+            rd,
+            rn,
+            rm,
+            ra,
+            m_swap: ins.bit(4),
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let rn = proc[self.rn];
+        let operand2 = ror(proc[self.rm], if self.m_swap { 16 } else { 0 });
+        let product1 = (rn as i16 as i64) * (operand2 as i16 as i64);
+        let product2 = ((rn >> 16) as i16 as i64) * ((operand2 >> 16) as i16 as i64);
+        let result = product1 + product2 + proc[self.ra] as i32 as i64;
+        proc.set(self.rd, result as u32);
+        if result != result as i32 as i64 {
+            proc.registers.psr.set_q(true);
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        if self.m_swap { "smladx" } else { "smlad" }.into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}, {}, {}", self.rd, self.rn, self.rm, self.ra)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Smlad;
+    use crate::{
+        core::{Config, Processor},
+        instructions::Instruction,
+        registers::RegisterIndex,
+    };
+
+    #[test]
+    fn test_smlad() {
+        let vectors = [
+            (false, 0x00030002, 0xfffd0004, 10, 9, false),
+            (true, 0x00030002, 0xfffd0004, 10, 16, false),
+            (false, 0x80008000, 0x80008000, 0, 0x80000000, true),
+            (false, 0x80008000, 0x80008000, 0x80000000, 0x00000000, false),
+            (false, 0x7fff8000, 0x80008000, 0x7fffffff, 0x80007fff, true),
+        ];
+
+        for v in vectors {
+            let mut proc = Processor::new(Config::v7em());
+            let (rd, rn, rm, ra) = RegisterIndex::pick_four_general_distinct();
+            proc.set(rn, v.1);
+            proc.set(rm, v.2);
+            proc.set(ra, v.3);
+            let mut expected = proc.registers;
+            expected.set(rd, v.4);
+            expected.psr.set_q(v.5);
+            Smlad {
+                rd,
+                rn,
+                rm,
+                ra,
+                m_swap: v.0,
+            }
+            .execute(&mut proc)
+            .unwrap();
+            assert_eq!(proc.registers, expected);
+        }
+    }
+}
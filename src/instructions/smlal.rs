@@ -111,7 +111,7 @@ mod tests {
             proc.set(rdlo, v.0 as u32);
             proc.set(rn, v.1 as u32);
             proc.set(rm, v.2 as u32);
-            let mut expected = proc.registers.clone();
+            let mut expected = proc.registers;
             expected.set(rdhi, (v.3 >> 32) as u32);
             expected.set(rdlo, v.3 as u32);
             Smlal { rdlo, rdhi, rn, rm }.execute(&mut proc).unwrap();
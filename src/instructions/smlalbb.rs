@@ -0,0 +1,87 @@
+//! Implements SMLALBB, SMLALBT, SMLALTB and SMLALTT (Signed Multiply Accumulate Long halfwords)
+//! instructions.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    helpers::BitAccess,
+    registers::RegisterIndex,
+};
+
+/// SMLALBB, SMLALBT, SMLALTB or SMLALTT instruction.
+///
+/// Signed Multiply Accumulate Long (halfwords).
+pub struct Smlalbb {
+    /// Source register for the lower 32 bits of the accumulate value, and destination register for
+    /// the lower 32 bits of the result.
+    rdlo: RegisterIndex,
+    /// Source register for the higher 32 bits of the accumulate value, and destination register
+    /// for the higher 32 bits of the result.
+    rdhi: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+    /// True to use the top halfword of Rn, false to use the bottom halfword.
+    n_high: bool,
+    /// True to use the top halfword of Rm, false to use the bottom halfword.
+    m_high: bool,
+}
+
+impl Instruction for Smlalbb {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111110111100xxxxxxxxxxxx10xxxxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rdlo = ins.reg4(12);
+        let rdhi = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        unpredictable(
+            rdlo.is_sp_or_pc() || rdhi.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_sp_or_pc(),
+        )?;
+        unpredictable(rdlo == rdhi)?;
+        Ok(Self {
+            rdlo,
+            rdhi,
+            rn,
+            rm,
+            n_high: ins.bit(5),
+            m_high: ins.bit(4),
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let rn = proc[self.rn];
+        let rm = proc[self.rm];
+        let operand1 = if self.n_high { rn >> 16 } else { rn } as i16 as i64;
+        let operand2 = if self.m_high { rm >> 16 } else { rm } as i16 as i64;
+        let accumulator = (((proc[self.rdhi] as u64) << 32) | proc[self.rdlo] as u64) as i64;
+        let result = (operand1 * operand2).wrapping_add(accumulator);
+        proc.set(self.rdhi, (result >> 32) as u32);
+        proc.set(self.rdlo, result as u32);
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "smlal{}{}",
+            if self.n_high { 't' } else { 'b' },
+            if self.m_high { 't' } else { 'b' }
+        )
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}, {}, {}", self.rdlo, self.rdhi, self.rn, self.rm)
+    }
+}
@@ -0,0 +1,135 @@
+//! Implements SMLALD and SMLALDX (Signed Multiply Accumulate Long Dual) instructions.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    arith::ror,
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    helpers::BitAccess,
+    registers::RegisterIndex,
+};
+
+/// SMLALD or SMLALDX instruction.
+///
+/// Signed Multiply Accumulate Long Dual.
+pub struct Smlald {
+    /// Source register for the lower 32 bits of the accumulate value, and destination register for
+    /// the lower 32 bits of the result.
+    rdlo: RegisterIndex,
+    /// Source register for the higher 32 bits of the accumulate value, and destination register
+    /// for the higher 32 bits of the result.
+    rdhi: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+    /// True to swap the halfwords of Rm before multiplication (SMLALDX).
+    m_swap: bool,
+}
+
+impl Instruction for Smlald {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111110111100xxxxxxxxxxxx110xxxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rdlo = ins.reg4(12);
+        let rdhi = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        unpredictable(
+            rdlo.is_sp_or_pc() || rdhi.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_sp_or_pc(),
+        )?;
+        unpredictable(rdlo == rdhi)?;
+        Ok(Self {
+            rdlo,
+            rdhi,
+            rn,
+            rm,
+            m_swap: ins.bit(4),
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let rn = proc[self.rn];
+        let operand2 = ror(proc[self.rm], if self.m_swap { 16 } else { 0 });
+        let product1 = (rn as i16 as i64) * (operand2 as i16 as i64);
+        let product2 = ((rn >> 16) as i16 as i64) * ((operand2 >> 16) as i16 as i64);
+        let accumulator = (((proc[self.rdhi] as u64) << 32) | proc[self.rdlo] as u64) as i64;
+        let result = (product1 + product2).wrapping_add(accumulator);
+        proc.set(self.rdhi, (result >> 32) as u32);
+        proc.set(self.rdlo, result as u32);
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        if self.m_swap { "smlaldx" } else { "smlald" }.into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}, {}, {}", self.rdlo, self.rdhi, self.rn, self.rm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Smlald;
+    use crate::{
+        core::{Config, Processor},
+        instructions::Instruction,
+        registers::RegisterIndex,
+    };
+
+    #[test]
+    fn test_smlald() {
+        let vectors: [(bool, u32, u32, u64, u64); 5] = [
+            (false, 0x00030002, 0xfffd0004, 10, 9),
+            (true, 0x00030002, 0xfffd0004, 10, 16),
+            (false, 0x00030002, 0xfffd0004, 0, 0xffffffff_ffffffff),
+            (
+                false,
+                0x80008000,
+                0x80008000,
+                0x80000000,
+                0x00000001_00000000,
+            ),
+            (
+                false,
+                0x80008000,
+                0x80008000,
+                0x7fffffff_ffffffff,
+                0x80000000_7fffffff,
+            ),
+        ];
+
+        for v in vectors {
+            let mut proc = Processor::new(Config::v7em());
+            let (rdlo, rdhi, rn, rm) = RegisterIndex::pick_four_general_distinct();
+            proc.set(rn, v.1);
+            proc.set(rm, v.2);
+            proc.set(rdlo, v.3 as u32);
+            proc.set(rdhi, (v.3 >> 32) as u32);
+            let mut expected = proc.registers;
+            expected.set(rdlo, v.4 as u32);
+            expected.set(rdhi, (v.4 >> 32) as u32);
+            Smlald {
+                rdlo,
+                rdhi,
+                rn,
+                rm,
+                m_swap: v.0,
+            }
+            .execute(&mut proc)
+            .unwrap();
+            assert_eq!(proc.registers, expected);
+        }
+    }
+}
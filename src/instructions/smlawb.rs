@@ -0,0 +1,116 @@
+//! Implements SMLAWB and SMLAWT (Signed Multiply Accumulate word by halfword) instructions.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{other, unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    helpers::BitAccess,
+    registers::RegisterIndex,
+};
+
+/// SMLAWB or SMLAWT instruction.
+///
+/// Signed Multiply Accumulate (word by halfword).
+pub struct Smlawb {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+    /// Accumulator register.
+    ra: RegisterIndex,
+    /// True to use the top halfword of Rm, false to use the bottom halfword.
+    m_high: bool,
+}
+
+impl Instruction for Smlawb {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111110110011xxxxxxxxxxxx000xxxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        let ra = ins.reg4(12);
+        other(ra.is_pc())?; // SMULWB, SMULWT
+        unpredictable(rd.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_sp_or_pc() || ra.is_sp())?;
+        Ok(Self {
+            rd,
+            rn,
+            rm,
+            ra,
+            m_high: ins.bit(4),
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let rm = proc[self.rm];
+        let operand2 = if self.m_high { rm >> 16 } else { rm } as i16 as i64;
+        let result = proc[self.rn] as i32 as i64 * operand2 + ((proc[self.ra] as i32 as i64) << 16);
+        proc.set(self.rd, (result >> 16) as u32);
+        if result >> 16 != (result >> 16) as i32 as i64 {
+            proc.registers.psr.set_q(true);
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        if self.m_high { "smlawt" } else { "smlawb" }.into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}, {}, {}", self.rd, self.rn, self.rm, self.ra)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Smlawb;
+    use crate::{
+        core::{Config, Processor},
+        instructions::Instruction,
+        registers::RegisterIndex,
+    };
+
+    #[test]
+    fn test_smlawb() {
+        let vectors = [
+            (false, 0x00030000, 0xfffd0004, 10, 22, false),
+            (true, 0x00030000, 0xfffd0004, 10, 1, false),
+            (false, 0xffff0000, 0x00000002, 0, 0xfffffffe, false),
+            (false, 0x80000000, 0x00008000, 0, 0x40000000, false),
+            (false, 0x80000000, 0x00008000, 0x40000000, 0x80000000, true),
+        ];
+
+        for v in vectors {
+            let mut proc = Processor::new(Config::v7em());
+            let (rd, rn, rm, ra) = RegisterIndex::pick_four_general_distinct();
+            proc.set(rn, v.1);
+            proc.set(rm, v.2);
+            proc.set(ra, v.3);
+            let mut expected = proc.registers;
+            expected.set(rd, v.4);
+            expected.psr.set_q(v.5);
+            Smlawb {
+                rd,
+                rn,
+                rm,
+                ra,
+                m_high: v.0,
+            }
+            .execute(&mut proc)
+            .unwrap();
+            assert_eq!(proc.registers, expected);
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! Implements SMLSLD and SMLSLDX (Signed Multiply Subtract Long Dual) instructions.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    arith::ror,
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    helpers::BitAccess,
+    registers::RegisterIndex,
+};
+
+/// SMLSLD or SMLSLDX instruction.
+///
+/// Signed Multiply Subtract Long Dual.
+pub struct Smlsld {
+    /// Source register for the lower 32 bits of the accumulate value, and destination register for
+    /// the lower 32 bits of the result.
+    rdlo: RegisterIndex,
+    /// Source register for the higher 32 bits of the accumulate value, and destination register
+    /// for the higher 32 bits of the result.
+    rdhi: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+    /// True to swap the halfwords of Rm before multiplication (SMLSLDX).
+    m_swap: bool,
+}
+
+impl Instruction for Smlsld {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111110111101xxxxxxxxxxxx110xxxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rdlo = ins.reg4(12);
+        let rdhi = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        unpredictable(
+            rdlo.is_sp_or_pc() || rdhi.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_sp_or_pc(),
+        )?;
+        unpredictable(rdlo == rdhi)?;
+        Ok(Self {
+            rdlo,
+            rdhi,
+            rn,
+            rm,
+            m_swap: ins.bit(4),
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let rn = proc[self.rn];
+        let operand2 = ror(proc[self.rm], if self.m_swap { 16 } else { 0 });
+        let product1 = (rn as i16 as i64) * (operand2 as i16 as i64);
+        let product2 = ((rn >> 16) as i16 as i64) * ((operand2 >> 16) as i16 as i64);
+        let accumulator = (((proc[self.rdhi] as u64) << 32) | proc[self.rdlo] as u64) as i64;
+        let result = (product1 - product2).wrapping_add(accumulator);
+        proc.set(self.rdhi, (result >> 32) as u32);
+        proc.set(self.rdlo, result as u32);
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        if self.m_swap { "smlsldx" } else { "smlsld" }.into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}, {}, {}", self.rdlo, self.rdhi, self.rn, self.rm)
+    }
+}
@@ -0,0 +1,119 @@
+//! Implements SMMLA and SMMLAR (Signed Most Significant Word Multiply Accumulate) instructions.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{other, unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    helpers::BitAccess,
+    registers::RegisterIndex,
+};
+
+/// SMMLA or SMMLAR instruction.
+///
+/// Signed Most Significant Word Multiply Accumulate.
+pub struct Smmla {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+    /// Accumulator register.
+    ra: RegisterIndex,
+    /// True to round the result instead of truncating it (SMMLAR).
+    round: bool,
+}
+
+impl Instruction for Smmla {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111110110101xxxxxxxxxxxx000xxxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        let ra = ins.reg4(12);
+        other(ra.is_pc())?; // SMMUL
+        unpredictable(rd.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_sp_or_pc() || ra.is_sp())?;
+        Ok(Self {
+            rd,
+            rn,
+            rm,
+            ra,
+            round: ins.bit(4),
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let product = proc[self.rn] as i32 as i64 * proc[self.rm] as i32 as i64;
+        // Only the 32 most significant bits of the result are kept, so wrapping arithmetic can be
+        // used.
+        let mut result = ((proc[self.ra] as i64) << 32).wrapping_add(product);
+        if self.round {
+            result = result.wrapping_add(0x80000000);
+        }
+        proc.set(self.rd, (result >> 32) as u32);
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        if self.round { "smmlar" } else { "smmla" }.into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}, {}, {}", self.rd, self.rn, self.rm, self.ra)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Smmla;
+    use crate::{
+        core::{Config, Processor},
+        instructions::Instruction,
+        registers::RegisterIndex,
+    };
+
+    #[test]
+    fn test_smmla() {
+        let vectors = [
+            (false, 0x40000000, 0x00000004, 10, 11),
+            (false, 0x7fffffff, 0x7fffffff, 0, 0x3fffffff),
+            (true, 0x7fffffff, 0x7fffffff, 0, 0x3fffffff),
+            (false, 0x00010000, 0x00008000, 0, 0),
+            (true, 0x00010000, 0x00008000, 0, 1),
+            (false, 0xffffffff, 0x00000001, 0, 0xffffffff),
+            (true, 0xffffffff, 0x00000001, 0, 0),
+            (false, 0x80000000, 0x80000000, 0x80000000, 0xc0000000),
+        ];
+
+        for v in vectors {
+            let mut proc = Processor::new(Config::v7em());
+            let (rd, rn, rm, ra) = RegisterIndex::pick_four_general_distinct();
+            proc.set(rn, v.1);
+            proc.set(rm, v.2);
+            proc.set(ra, v.3);
+            let mut expected = proc.registers;
+            expected.set(rd, v.4);
+            Smmla {
+                rd,
+                rn,
+                rm,
+                ra,
+                round: v.0,
+            }
+            .execute(&mut proc)
+            .unwrap();
+            assert_eq!(proc.registers, expected);
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! Implements SMMLS and SMMLSR (Signed Most Significant Word Multiply Subtract) instructions.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    helpers::BitAccess,
+    registers::RegisterIndex,
+};
+
+/// SMMLS or SMMLSR instruction.
+///
+/// Signed Most Significant Word Multiply Subtract.
+pub struct Smmls {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+    /// Accumulator register.
+    ra: RegisterIndex,
+    /// True to round the result instead of truncating it (SMMLSR).
+    round: bool,
+}
+
+impl Instruction for Smmls {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111110110110xxxxxxxxxxxx000xxxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        let ra = ins.reg4(12);
+        unpredictable(
+            rd.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_sp_or_pc() || ra.is_sp_or_pc(),
+        )?;
+        Ok(Self {
+            rd,
+            rn,
+            rm,
+            ra,
+            round: ins.bit(4),
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let product = proc[self.rn] as i32 as i64 * proc[self.rm] as i32 as i64;
+        // Only the 32 most significant bits of the result are kept, so wrapping arithmetic can be
+        // used.
+        let mut result = ((proc[self.ra] as i64) << 32).wrapping_sub(product);
+        if self.round {
+            result = result.wrapping_add(0x80000000);
+        }
+        proc.set(self.rd, (result >> 32) as u32);
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        if self.round { "smmlsr" } else { "smmls" }.into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}, {}, {}", self.rd, self.rn, self.rm, self.ra)
+    }
+}
@@ -0,0 +1,68 @@
+//! Implements SMMUL and SMMULR (Signed Most Significant Word Multiply) instructions.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    helpers::BitAccess,
+    registers::RegisterIndex,
+};
+
+/// SMMUL or SMMULR instruction.
+///
+/// Signed Most Significant Word Multiply.
+pub struct Smmul {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+    /// True to round the result instead of truncating it (SMMULR).
+    round: bool,
+}
+
+impl Instruction for Smmul {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111110110101xxxx1111xxxx000xxxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        unpredictable(rd.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_sp_or_pc())?;
+        Ok(Self {
+            rd,
+            rn,
+            rm,
+            round: ins.bit(4),
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let mut result = proc[self.rn] as i32 as i64 * proc[self.rm] as i32 as i64;
+        if self.round {
+            // Cannot overflow since product is at most 2^62.
+            result += 0x80000000;
+        }
+        proc.set(self.rd, (result >> 32) as u32);
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        if self.round { "smmulr" } else { "smmul" }.into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}, {}", self.rd, self.rn, self.rm)
+    }
+}
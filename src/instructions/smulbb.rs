@@ -0,0 +1,74 @@
+//! Implements SMULBB, SMULBT, SMULTB and SMULTT (Signed Multiply halfwords) instructions.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    helpers::BitAccess,
+    registers::RegisterIndex,
+};
+
+/// SMULBB, SMULBT, SMULTB or SMULTT instruction.
+///
+/// Signed Multiply (halfwords).
+pub struct Smulbb {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+    /// True to use the top halfword of Rn, false to use the bottom halfword.
+    n_high: bool,
+    /// True to use the top halfword of Rm, false to use the bottom halfword.
+    m_high: bool,
+}
+
+impl Instruction for Smulbb {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111110110001xxxx1111xxxx00xxxxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        unpredictable(rd.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_sp_or_pc())?;
+        Ok(Self {
+            rd,
+            rn,
+            rm,
+            n_high: ins.bit(5),
+            m_high: ins.bit(4),
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let rn = proc[self.rn];
+        let rm = proc[self.rm];
+        let operand1 = if self.n_high { rn >> 16 } else { rn } as i16 as i32;
+        let operand2 = if self.m_high { rm >> 16 } else { rm } as i16 as i32;
+        proc.set(self.rd, (operand1 * operand2) as u32);
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "smul{}{}",
+            if self.n_high { 't' } else { 'b' },
+            if self.m_high { 't' } else { 'b' }
+        )
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}, {}", self.rd, self.rn, self.rm)
+    }
+}
@@ -91,7 +91,7 @@ mod tests {
             let (rdlo, rdhi, rn, rm) = RegisterIndex::pick_four_general_distinct();
             proc.set(rn, v.0 as u32);
             proc.set(rm, v.1 as u32);
-            let mut expected = proc.registers.clone();
+            let mut expected = proc.registers;
             expected.set(rdhi, (v.2 >> 32) as u32);
             expected.set(rdlo, v.2 as u32);
             Smull { rdlo, rdhi, rn, rm }.execute(&mut proc).unwrap();
@@ -0,0 +1,66 @@
+//! Implements SMULWB and SMULWT (Signed Multiply word by halfword) instructions.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    helpers::BitAccess,
+    registers::RegisterIndex,
+};
+
+/// SMULWB or SMULWT instruction.
+///
+/// Signed Multiply (word by halfword).
+pub struct Smulwb {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+    /// True to use the top halfword of Rm, false to use the bottom halfword.
+    m_high: bool,
+}
+
+impl Instruction for Smulwb {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111110110011xxxx1111xxxx000xxxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        unpredictable(rd.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_sp_or_pc())?;
+        Ok(Self {
+            rd,
+            rn,
+            rm,
+            m_high: ins.bit(4),
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let rm = proc[self.rm];
+        let operand2 = if self.m_high { rm >> 16 } else { rm } as i16 as i64;
+        let product = proc[self.rn] as i32 as i64 * operand2;
+        proc.set(self.rd, (product >> 16) as u32);
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        if self.m_high { "smulwt" } else { "smulwb" }.into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}, {}", self.rd, self.rn, self.rm)
+    }
+}
@@ -114,7 +114,7 @@ mod tests {
             let (rd, rn) = RegisterIndex::pick_two_general_distinct();
             proc.set(rn, v.0);
             proc.registers.psr.set_q(v.4);
-            let mut expected = proc.registers.clone();
+            let mut expected = proc.registers;
             expected.set(rd, v.3);
             Ssat {
                 rd,
@@ -0,0 +1,113 @@
+//! Implements SSAT16 (Signed Saturate 16) instruction.
+
+use crate::{
+    arith::signed_sat_q,
+    core::ItState,
+    core::{
+        ArmVersion::{V7EM, V8M},
+        Effect, Processor, RunError,
+    },
+    decoder::DecodeError,
+    instructions::{unpredictable, DecodeHelper},
+    registers::RegisterIndex,
+};
+
+use super::Encoding::{self, T1};
+use super::{Instruction, Pattern};
+
+/// SSAT16 instruction.
+///
+/// Signed Saturate 16.
+pub struct Ssat16 {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// Bit position for saturation, in range 1 to 16.
+    saturate_to: u8,
+    /// Register containing the value to be saturated.
+    rn: RegisterIndex,
+}
+
+impl Instruction for Ssat16 {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "11110(0)110010xxxx0000xxxx00(0)(0)xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rn = ins.reg4(16);
+        unpredictable(rd.is_sp_or_pc() || rn.is_sp_or_pc())?;
+        Ok(Self {
+            rd,
+            saturate_to: ins.imm4(0) as u8 + 1,
+            rn,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let rn = proc[self.rn];
+        let (result1, sat1) = signed_sat_q(rn as i16 as i64, self.saturate_to);
+        let (result2, sat2) = signed_sat_q((rn >> 16) as i16 as i64, self.saturate_to);
+        proc.set(
+            self.rd,
+            (result1 as u16 as u32) | ((result2 as u16 as u32) << 16),
+        );
+        if sat1 || sat2 {
+            proc.registers.psr.set_q(true);
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "ssat16".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, #{}, {}", self.rd, self.saturate_to, self.rn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Ssat16;
+    use crate::{
+        core::{Config, Processor},
+        instructions::Instruction,
+        registers::RegisterIndex,
+    };
+
+    #[test]
+    fn test_ssat16() {
+        let vectors = [
+            (0x00000000, 1, 0x00000000, false),
+            (0x0001ffff, 1, 0x0000ffff, true),
+            (0x007fff80, 8, 0x007fff80, false),
+            (0x0080ff7f, 8, 0x007fff80, true),
+            (0x7fff8000, 16, 0x7fff8000, false),
+            (0x12340123, 12, 0x07ff0123, true),
+            (0x8000f000, 12, 0xf800f800, true),
+        ];
+
+        for v in vectors {
+            let mut proc = Processor::new(Config::v7em());
+            let rd = RegisterIndex::new_general_random();
+            let rn = RegisterIndex::new_general_random();
+            proc.set(rn, v.0);
+            let mut expected = proc.registers;
+            expected.set(rd, v.2);
+            expected.psr.set_q(v.3);
+            Ssat16 {
+                rd,
+                saturate_to: v.1,
+                rn,
+            }
+            .execute(&mut proc)
+            .unwrap();
+            assert_eq!(proc.registers, expected);
+        }
+    }
+}
@@ -0,0 +1,97 @@
+//! Implements SSAX (Signed Subtract and Add with Exchange) instruction.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{Instruction, Pattern};
+use crate::{
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    instructions::{unpredictable, DecodeHelper},
+    registers::RegisterIndex,
+};
+
+/// SSAX instruction.
+///
+/// Signed Subtract and Add with Exchange.
+pub struct Ssax {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+}
+
+impl Instruction for Ssax {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111110101110xxxx1111xxxx0000xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        unpredictable(rd.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_sp_or_pc())?;
+        Ok(Self { rd, rn, rm })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let rn = proc[self.rn];
+        let rm = proc[self.rm];
+        let sum = (rn as i16 as i32) + ((rm >> 16) as i16 as i32);
+        let diff = ((rn >> 16) as i16 as i32) - (rm as i16 as i32);
+        proc.set(self.rd, (sum as u16 as u32) | ((diff as u16 as u32) << 16));
+        let ge10 = if sum >= 0 { 0b0011 } else { 0 };
+        let ge32 = if diff >= 0 { 0b1100 } else { 0 };
+        proc.registers.psr.set_ge(ge10 | ge32);
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "ssax".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}, {}", self.rd, self.rn, self.rm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Ssax;
+    use crate::core::{Config, Processor};
+    use crate::instructions::Instruction;
+    use crate::registers::RegisterIndex;
+
+    #[test]
+    fn test_ssax() {
+        let vectors = [
+            (0x7fff8000, 0x00017fff, 0x00008001, 0b1100),
+            (0x80008000, 0x00010001, 0x7fff8001, 0b0000),
+            (0x12345678, 0x87654321, 0xcf13dddd, 0b0000),
+            (0xffff0001, 0x0001ffff, 0x00000002, 0b1111),
+            (0x807f01fe, 0x7f8001ff, 0x7e80817e, 0b0011),
+            (0xffc6e35c, 0x81a0d5b3, 0x2a1364fc, 0b1100),
+            (0x359b1548, 0x6694f229, 0x43727bdc, 0b1111),
+        ];
+
+        for v in vectors {
+            let mut proc = Processor::new(Config::v7em());
+            let rd = RegisterIndex::new_general_random();
+            let (rn, rm) = RegisterIndex::pick_two_general_distinct();
+            proc.set(rn, v.0);
+            proc.set(rm, v.1);
+            let mut expected = proc.registers;
+            expected.set(rd, v.2);
+            expected.psr.set_ge(v.3);
+            Ssax { rd, rn, rm }.execute(&mut proc).unwrap();
+            assert_eq!(proc.registers, expected)
+        }
+    }
+}
@@ -0,0 +1,100 @@
+//! Implements SSUB16 (Signed Subtract 16) instruction.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{Instruction, Pattern};
+use crate::{
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    instructions::{unpredictable, DecodeHelper},
+    registers::RegisterIndex,
+};
+
+/// SSUB16 instruction.
+///
+/// Signed Subtract 16.
+pub struct Ssub16 {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+}
+
+impl Instruction for Ssub16 {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111110101101xxxx1111xxxx0000xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        unpredictable(rd.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_sp_or_pc())?;
+        Ok(Self { rd, rn, rm })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let rn = proc[self.rn];
+        let rm = proc[self.rm];
+        let diff1 = (rn as i16 as i32) - (rm as i16 as i32);
+        let diff2 = ((rn >> 16) as i16 as i32) - ((rm >> 16) as i16 as i32);
+        proc.set(
+            self.rd,
+            (diff1 as u16 as u32) | ((diff2 as u16 as u32) << 16),
+        );
+        let ge10 = if diff1 >= 0 { 0b0011 } else { 0 };
+        let ge32 = if diff2 >= 0 { 0b1100 } else { 0 };
+        proc.registers.psr.set_ge(ge10 | ge32);
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "ssub16".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}, {}", self.rd, self.rn, self.rm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Ssub16;
+    use crate::core::{Config, Processor};
+    use crate::instructions::Instruction;
+    use crate::registers::RegisterIndex;
+
+    #[test]
+    fn test_ssub16() {
+        let vectors = [
+            (0x7fff8000, 0x00017fff, 0x7ffe0001, 0b1100),
+            (0x80008000, 0x00010001, 0x7fff7fff, 0b0000),
+            (0x12345678, 0x87654321, 0x8acf1357, 0b1111),
+            (0xffff0001, 0x0001ffff, 0xfffe0002, 0b0011),
+            (0x807f01fe, 0x7f8001ff, 0x00ffffff, 0b0000),
+            (0x15ceb3a1, 0x230824d2, 0xf2c68ecf, 0b0000),
+            (0x3f584ad4, 0xcfaf0010, 0x6fa94ac4, 0b1111),
+        ];
+
+        for v in vectors {
+            let mut proc = Processor::new(Config::v7em());
+            let rd = RegisterIndex::new_general_random();
+            let (rn, rm) = RegisterIndex::pick_two_general_distinct();
+            proc.set(rn, v.0);
+            proc.set(rm, v.1);
+            let mut expected = proc.registers;
+            expected.set(rd, v.2);
+            expected.psr.set_ge(v.3);
+            Ssub16 { rd, rn, rm }.execute(&mut proc).unwrap();
+            assert_eq!(proc.registers, expected)
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! Implements SSUB8 (Signed Subtract 8) instruction.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{Instruction, Pattern};
+use crate::{
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    instructions::{unpredictable, DecodeHelper},
+    registers::RegisterIndex,
+};
+
+/// SSUB8 instruction.
+///
+/// Signed Subtract 8.
+pub struct Ssub8 {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+}
+
+impl Instruction for Ssub8 {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111110101100xxxx1111xxxx0000xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        unpredictable(rd.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_sp_or_pc())?;
+        Ok(Self { rd, rn, rm })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let rn = proc[self.rn];
+        let rm = proc[self.rm];
+        let mut result = 0;
+        let mut ge = 0;
+        for i in 0..4 {
+            let diff = ((rn >> (i * 8)) as i8 as i32) - ((rm >> (i * 8)) as i8 as i32);
+            result |= (diff as u8 as u32) << (i * 8);
+            if diff >= 0 {
+                ge |= 1 << i;
+            }
+        }
+        proc.set(self.rd, result);
+        proc.registers.psr.set_ge(ge);
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "ssub8".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}, {}", self.rd, self.rn, self.rm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Ssub8;
+    use crate::core::{Config, Processor};
+    use crate::instructions::Instruction;
+    use crate::registers::RegisterIndex;
+
+    #[test]
+    fn test_ssub8() {
+        let vectors = [
+            (0x7fff8000, 0x00017fff, 0x7ffe0101, 0b1001),
+            (0x80008000, 0x00010001, 0x80ff80ff, 0b0000),
+            (0x12345678, 0x87654321, 0x8bcf1357, 0b1011),
+            (0xffff0001, 0x0001ffff, 0xfffe0102, 0b0011),
+            (0x807f01fe, 0x7f8001ff, 0x01ff00ff, 0b0110),
+            (0x205738d1, 0x317017a6, 0xefe7212b, 0b0011),
+            (0xb46ee1da, 0x0b3510b0, 0xa939d12a, 0b0101),
+        ];
+
+        for v in vectors {
+            let mut proc = Processor::new(Config::v7em());
+            let rd = RegisterIndex::new_general_random();
+            let (rn, rm) = RegisterIndex::pick_two_general_distinct();
+            proc.set(rn, v.0);
+            proc.set(rm, v.1);
+            let mut expected = proc.registers;
+            expected.set(rd, v.2);
+            expected.psr.set_ge(v.3);
+            Ssub8 { rd, rn, rm }.execute(&mut proc).unwrap();
+            assert_eq!(proc.registers, expected)
+        }
+    }
+}
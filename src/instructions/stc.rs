@@ -63,6 +63,7 @@ impl Instruction for Stc {
 
     fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
         debug_assert!((encoding == T1) || (encoding == T2));
+        other(encoding == T1 && ins.imm3(9) == 0b101)?; // Floating-point extension
         let index = ins.bit(24);
         let add = ins.bit(23);
         let d = ins.bit(22);
@@ -87,8 +88,7 @@ impl Instruction for Stc {
 
     fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
         let Some(coprocessor) = proc.coproc_accepted(self.coproc, self.ins) else {
-            proc.generate_coprocessor_exception();
-            return Ok(Effect::None);
+            return Err(proc.generate_coprocessor_exception());
         };
 
         let rn = proc[self.rn];
@@ -156,7 +156,7 @@ pub mod tests {
         }
 
         fn done_storing(&self, _ins: u32) -> bool {
-            self.0.len() == 0
+            self.0.is_empty()
         }
 
         fn get_one_word(&mut self, _ins: u32) -> u32 {
@@ -200,7 +200,7 @@ pub mod tests {
         proc.map(0x1000, &[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0])
             .unwrap();
         proc.set(rn, 0x1000 - 100);
-        let mut expected = proc.registers.clone();
+        let mut expected = proc.registers;
         expected.set(rn, 0x1000);
         Stc {
             coproc: cp,
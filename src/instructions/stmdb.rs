@@ -76,13 +76,15 @@ impl Instruction for Stmdb {
         // SP and PC cannot be pushed.
         // Instruction decoder should prevent this to happen
         debug_assert!(!self.registers.has_sp() && !self.registers.has_pc());
-        let mut addr = proc[self.rn];
-        for reg in self.registers.iter().rev() {
-            addr = addr.wrapping_sub(4);
-            proc.write_u32_aligned(addr, proc[reg])?;
+        let address = proc[self.rn].wrapping_sub(4 * self.registers.len() as u32);
+        // Memory is not written if the write-back violates the stack limit.
+        if !(self.wback && proc.sp_writeback_violates_limit(self.rn, address)) {
+            for (i, reg) in self.registers.iter().enumerate() {
+                proc.write_u32_aligned(address.wrapping_add(4 * i as u32), proc[reg])?;
+            }
         }
         if self.wback {
-            proc.set(self.rn, addr);
+            proc.set(self.rn, address);
         }
         Ok(Effect::None)
     }
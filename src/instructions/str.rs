@@ -122,7 +122,10 @@ impl Instruction for StrImm {
         let rn = proc[self.rn];
         let offset_addr = rn.wrapping_add_or_sub(self.imm32, self.add);
         let address = if self.index { offset_addr } else { rn };
-        proc.write_u32_unaligned(address, proc[self.rt])?;
+        // Memory is not written if the write-back violates the stack limit.
+        if !(self.wback && proc.sp_writeback_violates_limit(self.rn, offset_addr)) {
+            proc.write_u32_unaligned(address, proc[self.rt])?;
+        }
         if self.wback {
             proc.set(self.rn, offset_addr)
         }
@@ -108,7 +108,10 @@ impl Instruction for StrbImm {
         let rn = proc[self.rn];
         let offset_addr = rn.wrapping_add_or_sub(self.imm32, self.add);
         let address = if self.index { offset_addr } else { rn };
-        proc.write_u8(address, (proc[self.rt] & 0xff) as u8)?;
+        // Memory is not written if the write-back violates the stack limit.
+        if !(self.wback && proc.sp_writeback_violates_limit(self.rn, offset_addr)) {
+            proc.write_u8(address, (proc[self.rt] & 0xff) as u8)?;
+        }
         if self.wback {
             proc.set(self.rn, offset_addr)
         }
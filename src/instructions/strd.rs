@@ -68,8 +68,11 @@ impl Instruction for StrdImm {
         let address = if self.index { offset_addr } else { rn };
         let rt = proc[self.rt];
         let rt2 = proc[self.rt2];
-        proc.write_u32_aligned(address, rt)?;
-        proc.write_u32_aligned(address.wrapping_add(4), rt2)?;
+        // Memory is not written if the write-back violates the stack limit.
+        if !(self.wback && proc.sp_writeback_violates_limit(self.rn, offset_addr)) {
+            proc.write_u32_aligned(address, rt)?;
+            proc.write_u32_aligned(address.wrapping_add(4), rt2)?;
+        }
         if self.wback {
             proc.set(self.rn, offset_addr);
         }
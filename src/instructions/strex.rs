@@ -7,7 +7,7 @@ use crate::{
         Effect, ItState, Processor, RunError,
     },
     decoder::DecodeError,
-    instructions::{indexing_args, unpredictable, DecodeHelper, Encoding, Instruction},
+    instructions::{indexing_args, other, unpredictable, DecodeHelper, Encoding, Instruction},
     registers::RegisterIndex,
 };
 
@@ -39,6 +39,7 @@ impl Instruction for Strex {
         let rd = ins.reg4(8);
         let rt = ins.reg4(12);
         let rn = ins.reg4(16);
+        other(rt.is_pc())?; // TT, TTT, TTA, TTAT
         unpredictable(rd.is_sp_or_pc() || rt.is_sp_or_pc() || rn.is_pc())?;
         unpredictable(rd == rn || rd == rt)?;
         Ok(Self {
@@ -0,0 +1,112 @@
+//! Implements SXTAB (Signed Extend and Add Byte) instruction.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{other, unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    arith::{ror, Shift},
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    registers::RegisterIndex,
+};
+
+/// SXTAB instruction.
+///
+/// Signed Extend and Add Byte.
+pub struct Sxtab {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+    /// Rotation applied to Rm.
+    /// Can be 0, 8, 16 or 24.
+    rotation: u8,
+}
+
+impl Instruction for Sxtab {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111110100100xxxx1111xxxx1(0)xxxxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        other(rn.is_pc())?; // SXTB
+        unpredictable(rd.is_sp_or_pc() || rn.is_sp() || rm.is_sp_or_pc())?;
+        Ok(Self {
+            rd,
+            rn,
+            rm,
+            rotation: (ins.imm2(4) << 3) as u8,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let rotated = ror(proc[self.rm], self.rotation as u32);
+        let result = proc[self.rn].wrapping_add(rotated as i8 as i32 as u32);
+        proc.set(self.rd, result);
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "sxtab".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!(
+            "{}, {}, {}{}",
+            self.rd,
+            self.rn,
+            self.rm,
+            Shift::ror(self.rotation as u32).arg_string()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Sxtab;
+    use crate::{
+        core::{Config, Processor},
+        instructions::Instruction,
+        registers::RegisterIndex,
+    };
+
+    #[test]
+    fn test_sxtab() {
+        let vectors = [
+            (0x00001000, 0x000000ff, 0, 0x00000fff),
+            (0x00001000, 0x00007f00, 8, 0x0000107f),
+            (0x00000000, 0x80000000, 24, 0xffffff80),
+            (0xffffffff, 0x00010000, 16, 0x00000000),
+        ];
+
+        for v in vectors {
+            let mut proc = Processor::new(Config::v7em());
+            let rd = RegisterIndex::new_general_random();
+            let (rn, rm) = RegisterIndex::pick_two_general_distinct();
+            proc.set(rn, v.0);
+            proc.set(rm, v.1);
+            let mut expected = proc.registers;
+            expected.set(rd, v.3);
+            Sxtab {
+                rd,
+                rn,
+                rm,
+                rotation: v.2,
+            }
+            .execute(&mut proc)
+            .unwrap();
+            assert_eq!(proc.registers, expected);
+        }
+    }
+}
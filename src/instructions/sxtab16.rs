@@ -0,0 +1,113 @@
+//! Implements SXTAB16 (Signed Extend and Add Byte 16) instruction.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{other, unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    arith::{ror, Shift},
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    registers::RegisterIndex,
+};
+
+/// SXTAB16 instruction.
+///
+/// Signed Extend and Add Byte 16.
+pub struct Sxtab16 {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+    /// Rotation applied to Rm.
+    /// Can be 0, 8, 16 or 24.
+    rotation: u8,
+}
+
+impl Instruction for Sxtab16 {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111110100010xxxx1111xxxx1(0)xxxxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        other(rn.is_pc())?; // SXTB16
+        unpredictable(rd.is_sp_or_pc() || rn.is_sp() || rm.is_sp_or_pc())?;
+        Ok(Self {
+            rd,
+            rn,
+            rm,
+            rotation: (ins.imm2(4) << 3) as u8,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let rn = proc[self.rn];
+        let rotated = ror(proc[self.rm], self.rotation as u32);
+        let result1 = (rn as u16).wrapping_add(rotated as i8 as u16);
+        let result2 = ((rn >> 16) as u16).wrapping_add((rotated >> 16) as i8 as u16);
+        proc.set(self.rd, result1 as u32 | ((result2 as u32) << 16));
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "sxtab16".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!(
+            "{}, {}, {}{}",
+            self.rd,
+            self.rn,
+            self.rm,
+            Shift::ror(self.rotation as u32).arg_string()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Sxtab16;
+    use crate::{
+        core::{Config, Processor},
+        instructions::Instruction,
+        registers::RegisterIndex,
+    };
+
+    #[test]
+    fn test_sxtab16() {
+        let vectors = [
+            (0x10002000, 0x00ff0001, 0, 0x0fff2001),
+            (0x10002000, 0x80ff7f01, 8, 0x0f80207f),
+            (0xffff0000, 0x00010080, 0, 0x0000ff80),
+        ];
+
+        for v in vectors {
+            let mut proc = Processor::new(Config::v7em());
+            let rd = RegisterIndex::new_general_random();
+            let (rn, rm) = RegisterIndex::pick_two_general_distinct();
+            proc.set(rn, v.0);
+            proc.set(rm, v.1);
+            let mut expected = proc.registers;
+            expected.set(rd, v.3);
+            Sxtab16 {
+                rd,
+                rn,
+                rm,
+                rotation: v.2,
+            }
+            .execute(&mut proc)
+            .unwrap();
+            assert_eq!(proc.registers, expected);
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! Implements SXTB16 (Signed Extend Byte 16) instruction.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    arith::{ror, Shift},
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    registers::RegisterIndex,
+};
+
+/// SXTB16 instruction.
+///
+/// Signed Extend Byte 16.
+pub struct Sxtb16 {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// Operand register.
+    rm: RegisterIndex,
+    /// Rotation applied to Rm.
+    /// Can be 0, 8, 16 or 24.
+    rotation: u8,
+}
+
+impl Instruction for Sxtb16 {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "11111010001011111111xxxx1(0)xxxxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rm = ins.reg4(0);
+        unpredictable(rd.is_sp_or_pc() || rm.is_sp_or_pc())?;
+        Ok(Self {
+            rd,
+            rm,
+            rotation: (ins.imm2(4) << 3) as u8,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let rotated = ror(proc[self.rm], self.rotation as u32);
+        let result1 = rotated as i8 as u16;
+        let result2 = (rotated >> 16) as i8 as u16;
+        proc.set(self.rd, result1 as u32 | ((result2 as u32) << 16));
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "sxtb16".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!(
+            "{}, {}{}",
+            self.rd,
+            self.rm,
+            Shift::ror(self.rotation as u32).arg_string()
+        )
+    }
+}
@@ -169,7 +169,7 @@ mod tests {
             let rn = RegisterIndex::new_general_random();
             proc.set(rn, 0x12345678);
             proc.registers.psr.set_c(v.initial_c);
-            let mut expected = proc.registers.clone();
+            let mut expected = proc.registers;
             expected.psr.set_flags(v.expected_flags);
             TeqImm {
                 rn,
@@ -218,7 +218,7 @@ mod tests {
             let (rn, rm) = RegisterIndex::pick_two_general_distinct();
             proc.set(rn, 0x12345678);
             proc.set(rm, v.initial_rm);
-            let mut expected = proc.registers.clone();
+            let mut expected = proc.registers;
             expected.psr.set_flags(v.expected_flags);
             TeqReg {
                 rn,
@@ -0,0 +1,70 @@
+//! Implements TT, TTT, TTA and TTAT (Test Target) instructions.
+
+use super::Encoding::{self, T1};
+use super::{unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    core::{ArmVersion::V8M, Effect, ItState, Processor, RunError},
+    decoder::DecodeError,
+    helpers::BitAccess,
+    registers::RegisterIndex,
+};
+
+/// TT, TTT, TTA or TTAT instruction.
+///
+/// Test Target (Alternate Domain, Unprivileged). Queries the security state and access
+/// permissions of a memory location.
+pub struct Tt {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// Register holding the tested address.
+    rn: RegisterIndex,
+    /// True to test the permissions of the Non-secure state (TTA and TTAT).
+    alternate: bool,
+    /// True to test the unprivileged permissions (TTT and TTAT).
+    force_unprivileged: bool,
+}
+
+impl Instruction for Tt {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V8M],
+            expression: "111010000100xxxx1111xxxxxx(0)(0)(0)(0)(0)(0)",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rn = ins.reg4(16);
+        unpredictable(rd.is_sp_or_pc() || rn.is_pc())?;
+        Ok(Self {
+            rd,
+            rn,
+            alternate: ins.bit(7),
+            force_unprivileged: ins.bit(6),
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        // Alternate domain can only be tested from Secure state.
+        if self.alternate && !(proc.security_extension() && proc.is_secure()) {
+            return Err(RunError::InstructionUndefined);
+        }
+        let response = proc.test_target(proc[self.rn], self.alternate, self.force_unprivileged);
+        proc.set(self.rd, response);
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "tt{}{}",
+            if self.alternate { "a" } else { "" },
+            if self.force_unprivileged { "t" } else { "" }
+        )
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}", self.rd, self.rn)
+    }
+}
@@ -42,10 +42,13 @@ impl Instruction for Udiv {
 
     fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
         let divisor = proc[self.rm];
-        if divisor == 0 {
-            unimplemented!("Division by zero handling")
-        }
-        let result = proc[self.rn] / divisor;
+        let result = match proc[self.rn].checked_div(divisor) {
+            Some(result) => result,
+            None if proc.integer_zero_divide_trapping_enabled() => {
+                return Err(proc.generate_integer_zero_divide());
+            }
+            None => 0,
+        };
         proc.set(self.rd, result);
         Ok(Effect::None)
     }
@@ -0,0 +1,96 @@
+//! Implements UHADD16 (Unsigned Halving Add 16) instruction.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{Instruction, Pattern};
+use crate::{
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    instructions::{unpredictable, DecodeHelper},
+    registers::RegisterIndex,
+};
+
+/// UHADD16 instruction.
+///
+/// Unsigned Halving Add 16.
+pub struct Uhadd16 {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+}
+
+impl Instruction for Uhadd16 {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111110101001xxxx1111xxxx0110xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        unpredictable(rd.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_sp_or_pc())?;
+        Ok(Self { rd, rn, rm })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let rn = proc[self.rn];
+        let rm = proc[self.rm];
+        let sum1 = (rn as u16 as i32) + (rm as u16 as i32);
+        let sum2 = ((rn >> 16) as u16 as i32) + ((rm >> 16) as u16 as i32);
+        proc.set(
+            self.rd,
+            ((sum1 >> 1) as u16 as u32) | (((sum2 >> 1) as u16 as u32) << 16),
+        );
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "uhadd16".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}, {}", self.rd, self.rn, self.rm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Uhadd16;
+    use crate::core::{Config, Processor};
+    use crate::instructions::Instruction;
+    use crate::registers::RegisterIndex;
+
+    #[test]
+    fn test_uhadd16() {
+        let vectors = [
+            (0x7fff8000, 0x00017fff, 0x40007fff),
+            (0x80008000, 0x00010001, 0x40004000),
+            (0x12345678, 0x87654321, 0x4ccc4ccc),
+            (0xffff0001, 0x0001ffff, 0x80008000),
+            (0x807f01fe, 0x7f8001ff, 0x7fff01fe),
+            (0x56a3e957, 0x3c02e567, 0x4952e75f),
+            (0xb0f91306, 0x1607b1c4, 0x63806265),
+        ];
+
+        for v in vectors {
+            let mut proc = Processor::new(Config::v7em());
+            let rd = RegisterIndex::new_general_random();
+            let (rn, rm) = RegisterIndex::pick_two_general_distinct();
+            proc.set(rn, v.0);
+            proc.set(rm, v.1);
+            let mut expected = proc.registers;
+            expected.set(rd, v.2);
+            Uhadd16 { rd, rn, rm }.execute(&mut proc).unwrap();
+            assert_eq!(proc.registers, expected)
+        }
+    }
+}
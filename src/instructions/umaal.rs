@@ -0,0 +1,101 @@
+//! Implements UMAAL (Unsigned Multiply Accumulate Accumulate Long) instruction.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    registers::RegisterIndex,
+};
+
+/// UMAAL instruction.
+///
+/// Unsigned Multiply Accumulate Accumulate Long.
+pub struct Umaal {
+    /// Source register for the first 32-bit accumulate value, and destination register for the
+    /// lower 32 bits of the result.
+    rdlo: RegisterIndex,
+    /// Source register for the second 32-bit accumulate value, and destination register for the
+    /// higher 32 bits of the result.
+    rdhi: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+}
+
+impl Instruction for Umaal {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111110111110xxxxxxxxxxxx0110xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rdlo = ins.reg4(12);
+        let rdhi = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        unpredictable(
+            rdlo.is_sp_or_pc() || rdhi.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_sp_or_pc(),
+        )?;
+        unpredictable(rdlo == rdhi)?;
+        Ok(Self { rdlo, rdhi, rn, rm })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        // Result always fits in 64 bits: (2^32 - 1)^2 + 2 * (2^32 - 1) = 2^64 - 1.
+        let result = proc[self.rn] as u64 * proc[self.rm] as u64
+            + proc[self.rdhi] as u64
+            + proc[self.rdlo] as u64;
+        proc.set(self.rdhi, (result >> 32) as u32);
+        proc.set(self.rdlo, result as u32);
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "umaal".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}, {}, {}", self.rdlo, self.rdhi, self.rn, self.rm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Umaal;
+    use crate::{
+        core::{Config, Processor},
+        instructions::Instruction,
+        registers::RegisterIndex,
+    };
+
+    #[test]
+    fn test_umaal() {
+        let vectors: [(u32, u32, u32, u32, u64); 3] = [
+            (3, 4, 5, 6, 23),
+            (0x10000, 0x10000, 0, 0, 0x00000001_00000000),
+            (0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, u64::MAX),
+        ];
+
+        for v in vectors {
+            let mut proc = Processor::new(Config::v7em());
+            let (rdlo, rdhi, rn, rm) = RegisterIndex::pick_four_general_distinct();
+            proc.set(rn, v.0);
+            proc.set(rm, v.1);
+            proc.set(rdlo, v.2);
+            proc.set(rdhi, v.3);
+            let mut expected = proc.registers;
+            expected.set(rdlo, v.4 as u32);
+            expected.set(rdhi, (v.4 >> 32) as u32);
+            Umaal { rdlo, rdhi, rn, rm }.execute(&mut proc).unwrap();
+            assert_eq!(proc.registers, expected);
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! Implements USADA8 (Unsigned Sum of Absolute Differences and Accumulate) instruction.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{other, unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    registers::RegisterIndex,
+};
+
+/// USADA8 instruction.
+///
+/// Unsigned Sum of Absolute Differences and Accumulate.
+pub struct Usada8 {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+    /// Accumulator register.
+    ra: RegisterIndex,
+}
+
+impl Instruction for Usada8 {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111110110111xxxxxxxxxxxx0000xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        let ra = ins.reg4(12);
+        other(ra.is_pc())?; // USAD8
+        unpredictable(rd.is_sp_or_pc() || rn.is_sp_or_pc() || rm.is_sp_or_pc() || ra.is_sp())?;
+        Ok(Self { rd, rn, rm, ra })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let rn = proc[self.rn].to_le_bytes();
+        let rm = proc[self.rm].to_le_bytes();
+        let sum: u32 = (0..4).map(|i| rn[i].abs_diff(rm[i]) as u32).sum();
+        proc.set(self.rd, proc[self.ra].wrapping_add(sum));
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "usada8".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, {}, {}, {}", self.rd, self.rn, self.rm, self.ra)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Usada8;
+    use crate::{
+        core::{Config, Processor},
+        instructions::Instruction,
+        registers::RegisterIndex,
+    };
+
+    #[test]
+    fn test_usada8() {
+        let vectors = [
+            (0x00000000, 0x00000000, 0, 0),
+            (0x01020304, 0x04030201, 0, 8),
+            (0xff00ff00, 0x00ff00ff, 1, 1021),
+            (0x12345678, 0x12345678, 0xffffffff, 0xffffffff),
+            (0x000000ff, 0x00000000, 0xffffffff, 0x000000fe),
+        ];
+
+        for v in vectors {
+            let mut proc = Processor::new(Config::v7em());
+            let (rd, rn, rm, ra) = RegisterIndex::pick_four_general_distinct();
+            proc.set(rn, v.0);
+            proc.set(rm, v.1);
+            proc.set(ra, v.2);
+            let mut expected = proc.registers;
+            expected.set(rd, v.3);
+            Usada8 { rd, rn, rm, ra }.execute(&mut proc).unwrap();
+            assert_eq!(proc.registers, expected);
+        }
+    }
+}
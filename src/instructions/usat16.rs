@@ -97,7 +97,7 @@ mod tests {
             let rd = RegisterIndex::new_general_random();
             let rn = RegisterIndex::new_general_random();
             proc.set(rn, v.0);
-            let mut expected = proc.registers.clone();
+            let mut expected = proc.registers;
             expected.set(rd, v.2);
             expected.psr.set_q(v.3);
             Usat16 {
@@ -0,0 +1,73 @@
+//! Implements UXTAB (Unsigned Extend and Add Byte) instruction.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{other, unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    arith::{ror, Shift},
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    registers::RegisterIndex,
+};
+
+/// UXTAB instruction.
+///
+/// Unsigned Extend and Add Byte.
+pub struct Uxtab {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// First operand register.
+    rn: RegisterIndex,
+    /// Second operand register.
+    rm: RegisterIndex,
+    /// Rotation applied to Rm.
+    /// Can be 0, 8, 16 or 24.
+    rotation: u8,
+}
+
+impl Instruction for Uxtab {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "111110100101xxxx1111xxxx1(0)xxxxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rn = ins.reg4(16);
+        let rm = ins.reg4(0);
+        other(rn.is_pc())?; // UXTB
+        unpredictable(rd.is_sp_or_pc() || rn.is_sp() || rm.is_sp_or_pc())?;
+        Ok(Self {
+            rd,
+            rn,
+            rm,
+            rotation: (ins.imm2(4) << 3) as u8,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let rotated = ror(proc[self.rm], self.rotation as u32);
+        let result = proc[self.rn].wrapping_add(rotated & 0xff);
+        proc.set(self.rd, result);
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "uxtab".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!(
+            "{}, {}, {}{}",
+            self.rd,
+            self.rn,
+            self.rm,
+            Shift::ror(self.rotation as u32).arg_string()
+        )
+    }
+}
@@ -0,0 +1,102 @@
+//! Implements UXTB16 (Unsigned Extend Byte 16) instruction.
+
+use super::ArmVersion::{V7EM, V8M};
+use super::Encoding::{self, T1};
+use super::{unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    arith::{ror, Shift},
+    core::ItState,
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    registers::RegisterIndex,
+};
+
+/// UXTB16 instruction.
+///
+/// Unsigned Extend Byte 16.
+pub struct Uxtb16 {
+    /// Destination register.
+    rd: RegisterIndex,
+    /// Operand register.
+    rm: RegisterIndex,
+    /// Rotation applied to Rm.
+    /// Can be 0, 8, 16 or 24.
+    rotation: u8,
+}
+
+impl Instruction for Uxtb16 {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7EM, V8M],
+            expression: "11111010001111111111xxxx1(0)xxxxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rd = ins.reg4(8);
+        let rm = ins.reg4(0);
+        unpredictable(rd.is_sp_or_pc() || rm.is_sp_or_pc())?;
+        Ok(Self {
+            rd,
+            rm,
+            rotation: (ins.imm2(4) << 3) as u8,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let rotated = ror(proc[self.rm], self.rotation as u32);
+        proc.set(self.rd, rotated & 0x00ff00ff);
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "uxtb16".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!(
+            "{}, {}{}",
+            self.rd,
+            self.rm,
+            Shift::ror(self.rotation as u32).arg_string()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Uxtb16;
+    use crate::{
+        core::{Config, Processor},
+        instructions::Instruction,
+        registers::RegisterIndex,
+    };
+
+    #[test]
+    fn test_uxtb16() {
+        let vectors = [
+            (0x12345678, 0, 0x00340078),
+            (0x12345678, 8, 0x00120056),
+            (0x12345678, 16, 0x00780034),
+            (0x12345678, 24, 0x00560012),
+        ];
+
+        for v in vectors {
+            let mut proc = Processor::new(Config::v7em());
+            let (rd, rm) = RegisterIndex::pick_two_general_distinct();
+            proc.set(rm, v.0);
+            let mut expected = proc.registers;
+            expected.set(rd, v.2);
+            Uxtb16 {
+                rd,
+                rm,
+                rotation: v.1,
+            }
+            .execute(&mut proc)
+            .unwrap();
+            assert_eq!(proc.registers, expected);
+        }
+    }
+}
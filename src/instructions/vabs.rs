@@ -0,0 +1,78 @@
+//! Implements VABS (Floating-point Absolute) instruction.
+
+use super::Encoding::{self, T1};
+use super::{
+    extension_register, fp_data_type, undefined, DecodeHelper, ExtensionRegister, Instruction,
+    Pattern,
+};
+use crate::{
+    core::{
+        ArmVersion::{V7EM, V7M, V8M},
+        Effect, ItState, Processor, RunError,
+    },
+    decoder::DecodeError,
+    float::fp_abs,
+    helpers::BitAccess,
+    registers::CoreRegisters,
+};
+
+/// VABS instruction.
+///
+/// Floating-point Absolute.
+pub struct Vabs {
+    /// Destination register index.
+    vd: u8,
+    /// Operand register index.
+    vm: u8,
+    /// True for double-precision operation.
+    double: bool,
+}
+
+impl Vabs {
+    fn compute<T: ExtensionRegister>(&self, regs: &mut CoreRegisters) {
+        let op = T::read(regs, self.vm);
+        let result = fp_abs(op);
+        T::write(regs, self.vd, result);
+    }
+}
+
+impl Instruction for Vabs {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7M, V7EM, V8M],
+            expression: "111011101x110000xxxx101x11x0xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let double = ins.bit(8);
+        let vd = ins.vreg(12, 22, double);
+        let vm = ins.vreg(0, 5, double);
+        undefined(double && (vd >= 16 || vm >= 16))?;
+        Ok(Self { vd, vm, double })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        if self.double {
+            self.compute::<u64>(&mut proc.registers);
+        } else {
+            self.compute::<u32>(&mut proc.registers);
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        format!("vabs.{}", fp_data_type(self.double))
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!(
+            "{}, {}",
+            extension_register(self.double, self.vd),
+            extension_register(self.double, self.vm)
+        )
+    }
+}
@@ -0,0 +1,273 @@
+//! Implements VADD (Floating-point Add) and VADD (Vector Add) instructions.
+
+use super::Encoding::{self, T1, T2, T3, T4};
+use super::{
+    extension_register, fp_data_type, undefined, unpredictable, DecodeHelper, ExtensionRegister,
+    Instruction, Pattern,
+};
+use crate::{
+    core::{
+        ArmVersion::{V7EM, V7M, V81M, V8M},
+        Effect, ItState, Processor, RunError,
+    },
+    decoder::DecodeError,
+    float::fp_add,
+    helpers::BitAccess,
+    registers::CoreRegisters,
+    vector::VectorOperation,
+};
+
+/// VADD instruction.
+///
+/// Floating-point Add.
+pub struct Vadd {
+    /// Destination register index.
+    vd: u8,
+    /// First operand register index.
+    vn: u8,
+    /// Second operand register index.
+    vm: u8,
+    /// True for double-precision operation.
+    double: bool,
+}
+
+impl Vadd {
+    fn compute<T: ExtensionRegister>(&self, regs: &mut CoreRegisters) {
+        let (op1, op2) = (T::read(regs, self.vn), T::read(regs, self.vm));
+        let result = fp_add(op1, op2, &mut regs.fpscr);
+        T::write(regs, self.vd, result);
+    }
+}
+
+impl Instruction for Vadd {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7M, V7EM, V8M],
+            expression: "111011100x11xxxxxxxx101xx0x0xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let double = ins.bit(8);
+        let vd = ins.vreg(12, 22, double);
+        let vn = ins.vreg(16, 7, double);
+        let vm = ins.vreg(0, 5, double);
+        undefined(double && (vd >= 16 || vn >= 16 || vm >= 16))?;
+        Ok(Self { vd, vn, vm, double })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        if self.double {
+            self.compute::<u64>(&mut proc.registers);
+        } else {
+            self.compute::<u32>(&mut proc.registers);
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        format!("vadd.{}", fp_data_type(self.double))
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!(
+            "{}, {}, {}",
+            extension_register(self.double, self.vd),
+            extension_register(self.double, self.vn),
+            extension_register(self.double, self.vm)
+        )
+    }
+}
+
+/// VADD instruction (vector).
+///
+/// Vector Add, on integer or floating-point elements. The second operand can be a vector or
+/// a general-purpose register.
+pub struct VaddVector {
+    /// Operands.
+    op: VectorOperation,
+}
+
+impl Instruction for VaddVector {
+    fn patterns() -> &'static [Pattern] {
+        &[
+            Pattern {
+                encoding: T1,
+                versions: &[V81M],
+                expression: "111011110xxxxxx0xxx01000x1x0xxx0",
+            },
+            Pattern {
+                encoding: T2,
+                versions: &[V81M],
+                expression: "111011110x0xxxx0xxx01101x1x0xxx0",
+            },
+            Pattern {
+                encoding: T3,
+                versions: &[V81M],
+                expression: "111011100xxxxxx1xxx011110100xxxx",
+            },
+            Pattern {
+                encoding: T4,
+                versions: &[V81M],
+                expression: "111x11100x11xxx0xxx011110100xxxx",
+            },
+        ]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        let (float, scalar) = match encoding {
+            T1 => (false, false),
+            T2 => (true, false),
+            T3 => (false, true),
+            T4 => (true, true),
+            _ => panic!(),
+        };
+        let op = VectorOperation::decode(ins, float, scalar)?;
+        unpredictable(state.in_it_block())?;
+        Ok(Self { op })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let (float, esize) = (self.op.float, self.op.esize);
+        self.op.execute(proc, |a, b, fpscr| match (float, esize) {
+            (false, _) => a.wrapping_add(b),
+            (true, 16) => fp_add(a as u16, b as u16, fpscr) as u32,
+            (true, _) => fp_add(a, b, fpscr),
+        })
+    }
+
+    fn name(&self) -> String {
+        self.op.name("vadd")
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        self.op.args()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Vadd, VaddVector};
+    use crate::{
+        core::{Config, FloatingPointExtension, Irq, MveExtension, Processor, RunError},
+        instructions::Instruction,
+        registers::RegisterIndex,
+        vector::{VectorOperand, VectorOperation},
+    };
+
+    #[test]
+    fn test_vadd() {
+        let ins = Vadd {
+            vd: 2,
+            vn: 0,
+            vm: 31,
+            double: false,
+        };
+
+        // Floating-point extension not implemented.
+        let mut proc = Processor::new(Config::v7em());
+        assert_eq!(
+            ins.execute(&mut proc),
+            Err(RunError::Fault(Irq::UsageFault))
+        );
+
+        // Access to CP10 and CP11 not granted.
+        let mut proc = Processor::new(Config::v7em().fpu(FloatingPointExtension::SinglePrecision));
+        assert_eq!(
+            ins.execute(&mut proc),
+            Err(RunError::Fault(Irq::UsageFault))
+        );
+
+        proc.write_u32le_iface(0xe000ed88, 0x00f00000).unwrap();
+        proc.registers.s[0] = 1.5f32.to_bits();
+        proc.registers.s[31] = (-0.25f32).to_bits();
+        ins.execute(&mut proc).unwrap();
+        assert_eq!(proc.registers.s[2], 1.25f32.to_bits());
+        assert_eq!(proc.registers.fpscr.get(), 0);
+
+        proc.registers.s[0] = 1.0f32.to_bits();
+        proc.registers.s[31] = f32::MIN_POSITIVE.to_bits();
+        ins.execute(&mut proc).unwrap();
+        assert_eq!(proc.registers.s[2], 1.0f32.to_bits());
+        assert!(proc.registers.fpscr.ixc());
+    }
+
+    #[test]
+    fn test_vadd_double() {
+        let ins = Vadd {
+            vd: 0,
+            vn: 1,
+            vm: 15,
+            double: true,
+        };
+        let mut proc = Processor::new(Config::v7em().fpu(FloatingPointExtension::DoublePrecision));
+        proc.write_u32le_iface(0xe000ed88, 0x00f00000).unwrap();
+        proc.registers.set_d(1, 1.0f64.to_bits());
+        proc.registers.set_d(15, 1e-12f64.to_bits());
+        ins.execute(&mut proc).unwrap();
+        assert_eq!(proc.registers.d(0), (1.0f64 + 1e-12).to_bits());
+        assert_eq!(proc.registers.s[0], (1.0f64 + 1e-12).to_bits() as u32);
+        assert!(proc.registers.fpscr.ixc());
+    }
+
+    #[test]
+    fn test_vadd_vector() {
+        let mut proc = Processor::new(
+            Config::v81m()
+                .fpu(FloatingPointExtension::SinglePrecision)
+                .mve(MveExtension::FloatingPoint),
+        );
+        proc.write_u32le_iface(0xe000ed88, 0x00f00000).unwrap();
+        let ins = VaddVector {
+            op: VectorOperation {
+                qd: 0,
+                qn: 1,
+                operand: VectorOperand::Vector(2),
+                esize: 16,
+                float: false,
+            },
+        };
+        proc.registers
+            .set_q(1, 0x0007_0006_0005_0004_0003_0002_0001_ffff);
+        proc.registers
+            .set_q(2, 0x0001_0001_0001_0001_0001_0001_0001_0001);
+        ins.execute(&mut proc).unwrap();
+        assert_eq!(
+            proc.registers.q(0),
+            0x0008_0007_0006_0005_0004_0003_0002_0000
+        );
+
+        // Tail predication with 3 active elements: other elements are unchanged.
+        proc.registers.set_q(0, 0);
+        proc.registers.fpscr.set_ltpsize(1);
+        proc.set_lr(3);
+        ins.execute(&mut proc).unwrap();
+        assert_eq!(
+            proc.registers.q(0),
+            0x0000_0000_0000_0000_0000_0003_0002_0000
+        );
+        proc.registers.fpscr.set_ltpsize(4);
+
+        // Single-precision addition with a scalar operand.
+        let ins = VaddVector {
+            op: VectorOperation {
+                qd: 3,
+                qn: 3,
+                operand: VectorOperand::Scalar(RegisterIndex::R0),
+                esize: 32,
+                float: true,
+            },
+        };
+        for i in 0..4 {
+            proc.registers.s[12 + i] = (i as f32).to_bits();
+        }
+        proc.registers.r0 = 0.5f32.to_bits();
+        ins.execute(&mut proc).unwrap();
+        for i in 0..4 {
+            assert_eq!(proc.registers.s[12 + i], (i as f32 + 0.5).to_bits());
+        }
+    }
+}
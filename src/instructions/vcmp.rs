@@ -0,0 +1,191 @@
+//! Implements VCMP (Floating-point Compare), VCMPE (Floating-point Compare, raising Invalid
+//! Operation on NaN) and VCMP (Vector Compare) instructions.
+
+use super::Encoding::{self, T1, T2};
+use super::{
+    extension_register, fp_data_type, undefined, unpredictable, DecodeHelper, ExtensionRegister,
+    Instruction, Pattern,
+};
+use crate::{
+    core::{
+        ArmVersion::{V7EM, V7M, V81M, V8M},
+        Effect, ItState, Processor, RunError,
+    },
+    decoder::DecodeError,
+    float::fp_compare,
+    helpers::BitAccess,
+    registers::CoreRegisters,
+    vector::VectorComparison,
+};
+
+/// VCMP or VCMPE instruction.
+///
+/// Floating-point Compare. Result is written to FPSCR condition flags.
+pub struct Vcmp {
+    /// First operand register index.
+    vd: u8,
+    /// Second operand register index, or [None] to compare with zero.
+    vm: Option<u8>,
+    /// True for double-precision operation.
+    double: bool,
+    /// True for VCMPE, which raises Invalid Operation exception for quiet NaN operands.
+    quiet_nan_exc: bool,
+}
+
+impl Vcmp {
+    fn compute<T: ExtensionRegister>(&self, regs: &mut CoreRegisters) {
+        let op1 = T::read(regs, self.vd);
+        let op2 = self.vm.map_or(T::from_u64(0), |m| T::read(regs, m));
+        let nzcv = fp_compare(op1, op2, self.quiet_nan_exc, &mut regs.fpscr);
+        regs.fpscr.set_nzcv(nzcv);
+    }
+}
+
+impl Instruction for Vcmp {
+    fn patterns() -> &'static [Pattern] {
+        &[
+            Pattern {
+                encoding: T1,
+                versions: &[V7M, V7EM, V8M],
+                expression: "111011101x110100xxxx101xx1x0xxxx",
+            },
+            Pattern {
+                encoding: T2,
+                versions: &[V7M, V7EM, V8M],
+                expression: "111011101x110101xxxx101xx1(0)0(0)(0)(0)(0)",
+            },
+        ]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        let double = ins.bit(8);
+        let vd = ins.vreg(12, 22, double);
+        let vm = match encoding {
+            T1 => Some(ins.vreg(0, 5, double)),
+            T2 => None,
+            _ => panic!(),
+        };
+        undefined(double && (vd >= 16 || vm.is_some_and(|m| m >= 16)))?;
+        Ok(Self {
+            vd,
+            vm,
+            double,
+            quiet_nan_exc: ins.bit(7),
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        if self.double {
+            self.compute::<u64>(&mut proc.registers);
+        } else {
+            self.compute::<u32>(&mut proc.registers);
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        let name = if self.quiet_nan_exc { "vcmpe" } else { "vcmp" };
+        format!("{name}.{}", fp_data_type(self.double))
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        let d = extension_register(self.double, self.vd);
+        match self.vm {
+            Some(m) => format!("{d}, {}", extension_register(self.double, m)),
+            None => format!("{d}, #0"),
+        }
+    }
+}
+
+/// VCMP instruction (vector).
+///
+/// Vector Compare. Compares the elements of a vector with another vector or a scalar, and writes
+/// the result to VPR.P0.
+pub struct VcmpVector {
+    /// Comparison.
+    cmp: VectorComparison,
+}
+
+impl Instruction for VcmpVector {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V81M],
+            expression: "111x111000xxxxx1000x1111xxx0xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let cmp = VectorComparison::decode(ins)?;
+        unpredictable(state.in_it_block())?;
+        Ok(Self { cmp })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_mve_check(self.cmp.float)?;
+        proc.execute_beats(|proc, beat, mask| {
+            let predicate = self.cmp.compare_beat(&mut proc.registers, beat) & mask;
+            proc.registers.vpr.set_beat_p0(beat, predicate);
+            Ok(())
+        })?;
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        format!("vcmp.{}", self.cmp.data_type())
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        self.cmp.args()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Vcmp;
+    use crate::{
+        core::{Config, FloatingPointExtension, Processor},
+        instructions::Instruction,
+    };
+
+    #[test]
+    fn test_vcmp() {
+        let mut proc = Processor::new(Config::v7em().fpu(FloatingPointExtension::SinglePrecision));
+        proc.write_u32le_iface(0xe000ed88, 0x00f00000).unwrap();
+        let vectors = [
+            (1.0f32, Some(2.0f32), false, 0b1000),
+            (2.0, Some(2.0), false, 0b0110),
+            (3.0, Some(2.0), false, 0b0010),
+            (-0.0, None, false, 0b0110),
+            (-1.0, None, false, 0b1000),
+            (f32::NAN, Some(2.0), false, 0b0011),
+        ];
+        for (a, b, quiet_nan_exc, nzcv) in vectors {
+            proc.registers.s[4] = a.to_bits();
+            proc.registers.s[5] = b.unwrap_or_default().to_bits();
+            Vcmp {
+                vd: 4,
+                vm: b.map(|_| 5),
+                double: false,
+                quiet_nan_exc,
+            }
+            .execute(&mut proc)
+            .unwrap();
+            assert_eq!(proc.registers.fpscr.nzcv(), nzcv);
+        }
+        assert!(!proc.registers.fpscr.ioc());
+
+        // VCMPE raises Invalid Operation exception for quiet NaNs.
+        Vcmp {
+            vd: 4,
+            vm: Some(5),
+            double: false,
+            quiet_nan_exc: true,
+        }
+        .execute(&mut proc)
+        .unwrap();
+        assert!(proc.registers.fpscr.ioc());
+    }
+}
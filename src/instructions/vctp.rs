@@ -0,0 +1,62 @@
+//! Implements VCTP (Create Vector Tail Predicate) instruction.
+
+use super::Encoding::{self, T1};
+use super::{unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    core::{ArmVersion::V81M, Effect, ItState, Processor, RunError},
+    decoder::DecodeError,
+    registers::RegisterIndex,
+};
+
+/// VCTP instruction.
+///
+/// Create Vector Tail Predicate. Sets the VPR.P0 predicate flags of the elements whose index is
+/// lower than the value of a register, and clears the others.
+pub struct Vctp {
+    /// Register holding the number of active elements.
+    rn: RegisterIndex,
+    /// Element size, as a power of two number of bytes.
+    size: u8,
+}
+
+impl Instruction for Vctp {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V81M],
+            expression: "1111000000xxxxxx1110100000000001",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rn = ins.reg4(16);
+        unpredictable(rn.is_sp_or_pc())?;
+        unpredictable(state.in_it_block())?;
+        Ok(Self {
+            rn,
+            size: ins.imm2(20) as u8,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_mve_check(false)?;
+        let count = proc[self.rn] as u64;
+        proc.execute_beats(|proc, beat, mask| {
+            let predicate = (0..4)
+                .filter(|byte| (((beat * 4 + byte) >> self.size) as u64) < count)
+                .fold(0, |acc, byte| acc | (1 << byte));
+            proc.registers.vpr.set_beat_p0(beat, predicate & mask);
+            Ok(())
+        })?;
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        format!("vctp.{}", 8 << self.size)
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        self.rn.to_string()
+    }
+}
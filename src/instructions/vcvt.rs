@@ -0,0 +1,586 @@
+//! Implements VCVT and VCVTR (Floating-point Convert) instructions, for conversions between
+//! floating-point and integer or fixed-point values, between single-precision and
+//! double-precision values, VCVTB and VCVTT for conversions from and to half-precision values,
+//! and VCVTA, VCVTN, VCVTP and VCVTM for conversions to integer with directed rounding.
+
+use super::Encoding::{self, T1};
+use super::{
+    extension_register, fp_data_type, other, undefined, unpredictable, DecodeHelper, Instruction,
+    Pattern,
+};
+use crate::{
+    core::{
+        ArmVersion::{V7EM, V7M, V8M},
+        Effect, ItState, Processor, RunError,
+    },
+    decoder::DecodeError,
+    float::{fixed_to_fp, fp_convert, fp_to_fixed},
+    helpers::BitAccess,
+    registers::RoundingMode,
+};
+
+/// VCVT or VCVTR instruction, between floating-point and integer.
+pub struct VcvtInt {
+    /// Destination register index.
+    vd: u8,
+    /// Operand register index.
+    vm: u8,
+    /// True for floating-point to integer conversion, false for integer to floating-point.
+    to_integer: bool,
+    /// True if the integer is unsigned.
+    unsigned: bool,
+    /// True to round towards zero (VCVT), false to use FPSCR rounding mode (VCVTR).
+    /// Only used when converting to integer.
+    round_zero: bool,
+    /// True if the floating-point value is double-precision. The integer is always held in a
+    /// single-precision register.
+    double: bool,
+}
+
+impl Instruction for VcvtInt {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7M, V7EM, V8M],
+            expression: "111011101x111xxxxxxx101xx1x0xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let opc2 = ins.imm3(16);
+        other(opc2 != 0 && opc2 & 0b110 != 0b100)?; // Fixed-point conversions
+        let to_integer = opc2 & 0b100 != 0;
+        let op = ins.bit(7);
+        let double = ins.bit(8);
+        let vd = ins.vreg(12, 22, double && !to_integer);
+        let vm = ins.vreg(0, 5, double && to_integer);
+        undefined(double && if to_integer { vm >= 16 } else { vd >= 16 })?;
+        Ok(Self {
+            vd,
+            vm,
+            to_integer,
+            unsigned: if to_integer { opc2 & 1 == 0 } else { !op },
+            round_zero: to_integer && op,
+            double,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        let regs = &mut proc.registers;
+        let rmode = regs.fpscr.rmode();
+        let (d, m) = (self.vd as usize, self.vm as usize);
+        if self.to_integer {
+            let rounding = if self.round_zero {
+                RoundingMode::Zero
+            } else {
+                rmode
+            };
+            regs.s[d] = if self.double {
+                fp_to_fixed(regs.d(m), 32, 0, self.unsigned, rounding, &mut regs.fpscr)
+            } else {
+                fp_to_fixed(regs.s[m], 32, 0, self.unsigned, rounding, &mut regs.fpscr)
+            };
+        } else if self.double {
+            let result = fixed_to_fp(regs.s[m], 32, 0, self.unsigned, rmode, &mut regs.fpscr);
+            regs.set_d(d, result);
+        } else {
+            regs.s[d] = fixed_to_fp(regs.s[m], 32, 0, self.unsigned, rmode, &mut regs.fpscr);
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        let int = if self.unsigned { "u32" } else { "s32" };
+        let float = fp_data_type(self.double);
+        if self.to_integer {
+            let r = if self.round_zero { "" } else { "r" };
+            format!("vcvt{r}.{int}.{float}")
+        } else {
+            format!("vcvt.{float}.{int}")
+        }
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!(
+            "{}, {}",
+            extension_register(self.double && !self.to_integer, self.vd),
+            extension_register(self.double && self.to_integer, self.vm)
+        )
+    }
+}
+
+/// VCVT instruction, between floating-point and fixed-point.
+pub struct VcvtFixed {
+    /// Source and destination register index.
+    vd: u8,
+    /// True for floating-point to fixed-point conversion, false for fixed-point to
+    /// floating-point.
+    to_fixed: bool,
+    /// True if the fixed-point value is unsigned.
+    unsigned: bool,
+    /// Fixed-point value size in bits, 16 or 32.
+    size: u32,
+    /// Number of fraction bits of the fixed-point value.
+    frac_bits: u32,
+    /// True for a double-precision register. The fixed-point value is held in its lower half.
+    double: bool,
+}
+
+impl Instruction for VcvtFixed {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7M, V7EM, V8M],
+            expression: "111011101x111x1xxxxx101xx1x0xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let size = if ins.bit(7) { 32 } else { 16 };
+        let imm5 = ins.imm4(0) << 1 | ins.imm1(5);
+        unpredictable(imm5 > size)?;
+        let double = ins.bit(8);
+        let vd = ins.vreg(12, 22, double);
+        undefined(double && vd >= 16)?;
+        Ok(Self {
+            vd,
+            to_fixed: ins.bit(18),
+            unsigned: ins.bit(16),
+            size,
+            frac_bits: size - imm5,
+            double,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        let regs = &mut proc.registers;
+        let d = self.vd as usize;
+        let (size, frac_bits, unsigned) = (self.size, self.frac_bits, self.unsigned);
+        if self.to_fixed {
+            let rounding = RoundingMode::Zero;
+            let result = if self.double {
+                fp_to_fixed(
+                    regs.d(d),
+                    size,
+                    frac_bits,
+                    unsigned,
+                    rounding,
+                    &mut regs.fpscr,
+                )
+            } else {
+                fp_to_fixed(
+                    regs.s[d],
+                    size,
+                    frac_bits,
+                    unsigned,
+                    rounding,
+                    &mut regs.fpscr,
+                )
+            };
+            if self.double {
+                // Result is zero-extended or sign-extended to 64 bits.
+                let extended = if unsigned {
+                    result as u64
+                } else {
+                    result as i32 as u64
+                };
+                regs.set_d(d, extended);
+            } else {
+                regs.s[d] = result;
+            }
+        } else if self.double {
+            // Fixed-point value is held in the lower half of the double-precision register.
+            let op = regs.d(d) as u32;
+            let result = fixed_to_fp(
+                op,
+                size,
+                frac_bits,
+                unsigned,
+                RoundingMode::Nearest,
+                &mut regs.fpscr,
+            );
+            regs.set_d(d, result);
+        } else {
+            regs.s[d] = fixed_to_fp(
+                regs.s[d],
+                size,
+                frac_bits,
+                unsigned,
+                RoundingMode::Nearest,
+                &mut regs.fpscr,
+            );
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        let fixed = format!("{}{}", if self.unsigned { "u" } else { "s" }, self.size);
+        let float = fp_data_type(self.double);
+        if self.to_fixed {
+            format!("vcvt.{fixed}.{float}")
+        } else {
+            format!("vcvt.{float}.{fixed}")
+        }
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        let d = extension_register(self.double, self.vd);
+        format!("{d}, {d}, #{}", self.frac_bits)
+    }
+}
+
+/// VCVT instruction, between single-precision and double-precision.
+pub struct VcvtDouble {
+    /// Destination register index.
+    vd: u8,
+    /// Operand register index.
+    vm: u8,
+    /// True to convert double-precision to single-precision, false for the opposite.
+    to_single: bool,
+}
+
+impl Instruction for VcvtDouble {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7M, V7EM, V8M],
+            expression: "111011101x110111xxxx101x11x0xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let to_single = ins.bit(8);
+        let vd = ins.vreg(12, 22, !to_single);
+        let vm = ins.vreg(0, 5, to_single);
+        undefined(if to_single { vm >= 16 } else { vd >= 16 })?;
+        Ok(Self { vd, vm, to_single })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        let regs = &mut proc.registers;
+        let (d, m) = (self.vd as usize, self.vm as usize);
+        if self.to_single {
+            regs.s[d] = fp_convert(regs.d(m), &mut regs.fpscr);
+        } else {
+            let result = fp_convert(regs.s[m], &mut regs.fpscr);
+            regs.set_d(d, result);
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        if self.to_single {
+            "vcvt.f32.f64"
+        } else {
+            "vcvt.f64.f32"
+        }
+        .into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!(
+            "{}, {}",
+            extension_register(!self.to_single, self.vd),
+            extension_register(self.to_single, self.vm)
+        )
+    }
+}
+
+/// VCVTB or VCVTT instruction.
+///
+/// Converts between half-precision and single-precision or double-precision. The half-precision
+/// value is in the bottom half (VCVTB) or top half (VCVTT) of a single-precision register.
+pub struct VcvtHalf {
+    /// Destination register index.
+    vd: u8,
+    /// Operand register index.
+    vm: u8,
+    /// True to convert to half-precision, false for the opposite.
+    to_half: bool,
+    /// True for VCVTT, which uses the top half of the register.
+    top: bool,
+    /// True if the other value is double-precision.
+    double: bool,
+}
+
+impl Instruction for VcvtHalf {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7M, V7EM, V8M],
+            expression: "111011101x11001xxxxx101xx1x0xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let to_half = ins.bit(16);
+        let double = ins.bit(8);
+        let vd = ins.vreg(12, 22, double && !to_half);
+        let vm = ins.vreg(0, 5, double && to_half);
+        undefined(double && if to_half { vm >= 16 } else { vd >= 16 })?;
+        Ok(Self {
+            vd,
+            vm,
+            to_half,
+            top: ins.bit(7),
+            double,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        let regs = &mut proc.registers;
+        let lowbit = if self.top { 16 } else { 0 };
+        let (d, m) = (self.vd as usize, self.vm as usize);
+        if self.to_half {
+            let half: u16 = if self.double {
+                fp_convert(regs.d(m), &mut regs.fpscr)
+            } else {
+                fp_convert(regs.s[m], &mut regs.fpscr)
+            };
+            regs.s[d] = regs.s[d] & !(0xffff << lowbit) | (half as u32) << lowbit;
+        } else {
+            let half = (regs.s[m] >> lowbit) as u16;
+            if self.double {
+                let result = fp_convert(half, &mut regs.fpscr);
+                regs.set_d(d, result);
+            } else {
+                regs.s[d] = fp_convert(half, &mut regs.fpscr);
+            }
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        let bt = if self.top { "t" } else { "b" };
+        let float = fp_data_type(self.double);
+        if self.to_half {
+            format!("vcvt{bt}.f16.{float}")
+        } else {
+            format!("vcvt{bt}.{float}.f16")
+        }
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!(
+            "{}, {}",
+            extension_register(self.double && !self.to_half, self.vd),
+            extension_register(self.double && self.to_half, self.vm)
+        )
+    }
+}
+
+/// VCVTA, VCVTN, VCVTP or VCVTM instruction.
+///
+/// Converts a floating-point value to integer, using the rounding mode encoded in the
+/// instruction instead of the one selected by FPSCR.
+pub struct VcvtDirected {
+    /// Destination register index.
+    sd: u8,
+    /// Operand register index.
+    vm: u8,
+    /// Rounding mode.
+    rounding: RoundingMode,
+    /// True if the integer is unsigned.
+    unsigned: bool,
+    /// True if the operand is double-precision.
+    double: bool,
+}
+
+impl Instruction for VcvtDirected {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7M, V7EM, V8M],
+            expression: "111111101x1111xxxxxx101xx1x0xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        unpredictable(state.in_it_block())?;
+        let double = ins.bit(8);
+        let vm = ins.vreg(0, 5, double);
+        undefined(double && vm >= 16)?;
+        Ok(Self {
+            sd: ins.sreg(12, 22),
+            vm,
+            rounding: RoundingMode::from_rm(ins.imm2(16)),
+            unsigned: !ins.bit(7),
+            double,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        let regs = &mut proc.registers;
+        let m = self.vm as usize;
+        regs.s[self.sd as usize] = if self.double {
+            fp_to_fixed(
+                regs.d(m),
+                32,
+                0,
+                self.unsigned,
+                self.rounding,
+                &mut regs.fpscr,
+            )
+        } else {
+            fp_to_fixed(
+                regs.s[m],
+                32,
+                0,
+                self.unsigned,
+                self.rounding,
+                &mut regs.fpscr,
+            )
+        };
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        let mode = match self.rounding {
+            RoundingMode::TiesAway => "a",
+            RoundingMode::Nearest => "n",
+            RoundingMode::PlusInfinity => "p",
+            _ => "m",
+        };
+        let int = if self.unsigned { "u32" } else { "s32" };
+        format!("vcvt{mode}.{int}.{}", fp_data_type(self.double))
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("s{}, {}", self.sd, extension_register(self.double, self.vm))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{VcvtDirected, VcvtDouble, VcvtFixed, VcvtInt};
+    use crate::{
+        core::{Config, FloatingPointExtension, Processor},
+        instructions::Instruction,
+        registers::RoundingMode,
+    };
+
+    #[test]
+    fn test_vcvt_int() {
+        let mut proc = Processor::new(Config::v7em().fpu(FloatingPointExtension::SinglePrecision));
+        proc.write_u32le_iface(0xe000ed88, 0x00f00000).unwrap();
+        let to_s32 = |round_zero| VcvtInt {
+            vd: 0,
+            vm: 1,
+            to_integer: true,
+            unsigned: false,
+            round_zero,
+            double: false,
+        };
+
+        proc.registers.s[1] = (-2.5f32).to_bits();
+        to_s32(true).execute(&mut proc).unwrap();
+        assert_eq!(proc.registers.s[0], -2i32 as u32);
+        to_s32(false).execute(&mut proc).unwrap();
+        assert_eq!(proc.registers.s[0], -2i32 as u32);
+        proc.registers.fpscr.set_rmode(RoundingMode::MinusInfinity);
+        to_s32(false).execute(&mut proc).unwrap();
+        assert_eq!(proc.registers.s[0], -3i32 as u32);
+
+        // Saturation.
+        proc.registers.s[1] = 1e10f32.to_bits();
+        to_s32(true).execute(&mut proc).unwrap();
+        assert_eq!(proc.registers.s[0], i32::MAX as u32);
+        assert!(proc.registers.fpscr.ioc());
+
+        proc.registers.fpscr.set_rmode(RoundingMode::Nearest);
+        proc.registers.s[1] = 0xffffffff;
+        VcvtInt {
+            vd: 0,
+            vm: 1,
+            to_integer: false,
+            unsigned: true,
+            round_zero: false,
+            double: false,
+        }
+        .execute(&mut proc)
+        .unwrap();
+        assert_eq!(proc.registers.s[0], 4294967296f32.to_bits());
+    }
+
+    #[test]
+    fn test_vcvt_fixed() {
+        let mut proc = Processor::new(Config::v7em().fpu(FloatingPointExtension::SinglePrecision));
+        proc.write_u32le_iface(0xe000ed88, 0x00f00000).unwrap();
+        proc.registers.s[3] = (-1.75f32).to_bits();
+        let fixed = |to_fixed| VcvtFixed {
+            vd: 3,
+            to_fixed,
+            unsigned: false,
+            size: 16,
+            frac_bits: 8,
+            double: false,
+        };
+        fixed(true).execute(&mut proc).unwrap();
+        assert_eq!(proc.registers.s[3], 0xfffffe40);
+        fixed(false).execute(&mut proc).unwrap();
+        assert_eq!(proc.registers.s[3], (-1.75f32).to_bits());
+    }
+
+    #[test]
+    fn test_vcvt_double() {
+        let mut proc = Processor::new(Config::v7em().fpu(FloatingPointExtension::DoublePrecision));
+        proc.write_u32le_iface(0xe000ed88, 0x00f00000).unwrap();
+        proc.registers.s[1] = (-1.5f32).to_bits();
+        VcvtDouble {
+            vd: 0,
+            vm: 1,
+            to_single: false,
+        }
+        .execute(&mut proc)
+        .unwrap();
+        assert_eq!(proc.registers.d(0), (-1.5f64).to_bits());
+
+        proc.registers.set_d(1, 0.1f64.to_bits());
+        VcvtDouble {
+            vd: 5,
+            vm: 1,
+            to_single: true,
+        }
+        .execute(&mut proc)
+        .unwrap();
+        assert_eq!(proc.registers.s[5], 0.1f32.to_bits());
+        assert!(proc.registers.fpscr.ixc());
+    }
+
+    #[test]
+    fn test_vcvt_directed() {
+        let mut proc = Processor::new(Config::v7em().fpu(FloatingPointExtension::DoublePrecision));
+        proc.write_u32le_iface(0xe000ed88, 0x00f00000).unwrap();
+        let vcvt = |rounding, unsigned| VcvtDirected {
+            sd: 0,
+            vm: 1,
+            rounding,
+            unsigned,
+            double: true,
+        };
+        proc.registers.set_d(1, (-2.5f64).to_bits());
+        let vectors = [
+            (RoundingMode::TiesAway, false, -3i32 as u32),
+            (RoundingMode::Nearest, false, -2i32 as u32),
+            (RoundingMode::PlusInfinity, false, -2i32 as u32),
+            (RoundingMode::MinusInfinity, false, -3i32 as u32),
+            (RoundingMode::TiesAway, true, 0),
+        ];
+        for (rounding, unsigned, expected) in vectors {
+            vcvt(rounding, unsigned).execute(&mut proc).unwrap();
+            assert_eq!(proc.registers.s[0], expected);
+        }
+        // Rounding mode from FPSCR is ignored.
+        assert_eq!(proc.registers.fpscr.rmode(), RoundingMode::Nearest);
+    }
+}
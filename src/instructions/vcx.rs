@@ -0,0 +1,357 @@
+//! Implements VCX1, VCX2 and VCX3 (Vector Custom Extension Instruction) instructions of the
+//! Custom Datapath Extension, including their accumulating variants.
+
+use super::Encoding::{self, T1};
+use super::{undefined, unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    core::{ArmVersion::V8M, CustomInstruction, Effect, ItState, Processor, RunError},
+    decoder::DecodeError,
+    helpers::BitAccess,
+    vector::merge,
+};
+
+/// Kind of floating-point or vector registers VCX instructions operate on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VcxRegisters {
+    /// Single-precision registers S0 to S31.
+    Single,
+    /// Double-precision registers D0 to D15.
+    Double,
+    /// Vector registers Q0 to Q7.
+    Vector,
+}
+
+impl VcxRegisters {
+    /// Decodes a register index from the 4 bits at `lsb_index` of `ins` and the extra bit at
+    /// `ext_index`.
+    fn decode(self, ins: u32, lsb_index: u8, ext_index: u8) -> Result<u8, DecodeError> {
+        let vx = ins.imm4(lsb_index) as u8;
+        let ext = ins.bit(ext_index as usize) as u8;
+        Ok(match self {
+            Self::Single => (vx << 1) | ext,
+            Self::Double => {
+                undefined(ext == 1)?;
+                vx
+            }
+            Self::Vector => {
+                undefined(ext == 1 || vx & 1 == 1)?;
+                vx >> 1
+            }
+        })
+    }
+
+    /// Register name prefix in disassembly.
+    fn prefix(self) -> char {
+        match self {
+            Self::Single => 's',
+            Self::Double => 'd',
+            Self::Vector => 'q',
+        }
+    }
+}
+
+/// Operands of VCX1, VCX2 and VCX3 instructions.
+struct VcxOperation {
+    /// Coprocessor index, class, accumulation and immediate value.
+    ins: CustomInstruction,
+    /// Kind of registers.
+    registers: VcxRegisters,
+    /// Destination register.
+    d: u8,
+    /// Source operand registers.
+    sources: Vec<u8>,
+}
+
+impl VcxOperation {
+    /// Decodes the operands common to all classes. `imm` is the immediate value for
+    /// single-precision and double-precision variants, which is extended with bit 24 for vector
+    /// variants. `sources` gives the bit positions of the source registers fields and of their
+    /// extra bit.
+    fn decode(
+        ins: u32,
+        class: u8,
+        imm: u32,
+        imm_bits: u32,
+        sources: &[(u8, u8)],
+        state: ItState,
+    ) -> Result<Self, DecodeError> {
+        let (registers, imm) = if ins.bit(6) {
+            unpredictable(state.in_it_block())?;
+            (VcxRegisters::Vector, imm | ins.imm1(24) << imm_bits)
+        } else if ins.bit(24) {
+            (VcxRegisters::Double, imm)
+        } else {
+            (VcxRegisters::Single, imm)
+        };
+        Ok(Self {
+            ins: CustomInstruction {
+                coproc: ins.imm3(8) as u8,
+                class,
+                accumulate: ins.bit(28),
+                imm,
+            },
+            registers,
+            d: registers.decode(ins, 12, 22)?,
+            sources: sources
+                .iter()
+                .map(|(lsb, ext)| registers.decode(ins, *lsb, *ext))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        if self.registers == VcxRegisters::Vector {
+            proc.execute_mve_check(false)?;
+        } else {
+            proc.execute_fp_check()?;
+        }
+        let datapath = proc.custom_datapath_check(self.ins.coproc)?;
+        let mut datapath = datapath.borrow_mut();
+        let d = self.d as usize;
+        let n = self.sources.first().map(|n| *n as usize);
+        let m = self.sources.get(1).map(|m| *m as usize);
+        match self.registers {
+            VcxRegisters::Single => {
+                let regs = &mut proc.registers;
+                let n = n.map_or(0, |n| regs.s[n]);
+                let m = m.map_or(0, |m| regs.s[m]);
+                regs.s[d] = datapath
+                    .vcx_single(&self.ins, regs.s[d], n, m)
+                    .ok_or(RunError::InstructionUndefined)?;
+            }
+            VcxRegisters::Double => {
+                let regs = &mut proc.registers;
+                let n = n.map_or(0, |n| regs.d(n));
+                let m = m.map_or(0, |m| regs.d(m));
+                let result = datapath
+                    .vcx_double(&self.ins, regs.d(d), n, m)
+                    .ok_or(RunError::InstructionUndefined)?;
+                regs.set_d(d, result);
+            }
+            VcxRegisters::Vector => proc.execute_beats(|proc, beat, mask| {
+                if mask == 0 {
+                    return Ok(());
+                }
+                let regs = &mut proc.registers;
+                let old = regs.s[d * 4 + beat];
+                let n = n.map_or(0, |n| regs.s[n * 4 + beat]);
+                let m = m.map_or(0, |m| regs.s[m * 4 + beat]);
+                let result = datapath
+                    .vcx_vector(&self.ins, beat, old, n, m)
+                    .ok_or(RunError::InstructionUndefined)?;
+                regs.s[d * 4 + beat] = merge(old, result, mask);
+                Ok(())
+            })?,
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "vcx{}{}",
+            self.ins.class,
+            if self.ins.accumulate { "a" } else { "" }
+        )
+    }
+
+    fn args(&self) -> String {
+        let prefix = self.registers.prefix();
+        let registers: Vec<String> = [self.d]
+            .iter()
+            .chain(self.sources.iter())
+            .map(|r| format!("{prefix}{r}"))
+            .collect();
+        format!(
+            "p{}, {}, #{}",
+            self.ins.coproc,
+            registers.join(", "),
+            self.ins.imm
+        )
+    }
+}
+
+/// VCX1 or VCX1A instruction.
+///
+/// Vector Custom Extension Instruction Class 1.
+pub struct Vcx1 {
+    op: VcxOperation,
+}
+
+impl Instruction for Vcx1 {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V8M],
+            expression: "111x110x0x10xxxxxxxx0xxxxxxxxxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let imm = (ins.imm4(16) << 7) | (ins.imm1(7) << 6) | (ins & 0x3f);
+        Ok(Self {
+            op: VcxOperation::decode(ins, 1, imm, 11, &[], state)?,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        self.op.execute(proc)
+    }
+
+    fn name(&self) -> String {
+        self.op.name()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        self.op.args()
+    }
+}
+
+/// VCX2 or VCX2A instruction.
+///
+/// Vector Custom Extension Instruction Class 2.
+pub struct Vcx2 {
+    op: VcxOperation,
+}
+
+impl Instruction for Vcx2 {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V8M],
+            expression: "111x110x0x11xxxxxxxx0xxxxxxxxxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let imm = (ins.imm4(16) << 2) | (ins.imm1(7) << 1) | ins.imm1(4);
+        Ok(Self {
+            op: VcxOperation::decode(ins, 2, imm, 6, &[(0, 5)], state)?,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        self.op.execute(proc)
+    }
+
+    fn name(&self) -> String {
+        self.op.name()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        self.op.args()
+    }
+}
+
+/// VCX3 or VCX3A instruction.
+///
+/// Vector Custom Extension Instruction Class 3.
+pub struct Vcx3 {
+    op: VcxOperation,
+}
+
+impl Instruction for Vcx3 {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V8M],
+            expression: "111x110x1xxxxxxxxxxx0xxxxxxxxxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let imm = (ins.imm2(20) << 1) | ins.imm1(4);
+        Ok(Self {
+            op: VcxOperation::decode(ins, 3, imm, 3, &[(16, 7), (0, 5)], state)?,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        self.op.execute(proc)
+    }
+
+    fn name(&self) -> String {
+        self.op.name()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        self.op.args()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Vcx1, Vcx2, Vcx3};
+    use crate::{
+        core::{
+            Config, CustomDatapath, CustomInstruction, FloatingPointExtension, ItState,
+            MveExtension, Processor, RunError,
+        },
+        instructions::{Encoding::T1, Instruction},
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    struct TestDatapath;
+
+    impl CustomDatapath for TestDatapath {
+        fn vcx_single(&mut self, ins: &CustomInstruction, d: u32, n: u32, m: u32) -> Option<u32> {
+            Some(d * ins.accumulate as u32 + n * m + ins.imm)
+        }
+
+        fn vcx_vector(
+            &mut self,
+            ins: &CustomInstruction,
+            beat: usize,
+            d: u32,
+            n: u32,
+            _m: u32,
+        ) -> Option<u32> {
+            Some(d ^ n ^ (beat as u32) ^ ins.imm)
+        }
+    }
+
+    #[test]
+    fn test_vcx() {
+        let mut proc = Processor::new(
+            Config::v81m()
+                .fpu(FloatingPointExtension::SinglePrecision)
+                .mve(MveExtension::Integer)
+                .custom_datapath(2)
+                .custom_datapath(3),
+        );
+        proc.set_custom_datapath(2, Rc::new(RefCell::new(TestDatapath)));
+        proc.set_custom_datapath(3, Rc::new(RefCell::new(TestDatapath)));
+        proc.write_u32le_iface(0xe000ed88, 0x00f000f0).unwrap();
+
+        // vcx3a p2, s5, s6, s7, #1
+        let ins = Vcx3::try_decode(T1, 0xfcc32233, ItState::new()).unwrap();
+        proc.registers.s[5] = 100;
+        proc.registers.s[6] = 3;
+        proc.registers.s[7] = 4;
+        ins.execute(&mut proc).unwrap();
+        assert_eq!(proc.registers.s[5], 113);
+
+        // vcx2a p3, q7, q6, #127
+        let ins = Vcx2::try_decode(T1, 0xfd3fe3dc, ItState::new()).unwrap();
+        proc.registers
+            .set_q(7, 0x00000000_00000000_00000000_00000000);
+        proc.registers
+            .set_q(6, 0x44444444_33333333_22222222_11111111);
+        // Predicate out the two last beats.
+        proc.registers.lr = 2;
+        proc.registers.fpscr.set_ltpsize(2);
+        ins.execute(&mut proc).unwrap();
+        assert_eq!(proc.registers.q(7), 0x00000000_00000000_2222225c_1111116e);
+
+        // vcx1 p2, d15, #2047 is not supported by the datapath.
+        let ins = Vcx1::try_decode(T1, 0xed2ff2bf, ItState::new()).unwrap();
+        assert_eq!(ins.execute(&mut proc), Err(RunError::InstructionUndefined));
+
+        // Double-precision registers D16 to D31 do not exist.
+        assert!(Vcx1::try_decode(T1, 0xed6b901f, ItState::new()).is_err());
+        assert!(Vcx3::try_decode(T1, 0xede71087, ItState::new()).is_err());
+        assert!(Vcx3::try_decode(T1, 0xfdfe4026, ItState::new()).is_err());
+    }
+}
@@ -0,0 +1,72 @@
+//! Implements VDUP (Vector Duplicate) instruction.
+
+use super::Encoding::{self, T1};
+use super::{undefined, unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    core::{ArmVersion::V81M, Effect, ItState, Processor, RunError},
+    decoder::DecodeError,
+    helpers::BitAccess,
+    registers::RegisterIndex,
+    vector::{duplicate, merge, qreg},
+};
+
+/// VDUP instruction.
+///
+/// Vector Duplicate. Sets all the elements of a vector to the value of a general-purpose
+/// register.
+pub struct Vdup {
+    /// Destination vector register.
+    qd: u8,
+    /// Source register.
+    rt: RegisterIndex,
+    /// Element size in bits.
+    esize: u32,
+}
+
+impl Instruction for Vdup {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V81M],
+            expression: "111011101x10xxx0xxxx1011x0x10000",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let (b, e) = (ins.bit(22), ins.bit(5));
+        undefined(b && e)?;
+        let esize = match (b, e) {
+            (true, _) => 8,
+            (false, true) => 16,
+            (false, false) => 32,
+        };
+        let rt = ins.reg4(12);
+        unpredictable(rt.is_sp_or_pc())?;
+        unpredictable(state.in_it_block())?;
+        Ok(Self {
+            qd: qreg(ins, 17, 7)?,
+            rt,
+            esize,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_mve_check(false)?;
+        let value = duplicate(proc[self.rt], self.esize);
+        proc.execute_beats(|proc, beat, mask| {
+            let s = &mut proc.registers.s[self.qd as usize * 4 + beat];
+            *s = merge(*s, value, mask);
+            Ok(())
+        })?;
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        format!("vdup.{}", self.esize)
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("q{}, {}", self.qd, self.rt)
+    }
+}
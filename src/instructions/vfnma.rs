@@ -0,0 +1,95 @@
+//! Implements VFNMA (Floating-point Fused Negate Multiply Accumulate) and VFNMS (Floating-point
+//! Fused Negate Multiply Subtract) instructions.
+
+use super::Encoding::{self, T1};
+use super::{
+    extension_register, fp_data_type, undefined, DecodeHelper, ExtensionRegister, Instruction,
+    Pattern,
+};
+use crate::{
+    core::{
+        ArmVersion::{V7EM, V7M, V8M},
+        Effect, ItState, Processor, RunError,
+    },
+    decoder::DecodeError,
+    float::{fp_mul_add, fp_neg},
+    helpers::BitAccess,
+    registers::CoreRegisters,
+};
+
+/// VFNMA or VFNMS instruction.
+///
+/// Floating-point Fused Negate Multiply Accumulate or Subtract: the destination register is
+/// negated before accumulating the product, without intermediate rounding.
+pub struct Vfnma {
+    /// Destination and accumulator register index.
+    vd: u8,
+    /// First operand register index.
+    vn: u8,
+    /// Second operand register index.
+    vm: u8,
+    /// True for double-precision operation.
+    double: bool,
+    /// True for VFNMA, which negates the product, false for VFNMS.
+    negate: bool,
+}
+
+impl Vfnma {
+    fn compute<T: ExtensionRegister>(&self, regs: &mut CoreRegisters) {
+        let op1 = T::read(regs, self.vn);
+        let op1 = if self.negate { fp_neg(op1) } else { op1 };
+        let (addend, op2) = (fp_neg(T::read(regs, self.vd)), T::read(regs, self.vm));
+        let result = fp_mul_add(addend, op1, op2, &mut regs.fpscr);
+        T::write(regs, self.vd, result);
+    }
+}
+
+impl Instruction for Vfnma {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7M, V7EM, V8M],
+            expression: "111011101x01xxxxxxxx101xxxx0xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let double = ins.bit(8);
+        let vd = ins.vreg(12, 22, double);
+        let vn = ins.vreg(16, 7, double);
+        let vm = ins.vreg(0, 5, double);
+        undefined(double && (vd >= 16 || vn >= 16 || vm >= 16))?;
+        Ok(Self {
+            vd,
+            vn,
+            vm,
+            double,
+            negate: ins.bit(6),
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        if self.double {
+            self.compute::<u64>(&mut proc.registers);
+        } else {
+            self.compute::<u32>(&mut proc.registers);
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        let name = if self.negate { "vfnma" } else { "vfnms" };
+        format!("{name}.{}", fp_data_type(self.double))
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!(
+            "{}, {}, {}",
+            extension_register(self.double, self.vd),
+            extension_register(self.double, self.vn),
+            extension_register(self.double, self.vm)
+        )
+    }
+}
@@ -0,0 +1,186 @@
+//! Implements VLDM (Floating-point Load Multiple) instruction.
+
+use super::Encoding::{self, T1, T2};
+use super::{
+    extension_registers_args, other, undefined, unpredictable, DecodeHelper, Instruction, Pattern,
+};
+use crate::{
+    core::{
+        ArmVersion::{V7EM, V7M, V8M},
+        Effect, ItState, Processor, RunError,
+    },
+    decoder::DecodeError,
+    helpers::BitAccess,
+    registers::RegisterIndex,
+};
+
+/// VLDM instruction.
+///
+/// Loads multiple consecutive extension registers from memory.
+pub struct Vldm {
+    /// True for single-precision registers, false for double-precision registers.
+    pub single: bool,
+    /// First loaded register index.
+    pub vd: u8,
+    /// Number of loaded registers.
+    pub regs: u8,
+    /// Base register.
+    ///
+    /// [RegisterIndex::Sp] in case of VPOP instruction.
+    pub rn: RegisterIndex,
+    /// Offset applied to the base register for write-back.
+    ///
+    /// For double-precision transfers with an odd immediate (FLDMX), this is one word more than
+    /// the size of the transferred registers.
+    pub imm32: u32,
+    /// True for increment after, false for decrement before.
+    pub add: bool,
+    /// Wether Rn is written back with a modified value.
+    pub wback: bool,
+}
+
+impl Instruction for Vldm {
+    fn patterns() -> &'static [Pattern] {
+        &[
+            Pattern {
+                encoding: T1,
+                versions: &[V7M, V7EM, V8M],
+                expression: "1110110xxxx1xxxxxxxx1011xxxxxxxx",
+            },
+            Pattern {
+                encoding: T2,
+                versions: &[V7M, V7EM, V8M],
+                expression: "1110110xxxx1xxxxxxxx1010xxxxxxxx",
+            },
+        ]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        let single = match encoding {
+            T1 => false,
+            T2 => true,
+            _ => panic!(),
+        };
+        let (p, u, w) = (ins.bit(24), ins.bit(23), ins.bit(21));
+        let rn = ins.reg4(16);
+        other(!p && !u && !w)?; // 64-bit transfers
+        other(!p && u && w && rn.is_sp())?; // VPOP
+        other(p && !w)?; // VLDR
+        undefined(p == u && w)?;
+        let imm8 = ins.imm8(0);
+        let (vd, regs) = if single {
+            (ins.sreg(12, 22), imm8)
+        } else {
+            (ins.dreg(12, 22), imm8 / 2)
+        };
+        unpredictable(rn.is_pc())?;
+        unpredictable(regs == 0 || regs > 16)?;
+        unpredictable(vd as u32 + regs > if single { 32 } else { 16 })?;
+        Ok(Self {
+            single,
+            vd,
+            regs: regs as u8,
+            rn,
+            imm32: imm8 << 2,
+            add: u,
+            wback: w,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        let rn = proc[self.rn];
+        let mut address = if self.add {
+            rn
+        } else {
+            rn.wrapping_sub(self.imm32)
+        };
+        // Double-precision registers are loaded as pairs of words, least significant word first,
+        // which is the same as loading the aliased single-precision registers.
+        let (first, count) = if self.single {
+            (self.vd, self.regs)
+        } else {
+            (self.vd * 2, self.regs * 2)
+        };
+        for i in first..first + count {
+            proc.registers.s[i as usize] = proc.read_u32_aligned(address)?;
+            address = address.wrapping_add(4);
+        }
+        if self.wback {
+            let value = if self.add {
+                rn.wrapping_add(self.imm32)
+            } else {
+                rn.wrapping_sub(self.imm32)
+            };
+            proc.set(self.rn, value);
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        let mode = if self.add { "ia" } else { "db" };
+        if !self.single && self.imm32 & 4 != 0 {
+            format!("fldm{mode}x")
+        } else {
+            format!("vldm{mode}")
+        }
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        let ws = if self.wback { "!" } else { "" };
+        format!(
+            "{}{ws}, {}",
+            self.rn,
+            extension_registers_args(self.single, self.vd, self.regs)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Vldm;
+    use crate::{
+        core::{Config, FloatingPointExtension, Processor},
+        instructions::Instruction,
+        registers::RegisterIndex,
+    };
+
+    #[test]
+    fn test_vldm() {
+        let mut proc = Processor::new(Config::v7em().fpu(FloatingPointExtension::SinglePrecision));
+        proc.map(0x1000, &[0; 0x100]).unwrap();
+        proc.write_u32le_iface(0xe000ed88, 0x00f00000).unwrap();
+        for i in 0..8 {
+            proc.write_u32le_iface(0x1000 + i * 4, i + 1).unwrap();
+        }
+
+        proc.set(RegisterIndex::R0, 0x1000);
+        let ins = Vldm {
+            single: true,
+            vd: 4,
+            regs: 3,
+            rn: RegisterIndex::R0,
+            imm32: 12,
+            add: true,
+            wback: true,
+        };
+        ins.execute(&mut proc).unwrap();
+        assert_eq!(proc.registers.s[3..8], [0, 1, 2, 3, 0]);
+        assert_eq!(proc[RegisterIndex::R0], 0x100c);
+
+        proc.set(RegisterIndex::R1, 0x1020);
+        let ins = Vldm {
+            single: false,
+            vd: 1,
+            regs: 2,
+            rn: RegisterIndex::R1,
+            imm32: 16,
+            add: false,
+            wback: false,
+        };
+        ins.execute(&mut proc).unwrap();
+        assert_eq!(proc.registers.d(1), 0x0000000600000005);
+        assert_eq!(proc.registers.d(2), 0x0000000800000007);
+        assert_eq!(proc[RegisterIndex::R1], 0x1020);
+    }
+}
@@ -0,0 +1,280 @@
+//! Implements VLDR (Floating-point Load Register) and VLDRB, VLDRH and VLDRW (Vector Load
+//! Register) instructions.
+
+use super::Encoding::{self, T1, T2};
+use super::{
+    indexing_args, undefined, unpredictable, AddOrSub, DecodeHelper, Instruction, Pattern,
+};
+use crate::{
+    align::Align,
+    core::{
+        ArmVersion::{V7EM, V7M, V81M, V8M},
+        Effect, ItState, Processor, RunError,
+    },
+    decoder::DecodeError,
+    helpers::BitAccess,
+    registers::RegisterIndex,
+    vector::{
+        element_active, elements_per_beat, read_element, sign_extend, with_element,
+        ContiguousAccess, GatherAccess,
+    },
+};
+
+/// VLDR instruction.
+///
+/// Loads a single extension register from memory.
+pub struct Vldr {
+    /// True for a single-precision register, false for a double-precision register.
+    single: bool,
+    /// Destination register index.
+    vd: u8,
+    /// Base register. PC for literal addressing.
+    rn: RegisterIndex,
+    /// Offset from base.
+    imm32: u32,
+    /// True to add offset, false to subtract.
+    add: bool,
+}
+
+impl Instruction for Vldr {
+    fn patterns() -> &'static [Pattern] {
+        &[
+            Pattern {
+                encoding: T1,
+                versions: &[V7M, V7EM, V8M],
+                expression: "11101101xx01xxxxxxxx1011xxxxxxxx",
+            },
+            Pattern {
+                encoding: T2,
+                versions: &[V7M, V7EM, V8M],
+                expression: "11101101xx01xxxxxxxx1010xxxxxxxx",
+            },
+        ]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        let single = match encoding {
+            T1 => false,
+            T2 => true,
+            _ => panic!(),
+        };
+        let vd = if single {
+            ins.sreg(12, 22)
+        } else {
+            ins.dreg(12, 22)
+        };
+        undefined(!single && vd >= 16)?;
+        Ok(Self {
+            single,
+            vd,
+            rn: ins.reg4(16),
+            imm32: ins.imm8(0) << 2,
+            add: ins.bit(23),
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        let base = if self.rn.is_pc() {
+            proc.pc().align(4)
+        } else {
+            proc[self.rn]
+        };
+        let address = base.wrapping_add_or_sub(self.imm32, self.add);
+        let n = self.vd as usize;
+        if self.single {
+            proc.registers.s[n] = proc.read_u32_aligned(address)?;
+        } else {
+            let word1 = proc.read_u32_aligned(address)?;
+            let word2 = proc.read_u32_aligned(address.wrapping_add(4))?;
+            proc.registers.set_d(n, (word2 as u64) << 32 | word1 as u64);
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "vldr".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        let prefix = if self.single { 's' } else { 'd' };
+        format!(
+            "{prefix}{}, {}",
+            self.vd,
+            indexing_args(self.rn, self.imm32, !self.add, true, self.add, false)
+        )
+    }
+}
+
+/// VLDRB, VLDRH or VLDRW instruction (contiguous).
+///
+/// Vector Load Register. Loads consecutive elements from memory, optionally widening them.
+/// Inactive elements are set to zero.
+pub struct VldrVector {
+    /// Memory access.
+    access: ContiguousAccess,
+}
+
+impl Instruction for VldrVector {
+    fn patterns() -> &'static [Pattern] {
+        &[
+            Pattern {
+                encoding: T1,
+                versions: &[V81M],
+                expression: "1110110xxxx1xxxxxxx1111xxxxxxxxx",
+            },
+            Pattern {
+                encoding: T2,
+                versions: &[V81M],
+                expression: "111x110xxxx1xxxxxxx0111xxxxxxxxx",
+            },
+        ]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        let access = ContiguousAccess::decode(ins, encoding == T2)?;
+        unpredictable(state.in_it_block())?;
+        Ok(Self { access })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_mve_check(false)?;
+        let a = &self.access;
+        let (start, offset_addr) = a.addresses(&proc.registers);
+        proc.execute_beats(|proc, beat, mask| {
+            let mut word = 0;
+            for i in 0..elements_per_beat(a.esize) {
+                if element_active(mask, a.esize, i) {
+                    let value = read_element(proc, a.element_address(start, beat, i), a.msize)?;
+                    let value = if a.unsigned {
+                        value
+                    } else {
+                        sign_extend(value, a.msize) as u32
+                    };
+                    word = with_element(word, a.esize, i, value);
+                }
+            }
+            proc.registers.s[a.qd as usize * 4 + beat] = word;
+            Ok(())
+        })?;
+        if a.wback {
+            proc.set(a.rn, offset_addr);
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        self.access.name(true)
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        self.access.args()
+    }
+}
+
+/// VLDRB, VLDRH or VLDRW instruction (gather).
+///
+/// Vector Gather Load. Loads elements from memory at addresses given by a vector of offsets or
+/// a vector of base addresses. Inactive elements are set to zero.
+pub struct VldrGather {
+    /// Memory access.
+    access: GatherAccess,
+}
+
+impl Instruction for VldrGather {
+    fn patterns() -> &'static [Pattern] {
+        &[
+            Pattern {
+                encoding: T1,
+                versions: &[V81M],
+                expression: "111x11001x01xxxxxxx0111xxxxxxxxx",
+            },
+            Pattern {
+                encoding: T2,
+                versions: &[V81M],
+                expression: "11111101xxx1xxx0xxx11110xxxxxxxx",
+            },
+        ]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        let access = GatherAccess::decode(ins, encoding == T2)?;
+        unpredictable(state.in_it_block())?;
+        Ok(Self { access })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_mve_check(false)?;
+        let a = &self.access;
+        proc.execute_beats(|proc, beat, mask| {
+            let mut word = 0;
+            for i in 0..elements_per_beat(a.esize) {
+                if element_active(mask, a.esize, i) {
+                    let address = a.element_address(&proc.registers, beat, i);
+                    let value = read_element(proc, address, a.msize)?;
+                    let value = if a.unsigned {
+                        value
+                    } else {
+                        sign_extend(value, a.msize) as u32
+                    };
+                    word = with_element(word, a.esize, i, value);
+                }
+            }
+            a.addressing.write_back(&mut proc.registers, beat);
+            proc.registers.s[a.qd as usize * 4 + beat] = word;
+            Ok(())
+        })?;
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        self.access.name(true)
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        self.access.args()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        core::{Config, FloatingPointExtension, Processor},
+        instructions::Instruction,
+        registers::RegisterIndex,
+    };
+
+    use super::Vldr;
+
+    #[test]
+    fn test_vldr() {
+        let mut proc = Processor::new(Config::v7em().fpu(FloatingPointExtension::SinglePrecision));
+        proc.map(0x1000, &[0; 0x100]).unwrap();
+        proc.write_u32le_iface(0xe000ed88, 0x00f00000).unwrap();
+        proc.write_u32le_iface(0x1010, 0x11223344).unwrap();
+        proc.write_u32le_iface(0x1014, 0x55667788).unwrap();
+        proc.set(RegisterIndex::R0, 0x1018);
+
+        let ins = Vldr {
+            single: true,
+            vd: 3,
+            rn: RegisterIndex::R0,
+            imm32: 8,
+            add: false,
+        };
+        ins.execute(&mut proc).unwrap();
+        assert_eq!(proc.registers.s[3], 0x11223344);
+
+        let ins = Vldr {
+            single: false,
+            vd: 15,
+            rn: RegisterIndex::R0,
+            imm32: 8,
+            add: false,
+        };
+        ins.execute(&mut proc).unwrap();
+        assert_eq!(proc.registers.s[30], 0x11223344);
+        assert_eq!(proc.registers.s[31], 0x55667788);
+        assert_eq!(proc.registers.d(15), 0x5566778811223344);
+    }
+}
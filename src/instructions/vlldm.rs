@@ -0,0 +1,64 @@
+//! Implements VLLDM (Floating-point Lazy Load Multiple) instruction.
+
+use super::Encoding::{self, T1};
+use super::{unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    core::{ArmVersion::V8M, Effect, ItState, Processor, RunError},
+    decoder::DecodeError,
+    registers::RegisterIndex,
+};
+
+/// VLLDM instruction.
+///
+/// Restores the floating-point context saved by [VLSTM](super::vlstm::Vlstm). If lazy state
+/// preservation is still active, the registers have not been modified and are not loaded.
+pub struct Vlldm {
+    /// Base register, pointing to the saved frame.
+    rn: RegisterIndex,
+}
+
+impl Instruction for Vlldm {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V8M],
+            expression: "111011000011xxxx(0)(0)(0)(0)1010(0)0(0)(0)(0)(0)(0)(0)",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rn = ins.reg4(16);
+        unpredictable(rn.is_pc())?;
+        Ok(Self { rn })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        if !proc.is_secure() {
+            return Err(RunError::InstructionUndefined);
+        }
+        // Behaves as a NOP when there is no floating-point context to be restored.
+        if proc.fpu().is_none() || !proc.registers.control.fpca() {
+            return Ok(Effect::None);
+        }
+        if proc.fp_control().fpccr.lspact() {
+            proc.fp_control().fpccr.set_lspact(false);
+            return Ok(Effect::None);
+        }
+        let address = proc[self.rn];
+        for i in 0..16 {
+            proc.registers.s[i as usize] = proc.read_u32_aligned(address.wrapping_add(4 * i))?;
+        }
+        let fpscr = proc.read_u32_aligned(address.wrapping_add(0x40))?;
+        proc.registers.fpscr.set(fpscr);
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "vlldm".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        self.rn.to_string()
+    }
+}
@@ -0,0 +1,115 @@
+//! Implements VLSTM (Floating-point Lazy Store Multiple) instruction.
+
+use super::Encoding::{self, T1};
+use super::{unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    core::{ArmVersion::V8M, Effect, ItState, Processor, RunError},
+    decoder::DecodeError,
+    registers::RegisterIndex,
+};
+
+/// VLSTM instruction.
+///
+/// Saves the floating-point context to a stack frame prepared by software, or reserves it for
+/// lazy state preservation when FPCCR.LSPEN is set. This is used by Secure code before calling
+/// Non-secure functions.
+pub struct Vlstm {
+    /// Base register, pointing to the reserved frame.
+    rn: RegisterIndex,
+}
+
+impl Instruction for Vlstm {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V8M],
+            expression: "111011000010xxxx(0)(0)(0)(0)1010(0)0(0)(0)(0)(0)(0)(0)",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rn = ins.reg4(16);
+        unpredictable(rn.is_pc())?;
+        Ok(Self { rn })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        if !proc.is_secure() {
+            return Err(RunError::InstructionUndefined);
+        }
+        // Behaves as a NOP when there is no floating-point context to be saved.
+        if proc.fpu().is_none() || !proc.registers.control.fpca() {
+            return Ok(Effect::None);
+        }
+        proc.execute_fp_check()?;
+        let address = proc[self.rn];
+        if proc.fp_control().fpccr.lspen() {
+            proc.update_fpccr(address);
+        } else {
+            for i in 0..16 {
+                proc.write_u32_aligned(address.wrapping_add(4 * i), proc.registers.s[i as usize])?;
+            }
+            proc.write_u32_aligned(address.wrapping_add(0x40), proc.registers.fpscr.get())?;
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "vlstm".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        self.rn.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Vlstm;
+    use crate::{
+        core::{Config, FloatingPointExtension, ItState, Processor},
+        instructions::{vlldm::Vlldm, Encoding, Instruction},
+        registers::RegisterIndex,
+    };
+
+    #[test]
+    fn test_vlstm_vlldm() {
+        for lspen in [true, false] {
+            let mut proc =
+                Processor::new(Config::v8m().fpu(FloatingPointExtension::SinglePrecision));
+            proc.map_ram(0x20000000, 0x100).unwrap();
+            proc.write_u32le_iface(0xe000ed88, 0x00f00000).unwrap();
+            if !lspen {
+                proc.fp_control().fpccr.write(0x80000000).unwrap();
+            }
+            proc.registers.r0 = 0x20000000;
+            proc.registers.s[3] = 0x12345678;
+            let vlstm = Vlstm {
+                rn: RegisterIndex::R0,
+            };
+            // vlldm r0
+            let vlldm = Vlldm::try_decode(Encoding::T1, 0xec300a00, ItState::new()).unwrap();
+
+            // No floating-point context: both instructions are NOPs.
+            vlstm.execute(&mut proc).unwrap();
+            assert_eq!(proc.read_u32le_iface(0x2000000c).unwrap(), 0);
+            assert!(!proc.fp_control().fpccr.lspact());
+
+            proc.registers.control.set_fpca(true);
+            vlstm.execute(&mut proc).unwrap();
+            assert_eq!(proc.fp_control().fpccr.lspact(), lspen);
+            if lspen {
+                assert_eq!(proc.fp_control().fpcar, 0x20000000);
+                // Next floating-point instruction triggers state preservation.
+                proc.execute_fp_check().unwrap();
+                assert!(!proc.fp_control().fpccr.lspact());
+            }
+            assert_eq!(proc.read_u32le_iface(0x2000000c).unwrap(), 0x12345678);
+
+            proc.registers.s[3] = 0;
+            vlldm.execute(&mut proc).unwrap();
+            assert_eq!(proc.registers.s[3], 0x12345678);
+        }
+    }
+}
@@ -0,0 +1,94 @@
+//! Implements VMAXNM (Floating-point Maximum Number) and VMINNM (Floating-point Minimum Number)
+//! instructions.
+
+use super::Encoding::{self, T1};
+use super::{
+    extension_register, fp_data_type, undefined, unpredictable, DecodeHelper, ExtensionRegister,
+    Instruction, Pattern,
+};
+use crate::{
+    core::{
+        ArmVersion::{V7EM, V7M, V8M},
+        Effect, ItState, Processor, RunError,
+    },
+    decoder::DecodeError,
+    float::fp_max_min_num,
+    helpers::BitAccess,
+    registers::CoreRegisters,
+};
+
+/// VMAXNM or VMINNM instruction.
+///
+/// Floating-point Maximum or Minimum Number. When one of the operands is a quiet NaN and the
+/// other is a number, the number is returned.
+pub struct Vmaxnm {
+    /// Destination register index.
+    vd: u8,
+    /// First operand register index.
+    vn: u8,
+    /// Second operand register index.
+    vm: u8,
+    /// True for double-precision operation.
+    double: bool,
+    /// True for VMINNM.
+    minimum: bool,
+}
+
+impl Vmaxnm {
+    fn compute<T: ExtensionRegister>(&self, regs: &mut CoreRegisters) {
+        let (op1, op2) = (T::read(regs, self.vn), T::read(regs, self.vm));
+        let result = fp_max_min_num(op1, op2, self.minimum, &mut regs.fpscr);
+        T::write(regs, self.vd, result);
+    }
+}
+
+impl Instruction for Vmaxnm {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7M, V7EM, V8M],
+            expression: "111111101x00xxxxxxxx101xxxx0xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        unpredictable(state.in_it_block())?;
+        let double = ins.bit(8);
+        let vd = ins.vreg(12, 22, double);
+        let vn = ins.vreg(16, 7, double);
+        let vm = ins.vreg(0, 5, double);
+        undefined(double && (vd >= 16 || vn >= 16 || vm >= 16))?;
+        Ok(Self {
+            vd,
+            vn,
+            vm,
+            double,
+            minimum: ins.bit(6),
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        if self.double {
+            self.compute::<u64>(&mut proc.registers);
+        } else {
+            self.compute::<u32>(&mut proc.registers);
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        let name = if self.minimum { "vminnm" } else { "vmaxnm" };
+        format!("{name}.{}", fp_data_type(self.double))
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!(
+            "{}, {}, {}",
+            extension_register(self.double, self.vd),
+            extension_register(self.double, self.vn),
+            extension_register(self.double, self.vm)
+        )
+    }
+}
@@ -0,0 +1,98 @@
+//! Implements VMLA (Floating-point Multiply Accumulate) and VMLS (Floating-point Multiply
+//! Subtract) instructions.
+
+use super::Encoding::{self, T1};
+use super::{
+    extension_register, fp_data_type, undefined, DecodeHelper, ExtensionRegister, Instruction,
+    Pattern,
+};
+use crate::{
+    core::{
+        ArmVersion::{V7EM, V7M, V8M},
+        Effect, ItState, Processor, RunError,
+    },
+    decoder::DecodeError,
+    float::{fp_add, fp_mul, fp_neg},
+    helpers::BitAccess,
+    registers::CoreRegisters,
+};
+
+/// VMLA or VMLS instruction.
+///
+/// Floating-point Multiply Accumulate or Subtract, with intermediate rounding of the product.
+pub struct Vmla {
+    /// Destination and accumulator register index.
+    vd: u8,
+    /// First operand register index.
+    vn: u8,
+    /// Second operand register index.
+    vm: u8,
+    /// True for double-precision operation.
+    double: bool,
+    /// True for VMLS, which subtracts the product.
+    subtract: bool,
+}
+
+impl Vmla {
+    fn compute<T: ExtensionRegister>(&self, regs: &mut CoreRegisters) {
+        let (op1, op2) = (T::read(regs, self.vn), T::read(regs, self.vm));
+        let product = fp_mul(op1, op2, &mut regs.fpscr);
+        let addend = if self.subtract {
+            fp_neg(product)
+        } else {
+            product
+        };
+        let result = fp_add(T::read(regs, self.vd), addend, &mut regs.fpscr);
+        T::write(regs, self.vd, result);
+    }
+}
+
+impl Instruction for Vmla {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7M, V7EM, V8M],
+            expression: "111011100x00xxxxxxxx101xxxx0xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let double = ins.bit(8);
+        let vd = ins.vreg(12, 22, double);
+        let vn = ins.vreg(16, 7, double);
+        let vm = ins.vreg(0, 5, double);
+        undefined(double && (vd >= 16 || vn >= 16 || vm >= 16))?;
+        Ok(Self {
+            vd,
+            vn,
+            vm,
+            double,
+            subtract: ins.bit(6),
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        if self.double {
+            self.compute::<u64>(&mut proc.registers);
+        } else {
+            self.compute::<u32>(&mut proc.registers);
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        let name = if self.subtract { "vmls" } else { "vmla" };
+        format!("{name}.{}", fp_data_type(self.double))
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!(
+            "{}, {}, {}",
+            extension_register(self.double, self.vd),
+            extension_register(self.double, self.vn),
+            extension_register(self.double, self.vm)
+        )
+    }
+}
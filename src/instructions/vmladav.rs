@@ -0,0 +1,165 @@
+//! Implements VMLADAV (Vector Multiply Add Dual Accumulate Across Vector) instruction.
+
+use super::Encoding::{self, T1};
+use super::{other, undefined, unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    core::{ArmVersion::V81M, Effect, ItState, Processor, RunError},
+    decoder::DecodeError,
+    helpers::BitAccess,
+    registers::RegisterIndex,
+    vector::{element, element_active, elements_per_beat, qreg, sign_extend},
+};
+
+/// VMLADAV instruction.
+///
+/// Vector Multiply Add Dual Accumulate Across Vector. Multiplies the elements of two vectors and
+/// sums the products into a general-purpose register.
+pub struct Vmladav {
+    /// Destination and accumulator register.
+    rda: RegisterIndex,
+    /// First operand vector register.
+    qn: u8,
+    /// Second operand vector register.
+    qm: u8,
+    /// Element size in bits.
+    esize: u32,
+    /// `true` for unsigned elements.
+    unsigned: bool,
+    /// `true` to multiply each element with the other element of its pair in the second operand
+    /// (exchange).
+    exchange: bool,
+    /// `true` to add the destination register initial value to the result (accumulate).
+    accumulate: bool,
+}
+
+impl Instruction for Vmladav {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V81M],
+            expression: "111x11101111xxxxxxxx111x00x0xxx0",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let esize = if ins.bit(8) {
+            other(ins.bit(16))?;
+            8
+        } else if ins.bit(16) {
+            32
+        } else {
+            16
+        };
+        let unsigned = ins.bit(28);
+        let exchange = ins.bit(12);
+        undefined(unsigned && exchange)?;
+        unpredictable(state.in_it_block())?;
+        Ok(Self {
+            rda: RegisterIndex::new_main(((ins >> 13) & 7) * 2),
+            qn: qreg(ins, 17, 7)?,
+            qm: ins.imm3(1) as u8,
+            esize,
+            unsigned,
+            exchange,
+            accumulate: ins.bit(5),
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_mve_check(false)?;
+        let (n, m) = (
+            proc.registers.q(self.qn as usize),
+            proc.registers.q(self.qm as usize),
+        );
+        let mut result = if self.accumulate { proc[self.rda] } else { 0 };
+        let esize = self.esize;
+        let extend = |value: u32| {
+            if self.unsigned {
+                value
+            } else {
+                sign_extend(value, esize) as u32
+            }
+        };
+        proc.execute_beats(|_, beat, mask| {
+            for i in 0..elements_per_beat(esize) {
+                if element_active(mask, esize, i) {
+                    let index = beat as u32 * elements_per_beat(esize) + i;
+                    let other = index ^ self.exchange as u32;
+                    let a = element((n >> (index * esize)) as u32, esize, 0);
+                    let b = element((m >> (other * esize)) as u32, esize, 0);
+                    result = result.wrapping_add(extend(a).wrapping_mul(extend(b)));
+                }
+            }
+            Ok(())
+        })?;
+        proc.set(self.rda, result);
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "{}{}{}.{}{}",
+            if self.exchange { "vmladav" } else { "vmlav" },
+            if self.accumulate { "a" } else { "" },
+            if self.exchange { "x" } else { "" },
+            if self.unsigned { 'u' } else { 's' },
+            self.esize
+        )
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, q{}, q{}", self.rda, self.qn, self.qm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Vmladav;
+    use crate::{
+        core::{Config, FloatingPointExtension, MveExtension, Processor},
+        instructions::Instruction,
+        registers::RegisterIndex,
+    };
+
+    #[test]
+    fn test_vmladav() {
+        let mut proc = Processor::new(
+            Config::v81m()
+                .fpu(FloatingPointExtension::SinglePrecision)
+                .mve(MveExtension::Integer),
+        );
+        proc.write_u32le_iface(0xe000ed88, 0x00f00000).unwrap();
+        proc.registers
+            .set_q(1, 0x0001_0002_0003_0004_0005_0006_0007_fff8);
+        proc.registers
+            .set_q(2, 0x0008_0007_0006_0005_0004_0003_0002_0001);
+        proc.registers.r4 = 1000;
+        let mut ins = Vmladav {
+            rda: RegisterIndex::R4,
+            qn: 1,
+            qm: 2,
+            esize: 16,
+            unsigned: false,
+            exchange: false,
+            accumulate: false,
+        };
+        ins.execute(&mut proc).unwrap();
+        assert_eq!(proc.registers.r4, 8 + 14 + 18 + 20 + 20 + 18 + 14 - 8);
+
+        ins.unsigned = true;
+        ins.accumulate = true;
+        proc.registers.r4 = 1000;
+        ins.execute(&mut proc).unwrap();
+        assert_eq!(
+            proc.registers.r4,
+            1000 + 8 + 14 + 18 + 20 + 20 + 18 + 14 + 0xfff8
+        );
+
+        ins.unsigned = false;
+        ins.accumulate = false;
+        ins.exchange = true;
+        ins.execute(&mut proc).unwrap();
+        assert_eq!(proc.registers.r4, 7 + 24 + 15 + 24 + 15 + 16 + 7 - 16);
+    }
+}
@@ -0,0 +1,454 @@
+//! Implements VMOV (Floating-point Move) instruction variants.
+
+use super::Encoding::{self, T1};
+use super::{
+    extension_register, fp_data_type, undefined, unpredictable, DecodeHelper, Instruction, Pattern,
+};
+use crate::{
+    core::{
+        ArmVersion::{V7EM, V7M, V8M},
+        Effect, ItState, Processor, RunError,
+    },
+    decoder::DecodeError,
+    helpers::BitAccess,
+    registers::RegisterIndex,
+};
+
+/// Expands the 8-bit immediate of VMOV (immediate) to a single-precision value, or to a
+/// double-precision value if `double` is `true`.
+///
+/// Corresponds to `VFPExpandImm()` in the Arm Architecture Reference Manual.
+fn vfp_expand_imm(imm8: u32, double: bool) -> u64 {
+    let (e, f) = if double { (11, 52) } else { (8, 23) };
+    let sign = imm8 as u64 >> 7;
+    let b = imm8.imm1(6) as u64;
+    let exp = (b ^ 1) << (e - 1)
+        | (if b != 0 { (1 << (e - 3)) - 1 } else { 0 }) << 2
+        | imm8.imm2(4) as u64;
+    sign << (e + f) | exp << f | (imm8.imm4(0) as u64) << (f - 4)
+}
+
+/// Formats a floating-point immediate value the same way `llvm-objdump` does, for instance
+/// `1.250000e-01`.
+fn float_imm_string(value: f64) -> String {
+    let s = format!("{value:.6e}");
+    let (mantissa, exp) = s.split_once('e').unwrap();
+    let exp: i32 = exp.parse().unwrap();
+    let sign = if exp < 0 { '-' } else { '+' };
+    format!("{mantissa}e{sign}{:02}", exp.abs())
+}
+
+/// VMOV (immediate) instruction.
+///
+/// Copies a floating-point constant into a register.
+pub struct VmovImm {
+    /// Destination register index.
+    vd: u8,
+    /// Expanded immediate value.
+    imm64: u64,
+    /// True for a double-precision register and constant.
+    double: bool,
+}
+
+impl Instruction for VmovImm {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7M, V7EM, V8M],
+            expression: "111011101x11xxxxxxxx101x(0)0(0)0xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let double = ins.bit(8);
+        let vd = ins.vreg(12, 22, double);
+        undefined(double && vd >= 16)?;
+        Ok(Self {
+            vd,
+            imm64: vfp_expand_imm(ins.imm4(16) << 4 | ins.imm4(0), double),
+            double,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        if self.double {
+            proc.registers.set_d(self.vd as usize, self.imm64);
+        } else {
+            proc.registers.s[self.vd as usize] = self.imm64 as u32;
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        format!("vmov.{}", fp_data_type(self.double))
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        let value = if self.double {
+            f64::from_bits(self.imm64)
+        } else {
+            f32::from_bits(self.imm64 as u32) as f64
+        };
+        format!(
+            "{}, #{}",
+            extension_register(self.double, self.vd),
+            float_imm_string(value)
+        )
+    }
+}
+
+/// VMOV (register) instruction.
+///
+/// Copies the content of a floating-point register to another.
+pub struct VmovReg {
+    /// Destination register index.
+    vd: u8,
+    /// Source register index.
+    vm: u8,
+    /// True for double-precision registers.
+    double: bool,
+}
+
+impl Instruction for VmovReg {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7M, V7EM, V8M],
+            expression: "111011101x110000xxxx101x01x0xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let double = ins.bit(8);
+        let vd = ins.vreg(12, 22, double);
+        let vm = ins.vreg(0, 5, double);
+        undefined(double && (vd >= 16 || vm >= 16))?;
+        Ok(Self { vd, vm, double })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        let regs = &mut proc.registers;
+        if self.double {
+            regs.set_d(self.vd as usize, regs.d(self.vm as usize));
+        } else {
+            regs.s[self.vd as usize] = regs.s[self.vm as usize];
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        format!("vmov.{}", fp_data_type(self.double))
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!(
+            "{}, {}",
+            extension_register(self.double, self.vd),
+            extension_register(self.double, self.vm)
+        )
+    }
+}
+
+/// VMOV (Arm core register to scalar) instruction.
+///
+/// Copies an Arm core register to one half of a double-precision register.
+pub struct VmovCoreToScalar {
+    /// Destination double-precision register index.
+    dd: u8,
+    /// Half of the destination register, 0 for the lower half and 1 for the upper half.
+    index: u8,
+    /// Source register.
+    rt: RegisterIndex,
+}
+
+impl Instruction for VmovCoreToScalar {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7M, V7EM, V8M],
+            expression: "1110111000x0xxxxxxxx1011x001(0)(0)(0)(0)",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rt = ins.reg4(12);
+        let dd = ins.dreg(16, 7);
+        undefined(dd >= 16)?;
+        unpredictable(rt.is_sp_or_pc())?;
+        Ok(Self {
+            dd,
+            index: ins.imm1(21) as u8,
+            rt,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        proc.registers.s[(self.dd * 2 + self.index) as usize] = proc[self.rt];
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "vmov.32".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("d{}[{}], {}", self.dd, self.index, self.rt)
+    }
+}
+
+/// VMOV (scalar to Arm core register) instruction.
+///
+/// Copies one half of a double-precision register to an Arm core register.
+pub struct VmovScalarToCore {
+    /// Destination register.
+    rt: RegisterIndex,
+    /// Source double-precision register index.
+    dn: u8,
+    /// Half of the source register, 0 for the lower half and 1 for the upper half.
+    index: u8,
+}
+
+impl Instruction for VmovScalarToCore {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7M, V7EM, V8M],
+            expression: "1110111000x1xxxxxxxx1011x001(0)(0)(0)(0)",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rt = ins.reg4(12);
+        let dn = ins.dreg(16, 7);
+        undefined(dn >= 16)?;
+        unpredictable(rt.is_sp_or_pc())?;
+        Ok(Self {
+            rt,
+            dn,
+            index: ins.imm1(21) as u8,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        let value = proc.registers.s[(self.dn * 2 + self.index) as usize];
+        proc.set(self.rt, value);
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "vmov.32".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("{}, d{}[{}]", self.rt, self.dn, self.index)
+    }
+}
+
+/// VMOV (between Arm core register and single-precision register) instruction.
+pub struct VmovCoreSingle {
+    /// Single-precision register index.
+    sn: u8,
+    /// Arm core register.
+    rt: RegisterIndex,
+    /// True to copy to the Arm core register, false to copy to the single-precision register.
+    to_core: bool,
+}
+
+impl Instruction for VmovCoreSingle {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7M, V7EM, V8M],
+            expression: "11101110000xxxxxxxxx1010x(0)(0)1(0)(0)(0)(0)",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rt = ins.reg4(12);
+        unpredictable(rt.is_sp_or_pc())?;
+        Ok(Self {
+            sn: ins.sreg(16, 7),
+            rt,
+            to_core: ins.bit(20),
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        let n = self.sn as usize;
+        if self.to_core {
+            proc.set(self.rt, proc.registers.s[n]);
+        } else {
+            proc.registers.s[n] = proc[self.rt];
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "vmov".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        if self.to_core {
+            format!("{}, s{}", self.rt, self.sn)
+        } else {
+            format!("s{}, {}", self.sn, self.rt)
+        }
+    }
+}
+
+/// VMOV (between two Arm core registers and two single-precision registers) instruction.
+pub struct VmovCoreTwoSingles {
+    /// First single-precision register index. The second register is the next one.
+    sm: u8,
+    /// First Arm core register.
+    rt: RegisterIndex,
+    /// Second Arm core register.
+    rt2: RegisterIndex,
+    /// True to copy to the Arm core registers, false to copy to the single-precision registers.
+    to_core: bool,
+}
+
+impl Instruction for VmovCoreTwoSingles {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7M, V7EM, V8M],
+            expression: "11101100010xxxxxxxxx101000x1xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rt = ins.reg4(12);
+        let rt2 = ins.reg4(16);
+        let sm = ins.sreg(0, 5);
+        let to_core = ins.bit(20);
+        unpredictable(rt.is_sp_or_pc() || rt2.is_sp_or_pc() || sm == 31)?;
+        unpredictable(to_core && rt == rt2)?;
+        Ok(Self {
+            sm,
+            rt,
+            rt2,
+            to_core,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        let m = self.sm as usize;
+        if self.to_core {
+            proc.set(self.rt, proc.registers.s[m]);
+            proc.set(self.rt2, proc.registers.s[m + 1]);
+        } else {
+            proc.registers.s[m] = proc[self.rt];
+            proc.registers.s[m + 1] = proc[self.rt2];
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "vmov".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        let (sm, sm2) = (self.sm, self.sm + 1);
+        if self.to_core {
+            format!("{}, {}, s{sm}, s{sm2}", self.rt, self.rt2)
+        } else {
+            format!("s{sm}, s{sm2}, {}, {}", self.rt, self.rt2)
+        }
+    }
+}
+
+/// VMOV (between two Arm core registers and a double-precision register) instruction.
+pub struct VmovCoreDouble {
+    /// Double-precision register index.
+    dm: u8,
+    /// Arm core register for the lower half.
+    rt: RegisterIndex,
+    /// Arm core register for the upper half.
+    rt2: RegisterIndex,
+    /// True to copy to the Arm core registers, false to copy to the double-precision register.
+    to_core: bool,
+}
+
+impl Instruction for VmovCoreDouble {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7M, V7EM, V8M],
+            expression: "11101100010xxxxxxxxx101100x1xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rt = ins.reg4(12);
+        let rt2 = ins.reg4(16);
+        let dm = ins.dreg(0, 5);
+        let to_core = ins.bit(20);
+        undefined(dm >= 16)?;
+        unpredictable(rt.is_sp_or_pc() || rt2.is_sp_or_pc())?;
+        unpredictable(to_core && rt == rt2)?;
+        Ok(Self {
+            dm,
+            rt,
+            rt2,
+            to_core,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        let m = self.dm as usize;
+        if self.to_core {
+            let value = proc.registers.d(m);
+            proc.set(self.rt, value as u32);
+            proc.set(self.rt2, (value >> 32) as u32);
+        } else {
+            let value = (proc[self.rt2] as u64) << 32 | proc[self.rt] as u64;
+            proc.registers.set_d(m, value);
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "vmov".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        if self.to_core {
+            format!("{}, {}, d{}", self.rt, self.rt2, self.dm)
+        } else {
+            format!("d{}, {}, {}", self.dm, self.rt, self.rt2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{float_imm_string, vfp_expand_imm};
+
+    #[test]
+    fn test_vfp_expand_imm() {
+        assert_eq!(vfp_expand_imm(0x70, false), 0x3f800000); // 1.0
+        assert_eq!(vfp_expand_imm(0xc0, false), 0xbe000000); // -0.125
+        assert_eq!(vfp_expand_imm(0x3f, false), 0x41f80000); // 31.0
+        assert_eq!(vfp_expand_imm(0x70, true), 0x3ff0000000000000); // 1.0
+        assert_eq!(vfp_expand_imm(0xc0, true), 0xbfc0000000000000); // -0.125
+        assert_eq!(vfp_expand_imm(0x3f, true), 0x403f000000000000); // 31.0
+        assert_eq!(float_imm_string(1.0), "1.000000e+00");
+        assert_eq!(float_imm_string(-0.125), "-1.250000e-01");
+        assert_eq!(float_imm_string(31.0), "3.100000e+01");
+    }
+}
@@ -0,0 +1,66 @@
+//! Implements VMRS (Move to Arm core register from floating-point Special register) instruction.
+
+use super::Encoding::{self, T1};
+use super::{unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    core::{
+        ArmVersion::{V7EM, V7M, V8M},
+        Effect, ItState, Processor, RunError,
+    },
+    decoder::DecodeError,
+    registers::RegisterIndex,
+};
+
+/// VMRS instruction.
+///
+/// Reads FPSCR into an Arm core register, or transfers FPSCR condition flags to APSR when the
+/// destination is PC.
+pub struct Vmrs {
+    /// Destination register. PC means APSR condition flags.
+    rt: RegisterIndex,
+}
+
+impl Instruction for Vmrs {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7M, V7EM, V8M],
+            expression: "1110111011110001xxxx1010(0)(0)(0)1(0)(0)(0)(0)",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rt = ins.reg4(12);
+        unpredictable(rt.is_sp())?;
+        Ok(Self { rt })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        let fpscr = proc.registers.fpscr;
+        if self.rt.is_pc() {
+            proc.registers
+                .psr
+                .set_n(fpscr.n())
+                .set_z(fpscr.z())
+                .set_c(fpscr.c())
+                .set_v(fpscr.v());
+        } else {
+            proc.set(self.rt, fpscr.get());
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "vmrs".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        if self.rt.is_pc() {
+            "apsr_nzcv, fpscr".into()
+        } else {
+            format!("{}, fpscr", self.rt)
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! Implements VMSR (Move to floating-point Special register from Arm core register) instruction.
+
+use super::Encoding::{self, T1};
+use super::{unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    core::{
+        ArmVersion::{V7EM, V7M, V8M},
+        Effect, ItState, Processor, RunError,
+    },
+    decoder::DecodeError,
+    registers::RegisterIndex,
+};
+
+/// VMSR instruction.
+///
+/// Writes an Arm core register to FPSCR.
+pub struct Vmsr {
+    /// Source register.
+    rt: RegisterIndex,
+}
+
+impl Instruction for Vmsr {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7M, V7EM, V8M],
+            expression: "1110111011100001xxxx1010(0)(0)(0)1(0)(0)(0)(0)",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let rt = ins.reg4(12);
+        unpredictable(rt.is_sp_or_pc())?;
+        Ok(Self { rt })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        let value = proc[self.rt];
+        proc.registers.fpscr.set(value);
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "vmsr".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!("fpscr, {}", self.rt)
+    }
+}
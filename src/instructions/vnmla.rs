@@ -0,0 +1,125 @@
+//! Implements VNMLA (Floating-point Multiply Accumulate and Negate), VNMLS (Floating-point
+//! Multiply Subtract and Negate) and VNMUL (Floating-point Multiply and Negate) instructions.
+
+use super::Encoding::{self, T1, T2};
+use super::{
+    extension_register, fp_data_type, undefined, DecodeHelper, ExtensionRegister, Instruction,
+    Pattern,
+};
+use crate::{
+    core::{
+        ArmVersion::{V7EM, V7M, V8M},
+        Effect, ItState, Processor, RunError,
+    },
+    decoder::DecodeError,
+    float::{fp_add, fp_mul, fp_neg},
+    helpers::BitAccess,
+    registers::CoreRegisters,
+};
+
+/// Operation performed by [Vnmla].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NegMul {
+    /// Computes `-Vd - Vn * Vm`.
+    Vnmla,
+    /// Computes `-Vd + Vn * Vm`.
+    Vnmls,
+    /// Computes `-(Vn * Vm)`.
+    Vnmul,
+}
+
+/// VNMLA, VNMLS or VNMUL instruction.
+///
+/// Floating-point Multiply and Negate, with optional accumulation.
+pub struct Vnmla {
+    /// Destination register index.
+    vd: u8,
+    /// First operand register index.
+    vn: u8,
+    /// Second operand register index.
+    vm: u8,
+    /// True for double-precision operation.
+    double: bool,
+    /// Operation type.
+    op: NegMul,
+}
+
+impl Vnmla {
+    fn compute<T: ExtensionRegister>(&self, regs: &mut CoreRegisters) {
+        let (op1, op2) = (T::read(regs, self.vn), T::read(regs, self.vm));
+        let product = fp_mul(op1, op2, &mut regs.fpscr);
+        let d = T::read(regs, self.vd);
+        let result = match self.op {
+            NegMul::Vnmla => fp_add(fp_neg(d), fp_neg(product), &mut regs.fpscr),
+            NegMul::Vnmls => fp_add(fp_neg(d), product, &mut regs.fpscr),
+            NegMul::Vnmul => fp_neg(product),
+        };
+        T::write(regs, self.vd, result);
+    }
+}
+
+impl Instruction for Vnmla {
+    fn patterns() -> &'static [Pattern] {
+        &[
+            Pattern {
+                encoding: T1,
+                versions: &[V7M, V7EM, V8M],
+                expression: "111011100x01xxxxxxxx101xxxx0xxxx",
+            },
+            Pattern {
+                encoding: T2,
+                versions: &[V7M, V7EM, V8M],
+                expression: "111011100x10xxxxxxxx101xx1x0xxxx",
+            },
+        ]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        let op = match encoding {
+            T1 if ins.bit(6) => NegMul::Vnmla,
+            T1 => NegMul::Vnmls,
+            T2 => NegMul::Vnmul,
+            _ => panic!(),
+        };
+        let double = ins.bit(8);
+        let vd = ins.vreg(12, 22, double);
+        let vn = ins.vreg(16, 7, double);
+        let vm = ins.vreg(0, 5, double);
+        undefined(double && (vd >= 16 || vn >= 16 || vm >= 16))?;
+        Ok(Self {
+            vd,
+            vn,
+            vm,
+            double,
+            op,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        if self.double {
+            self.compute::<u64>(&mut proc.registers);
+        } else {
+            self.compute::<u32>(&mut proc.registers);
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        let name = match self.op {
+            NegMul::Vnmla => "vnmla",
+            NegMul::Vnmls => "vnmls",
+            NegMul::Vnmul => "vnmul",
+        };
+        format!("{name}.{}", fp_data_type(self.double))
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!(
+            "{}, {}, {}",
+            extension_register(self.double, self.vd),
+            extension_register(self.double, self.vn),
+            extension_register(self.double, self.vm)
+        )
+    }
+}
@@ -0,0 +1,67 @@
+//! Implements VORR (Vector Bitwise OR) instruction.
+
+use super::Encoding::{self, T1};
+use super::{unpredictable, Instruction, Pattern};
+use crate::{
+    core::{ArmVersion::V81M, Effect, ItState, Processor, RunError},
+    decoder::DecodeError,
+    vector::{qreg, VectorOperand, VectorOperation},
+};
+
+/// VORR instruction.
+///
+/// Vector Bitwise OR. When both operands are the same register, this is the preferred encoding
+/// of VMOV (vector).
+pub struct Vorr {
+    /// Operands.
+    op: VectorOperation,
+}
+
+impl Instruction for Vorr {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V81M],
+            expression: "111011110x10xxx0xxx00001x1x1xxx0",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        unpredictable(state.in_it_block())?;
+        Ok(Self {
+            op: VectorOperation {
+                qd: qreg(ins, 13, 22)?,
+                qn: qreg(ins, 17, 7)?,
+                operand: VectorOperand::Vector(qreg(ins, 1, 5)?),
+                // Predication is applied per byte, so the operation is done on bytes.
+                esize: 8,
+                float: false,
+            },
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        self.op.execute(proc, |a, b, _| a | b)
+    }
+
+    fn name(&self) -> String {
+        if self.is_vmov() { "vmov" } else { "vorr" }.into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        if self.is_vmov() {
+            format!("q{}, q{}", self.op.qd, self.op.qn)
+        } else {
+            self.op.args()
+        }
+    }
+}
+
+impl Vorr {
+    /// Returns `true` if both operands are the same register, in which case the instruction is
+    /// disassembled as VMOV.
+    fn is_vmov(&self) -> bool {
+        self.op.operand == VectorOperand::Vector(self.op.qn)
+    }
+}
@@ -0,0 +1,48 @@
+//! Implements VPNOT (Vector Predicate NOT) instruction.
+
+use super::Encoding::{self, T1};
+use super::{unpredictable, Instruction, Pattern};
+use crate::{
+    core::{ArmVersion::V81M, Effect, ItState, Processor, RunError},
+    decoder::DecodeError,
+};
+
+/// VPNOT instruction.
+///
+/// Vector Predicate NOT. Inverts the predicate flags of VPR.P0. Flags of inactive elements are
+/// cleared.
+pub struct Vpnot {}
+
+impl Instruction for Vpnot {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V81M],
+            expression: "11111110001100010000111101001101",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, _ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        unpredictable(state.in_it_block())?;
+        Ok(Self {})
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_mve_check(false)?;
+        proc.execute_beats(|proc, beat, mask| {
+            let vpr = &mut proc.registers.vpr;
+            vpr.set_beat_p0(beat, !vpr.beat_p0(beat) & mask);
+            Ok(())
+        })?;
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "vpnot".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        "".into()
+    }
+}
@@ -0,0 +1,86 @@
+//! Implements VPOP (Floating-point Pop Registers) instruction.
+
+use super::Encoding::{self, T1, T2};
+use super::{extension_registers_args, unpredictable, vldm::Vldm, DecodeHelper, Instruction};
+use super::{
+    ArmVersion::{V7EM, V7M, V8M},
+    Pattern,
+};
+use crate::{
+    core::{Effect, ItState, Processor, RunError},
+    decoder::DecodeError,
+    registers::RegisterIndex,
+};
+
+/// VPOP instruction.
+pub struct Vpop {
+    /// True for single-precision registers, false for double-precision registers.
+    single: bool,
+    /// First popped register index.
+    vd: u8,
+    /// Number of popped registers.
+    regs: u8,
+    /// Stack pointer increment.
+    imm32: u32,
+}
+
+impl Instruction for Vpop {
+    fn patterns() -> &'static [Pattern] {
+        &[
+            Pattern {
+                encoding: T1,
+                versions: &[V7M, V7EM, V8M],
+                expression: "111011001x111101xxxx1011xxxxxxxx",
+            },
+            Pattern {
+                encoding: T2,
+                versions: &[V7M, V7EM, V8M],
+                expression: "111011001x111101xxxx1010xxxxxxxx",
+            },
+        ]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        let single = match encoding {
+            T1 => false,
+            T2 => true,
+            _ => panic!(),
+        };
+        let imm8 = ins.imm8(0);
+        let (vd, regs) = if single {
+            (ins.sreg(12, 22), imm8)
+        } else {
+            (ins.dreg(12, 22), imm8 / 2)
+        };
+        unpredictable(regs == 0 || regs > 16)?;
+        unpredictable(vd as u32 + regs > if single { 32 } else { 16 })?;
+        Ok(Self {
+            single,
+            vd,
+            regs: regs as u8,
+            imm32: imm8 << 2,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        // VPOP is equivalent to VLDMIA with SP as base register and write-back.
+        let vldm = Vldm {
+            single: self.single,
+            vd: self.vd,
+            regs: self.regs,
+            rn: RegisterIndex::Sp,
+            imm32: self.imm32,
+            add: true,
+            wback: true,
+        };
+        vldm.execute(proc)
+    }
+
+    fn name(&self) -> String {
+        "vpop".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        extension_registers_args(self.single, self.vd, self.regs)
+    }
+}
@@ -0,0 +1,52 @@
+//! Implements VPST (Vector Predicate Set Then) instruction.
+
+use super::Encoding::{self, T1};
+use super::{other, unpredictable, Instruction, Pattern};
+use crate::{
+    core::{ArmVersion::V81M, Effect, ItState, Processor, RunError},
+    decoder::DecodeError,
+    vector::{vpt_mask, vpt_suffix},
+};
+
+/// VPST instruction.
+///
+/// Vector Predicate Set Then. Starts a VPT block of up to four instructions predicated by the
+/// current value of VPR.P0.
+pub struct Vpst {
+    /// VPT block mask.
+    mask: u8,
+}
+
+impl Instruction for Vpst {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V81M],
+            expression: "111111100x110001xxx0111101001101",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let mask = vpt_mask(ins);
+        other(mask == 0)?; // VPNOT
+        unpredictable(state.in_it_block())?;
+        Ok(Self { mask })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_mve_check(false)?;
+        for beat in [0, 2] {
+            proc.registers.vpr.set_mask(beat, self.mask);
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        format!("vps{}", vpt_suffix(self.mask))
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        "".into()
+    }
+}
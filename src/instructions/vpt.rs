@@ -0,0 +1,126 @@
+//! Implements VPT (Vector Predicate Then) instruction.
+
+use super::Encoding::{self, T1};
+use super::{other, unpredictable, Instruction, Pattern};
+use crate::{
+    core::{ArmVersion::V81M, Effect, ItState, Processor, RunError},
+    decoder::DecodeError,
+    vector::{vpt_mask, vpt_suffix, VectorComparison},
+};
+
+/// VPT instruction.
+///
+/// Vector Predicate Then. Compares the elements of a vector with another vector or a scalar,
+/// writes the result to VPR.P0 and starts a VPT block of up to four instructions predicated by it.
+pub struct Vpt {
+    /// Comparison.
+    cmp: VectorComparison,
+    /// VPT block mask.
+    mask: u8,
+}
+
+impl Instruction for Vpt {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V81M],
+            expression: "111x11100xxxxxx1xxxx1111xxx0xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let mask = vpt_mask(ins);
+        other(mask == 0)?; // VCMP
+        let cmp = VectorComparison::decode(ins)?;
+        unpredictable(state.in_it_block())?;
+        Ok(Self { cmp, mask })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_mve_check(self.cmp.float)?;
+        for beat in 0..4 {
+            let mask = proc.beat_mask(beat);
+            let predicate = self.cmp.compare_beat(&mut proc.registers, beat) & mask;
+            proc.registers.vpr.set_beat_p0(beat, predicate);
+        }
+        for beat in [0, 2] {
+            proc.registers.vpr.set_mask(beat, self.mask);
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        format!("vp{}.{}", vpt_suffix(self.mask), self.cmp.data_type())
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        self.cmp.args()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Vpt;
+    use crate::{
+        core::{Config, FloatingPointExtension, MveExtension, Processor},
+        instructions::Instruction,
+        registers::RegisterIndex,
+        vector::{VectorComparison, VectorCondition, VectorOperand},
+    };
+
+    #[test]
+    fn test_vpt() {
+        let mut proc = Processor::new(
+            Config::v81m()
+                .fpu(FloatingPointExtension::SinglePrecision)
+                .mve(MveExtension::Integer),
+        );
+        proc.write_u32le_iface(0xe000ed88, 0x00f00000).unwrap();
+        proc.registers
+            .set_q(1, 0x00000005_fffffffe_00000003_00000001);
+        proc.registers.r2 = 2;
+        let ins = Vpt {
+            cmp: VectorComparison {
+                qn: 1,
+                operand: VectorOperand::Scalar(RegisterIndex::R2),
+                cond: VectorCondition::Gt,
+                esize: 32,
+                float: false,
+            },
+            mask: 0b1100,
+        };
+        ins.execute(&mut proc).unwrap();
+        assert_eq!(proc.registers.vpr.p0(), 0xf0f0);
+        assert_eq!(proc.registers.vpr.mask(0), 0b1100);
+        assert_eq!(proc.registers.vpr.mask(3), 0b1100);
+
+        // Unsigned comparison.
+        let ins = Vpt {
+            cmp: VectorComparison {
+                qn: 1,
+                operand: VectorOperand::Scalar(RegisterIndex::R2),
+                cond: VectorCondition::Hi,
+                esize: 32,
+                float: false,
+            },
+            mask: 0b1000,
+        };
+        proc.registers.vpr.set(0);
+        ins.execute(&mut proc).unwrap();
+        assert_eq!(proc.registers.vpr.p0(), 0xfff0);
+
+        // Floating-point comparisons are UNDEFINED with the integer subset only.
+        let ins = Vpt {
+            cmp: VectorComparison {
+                qn: 1,
+                operand: VectorOperand::Vector(2),
+                cond: VectorCondition::Eq,
+                esize: 32,
+                float: true,
+            },
+            mask: 0b1000,
+        };
+        assert!(ins.execute(&mut proc).is_err());
+    }
+}
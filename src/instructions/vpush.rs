@@ -0,0 +1,86 @@
+//! Implements VPUSH (Floating-point Push Registers) instruction.
+
+use super::Encoding::{self, T1, T2};
+use super::{extension_registers_args, unpredictable, vstm::Vstm, DecodeHelper, Instruction};
+use super::{
+    ArmVersion::{V7EM, V7M, V8M},
+    Pattern,
+};
+use crate::{
+    core::{Effect, ItState, Processor, RunError},
+    decoder::DecodeError,
+    registers::RegisterIndex,
+};
+
+/// VPUSH instruction.
+pub struct Vpush {
+    /// True for single-precision registers, false for double-precision registers.
+    single: bool,
+    /// First pushed register index.
+    vd: u8,
+    /// Number of pushed registers.
+    regs: u8,
+    /// Stack pointer decrement.
+    imm32: u32,
+}
+
+impl Instruction for Vpush {
+    fn patterns() -> &'static [Pattern] {
+        &[
+            Pattern {
+                encoding: T1,
+                versions: &[V7M, V7EM, V8M],
+                expression: "111011010x101101xxxx1011xxxxxxxx",
+            },
+            Pattern {
+                encoding: T2,
+                versions: &[V7M, V7EM, V8M],
+                expression: "111011010x101101xxxx1010xxxxxxxx",
+            },
+        ]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        let single = match encoding {
+            T1 => false,
+            T2 => true,
+            _ => panic!(),
+        };
+        let imm8 = ins.imm8(0);
+        let (vd, regs) = if single {
+            (ins.sreg(12, 22), imm8)
+        } else {
+            (ins.dreg(12, 22), imm8 / 2)
+        };
+        unpredictable(regs == 0 || regs > 16)?;
+        unpredictable(vd as u32 + regs > if single { 32 } else { 16 })?;
+        Ok(Self {
+            single,
+            vd,
+            regs: regs as u8,
+            imm32: imm8 << 2,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        // VPUSH is equivalent to VSTMDB with SP as base register and write-back.
+        let vstm = Vstm {
+            single: self.single,
+            vd: self.vd,
+            regs: self.regs,
+            rn: RegisterIndex::Sp,
+            imm32: self.imm32,
+            add: false,
+            wback: true,
+        };
+        vstm.execute(proc)
+    }
+
+    fn name(&self) -> String {
+        "vpush".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        extension_registers_args(self.single, self.vd, self.regs)
+    }
+}
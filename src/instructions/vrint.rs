@@ -0,0 +1,163 @@
+//! Implements VRINTA, VRINTN, VRINTP, VRINTM, VRINTR, VRINTX and VRINTZ (Floating-point Round
+//! to Integer) instructions.
+
+use super::Encoding::{self, T1, T2, T3};
+use super::{
+    extension_register, fp_data_type, undefined, unpredictable, DecodeHelper, ExtensionRegister,
+    Instruction, Pattern,
+};
+use crate::{
+    core::{
+        ArmVersion::{V7EM, V7M, V8M},
+        Effect, ItState, Processor, RunError,
+    },
+    decoder::DecodeError,
+    float::fp_round_int,
+    helpers::BitAccess,
+    registers::{CoreRegisters, RoundingMode},
+};
+
+/// VRINTA, VRINTN, VRINTP, VRINTM, VRINTR, VRINTX or VRINTZ instruction.
+///
+/// Rounds a floating-point value to an integral floating-point value.
+pub struct Vrint {
+    /// Destination register index.
+    vd: u8,
+    /// Operand register index.
+    vm: u8,
+    /// Rounding mode, or [None] to use the rounding mode selected by FPSCR.
+    rounding: Option<RoundingMode>,
+    /// True for VRINTX, which raises Inexact exception if the result differs from the operand.
+    exact: bool,
+    /// True for double-precision operation.
+    double: bool,
+}
+
+impl Vrint {
+    fn compute<T: ExtensionRegister>(&self, regs: &mut CoreRegisters) {
+        let rounding = self.rounding.unwrap_or(regs.fpscr.rmode());
+        let op = T::read(regs, self.vm);
+        let result = fp_round_int(op, rounding, self.exact, &mut regs.fpscr);
+        T::write(regs, self.vd, result);
+    }
+}
+
+impl Instruction for Vrint {
+    fn patterns() -> &'static [Pattern] {
+        &[
+            Pattern {
+                encoding: T1,
+                versions: &[V7M, V7EM, V8M],
+                expression: "111111101x1110xxxxxx101x01x0xxxx",
+            },
+            Pattern {
+                encoding: T2,
+                versions: &[V7M, V7EM, V8M],
+                expression: "111011101x110110xxxx101xx1x0xxxx",
+            },
+            Pattern {
+                encoding: T3,
+                versions: &[V7M, V7EM, V8M],
+                expression: "111011101x110111xxxx101x01x0xxxx",
+            },
+        ]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        let (rounding, exact) = match encoding {
+            T1 => {
+                // VRINTA, VRINTN, VRINTP and VRINTM
+                unpredictable(state.in_it_block())?;
+                (Some(RoundingMode::from_rm(ins.imm2(16))), false)
+            }
+            // VRINTZ or VRINTR
+            T2 => (ins.bit(7).then_some(RoundingMode::Zero), false),
+            // VRINTX
+            T3 => (None, true),
+            _ => panic!(),
+        };
+        let double = ins.bit(8);
+        let vd = ins.vreg(12, 22, double);
+        let vm = ins.vreg(0, 5, double);
+        undefined(double && (vd >= 16 || vm >= 16))?;
+        Ok(Self {
+            vd,
+            vm,
+            rounding,
+            exact,
+            double,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        if self.double {
+            self.compute::<u64>(&mut proc.registers);
+        } else {
+            self.compute::<u32>(&mut proc.registers);
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        let suffix = match (self.rounding, self.exact) {
+            (None, true) => "x",
+            (None, false) => "r",
+            (Some(RoundingMode::Zero), _) => "z",
+            (Some(RoundingMode::TiesAway), _) => "a",
+            (Some(RoundingMode::Nearest), _) => "n",
+            (Some(RoundingMode::PlusInfinity), _) => "p",
+            (Some(RoundingMode::MinusInfinity), _) => "m",
+        };
+        format!("vrint{suffix}.{}", fp_data_type(self.double))
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!(
+            "{}, {}",
+            extension_register(self.double, self.vd),
+            extension_register(self.double, self.vm)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Vrint;
+    use crate::{
+        core::{Config, FloatingPointExtension, Processor},
+        instructions::Instruction,
+        registers::RoundingMode,
+    };
+
+    #[test]
+    fn test_vrint() {
+        let mut proc = Processor::new(Config::v7em().fpu(FloatingPointExtension::DoublePrecision));
+        proc.write_u32le_iface(0xe000ed88, 0x00f00000).unwrap();
+        let vrint = |rounding, exact, double| Vrint {
+            vd: 0,
+            vm: 1,
+            rounding,
+            exact,
+            double,
+        };
+
+        proc.registers.set_d(1, 2.5f64.to_bits());
+        vrint(Some(RoundingMode::TiesAway), false, true)
+            .execute(&mut proc)
+            .unwrap();
+        assert_eq!(proc.registers.d(0), 3.0f64.to_bits());
+        vrint(None, false, true).execute(&mut proc).unwrap();
+        assert_eq!(proc.registers.d(0), 2.0f64.to_bits());
+        assert!(!proc.registers.fpscr.ixc());
+        vrint(None, true, true).execute(&mut proc).unwrap();
+        assert_eq!(proc.registers.d(0), 2.0f64.to_bits());
+        assert!(proc.registers.fpscr.ixc());
+
+        proc.registers.s[1] = (-0.5f32).to_bits();
+        vrint(Some(RoundingMode::Zero), false, false)
+            .execute(&mut proc)
+            .unwrap();
+        assert_eq!(proc.registers.s[0], (-0.0f32).to_bits());
+    }
+}
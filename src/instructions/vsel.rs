@@ -0,0 +1,97 @@
+//! Implements VSEL (Floating-point Conditional Select) instruction.
+
+use super::Encoding::{self, T1};
+use super::{
+    extension_register, fp_data_type, undefined, unpredictable, DecodeHelper, ExtensionRegister,
+    Instruction, Pattern,
+};
+use crate::{
+    core::{
+        ArmVersion::{V7EM, V7M, V8M},
+        Condition, Effect, ItState, Processor, RunError,
+    },
+    decoder::DecodeError,
+    helpers::BitAccess,
+    registers::CoreRegisters,
+};
+
+/// VSEL instruction.
+///
+/// Floating-point Conditional Select: copies one of the two operands depending on the APSR
+/// condition flags.
+pub struct Vsel {
+    /// Destination register index.
+    vd: u8,
+    /// Register selected when the condition passes.
+    vn: u8,
+    /// Register selected when the condition fails.
+    vm: u8,
+    /// Tested condition, one of EQ, VS, GE or GT.
+    cond: Condition,
+    /// True for double-precision registers.
+    double: bool,
+}
+
+impl Vsel {
+    fn compute<T: ExtensionRegister>(&self, regs: &mut CoreRegisters) {
+        let source = if regs.psr.test(self.cond) {
+            self.vn
+        } else {
+            self.vm
+        };
+        let value = T::read(regs, source);
+        T::write(regs, self.vd, value);
+    }
+}
+
+impl Instruction for Vsel {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7M, V7EM, V8M],
+            expression: "111111100xxxxxxxxxxx101xx0x0xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        unpredictable(state.in_it_block())?;
+        let cc = ins.imm2(20);
+        let cond = cc << 2 | ((cc >> 1 ^ cc) & 1) << 1;
+        let double = ins.bit(8);
+        let vd = ins.vreg(12, 22, double);
+        let vn = ins.vreg(16, 7, double);
+        let vm = ins.vreg(0, 5, double);
+        undefined(double && (vd >= 16 || vn >= 16 || vm >= 16))?;
+        Ok(Self {
+            vd,
+            vn,
+            vm,
+            cond: Condition::try_from(cond).unwrap(),
+            double,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        if self.double {
+            self.compute::<u64>(&mut proc.registers);
+        } else {
+            self.compute::<u32>(&mut proc.registers);
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        format!("vsel{}.{}", self.cond, fp_data_type(self.double))
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!(
+            "{}, {}, {}",
+            extension_register(self.double, self.vd),
+            extension_register(self.double, self.vn),
+            extension_register(self.double, self.vm)
+        )
+    }
+}
@@ -0,0 +1,140 @@
+//! Implements VSTM (Floating-point Store Multiple) instruction.
+
+use super::Encoding::{self, T1, T2};
+use super::{
+    extension_registers_args, other, undefined, unpredictable, DecodeHelper, Instruction, Pattern,
+};
+use crate::{
+    core::{
+        ArmVersion::{V7EM, V7M, V8M},
+        Effect, ItState, Processor, RunError,
+    },
+    decoder::DecodeError,
+    helpers::BitAccess,
+    registers::RegisterIndex,
+};
+
+/// VSTM instruction.
+///
+/// Stores multiple consecutive extension registers to memory.
+pub struct Vstm {
+    /// True for single-precision registers, false for double-precision registers.
+    pub single: bool,
+    /// First stored register index.
+    pub vd: u8,
+    /// Number of stored registers.
+    pub regs: u8,
+    /// Base register.
+    ///
+    /// [RegisterIndex::Sp] in case of VPUSH instruction.
+    pub rn: RegisterIndex,
+    /// Offset applied to the base register for write-back.
+    ///
+    /// For double-precision transfers with an odd immediate (FSTMX), this is one word more than
+    /// the size of the transferred registers.
+    pub imm32: u32,
+    /// True for increment after, false for decrement before.
+    pub add: bool,
+    /// Wether Rn is written back with a modified value.
+    pub wback: bool,
+}
+
+impl Instruction for Vstm {
+    fn patterns() -> &'static [Pattern] {
+        &[
+            Pattern {
+                encoding: T1,
+                versions: &[V7M, V7EM, V8M],
+                expression: "1110110xxxx0xxxxxxxx1011xxxxxxxx",
+            },
+            Pattern {
+                encoding: T2,
+                versions: &[V7M, V7EM, V8M],
+                expression: "1110110xxxx0xxxxxxxx1010xxxxxxxx",
+            },
+        ]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        let single = match encoding {
+            T1 => false,
+            T2 => true,
+            _ => panic!(),
+        };
+        let (p, u, w) = (ins.bit(24), ins.bit(23), ins.bit(21));
+        let rn = ins.reg4(16);
+        other(!p && !u && !w)?; // 64-bit transfers
+        other(p && !u && w && rn.is_sp())?; // VPUSH
+        other(p && !w)?; // VSTR
+        undefined(p == u && w)?;
+        let imm8 = ins.imm8(0);
+        let (vd, regs) = if single {
+            (ins.sreg(12, 22), imm8)
+        } else {
+            (ins.dreg(12, 22), imm8 / 2)
+        };
+        unpredictable(rn.is_pc())?;
+        unpredictable(regs == 0 || regs > 16)?;
+        unpredictable(vd as u32 + regs > if single { 32 } else { 16 })?;
+        Ok(Self {
+            single,
+            vd,
+            regs: regs as u8,
+            rn,
+            imm32: imm8 << 2,
+            add: u,
+            wback: w,
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        let rn = proc[self.rn];
+        let mut address = if self.add {
+            rn
+        } else {
+            rn.wrapping_sub(self.imm32)
+        };
+        // Double-precision registers are stored as pairs of words, least significant word first,
+        // which is the same as storing the aliased single-precision registers.
+        let (first, count) = if self.single {
+            (self.vd, self.regs)
+        } else {
+            (self.vd * 2, self.regs * 2)
+        };
+        let wback_value = if self.add {
+            rn.wrapping_add(self.imm32)
+        } else {
+            rn.wrapping_sub(self.imm32)
+        };
+        // Memory is not written if the write-back violates the stack limit.
+        if !(self.wback && proc.sp_writeback_violates_limit(self.rn, wback_value)) {
+            for i in first..first + count {
+                proc.write_u32_aligned(address, proc.registers.s[i as usize])?;
+                address = address.wrapping_add(4);
+            }
+        }
+        if self.wback {
+            proc.set(self.rn, wback_value);
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        let mode = if self.add { "ia" } else { "db" };
+        if !self.single && self.imm32 & 4 != 0 {
+            format!("fstm{mode}x")
+        } else {
+            format!("vstm{mode}")
+        }
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        let ws = if self.wback { "!" } else { "" };
+        format!(
+            "{}{ws}, {}",
+            self.rn,
+            extension_registers_args(self.single, self.vd, self.regs)
+        )
+    }
+}
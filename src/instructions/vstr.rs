@@ -0,0 +1,219 @@
+//! Implements VSTR (Floating-point Store Register) and VSTRB, VSTRH and VSTRW (Vector Store
+//! Register) instructions.
+
+use super::Encoding::{self, T1, T2};
+use super::{
+    indexing_args, undefined, unpredictable, AddOrSub, DecodeHelper, Instruction, Pattern,
+};
+use crate::{
+    core::{
+        ArmVersion::{V7EM, V7M, V81M, V8M},
+        Effect, ItState, Processor, RunError,
+    },
+    decoder::DecodeError,
+    helpers::BitAccess,
+    registers::RegisterIndex,
+    vector::{
+        element, element_active, elements_per_beat, write_element, ContiguousAccess, GatherAccess,
+    },
+};
+
+/// VSTR instruction.
+///
+/// Stores a single extension register to memory.
+pub struct Vstr {
+    /// True for a single-precision register, false for a double-precision register.
+    single: bool,
+    /// Source register index.
+    vd: u8,
+    /// Base register.
+    rn: RegisterIndex,
+    /// Offset from base.
+    imm32: u32,
+    /// True to add offset, false to subtract.
+    add: bool,
+}
+
+impl Instruction for Vstr {
+    fn patterns() -> &'static [Pattern] {
+        &[
+            Pattern {
+                encoding: T1,
+                versions: &[V7M, V7EM, V8M],
+                expression: "11101101xx00xxxxxxxx1011xxxxxxxx",
+            },
+            Pattern {
+                encoding: T2,
+                versions: &[V7M, V7EM, V8M],
+                expression: "11101101xx00xxxxxxxx1010xxxxxxxx",
+            },
+        ]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        let single = match encoding {
+            T1 => false,
+            T2 => true,
+            _ => panic!(),
+        };
+        let vd = if single {
+            ins.sreg(12, 22)
+        } else {
+            ins.dreg(12, 22)
+        };
+        let rn = ins.reg4(16);
+        undefined(!single && vd >= 16)?;
+        unpredictable(rn.is_pc())?;
+        Ok(Self {
+            single,
+            vd,
+            rn,
+            imm32: ins.imm8(0) << 2,
+            add: ins.bit(23),
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        let address = proc[self.rn].wrapping_add_or_sub(self.imm32, self.add);
+        let n = self.vd as usize;
+        if self.single {
+            proc.write_u32_aligned(address, proc.registers.s[n])?;
+        } else {
+            let value = proc.registers.d(n);
+            proc.write_u32_aligned(address, value as u32)?;
+            proc.write_u32_aligned(address.wrapping_add(4), (value >> 32) as u32)?;
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        "vstr".into()
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        let prefix = if self.single { 's' } else { 'd' };
+        format!(
+            "{prefix}{}, {}",
+            self.vd,
+            indexing_args(self.rn, self.imm32, !self.add, true, self.add, false)
+        )
+    }
+}
+
+/// VSTRB, VSTRH or VSTRW instruction (contiguous).
+///
+/// Vector Store Register. Stores consecutive elements to memory, optionally narrowing them.
+/// Inactive elements are not written.
+pub struct VstrVector {
+    /// Memory access.
+    access: ContiguousAccess,
+}
+
+impl Instruction for VstrVector {
+    fn patterns() -> &'static [Pattern] {
+        &[
+            Pattern {
+                encoding: T1,
+                versions: &[V81M],
+                expression: "1110110xxxx0xxxxxxx1111xxxxxxxxx",
+            },
+            Pattern {
+                encoding: T2,
+                versions: &[V81M],
+                expression: "111x110xxxx0xxxxxxx0111xxxxxxxxx",
+            },
+        ]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        let access = ContiguousAccess::decode(ins, encoding == T2)?;
+        unpredictable(state.in_it_block())?;
+        Ok(Self { access })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_mve_check(false)?;
+        let a = &self.access;
+        let (start, offset_addr) = a.addresses(&proc.registers);
+        proc.execute_beats(|proc, beat, mask| {
+            let word = proc.registers.s[a.qd as usize * 4 + beat];
+            for i in 0..elements_per_beat(a.esize) {
+                if element_active(mask, a.esize, i) {
+                    let value = element(word, a.esize, i);
+                    write_element(proc, a.element_address(start, beat, i), a.msize, value)?;
+                }
+            }
+            Ok(())
+        })?;
+        if a.wback {
+            proc.set(a.rn, offset_addr);
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        self.access.name(false)
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        self.access.args()
+    }
+}
+
+/// VSTRB, VSTRH or VSTRW instruction (scatter).
+///
+/// Vector Scatter Store. Stores elements to memory at addresses given by a vector of offsets or
+/// a vector of base addresses. Inactive elements are not written.
+pub struct VstrScatter {
+    /// Memory access.
+    access: GatherAccess,
+}
+
+impl Instruction for VstrScatter {
+    fn patterns() -> &'static [Pattern] {
+        &[
+            Pattern {
+                encoding: T1,
+                versions: &[V81M],
+                expression: "111011001x00xxxxxxx0111xxxxxxxxx",
+            },
+            Pattern {
+                encoding: T2,
+                versions: &[V81M],
+                expression: "11111101xxx0xxx0xxx11110xxxxxxxx",
+            },
+        ]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        let access = GatherAccess::decode(ins, encoding == T2)?;
+        unpredictable(state.in_it_block())?;
+        Ok(Self { access })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_mve_check(false)?;
+        let a = &self.access;
+        proc.execute_beats(|proc, beat, mask| {
+            let word = proc.registers.s[a.qd as usize * 4 + beat];
+            for i in 0..elements_per_beat(a.esize) {
+                if element_active(mask, a.esize, i) {
+                    let address = a.element_address(&proc.registers, beat, i);
+                    write_element(proc, address, a.msize, element(word, a.esize, i))?;
+                }
+            }
+            a.addressing.write_back(&mut proc.registers, beat);
+            Ok(())
+        })?;
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        self.access.name(false)
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        self.access.args()
+    }
+}
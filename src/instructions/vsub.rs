@@ -0,0 +1,149 @@
+//! Implements VSUB (Floating-point Subtract) and VSUB (Vector Subtract) instructions.
+
+use super::Encoding::{self, T1, T2, T3, T4};
+use super::{
+    extension_register, fp_data_type, undefined, unpredictable, DecodeHelper, ExtensionRegister,
+    Instruction, Pattern,
+};
+use crate::{
+    core::{
+        ArmVersion::{V7EM, V7M, V81M, V8M},
+        Effect, ItState, Processor, RunError,
+    },
+    decoder::DecodeError,
+    float::fp_sub,
+    helpers::BitAccess,
+    registers::CoreRegisters,
+    vector::VectorOperation,
+};
+
+/// VSUB instruction.
+///
+/// Floating-point Subtract.
+pub struct Vsub {
+    /// Destination register index.
+    vd: u8,
+    /// First operand register index.
+    vn: u8,
+    /// Second operand register index.
+    vm: u8,
+    /// True for double-precision operation.
+    double: bool,
+}
+
+impl Vsub {
+    fn compute<T: ExtensionRegister>(&self, regs: &mut CoreRegisters) {
+        let (op1, op2) = (T::read(regs, self.vn), T::read(regs, self.vm));
+        let result = fp_sub(op1, op2, &mut regs.fpscr);
+        T::write(regs, self.vd, result);
+    }
+}
+
+impl Instruction for Vsub {
+    fn patterns() -> &'static [Pattern] {
+        &[Pattern {
+            encoding: T1,
+            versions: &[V7M, V7EM, V8M],
+            expression: "111011100x11xxxxxxxx101xx1x0xxxx",
+        }]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, _state: ItState) -> Result<Self, DecodeError> {
+        debug_assert_eq!(encoding, T1);
+        let double = ins.bit(8);
+        let vd = ins.vreg(12, 22, double);
+        let vn = ins.vreg(16, 7, double);
+        let vm = ins.vreg(0, 5, double);
+        undefined(double && (vd >= 16 || vn >= 16 || vm >= 16))?;
+        Ok(Self { vd, vn, vm, double })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        proc.execute_fp_check()?;
+        if self.double {
+            self.compute::<u64>(&mut proc.registers);
+        } else {
+            self.compute::<u32>(&mut proc.registers);
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        format!("vsub.{}", fp_data_type(self.double))
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        format!(
+            "{}, {}, {}",
+            extension_register(self.double, self.vd),
+            extension_register(self.double, self.vn),
+            extension_register(self.double, self.vm)
+        )
+    }
+}
+
+/// VSUB instruction (vector).
+///
+/// Vector Subtract, on integer or floating-point elements. The second operand can be a vector or
+/// a general-purpose register.
+pub struct VsubVector {
+    /// Operands.
+    op: VectorOperation,
+}
+
+impl Instruction for VsubVector {
+    fn patterns() -> &'static [Pattern] {
+        &[
+            Pattern {
+                encoding: T1,
+                versions: &[V81M],
+                expression: "111111110xxxxxx0xxx01000x1x0xxx0",
+            },
+            Pattern {
+                encoding: T2,
+                versions: &[V81M],
+                expression: "111011110x1xxxx0xxx01101x1x0xxx0",
+            },
+            Pattern {
+                encoding: T3,
+                versions: &[V81M],
+                expression: "111011100xxxxxx1xxx111110100xxxx",
+            },
+            Pattern {
+                encoding: T4,
+                versions: &[V81M],
+                expression: "111x11100x11xxx0xxx111110100xxxx",
+            },
+        ]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        let (float, scalar) = match encoding {
+            T1 => (false, false),
+            T2 => (true, false),
+            T3 => (false, true),
+            T4 => (true, true),
+            _ => panic!(),
+        };
+        let op = VectorOperation::decode(ins, float, scalar)?;
+        unpredictable(state.in_it_block())?;
+        Ok(Self { op })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        let (float, esize) = (self.op.float, self.op.esize);
+        self.op.execute(proc, |a, b, fpscr| match (float, esize) {
+            (false, _) => a.wrapping_sub(b),
+            (true, 16) => fp_sub(a as u16, b as u16, fpscr) as u32,
+            (true, _) => fp_sub(a, b, fpscr),
+        })
+    }
+
+    fn name(&self) -> String {
+        self.op.name("vsub")
+    }
+
+    fn args(&self, _pc: u32) -> String {
+        self.op.args()
+    }
+}
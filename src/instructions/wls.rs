@@ -0,0 +1,80 @@
+//! Implements WLS (While Loop Start) and WLSTP (While Loop Start with Tail Predication)
+//! instructions.
+
+use super::Encoding::{self, T1, T2};
+use super::{other, unpredictable, DecodeHelper, Instruction, Pattern};
+use crate::{
+    core::{ArmVersion::V81M, Effect, ItState, Processor, RunError},
+    decoder::DecodeError,
+    registers::RegisterIndex,
+};
+
+/// WLS or WLSTP instruction.
+///
+/// While Loop Start, with optional Tail Predication.
+pub struct Wls {
+    /// Register holding the loop iteration count, or the number of vector elements to be processed
+    /// for tail predicated loops.
+    rn: RegisterIndex,
+    /// Offset to the first instruction after the loop.
+    imm32: u32,
+    /// Element size for tail predicated loops, as a power of two number of bytes (WLSTP).
+    size: Option<u8>,
+}
+
+impl Instruction for Wls {
+    fn patterns() -> &'static [Pattern] {
+        &[
+            Pattern {
+                encoding: T1,
+                versions: &[V81M],
+                expression: "111100000100xxxx1100xxxxxxxxxxx1",
+            },
+            Pattern {
+                encoding: T2,
+                versions: &[V81M],
+                expression: "1111000000xxxxxx1100xxxxxxxxxxx1",
+            },
+        ]
+    }
+
+    fn try_decode(encoding: Encoding, ins: u32, state: ItState) -> Result<Self, DecodeError> {
+        let rn = ins.reg4(16);
+        other(encoding == T2 && rn.is_pc())?; // LE, LETP
+        unpredictable(rn.is_sp_or_pc())?;
+        unpredictable(state.in_it_block())?;
+        Ok(Self {
+            rn,
+            imm32: (ins.imm1(11) << 1) | (((ins >> 1) & 0x3ff) << 2),
+            size: (encoding == T2).then_some(ins.imm2(20) as u8),
+        })
+    }
+
+    fn execute(&self, proc: &mut Processor) -> Result<Effect, RunError> {
+        if self.size.is_some() {
+            proc.execute_mve_check(false)?;
+        }
+        let count = proc[self.rn];
+        if count == 0 {
+            // Loop is skipped.
+            proc.set_pc(proc.pc().wrapping_add(self.imm32));
+            return Ok(Effect::Branch);
+        }
+        proc.set_lr(count);
+        if let Some(size) = self.size {
+            proc.registers.fpscr.set_ltpsize(size);
+        }
+        Ok(Effect::None)
+    }
+
+    fn name(&self) -> String {
+        match self.size {
+            Some(size) => format!("wlstp.{}", 8 << size),
+            None => "wls".into(),
+        }
+    }
+
+    fn args(&self, pc: u32) -> String {
+        format!("lr, {}, 0x{:x}", self.rn, pc + 4 + self.imm32)
+    }
+}
@@ -71,7 +71,7 @@ mod tests {
     #[test]
     fn test_yield() {
         let mut proc = Processor::new(Config::v7m());
-        let expected = proc.registers.clone();
+        let expected = proc.registers;
         Yield { encoding: DontCare }.execute(&mut proc).unwrap();
         assert_eq!(proc.registers, expected);
     }
@@ -0,0 +1,250 @@
+//! Instrumentation Trace Macrocell.
+//!
+//! The ITM is mapped at address `0xe0000000` on ArmV7-M and ArmV8-M processors. Software writes
+//! to its 32 stimulus ports to emit trace data, for instance with CMSIS `ITM_SendChar`. A write
+//! is emitted only if DEMCR.TRCENA, ITM_TCR.ITMENA and the port bit in ITM_TER are set, and it is
+//! ignored if made unprivileged to a port protected by ITM_TPR.
+//!
+//! Emitted data is delivered to the callback installed with
+//! [InstrumentationTraceMacrocell::set_callback], or otherwise appended to the buffer of the port.
+//! It can also be encoded as a stream of SWO instrumentation packets, which can be decoded by
+//! tools like `itmdump`. Timestamp, synchronization and hardware source packets are not
+//! generated.
+
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    debug::DebugRegisters,
+    helpers::BitAccess,
+    memory::{Env, MemoryAccessError, MemoryInterface, MemoryReadResult, MemoryWriteResult},
+};
+
+/// Number of stimulus ports.
+pub const PORT_COUNT: usize = 32;
+
+/// Address of ITM_TER.
+const TER: u32 = 0xe00;
+/// Address of ITM_TPR.
+const TPR: u32 = 0xe40;
+/// Address of ITM_TCR.
+const TCR: u32 = 0xe80;
+/// Address of ITM_LAR.
+const LAR: u32 = 0xfb0;
+/// Address of ITM_LSR.
+const LSR: u32 = 0xfb4;
+
+/// Callback receiving the port number and the little-endian bytes of each stimulus port write.
+type Callback = Box<dyn FnMut(u8, &[u8])>;
+
+/// Instrumentation Trace Macrocell registers and host-side sinks.
+pub struct InstrumentationTraceMacrocell {
+    /// Debug registers, holding DEMCR.TRCENA.
+    debug: Rc<RefCell<DebugRegisters>>,
+    /// Trace Enable Register.
+    ter: u32,
+    /// Trace Privilege Register.
+    tpr: u32,
+    /// Trace Control Register.
+    tcr: u32,
+    /// Data written to each stimulus port, when no callback is installed.
+    pub ports: Vec<Vec<u8>>,
+    /// When set, writes to the stimulus ports are also encoded as SWO instrumentation packets
+    /// appended to this buffer.
+    pub swo: Option<Vec<u8>>,
+    callback: Option<Callback>,
+}
+
+impl InstrumentationTraceMacrocell {
+    pub fn new(debug: Rc<RefCell<DebugRegisters>>) -> Self {
+        Self {
+            debug,
+            ter: 0,
+            tpr: 0,
+            tcr: 0,
+            ports: vec![Vec::new(); PORT_COUNT],
+            swo: None,
+            callback: None,
+        }
+    }
+
+    /// Installs a callback receiving the data written to the stimulus ports, instead of storing
+    /// it in [InstrumentationTraceMacrocell::ports].
+    pub fn set_callback<F: FnMut(u8, &[u8]) + 'static>(&mut self, callback: F) {
+        self.callback = Some(Box::new(callback))
+    }
+
+    /// Removes the callback installed with [InstrumentationTraceMacrocell::set_callback].
+    pub fn clear_callback(&mut self) {
+        self.callback = None
+    }
+
+    /// Returns `true` if writes to stimulus port `port` are emitted.
+    fn is_port_enabled(&self, port: usize) -> bool {
+        self.debug.borrow().trcena() && self.tcr.bit(0) && self.ter.bit(port)
+    }
+
+    /// Handles the write of `data` to the stimulus port at `address`.
+    fn write_stimulus(&mut self, address: u32, data: &[u8], env: &Env) -> MemoryWriteResult {
+        if !address.is_multiple_of(4) {
+            return Err(MemoryAccessError::InvalidAddress);
+        }
+        let port = (address / 4) as usize;
+        if !self.is_port_enabled(port) || (!env.privileged && self.tpr.bit(port / 8)) {
+            return Ok(());
+        }
+        if let Some(swo) = self.swo.as_mut() {
+            // Software source packet header: port number and payload size.
+            let size = match data.len() {
+                1 => 0b01,
+                2 => 0b10,
+                _ => 0b11,
+            };
+            swo.push((port as u8) << 3 | size);
+            swo.extend_from_slice(data);
+        }
+        match self.callback.as_mut() {
+            Some(callback) => callback(port as u8, data),
+            None => self.ports[port].extend_from_slice(data),
+        }
+        Ok(())
+    }
+}
+
+impl MemoryInterface for InstrumentationTraceMacrocell {
+    fn read_u8(&mut self, address: u32, env: &mut Env) -> MemoryReadResult<u8> {
+        if address < 4 * PORT_COUNT as u32 {
+            self.read_u32le(address, env).map(|value| value as u8)
+        } else {
+            Err(MemoryAccessError::InvalidSize)
+        }
+    }
+
+    fn write_u8(&mut self, address: u32, value: u8, env: &mut Env) -> MemoryWriteResult {
+        if address < 4 * PORT_COUNT as u32 {
+            self.write_stimulus(address, &[value], env)
+        } else {
+            Err(MemoryAccessError::InvalidSize)
+        }
+    }
+
+    fn read_u16le(&mut self, address: u32, env: &mut Env) -> MemoryReadResult<u16> {
+        if address < 4 * PORT_COUNT as u32 {
+            self.read_u32le(address, env).map(|value| value as u16)
+        } else {
+            Err(MemoryAccessError::InvalidSize)
+        }
+    }
+
+    fn write_u16le(&mut self, address: u32, value: u16, env: &mut Env) -> MemoryWriteResult {
+        if address < 4 * PORT_COUNT as u32 {
+            self.write_stimulus(address, &value.to_le_bytes(), env)
+        } else {
+            Err(MemoryAccessError::InvalidSize)
+        }
+    }
+
+    fn read_u32le(&mut self, address: u32, env: &mut Env) -> MemoryReadResult<u32> {
+        if address < 4 * PORT_COUNT as u32 {
+            if !address.is_multiple_of(4) {
+                return Err(MemoryAccessError::InvalidAddress);
+            }
+            // FIFOREADY: the stimulus port can always accept data.
+            return Ok(1);
+        }
+        if !env.privileged {
+            return Err(MemoryAccessError::PrivilegedOnly);
+        }
+        match address {
+            TER => Ok(self.ter),
+            TPR => Ok(self.tpr),
+            TCR => Ok(self.tcr),
+            // Write-only.
+            LAR => Ok(0),
+            // Software lock is not implemented.
+            LSR => Ok(0),
+            _ => Err(MemoryAccessError::InvalidAddress),
+        }
+    }
+
+    fn write_u32le(&mut self, address: u32, value: u32, env: &mut Env) -> MemoryWriteResult {
+        if address < 4 * PORT_COUNT as u32 {
+            return self.write_stimulus(address, &value.to_le_bytes(), env);
+        }
+        if !env.privileged {
+            return Err(MemoryAccessError::PrivilegedOnly);
+        }
+        match address {
+            TER => self.ter = value,
+            TPR => self.tpr = value & 0xf,
+            TCR => self.tcr = value & 0x007f0f1f,
+            LAR => {}
+            LSR => {}
+            _ => return Err(MemoryAccessError::InvalidAddress),
+        }
+        Ok(())
+    }
+
+    fn size(&self) -> u32 {
+        0x1000
+    }
+
+    fn scheduled_updates(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InstrumentationTraceMacrocell;
+    use crate::{
+        core::ArmVersion,
+        debug::{DebugControlBlock, DebugControlRegister, DebugRegisters},
+        memory::{Env, MemoryInterface, RegistersMemoryInterface},
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    #[test]
+    fn test_itm() {
+        let debug = Rc::new(RefCell::new(DebugRegisters::new(ArmVersion::V7M)));
+        let mut itm = InstrumentationTraceMacrocell::new(debug.clone());
+        let mut env = Env::new(0, true);
+        itm.swo = Some(Vec::new());
+
+        // Writes are ignored until TRCENA, ITMENA and the port are enabled.
+        itm.write_u8(0, 0x41, &mut env).unwrap();
+        itm.write_u32le(0xe80, 1, &mut env).unwrap();
+        itm.write_u32le(0xe00, 0x80000001, &mut env).unwrap();
+        itm.write_u8(0, 0x42, &mut env).unwrap();
+        assert!(itm.ports[0].is_empty());
+        DebugControlBlock(debug.clone())
+            .write32(DebugControlRegister::Demcr, 1 << 24, &mut env)
+            .unwrap();
+        itm.write_u8(0, 0x43, &mut env).unwrap();
+        itm.write_u16le(0x7c, 0x1234, &mut env).unwrap();
+        itm.write_u32le(0x04, 0x11223344, &mut env).unwrap();
+        assert_eq!(itm.ports[0], [0x43]);
+        assert_eq!(itm.ports[31], [0x34, 0x12]);
+        assert!(itm.ports[1].is_empty());
+        assert_eq!(
+            itm.swo.as_deref(),
+            Some([0x01, 0x43, 0xfa, 0x34, 0x12].as_slice())
+        );
+
+        // Ports 24 to 31 are reserved to privileged software.
+        itm.write_u32le(0xe40, 0x8, &mut env).unwrap();
+        let mut unprivileged = Env::new(0, false);
+        itm.write_u8(0x7c, 0x56, &mut unprivileged).unwrap();
+        itm.write_u8(0, 0x44, &mut unprivileged).unwrap();
+        assert_eq!(itm.ports[31], [0x34, 0x12]);
+        assert_eq!(itm.ports[0], [0x43, 0x44]);
+        assert!(itm.read_u32le(0xe80, &mut unprivileged).is_err());
+
+        // Callback.
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let received_clone = received.clone();
+        itm.set_callback(move |port, data| received_clone.borrow_mut().push((port, data.to_vec())));
+        itm.write_u32le(0x00, 0x0a0d2121, &mut env).unwrap();
+        assert_eq!(itm.ports[0], [0x43, 0x44]);
+        assert_eq!(*received.borrow(), [(0, vec![0x21, 0x21, 0x0d, 0x0a])]);
+    }
+}
@@ -12,12 +12,22 @@
 mod align;
 mod arith;
 pub mod core;
+pub mod debug;
 pub mod decoder;
+mod float;
+pub mod fpb;
+pub mod fpu;
+pub mod gdb;
 pub mod harness;
 pub mod helpers;
 pub mod instructions;
+pub mod itm;
 pub mod memory;
 pub mod mpu;
+mod pac;
 pub mod registers;
+pub mod security;
+pub mod semihosting;
 pub mod symbols;
 pub mod system_control;
+mod vector;
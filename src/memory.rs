@@ -56,6 +56,42 @@
 //! #     }
 //! # }
 //! ```
+//!
+//! # Peripheral updates
+//!
+//! Peripherals whose state evolves over time, such as timers, implement
+//! [MemoryInterface::update]. By default, the processor calls it at every cycle. This is simple
+//! but slow, and the processor cannot skip idle cycles while sleeping in WFI or WFE.
+//!
+//! A peripheral can instead schedule its updates by returning `true` from
+//! [MemoryInterface::scheduled_updates]. `update` is then called once the interface is mapped,
+//! and then only at the cycles requested with [Env::request_update] during a read, a write or a
+//! previous update. While sleeping, the processor skips the cycles up to the next requested
+//! update:
+//!
+//! ```
+//! use armagnac::{core::Irq, memory::{Env, MemoryInterface}};
+//!
+//! /// Requests an interrupt every 1000 cycles.
+//! struct Timer {}
+//!
+//! impl MemoryInterface for Timer {
+//!     fn size(&self) -> u32 {
+//!         4
+//!     }
+//!
+//!     fn update(&mut self, env: &mut Env) {
+//!         if env.cycles > 0 {
+//!             env.request_interrupt(Irq::External(0));
+//!         }
+//!         env.request_update(1000);
+//!     }
+//!
+//!     fn scheduled_updates(&self) -> bool {
+//!         true
+//!     }
+//! }
+//! ```
 
 use crate::core::Irq;
 use std::iter::repeat_n;
@@ -64,10 +100,12 @@ use std::iter::repeat_n;
 pub enum MemoryOpAction {
     /// Software reset request
     Reset,
-    /// Peripheral update request in n cycles
+    /// Requests a call to the peripheral `update` method in n cycles.
     Update(u32),
     /// Interrupt request.
     Irq(Irq),
+    /// Removes the pending state of an exception.
+    CancelIrq(Irq),
 }
 
 pub type MemoryReadResult<T> = Result<T, MemoryAccessError>;
@@ -99,6 +137,9 @@ pub struct Env {
     pub actions: Vec<MemoryOpAction>,
     /// True if access is privileged.
     pub privileged: bool,
+    /// True if access is made from Secure state.
+    /// Always true when the processor does not implement the Security Extension.
+    pub secure: bool,
 }
 
 impl Env {
@@ -107,12 +148,22 @@ impl Env {
             cycles,
             actions: Vec::new(),
             privileged,
+            secure: true,
         }
     }
 
     pub fn request_interrupt(&mut self, irq: Irq) {
         self.actions.push(MemoryOpAction::Irq(irq))
     }
+
+    pub fn cancel_interrupt(&mut self, irq: Irq) {
+        self.actions.push(MemoryOpAction::CancelIrq(irq))
+    }
+
+    /// Schedules a call to the peripheral `update` method in `cycles` cycles.
+    pub fn request_update(&mut self, cycles: u32) {
+        self.actions.push(MemoryOpAction::Update(cycles))
+    }
 }
 
 /// This trait must be implemented by any platform peripheral which is connected to the processor
@@ -144,7 +195,17 @@ pub trait MemoryInterface {
 
     fn size(&self) -> u32;
 
+    /// Called by the processor at every cycle, or only when requested with [Env::request_update]
+    /// if [MemoryInterface::scheduled_updates] returns `true`. See
+    /// [Peripheral updates](self#peripheral-updates).
     fn update(&mut self, _env: &mut Env) {}
+
+    /// Returns `true` if [MemoryInterface::update] must only be called once the interface is
+    /// mapped and then at the cycles requested with [Env::request_update]. This lets the processor
+    /// skip idle cycles while sleeping.
+    fn scheduled_updates(&self) -> bool {
+        false
+    }
 }
 
 /// Similair to [MemoryInterface] for peripherals that use an enumeration to identify registers.
@@ -158,6 +219,9 @@ pub trait RegistersMemoryInterface {
     fn write32(&mut self, reg: Self::Register, value: u32, env: &mut Env) -> MemoryWriteResult;
     fn size(&self) -> u32;
     fn update(&mut self, _env: &mut Env) {}
+    fn scheduled_updates(&self) -> bool {
+        false
+    }
 }
 
 impl<T: RegistersMemoryInterface> MemoryInterface for T {
@@ -208,6 +272,10 @@ impl<T: RegistersMemoryInterface> MemoryInterface for T {
     fn update(&mut self, env: &mut Env) {
         RegistersMemoryInterface::update(self, env)
     }
+
+    fn scheduled_updates(&self) -> bool {
+        RegistersMemoryInterface::scheduled_updates(self)
+    }
 }
 
 /// RAM memory.
@@ -261,6 +329,10 @@ impl MemoryInterface for RamMemory {
         self.data.len() as u32
     }
 
+    fn scheduled_updates(&self) -> bool {
+        true
+    }
+
     fn read_u8(&mut self, address: u32, _env: &mut Env) -> MemoryReadResult<u8> {
         if let Some(val) = self.data.get(address as usize) {
             Ok(*val)
@@ -1,10 +1,64 @@
 //! Memory Protection Unit peripherals.
 
-use crate::memory::{MemoryAccessError, MemoryWriteResult};
+use crate::{
+    helpers::BitAccess,
+    memory::{MemoryAccessError, MemoryWriteResult},
+};
 
 pub mod v7m;
 pub mod v8m;
 
+/// Memory access to be validated by a Memory Protection Unit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Access {
+    /// Accessed address.
+    pub address: u32,
+    /// True for privileged accesses.
+    pub privileged: bool,
+    /// True for write accesses, false for read accesses.
+    pub write: bool,
+    /// True if the access is an instruction fetch.
+    pub instruction_fetch: bool,
+    /// True if current execution priority is negative (HardFault, NMI or FAULTMASK set).
+    pub negative_priority: bool,
+}
+
+/// Trait implemented by Memory Protection Units so the processor can verify memory accesses.
+pub trait MemoryProtectionUnit {
+    /// Returns `true` if the `access` is permitted by the current MPU configuration, or `false` if
+    /// it must raise a MemManage fault.
+    ///
+    /// Corresponds to the permission checks of `ValidateAddress()` in the Arm Architecture
+    /// Reference Manual.
+    fn check(&self, access: &Access) -> bool;
+
+    /// Returns the number of the enabled region matching `address`, if the MPU is enabled and
+    /// exactly one region matches. This is reported by the TT instruction.
+    fn region(&self, _address: u32) -> Option<u8> {
+        None
+    }
+}
+
+/// Returns `true` if `address` belongs to the Private Peripheral Bus. Accesses to this region
+/// always use the default memory map.
+pub fn is_ppb(address: u32) -> bool {
+    (0xe0000000..0xe0100000).contains(&address)
+}
+
+/// Returns `true` if instruction fetches at `address` are not permitted by the default memory
+/// map (Peripheral, Device and System regions).
+pub fn default_execute_never(address: u32) -> bool {
+    matches!(address >> 28, 0x4 | 0x5 | 0xa..=0xf)
+}
+
+/// Checks an access against the default memory map.
+///
+/// Corresponds to `DefaultPermissions()` in the Arm Architecture Reference Manual: all data
+/// accesses are permitted, and only instruction fetches in execute-never regions fail.
+pub fn check_default(access: &Access) -> bool {
+    !(access.instruction_fetch && default_execute_never(access.address))
+}
+
 /// MPU Control Register.
 ///
 /// For ARMv7M and ARMv8M.
@@ -19,4 +73,54 @@ impl Ctrl {
         self.0 = value;
         Ok(())
     }
+
+    /// Returns ENABLE bit value.
+    fn enable(&self) -> bool {
+        self.0.bit(0)
+    }
+
+    /// Returns HFNMIENA bit value.
+    fn hfnmiena(&self) -> bool {
+        self.0.bit(1)
+    }
+
+    /// Returns PRIVDEFENA bit value.
+    fn privdefena(&self) -> bool {
+        self.0.bit(2)
+    }
+
+    /// Returns `true` if the MPU region configuration applies to the given `access`, or `false`
+    /// if the default memory map is to be used instead.
+    fn applies(&self, access: &Access) -> bool {
+        // HardFault, NMI and FAULTMASK handlers ignore the MPU unless HFNMIENA is set.
+        self.enable() && !is_ppb(access.address) && (self.hfnmiena() || !access.negative_priority)
+    }
+
+    /// Checks an access which does not match any enabled region.
+    ///
+    /// Privileged accesses fall back to the default memory map when PRIVDEFENA is set, all other
+    /// accesses fault.
+    fn check_background(&self, access: &Access) -> bool {
+        self.privdefena() && access.privileged && check_default(access)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{default_execute_never, is_ppb};
+
+    #[test]
+    fn test_default_memory_map() {
+        assert!(!default_execute_never(0x00000000));
+        assert!(!default_execute_never(0x20001000));
+        assert!(default_execute_never(0x40000000));
+        assert!(default_execute_never(0x5fffffff));
+        assert!(!default_execute_never(0x60000000));
+        assert!(!default_execute_never(0x9fffffff));
+        assert!(default_execute_never(0xa0000000));
+        assert!(default_execute_never(0xe000ed00));
+        assert!(is_ppb(0xe000ed00));
+        assert!(!is_ppb(0xe0100000));
+        assert!(!is_ppb(0xdfffffff));
+    }
 }
@@ -1,7 +1,10 @@
-use crate::memory::{MemoryAccessError, MemoryWriteResult, RegistersMemoryInterface};
+use crate::{
+    helpers::BitAccess,
+    memory::{MemoryAccessError, MemoryWriteResult, RegistersMemoryInterface},
+};
 use num_enum::TryFromPrimitive;
 
-use super::Ctrl;
+use super::{check_default, Access, Ctrl, MemoryProtectionUnit};
 
 #[derive(TryFromPrimitive)]
 #[repr(u32)]
@@ -23,20 +26,60 @@ pub enum Register {
 pub struct MpuV7M {
     /// MPU_CTRL register.
     ctrl: Ctrl,
-    /// MPU_RBAR register.
-    rbar: Rbar,
-    /// MPU_RASR register.
-    rasr: Rasr,
+    /// MPU_RNR register.
+    rnr: u8,
+    /// MPU_RBAR register of each region.
+    rbar: Vec<Rbar>,
+    /// MPU_RASR register of each region.
+    rasr: Vec<Rasr>,
 }
 
 impl MpuV7M {
+    /// Number of regions supported by default, as in Cortex-M3 and Cortex-M4.
+    pub const DEFAULT_REGION_COUNT: usize = 8;
+
     pub fn new() -> Self {
+        Self::with_regions(Self::DEFAULT_REGION_COUNT)
+    }
+
+    /// Creates an MPU supporting `region_count` regions.
+    ///
+    /// `region_count` must not be greater than 255.
+    pub fn with_regions(region_count: usize) -> Self {
+        assert!(region_count <= 255);
         Self {
             ctrl: Default::default(),
-            rbar: Default::default(),
-            rasr: Default::default(),
+            rnr: 0,
+            rbar: vec![Default::default(); region_count],
+            rasr: vec![Default::default(); region_count],
+        }
+    }
+
+    /// Returns the currently selected region index, or [MemoryAccessError::InvalidAddress] if
+    /// MPU_RNR selects an unimplemented region.
+    fn region(&self) -> Result<usize, MemoryAccessError> {
+        let region = self.rnr as usize;
+        if region < self.rbar.len() {
+            Ok(region)
+        } else {
+            Err(MemoryAccessError::InvalidAddress)
         }
     }
+
+    /// Writes MPU_RBAR register, or one of its aliases.
+    fn write_rbar(&mut self, value: u32) -> MemoryWriteResult {
+        if value.bit(4) {
+            // VALID bit is set: REGION field selects the region to be updated.
+            let region = value & 0xf;
+            if region as usize >= self.rbar.len() {
+                return Err(MemoryAccessError::InvalidValue);
+            }
+            self.rnr = region as u8;
+        }
+        let region = self.region()?;
+        self.rbar[region].0 = value & 0xffffffe0;
+        Ok(())
+    }
 }
 
 impl Default for MpuV7M {
@@ -45,6 +88,29 @@ impl Default for MpuV7M {
     }
 }
 
+impl MemoryProtectionUnit for MpuV7M {
+    fn check(&self, access: &Access) -> bool {
+        if !self.ctrl.applies(access) {
+            return check_default(access);
+        }
+        // When regions overlap, the highest numbered region takes priority.
+        let Some(rasr) = self
+            .rbar
+            .iter()
+            .zip(self.rasr.iter())
+            .rev()
+            .find(|(rbar, rasr)| rasr.contains(rbar, access.address))
+            .map(|(_, rasr)| rasr)
+        else {
+            return self.ctrl.check_background(access);
+        };
+        if access.instruction_fetch && rasr.xn() {
+            return false;
+        }
+        check_ap(rasr.ap(), access.privileged, access.write)
+    }
+}
+
 impl RegistersMemoryInterface for MpuV7M {
     type Register = Register;
 
@@ -54,17 +120,16 @@ impl RegistersMemoryInterface for MpuV7M {
         _env: &mut crate::memory::Env,
     ) -> crate::memory::MemoryReadResult<u32> {
         Ok(match reg {
-            Register::Type => todo!(),
+            // Unified MPU, DREGION indicates the number of regions.
+            Register::Type => (self.rbar.len() as u32) << 8,
             Register::Ctrl => self.ctrl.0,
-            Register::Rnr => todo!(),
-            Register::Rbar => self.rbar.0,
-            Register::Rasr => self.rasr.0,
-            Register::RbarA1 => todo!(),
-            Register::RasrA1 => todo!(),
-            Register::RbarA2 => todo!(),
-            Register::RasrA2 => todo!(),
-            Register::RbarA3 => todo!(),
-            Register::RasrA3 => todo!(),
+            Register::Rnr => self.rnr as u32,
+            Register::Rbar | Register::RbarA1 | Register::RbarA2 | Register::RbarA3 => {
+                self.rbar[self.region()?].0 | (self.rnr as u32 & 0xf)
+            }
+            Register::Rasr | Register::RasrA1 | Register::RasrA2 | Register::RasrA3 => {
+                self.rasr[self.region()?].0
+            }
         })
     }
 
@@ -75,17 +140,22 @@ impl RegistersMemoryInterface for MpuV7M {
         _env: &mut crate::memory::Env,
     ) -> crate::memory::MemoryWriteResult {
         match reg {
-            Register::Type => todo!(),
+            // MPU_TYPE is read-only.
+            Register::Type => {}
             Register::Ctrl => self.ctrl.write(value)?,
-            Register::Rnr => todo!(),
-            Register::Rbar => self.rbar.0 = value,
-            Register::Rasr => self.rasr.write(value)?,
-            Register::RbarA1 => todo!(),
-            Register::RasrA1 => todo!(),
-            Register::RbarA2 => todo!(),
-            Register::RasrA2 => todo!(),
-            Register::RbarA3 => todo!(),
-            Register::RasrA3 => todo!(),
+            Register::Rnr => {
+                if value & 0xffffff00 != 0 {
+                    return Err(MemoryAccessError::InvalidValue);
+                }
+                self.rnr = value as u8;
+            }
+            Register::Rbar | Register::RbarA1 | Register::RbarA2 | Register::RbarA3 => {
+                self.write_rbar(value)?
+            }
+            Register::Rasr | Register::RasrA1 | Register::RasrA2 | Register::RasrA3 => {
+                let region = self.region()?;
+                self.rasr[region].write(value)?
+            }
         }
         Ok(())
     }
@@ -93,14 +163,35 @@ impl RegistersMemoryInterface for MpuV7M {
     fn size(&self) -> u32 {
         4 * 11
     }
+
+    fn scheduled_updates(&self) -> bool {
+        true
+    }
+}
+
+/// Checks an access against the AP field of a region.
+///
+/// Reserved AP encoding is considered as granting no access.
+fn check_ap(ap: u32, privileged: bool, write: bool) -> bool {
+    matches!(
+        (ap, privileged, write),
+        (0b001, true, _)
+            | (0b010, true, _)
+            | (0b010, false, false)
+            | (0b011, _, _)
+            | (0b101, true, false)
+            | (0b110 | 0b111, _, false)
+    )
 }
 
 /// MPU_RBAR register for ARMv7M.
-#[derive(Default)]
+///
+/// Only the ADDR field is stored, VALID and REGION fields are handled during the register write.
+#[derive(Default, Clone, Copy)]
 struct Rbar(u32);
 
 /// MPU_RASR register for ARMv7M.
-#[derive(Default)]
+#[derive(Default, Clone, Copy)]
 struct Rasr(u32);
 
 impl Rasr {
@@ -111,4 +202,110 @@ impl Rasr {
         self.0 = value;
         Ok(())
     }
+
+    /// Returns ENABLE bit value.
+    fn enable(&self) -> bool {
+        self.0.bit(0)
+    }
+
+    /// Returns the region size in bytes, as a power of two exponent.
+    ///
+    /// SIZE field values lower than 4 are unpredictable, we consider the smallest region size of
+    /// 32 bytes in that case.
+    fn size_log2(&self) -> u32 {
+        ((self.0 >> 1) & 0x1f).max(4) + 1
+    }
+
+    /// Returns SRD (Subregion Disable) field value.
+    fn srd(&self) -> u8 {
+        (self.0 >> 8) as u8
+    }
+
+    /// Returns AP (Access Permission) field value.
+    fn ap(&self) -> u32 {
+        (self.0 >> 24) & 7
+    }
+
+    /// Returns XN (Execute Never) bit value.
+    fn xn(&self) -> bool {
+        self.0.bit(28)
+    }
+
+    /// Returns `true` if the region is enabled, and `address` is in the region and not in a
+    /// disabled subregion.
+    fn contains(&self, rbar: &Rbar, address: u32) -> bool {
+        if !self.enable() {
+            return false;
+        }
+        let size_log2 = self.size_log2();
+        let mask = if size_log2 >= 32 {
+            0
+        } else {
+            u32::MAX << size_log2
+        };
+        if address & mask != rbar.0 & mask {
+            return false;
+        }
+        // Subregions are only supported for regions of 256 bytes or more.
+        if size_log2 >= 8 {
+            let subregion = ((address & !mask) >> (size_log2 - 3)) as usize;
+            if self.srd().bit(subregion) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MpuV7M, Register};
+    use crate::{
+        memory::{Env, RegistersMemoryInterface},
+        mpu::{Access, MemoryProtectionUnit},
+    };
+
+    fn access(address: u32, privileged: bool, write: bool, instruction_fetch: bool) -> Access {
+        Access {
+            address,
+            privileged,
+            write,
+            instruction_fetch,
+            negative_priority: false,
+        }
+    }
+
+    #[test]
+    fn test_mpu_v7m_regions() {
+        let mut mpu = MpuV7M::new();
+        let mut env = Env::new(0, true);
+        // Region 0: 0x20000000, 64 KiB, privileged RW, unprivileged RO, XN.
+        mpu.write32(Register::Rbar, 0x20000010, &mut env).unwrap();
+        mpu.write32(Register::Rasr, 0x1200001f, &mut env).unwrap();
+        // Region 1: 0x20000000, 1 KiB, full access, subregion 7 disabled.
+        mpu.write32(Register::Rbar, 0x20000011, &mut env).unwrap();
+        mpu.write32(Register::Rasr, 0x03008013, &mut env).unwrap();
+        assert_eq!(mpu.read32(Register::Rnr, &mut env), Ok(1));
+        assert_eq!(mpu.read32(Register::Rbar, &mut env), Ok(0x20000001));
+
+        // MPU is disabled: only default memory map applies.
+        assert!(mpu.check(&access(0x20008000, false, true, false)));
+        assert!(!mpu.check(&access(0x40000000, true, false, true)));
+
+        mpu.write32(Register::Ctrl, 1, &mut env).unwrap();
+        // Region 1 takes priority over region 0.
+        assert!(mpu.check(&access(0x20000000, false, true, false)));
+        // Disabled subregion of region 1 falls into region 0.
+        assert!(!mpu.check(&access(0x20000380, false, true, false)));
+        assert!(mpu.check(&access(0x20000380, false, false, false)));
+        assert!(mpu.check(&access(0x20000380, true, true, false)));
+        assert!(!mpu.check(&access(0x20000380, true, false, true)));
+        // Background region.
+        assert!(!mpu.check(&access(0x00001000, true, false, true)));
+        mpu.write32(Register::Ctrl, 5, &mut env).unwrap();
+        assert!(mpu.check(&access(0x00001000, true, false, true)));
+        assert!(!mpu.check(&access(0x00001000, false, false, true)));
+        // System control space always uses the default memory map.
+        assert!(mpu.check(&access(0xe000ed00, false, true, false)));
+    }
 }
@@ -1,6 +1,9 @@
-use super::Ctrl;
-use crate::memory::{
-    Env, MemoryAccessError, MemoryReadResult, MemoryWriteResult, RegistersMemoryInterface,
+use super::{check_default, Access, Ctrl, MemoryProtectionUnit};
+use crate::{
+    helpers::BitAccess,
+    memory::{
+        Env, MemoryAccessError, MemoryReadResult, MemoryWriteResult, RegistersMemoryInterface,
+    },
 };
 use num_enum::TryFromPrimitive;
 
@@ -37,17 +40,55 @@ impl RbarRegister {
         self.0 = value;
         Ok(())
     }
+
+    /// Returns region base address.
+    fn base(&self) -> u32 {
+        self.0 & 0xffffffe0
+    }
+
+    /// Returns AP (Access Permissions) field value.
+    fn ap(&self) -> u32 {
+        (self.0 >> 1) & 3
+    }
+
+    /// Returns XN (Execute Never) bit value.
+    fn xn(&self) -> bool {
+        self.0.bit(0)
+    }
 }
 
 /// MPU_RLAR register.
 #[derive(Default, Clone, Copy)]
 struct RlarRegister(u32);
 
+impl RlarRegister {
+    /// Returns EN bit value.
+    fn enable(&self) -> bool {
+        self.0.bit(0)
+    }
+
+    /// Returns the region limit address (inclusive).
+    fn limit(&self) -> u32 {
+        self.0 | 0x1f
+    }
+
+    /// Returns AttrIndx field value, which selects memory attributes in MPU_MAIR0 or MPU_MAIR1.
+    fn attr_index(&self) -> u32 {
+        (self.0 >> 1) & 7
+    }
+}
+
 /// MPU_MAIR0 or MPU_MAIR1 register.
 #[derive(Default)]
 struct MairRegister(u32);
 
-impl MairRegister {}
+impl MairRegister {
+    /// Returns the memory attributes at index `n`, in [0, 3].
+    fn attr(&self, n: u32) -> u8 {
+        debug_assert!(n < 4);
+        (self.0 >> (n * 8)) as u8
+    }
+}
 
 #[derive(TryFromPrimitive)]
 #[repr(u32)]
@@ -57,12 +98,29 @@ pub enum MemoryProtectionUnitRegisterV8M {
     Rnr = 0x08,
     Rbar = 0x0c,
     Rlar = 0x10,
-    RbarAn = 0x14,
-    RlarAn = 0x18,
+    RbarA1 = 0x14,
+    RlarA1 = 0x18,
+    RbarA2 = 0x1c,
+    RlarA2 = 0x20,
+    RbarA3 = 0x24,
+    RlarA3 = 0x28,
     Mair0 = 0x30,
     Mair1 = 0x34,
 }
 
+impl MemoryProtectionUnitRegisterV8M {
+    /// For MPU_RBAR and MPU_RLAR registers and their aliases, returns the region offset relative
+    /// to the region selected by MPU_RNR.
+    fn alias(&self) -> u8 {
+        match self {
+            Self::RbarA1 | Self::RlarA1 => 1,
+            Self::RbarA2 | Self::RlarA2 => 2,
+            Self::RbarA3 | Self::RlarA3 => 3,
+            _ => 0,
+        }
+    }
+}
+
 /// Memory Protection Unit form Arm-v8-M.
 pub struct MemoryProtectionUnitV8M {
     /// MPU_CTRL register.
@@ -76,94 +134,147 @@ pub struct MemoryProtectionUnitV8M {
     /// MPU_MAIR0 register.
     mair0: MairRegister,
     /// MPU_MAIR1 register.
-    _mair1: MairRegister,
+    mair1: MairRegister,
 }
 
 impl MemoryProtectionUnitV8M {
     pub fn new(region_count: usize) -> Self {
+        assert!(region_count <= 255);
         Self {
             ctrl: Default::default(),
             rnr: Default::default(),
             rbar: vec![Default::default(); region_count],
             rlar: vec![Default::default(); region_count],
             mair0: Default::default(),
-            _mair1: Default::default(),
+            mair1: Default::default(),
+        }
+    }
+
+    /// Returns the index of the region accessed by `reg`, considering MPU_RNR value and register
+    /// aliases.
+    fn region(&self, reg: &MemoryProtectionUnitRegisterV8M) -> Result<usize, MemoryAccessError> {
+        let region = if reg.alias() == 0 {
+            self.rnr.region() as usize
+        } else {
+            (self.rnr.region() & 0xfc) as usize + reg.alias() as usize
+        };
+        if region < self.rbar.len() {
+            Ok(region)
+        } else {
+            Err(MemoryAccessError::InvalidAddress)
+        }
+    }
+
+    /// Returns the memory attributes selected by the given AttrIndx value.
+    fn attributes(&self, index: u32) -> u8 {
+        if index < 4 {
+            self.mair0.attr(index)
+        } else {
+            self.mair1.attr(index - 4)
         }
     }
+
+    /// Returns the enabled regions containing `address`, with their index.
+    fn matching_regions(
+        &self,
+        address: u32,
+    ) -> impl Iterator<Item = (usize, (&RbarRegister, &RlarRegister))> {
+        self.rbar
+            .iter()
+            .zip(self.rlar.iter())
+            .enumerate()
+            .filter(move |(_, (rbar, rlar))| {
+                rlar.enable() && (rbar.base()..=rlar.limit()).contains(&address)
+            })
+    }
+}
+
+impl MemoryProtectionUnit for MemoryProtectionUnitV8M {
+    fn check(&self, access: &Access) -> bool {
+        if !self.ctrl.applies(access) {
+            return check_default(access);
+        }
+        let mut regions = self.matching_regions(access.address);
+        let Some((_, (rbar, rlar))) = regions.next() else {
+            return self.ctrl.check_background(access);
+        };
+        if regions.next().is_some() {
+            // Unlike ARMv7-M, an address matching multiple regions always faults.
+            return false;
+        }
+        if access.instruction_fetch {
+            // Device memory is always execute never.
+            let device = self.attributes(rlar.attr_index()) & 0xf0 == 0;
+            if rbar.xn() || device {
+                return false;
+            }
+        }
+        matches!(
+            (rbar.ap(), access.privileged, access.write),
+            (0b00, true, _) | (0b01, _, _) | (0b10, true, false) | (0b11, _, false)
+        )
+    }
+
+    fn region(&self, address: u32) -> Option<u8> {
+        if !self.ctrl.enable() {
+            return None;
+        }
+        let mut regions = self.matching_regions(address);
+        let (region, _) = regions.next()?;
+        regions.next().is_none().then_some(region as u8)
+    }
 }
 
 impl RegistersMemoryInterface for MemoryProtectionUnitV8M {
     type Register = MemoryProtectionUnitRegisterV8M;
 
     fn read32(&mut self, reg: Self::Register, env: &mut Env) -> MemoryReadResult<u32> {
+        if !env.privileged {
+            return Err(MemoryAccessError::PrivilegedOnly);
+        }
         Ok(match reg {
-            MemoryProtectionUnitRegisterV8M::Type => todo!(),
-            MemoryProtectionUnitRegisterV8M::Ctrl => {
-                if !env.privileged {
-                    return Err(MemoryAccessError::PrivilegedOnly);
-                }
-                self.ctrl.0
-            }
-            MemoryProtectionUnitRegisterV8M::Rnr => todo!(),
-            MemoryProtectionUnitRegisterV8M::Rbar => todo!(),
-            MemoryProtectionUnitRegisterV8M::Rlar => {
-                if !env.privileged {
-                    return Err(MemoryAccessError::PrivilegedOnly);
-                }
-                self.rlar
-                    .get(self.rnr.region() as usize)
-                    .ok_or(MemoryAccessError::InvalidAddress)?
-                    .0
-            }
-            MemoryProtectionUnitRegisterV8M::RbarAn => todo!(),
-            MemoryProtectionUnitRegisterV8M::RlarAn => todo!(),
-            MemoryProtectionUnitRegisterV8M::Mair0 => todo!(),
-            MemoryProtectionUnitRegisterV8M::Mair1 => todo!(),
+            // Unified MPU, DREGION indicates the number of regions.
+            MemoryProtectionUnitRegisterV8M::Type => (self.rbar.len() as u32) << 8,
+            MemoryProtectionUnitRegisterV8M::Ctrl => self.ctrl.0,
+            MemoryProtectionUnitRegisterV8M::Rnr => self.rnr.0,
+            MemoryProtectionUnitRegisterV8M::Rbar
+            | MemoryProtectionUnitRegisterV8M::RbarA1
+            | MemoryProtectionUnitRegisterV8M::RbarA2
+            | MemoryProtectionUnitRegisterV8M::RbarA3 => self.rbar[self.region(&reg)?].0,
+            MemoryProtectionUnitRegisterV8M::Rlar
+            | MemoryProtectionUnitRegisterV8M::RlarA1
+            | MemoryProtectionUnitRegisterV8M::RlarA2
+            | MemoryProtectionUnitRegisterV8M::RlarA3 => self.rlar[self.region(&reg)?].0,
+            MemoryProtectionUnitRegisterV8M::Mair0 => self.mair0.0,
+            MemoryProtectionUnitRegisterV8M::Mair1 => self.mair1.0,
         })
     }
 
     fn write32(&mut self, reg: Self::Register, value: u32, env: &mut Env) -> MemoryWriteResult {
+        if !env.privileged {
+            return Err(MemoryAccessError::PrivilegedOnly);
+        }
         match reg {
-            MemoryProtectionUnitRegisterV8M::Type => todo!(),
-            MemoryProtectionUnitRegisterV8M::Ctrl => {
-                if !env.privileged {
-                    return Err(MemoryAccessError::PrivilegedOnly);
-                }
-                self.ctrl.write(value)?
-            }
-            MemoryProtectionUnitRegisterV8M::Rnr => {
-                if !env.privileged {
-                    return Err(MemoryAccessError::PrivilegedOnly);
-                }
-                self.rnr.write(value)?
+            // MPU_TYPE is read-only.
+            MemoryProtectionUnitRegisterV8M::Type => {}
+            MemoryProtectionUnitRegisterV8M::Ctrl => self.ctrl.write(value)?,
+            MemoryProtectionUnitRegisterV8M::Rnr => self.rnr.write(value)?,
+            MemoryProtectionUnitRegisterV8M::Rbar
+            | MemoryProtectionUnitRegisterV8M::RbarA1
+            | MemoryProtectionUnitRegisterV8M::RbarA2
+            | MemoryProtectionUnitRegisterV8M::RbarA3 => {
+                let region = self.region(&reg)?;
+                self.rbar[region].write(value)?;
             }
-            MemoryProtectionUnitRegisterV8M::Rbar => {
-                if !env.privileged {
-                    return Err(MemoryAccessError::PrivilegedOnly);
-                }
-                self.rbar
-                    .get_mut(self.rnr.region() as usize)
-                    .ok_or(MemoryAccessError::InvalidAddress)?
-                    .write(value)?;
+            MemoryProtectionUnitRegisterV8M::Rlar
+            | MemoryProtectionUnitRegisterV8M::RlarA1
+            | MemoryProtectionUnitRegisterV8M::RlarA2
+            | MemoryProtectionUnitRegisterV8M::RlarA3 => {
+                let region = self.region(&reg)?;
+                self.rlar[region].0 = value;
             }
-            MemoryProtectionUnitRegisterV8M::Rlar => {
-                if !env.privileged {
-                    return Err(MemoryAccessError::PrivilegedOnly);
-                }
-                self.rlar
-                    .get_mut(self.rnr.region() as usize)
-                    .ok_or(MemoryAccessError::InvalidAddress)?
-                    .0 = value;
-            }
-            MemoryProtectionUnitRegisterV8M::RbarAn => todo!(),
-            MemoryProtectionUnitRegisterV8M::RlarAn => todo!(),
-            MemoryProtectionUnitRegisterV8M::Mair0 => {
-                if !env.privileged {
-                    return Err(MemoryAccessError::PrivilegedOnly);
-                }
-                self.mair0.0 = value
-            }
-            MemoryProtectionUnitRegisterV8M::Mair1 => todo!(),
+            MemoryProtectionUnitRegisterV8M::Mair0 => self.mair0.0 = value,
+            MemoryProtectionUnitRegisterV8M::Mair1 => self.mair1.0 = value,
         }
         Ok(())
     }
@@ -171,4 +282,57 @@ impl RegistersMemoryInterface for MemoryProtectionUnitV8M {
     fn size(&self) -> u32 {
         14 * 4
     }
+
+    fn scheduled_updates(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MemoryProtectionUnitRegisterV8M as Register, MemoryProtectionUnitV8M};
+    use crate::{
+        memory::{Env, RegistersMemoryInterface},
+        mpu::{Access, MemoryProtectionUnit},
+    };
+
+    fn access(address: u32, privileged: bool, write: bool, instruction_fetch: bool) -> Access {
+        Access {
+            address,
+            privileged,
+            write,
+            instruction_fetch,
+            negative_priority: false,
+        }
+    }
+
+    #[test]
+    fn test_mpu_v8m_regions() {
+        let mut mpu = MemoryProtectionUnitV8M::new(8);
+        let mut env = Env::new(0, true);
+        // Attribute 0: normal memory, attribute 1: device memory.
+        mpu.write32(Register::Mair0, 0x00ff, &mut env).unwrap();
+        // Region 0: code, read-only by any privilege level.
+        mpu.write32(Register::Rbar, 0x00000006, &mut env).unwrap();
+        mpu.write32(Register::Rlar, 0x0000ffe1, &mut env).unwrap();
+        // Region 1: peripherals, privileged RW, device memory.
+        mpu.write32(Register::RbarA1, 0x40000000, &mut env).unwrap();
+        mpu.write32(Register::RlarA1, 0x4000ffe3, &mut env).unwrap();
+        // Region 2 overlaps region 0.
+        mpu.write32(Register::RbarA2, 0x00001002, &mut env).unwrap();
+        mpu.write32(Register::RlarA2, 0x000010e1, &mut env).unwrap();
+        mpu.write32(Register::Ctrl, 1, &mut env).unwrap();
+
+        assert!(mpu.check(&access(0x100, false, false, true)));
+        assert!(!mpu.check(&access(0x100, true, true, false)));
+        assert!(mpu.check(&access(0x40000100, true, true, false)));
+        assert!(!mpu.check(&access(0x40000100, false, false, false)));
+        assert!(!mpu.check(&access(0x40000100, true, false, true)));
+        assert!(!mpu.check(&access(0x1000, true, false, false)));
+        assert!(!mpu.check(&access(0x20000000, true, false, false)));
+
+        // Registers are privileged only.
+        let mut env = Env::new(0, false);
+        assert!(mpu.read32(Register::Ctrl, &mut env).is_err());
+    }
 }
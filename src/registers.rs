@@ -37,11 +37,30 @@ pub enum RegisterIndex {
     Iepsr,
     Msp,
     Psp,
+    Msplim,
+    Psplim,
     Primask,
     Basepri,
     BasepriMax,
     FaultMask,
     Control,
+    MspNs,
+    PspNs,
+    MsplimNs,
+    PsplimNs,
+    PrimaskNs,
+    BasepriNs,
+    FaultMaskNs,
+    ControlNs,
+    SpNs,
+    /// One of the four words of the privileged pointer authentication key.
+    PacKeyP(u8),
+    /// One of the four words of the unprivileged pointer authentication key.
+    PacKeyU(u8),
+    /// One of the four words of the Non-secure privileged pointer authentication key.
+    PacKeyPNs(u8),
+    /// One of the four words of the Non-secure unprivileged pointer authentication key.
+    PacKeyUNs(u8),
 }
 
 impl RegisterIndex {
@@ -114,11 +133,26 @@ impl RegisterIndex {
             7 => Self::Iepsr,
             8 => Self::Msp,
             9 => Self::Psp,
+            10 => Self::Msplim,
+            11 => Self::Psplim,
             16 => Self::Primask,
             17 => Self::Basepri,
             18 => Self::BasepriMax,
             19 => Self::FaultMask,
             20 => Self::Control,
+            0x20..=0x23 => Self::PacKeyP(index as u8 & 3),
+            0x24..=0x27 => Self::PacKeyU(index as u8 & 3),
+            0x88 => Self::MspNs,
+            0x89 => Self::PspNs,
+            0x8a => Self::MsplimNs,
+            0x8b => Self::PsplimNs,
+            0x90 => Self::PrimaskNs,
+            0x91 => Self::BasepriNs,
+            0x93 => Self::FaultMaskNs,
+            0x94 => Self::ControlNs,
+            0x98 => Self::SpNs,
+            0xa0..=0xa3 => Self::PacKeyPNs(index as u8 & 3),
+            0xa4..=0xa7 => Self::PacKeyUNs(index as u8 & 3),
             _ => panic!("invalid sys register index"),
         }
     }
@@ -157,11 +191,26 @@ impl RegisterIndex {
             RegisterIndex::Iepsr => 7,
             RegisterIndex::Msp => 8,
             RegisterIndex::Psp => 9,
+            RegisterIndex::Msplim => 10,
+            RegisterIndex::Psplim => 11,
             RegisterIndex::Primask => 16,
             RegisterIndex::Basepri => 17,
             RegisterIndex::BasepriMax => 18,
             RegisterIndex::FaultMask => 19,
             RegisterIndex::Control => 20,
+            RegisterIndex::MspNs => 0x88,
+            RegisterIndex::PspNs => 0x89,
+            RegisterIndex::MsplimNs => 0x8a,
+            RegisterIndex::PsplimNs => 0x8b,
+            RegisterIndex::PrimaskNs => 0x90,
+            RegisterIndex::BasepriNs => 0x91,
+            RegisterIndex::FaultMaskNs => 0x93,
+            RegisterIndex::ControlNs => 0x94,
+            RegisterIndex::SpNs => 0x98,
+            RegisterIndex::PacKeyP(n) => 0x20 + *n as u32,
+            RegisterIndex::PacKeyU(n) => 0x24 + *n as u32,
+            RegisterIndex::PacKeyPNs(n) => 0xa0 + *n as u32,
+            RegisterIndex::PacKeyUNs(n) => 0xa4 + *n as u32,
             _ => panic!("not a sys register"),
         }
     }
@@ -224,11 +273,26 @@ impl Display for RegisterIndex {
             Self::Iepsr => "iepsr",
             Self::Msp => "msp",
             Self::Psp => "psp",
+            Self::Msplim => "msplim",
+            Self::Psplim => "psplim",
             Self::Primask => "primask",
             Self::Basepri => "basepri",
             Self::BasepriMax => "basepri_max",
             Self::FaultMask => "faultmask",
             Self::Control => "control",
+            Self::MspNs => "msp_ns",
+            Self::PspNs => "psp_ns",
+            Self::MsplimNs => "msplim_ns",
+            Self::PsplimNs => "psplim_ns",
+            Self::PrimaskNs => "primask_ns",
+            Self::BasepriNs => "basepri_ns",
+            Self::FaultMaskNs => "faultmask_ns",
+            Self::ControlNs => "control_ns",
+            Self::SpNs => "sp_ns",
+            Self::PacKeyP(n) => return write!(f, "pac_key_p_{n}"),
+            Self::PacKeyU(n) => return write!(f, "pac_key_u_{n}"),
+            Self::PacKeyPNs(n) => return write!(f, "pac_key_p_{n}_ns"),
+            Self::PacKeyUNs(n) => return write!(f, "pac_key_u_{n}_ns"),
         };
         write!(f, "{}", s)
     }
@@ -375,7 +439,7 @@ impl ProgramStatusRegister {
     /// IPSR sub register bits mask.
     const IPSR_MASK: u32 = 0x000001ff;
     /// EPSR sub register bits mask.
-    const EPSR_MASK: u32 = 0x0700fc00;
+    const EPSR_MASK: u32 = 0x0720fc00;
 
     pub fn new() -> Self {
         ProgramStatusRegister(0)
@@ -388,7 +452,7 @@ impl ProgramStatusRegister {
 
     /// Sets register value, except reserved and unused bits.
     pub fn set(&mut self, value: u32) {
-        self.0 = value & 0xff0ffdff;
+        self.0 = value & 0xff2ffdff;
     }
 
     /// Returns APSR register value.
@@ -536,7 +600,7 @@ impl ProgramStatusRegister {
     /// Sets Greater than or Equal flags (DSP extension).
     pub fn set_ge(&mut self, value: u8) {
         debug_assert!(value <= 0xf);
-        self.0 = (self.0 & 0x000f0000) | ((value as u32 & 0xf) << 16);
+        self.0 = (self.0 & !0x000f0000) | ((value as u32 & 0xf) << 16);
     }
 
     pub fn ici_it(&self) -> u8 {
@@ -558,6 +622,18 @@ impl ProgramStatusRegister {
         self
     }
 
+    /// Returns the EPSR.B bit, set by indirect branches when Branch Target Identification is
+    /// enabled. The next instruction must then be a BTI clearing instruction.
+    pub fn b(&self) -> bool {
+        self.0.bit(21)
+    }
+
+    /// Sets the EPSR.B bit.
+    pub fn set_b(&mut self, value: bool) -> &mut Self {
+        self.0.set_bit(21, value);
+        self
+    }
+
     /// Returns the exception number of the IPSR register.
     pub fn exception_number(&self) -> u16 {
         (self.0 & 0x1ff) as u16
@@ -698,6 +774,73 @@ impl ControlRegister {
     pub fn set_spsel(&mut self, value: bool) {
         self.0.set_bit(1, value)
     }
+
+    /// Returns true if the floating-point context is active, meaning the current context has
+    /// executed floating-point instructions and its floating-point state must be preserved on
+    /// exception entry.
+    pub fn fpca(&self) -> bool {
+        self.0.bit(2)
+    }
+
+    /// Change the floating-point context active bit.
+    pub fn set_fpca(&mut self, value: bool) {
+        self.0.set_bit(2, value)
+    }
+
+    /// Returns true if the floating-point context belongs to the Secure state.
+    ///
+    /// This bit only exists in the Secure CONTROL register, when the Security Extension is
+    /// implemented.
+    pub fn sfpa(&self) -> bool {
+        self.0.bit(3)
+    }
+
+    /// Change the Secure floating-point active bit.
+    pub fn set_sfpa(&mut self, value: bool) {
+        self.0.set_bit(3, value)
+    }
+
+    /// Returns true if Branch Target Identification is enabled for privileged code.
+    ///
+    /// This bit only exists when the PACBTI extension is implemented, as do the following ones.
+    pub fn bti_en(&self) -> bool {
+        self.0.bit(4)
+    }
+
+    /// Change the privileged Branch Target Identification enable bit.
+    pub fn set_bti_en(&mut self, value: bool) {
+        self.0.set_bit(4, value)
+    }
+
+    /// Returns true if Branch Target Identification is enabled for unprivileged code.
+    pub fn ubti_en(&self) -> bool {
+        self.0.bit(5)
+    }
+
+    /// Change the unprivileged Branch Target Identification enable bit.
+    pub fn set_ubti_en(&mut self, value: bool) {
+        self.0.set_bit(5, value)
+    }
+
+    /// Returns true if pointer authentication is enabled for privileged code.
+    pub fn pac_en(&self) -> bool {
+        self.0.bit(6)
+    }
+
+    /// Change the privileged pointer authentication enable bit.
+    pub fn set_pac_en(&mut self, value: bool) {
+        self.0.set_bit(6, value)
+    }
+
+    /// Returns true if pointer authentication is enabled for unprivileged code.
+    pub fn upac_en(&self) -> bool {
+        self.0.bit(7)
+    }
+
+    /// Change the unprivileged pointer authentication enable bit.
+    pub fn set_upac_en(&mut self, value: bool) {
+        self.0.set_bit(7, value)
+    }
 }
 
 impl Default for ControlRegister {
@@ -712,6 +855,342 @@ impl Debug for ControlRegister {
     }
 }
 
+/// Floating-point rounding mode, as selected by the FPSCR.RMode field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Round to Nearest (RN), ties to even.
+    Nearest,
+    /// Round towards Plus Infinity (RP).
+    PlusInfinity,
+    /// Round towards Minus Infinity (RM).
+    MinusInfinity,
+    /// Round towards Zero (RZ).
+    Zero,
+    /// Round to Nearest, ties away from zero.
+    ///
+    /// This mode cannot be selected in FPSCR, and is only used by VRINTA and VCVTA
+    /// instructions.
+    TiesAway,
+}
+
+impl RoundingMode {
+    /// Returns the rounding mode encoded by the 2-bit `value`, as found in FPSCR.RMode.
+    pub fn from_bits(value: u32) -> Self {
+        match value & 3 {
+            0 => Self::Nearest,
+            1 => Self::PlusInfinity,
+            2 => Self::MinusInfinity,
+            _ => Self::Zero,
+        }
+    }
+
+    /// Returns the 2-bit encoding of the rounding mode.
+    ///
+    /// # Panics
+    ///
+    /// Panics for [RoundingMode::TiesAway], which has no FPSCR encoding.
+    pub fn bits(&self) -> u32 {
+        match self {
+            Self::Nearest => 0,
+            Self::PlusInfinity => 1,
+            Self::MinusInfinity => 2,
+            Self::Zero => 3,
+            Self::TiesAway => panic!("ties away rounding mode cannot be encoded in FPSCR"),
+        }
+    }
+
+    /// Returns the rounding mode encoded by the 2-bit `rm` field of the VRINT{A,N,P,M} and
+    /// VCVT{A,N,P,M} instructions.
+    ///
+    /// Corresponds to `FPDecodeRM()` in the Arm Architecture Reference Manual.
+    pub fn from_rm(rm: u32) -> Self {
+        match rm & 3 {
+            0 => Self::TiesAway,
+            1 => Self::Nearest,
+            2 => Self::PlusInfinity,
+            _ => Self::MinusInfinity,
+        }
+    }
+}
+
+/// Floating-point Status and Control Register (FPSCR).
+///
+/// Holds the floating-point comparison flags, the cumulative exception flags and the controls for
+/// rounding, flush-to-zero, default NaN and alternative half-precision modes. On ArmV8.1-M, it
+/// also holds the tail predication state of low-overhead loops.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct FloatingPointStatusControlRegister(u32);
+
+impl FloatingPointStatusControlRegister {
+    /// Implemented bits mask.
+    const MASK: u32 = 0xf7c0009f;
+    /// LTPSIZE field mask. This field can only be modified by loop instructions.
+    const LTPSIZE_MASK: u32 = 0x00070000;
+
+    pub fn new() -> Self {
+        Self(0)
+    }
+
+    /// Returns FPSCR register value.
+    pub fn get(&self) -> u32 {
+        self.0
+    }
+
+    /// Sets register value, except reserved bits and LTPSIZE.
+    pub fn set(&mut self, value: u32) {
+        self.0 = value & Self::MASK | self.0 & Self::LTPSIZE_MASK;
+    }
+
+    /// Returns Negative condition flag.
+    pub fn n(&self) -> bool {
+        self.0.bit(31)
+    }
+
+    /// Returns Zero condition flag.
+    pub fn z(&self) -> bool {
+        self.0.bit(30)
+    }
+
+    /// Returns Carry condition flag.
+    pub fn c(&self) -> bool {
+        self.0.bit(29)
+    }
+
+    /// Returns Overflow condition flag.
+    pub fn v(&self) -> bool {
+        self.0.bit(28)
+    }
+
+    /// Returns N, Z, C and V flags at once, N being the most significant bit.
+    pub fn nzcv(&self) -> u8 {
+        (self.0 >> 28) as u8
+    }
+
+    /// Sets N, Z, C and V flags at once, N being the most significant bit.
+    ///
+    /// ```
+    /// # use armagnac::registers::FloatingPointStatusControlRegister;
+    /// let mut fpscr = FloatingPointStatusControlRegister::new();
+    /// fpscr.set_nzcv(0b0110);
+    /// assert!(!fpscr.n() && fpscr.z() && fpscr.c() && !fpscr.v());
+    /// ```
+    pub fn set_nzcv(&mut self, flags: u8) {
+        debug_assert!(flags < 16);
+        self.0 = self.0 & 0x0fffffff | ((flags as u32) << 28);
+    }
+
+    /// Returns AHP (Alternative Half-Precision) control bit value.
+    pub fn ahp(&self) -> bool {
+        self.0.bit(26)
+    }
+
+    /// Sets AHP (Alternative Half-Precision) control bit value.
+    pub fn set_ahp(&mut self, value: bool) {
+        self.0.set_bit(26, value)
+    }
+
+    /// Returns DN (Default NaN) control bit value.
+    pub fn dn(&self) -> bool {
+        self.0.bit(25)
+    }
+
+    /// Sets DN (Default NaN) control bit value.
+    pub fn set_dn(&mut self, value: bool) {
+        self.0.set_bit(25, value)
+    }
+
+    /// Returns FZ (Flush-to-zero) control bit value.
+    pub fn fz(&self) -> bool {
+        self.0.bit(24)
+    }
+
+    /// Sets FZ (Flush-to-zero) control bit value.
+    pub fn set_fz(&mut self, value: bool) {
+        self.0.set_bit(24, value)
+    }
+
+    /// Returns the rounding mode selected by the RMode field.
+    pub fn rmode(&self) -> RoundingMode {
+        RoundingMode::from_bits(self.0 >> 22)
+    }
+
+    /// Sets the RMode field.
+    pub fn set_rmode(&mut self, mode: RoundingMode) {
+        self.0 = self.0 & !(3 << 22) | (mode.bits() << 22);
+    }
+
+    /// Returns LTPSIZE (Loop Tail Predication Size) field value.
+    ///
+    /// Values 0 to 3 give the element size of tail predicated loops as a power of two number of
+    /// bytes. Value 4 indicates tail predication is not applied.
+    pub fn ltpsize(&self) -> u8 {
+        ((self.0 >> 16) & 7) as u8
+    }
+
+    /// Sets LTPSIZE (Loop Tail Predication Size) field value.
+    pub fn set_ltpsize(&mut self, value: u8) {
+        debug_assert!(value <= 4);
+        self.0 = self.0 & !Self::LTPSIZE_MASK | ((value as u32) << 16);
+    }
+
+    /// Returns IDC (Input Denormal cumulative exception) bit value.
+    pub fn idc(&self) -> bool {
+        self.0.bit(7)
+    }
+
+    /// Sets IDC (Input Denormal cumulative exception) bit value.
+    pub fn set_idc(&mut self, value: bool) {
+        self.0.set_bit(7, value)
+    }
+
+    /// Returns IXC (Inexact cumulative exception) bit value.
+    pub fn ixc(&self) -> bool {
+        self.0.bit(4)
+    }
+
+    /// Sets IXC (Inexact cumulative exception) bit value.
+    pub fn set_ixc(&mut self, value: bool) {
+        self.0.set_bit(4, value)
+    }
+
+    /// Returns UFC (Underflow cumulative exception) bit value.
+    pub fn ufc(&self) -> bool {
+        self.0.bit(3)
+    }
+
+    /// Sets UFC (Underflow cumulative exception) bit value.
+    pub fn set_ufc(&mut self, value: bool) {
+        self.0.set_bit(3, value)
+    }
+
+    /// Returns OFC (Overflow cumulative exception) bit value.
+    pub fn ofc(&self) -> bool {
+        self.0.bit(2)
+    }
+
+    /// Sets OFC (Overflow cumulative exception) bit value.
+    pub fn set_ofc(&mut self, value: bool) {
+        self.0.set_bit(2, value)
+    }
+
+    /// Returns DZC (Division by Zero cumulative exception) bit value.
+    pub fn dzc(&self) -> bool {
+        self.0.bit(1)
+    }
+
+    /// Sets DZC (Division by Zero cumulative exception) bit value.
+    pub fn set_dzc(&mut self, value: bool) {
+        self.0.set_bit(1, value)
+    }
+
+    /// Returns IOC (Invalid Operation cumulative exception) bit value.
+    pub fn ioc(&self) -> bool {
+        self.0.bit(0)
+    }
+
+    /// Sets IOC (Invalid Operation cumulative exception) bit value.
+    pub fn set_ioc(&mut self, value: bool) {
+        self.0.set_bit(0, value)
+    }
+}
+
+impl Default for FloatingPointStatusControlRegister {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for FloatingPointStatusControlRegister {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+/// Vector Predication Status and Control Register (VPR).
+///
+/// Holds the predicate flags of M-profile Vector Extension instructions, with one P0 bit per byte
+/// of a vector, and the VPT block state of each pair of beats.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct VectorPredicationStatusRegister(u32);
+
+impl VectorPredicationStatusRegister {
+    /// Implemented bits mask.
+    const MASK: u32 = 0x00ffffff;
+
+    pub fn new() -> Self {
+        Self(0)
+    }
+
+    /// Returns register value.
+    pub fn get(&self) -> u32 {
+        self.0
+    }
+
+    /// Sets register value, except reserved bits.
+    pub fn set(&mut self, value: u32) {
+        self.0 = value & Self::MASK;
+    }
+
+    /// Returns P0 field value: the predicate flags, one per byte of a vector.
+    pub fn p0(&self) -> u16 {
+        self.0 as u16
+    }
+
+    /// Sets P0 field value.
+    pub fn set_p0(&mut self, value: u16) {
+        self.0 = self.0 & !0xffff | value as u32;
+    }
+
+    /// Returns the four P0 bits of the given beat, one per byte of the beat.
+    pub fn beat_p0(&self, beat: usize) -> u8 {
+        ((self.0 >> (beat * 4)) & 0xf) as u8
+    }
+
+    /// Sets the four P0 bits of the given beat.
+    pub fn set_beat_p0(&mut self, beat: usize, value: u8) {
+        let shift = beat * 4;
+        self.0 = self.0 & !(0xf << shift) | ((value as u32 & 0xf) << shift);
+    }
+
+    /// Returns the VPT mask of the given beat: MASK01 field for beats 0 and 1, MASK23 field for
+    /// beats 2 and 3. A zero mask indicates the beat is not in a VPT block.
+    ///
+    /// ```
+    /// # use armagnac::registers::VectorPredicationStatusRegister;
+    /// let mut vpr = VectorPredicationStatusRegister::new();
+    /// vpr.set_mask(2, 0b1000);
+    /// assert_eq!(vpr.mask(0), 0);
+    /// assert_eq!(vpr.mask(3), 0b1000);
+    /// assert_eq!(vpr.get(), 0x00800000);
+    /// ```
+    pub fn mask(&self, beat: usize) -> u8 {
+        ((self.0 >> (16 + (beat / 2) * 4)) & 0xf) as u8
+    }
+
+    /// Sets the VPT mask of the given beat. See [VectorPredicationStatusRegister::mask].
+    pub fn set_mask(&mut self, beat: usize, value: u8) {
+        let shift = 16 + (beat / 2) * 4;
+        self.0 = self.0 & !(0xf << shift) | ((value as u32 & 0xf) << shift);
+    }
+
+    /// Returns `true` if any beat is in a VPT block.
+    pub fn in_vpt_block(&self) -> bool {
+        self.0 & 0x00ff0000 != 0
+    }
+}
+
+impl Default for VectorPredicationStatusRegister {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for VectorPredicationStatusRegister {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
 /// Processor execution mode.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Mode {
@@ -721,6 +1200,46 @@ pub enum Mode {
     Handler,
 }
 
+/// Processor security state, defined by the ArmV8-M Security Extension.
+///
+/// Processors which do not implement the Security Extension always execute in Secure state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecurityState {
+    /// State selected on reset, which has access to both Secure and Non-secure resources.
+    Secure,
+    /// State of the less trusted software, which can only access Non-secure resources.
+    NonSecure,
+}
+
+/// Registers banked between the Secure and Non-secure states.
+///
+/// [CoreRegisters] holds the registers of the current security state in its usual fields, and
+/// those of the other state in a [BankedRegisters], the two sets being swapped on each security
+/// state transition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BankedRegisters {
+    /// Main stack pointer.
+    pub msp: u32,
+    /// Process stack pointer.
+    pub psp: u32,
+    /// Main stack pointer limit.
+    pub msplim: u32,
+    /// Process stack pointer limit.
+    pub psplim: u32,
+    /// Exception mask register.
+    pub primask: MaskRegister,
+    /// Base priority mask register.
+    pub basepri: u32,
+    /// Fault mask register.
+    pub faultmask: MaskRegister,
+    /// Special purpose control register. Only nPRIV, SPSEL and PACBTI enable bits are banked.
+    pub control: ControlRegister,
+    /// Privileged pointer authentication key.
+    pub pac_key_p: [u32; 4],
+    /// Unprivileged pointer authentication key.
+    pub pac_key_u: [u32; 4],
+}
+
 /// Core registers of the ARM processor.
 ///
 /// Registers can be accessed directly, e.g.:
@@ -819,17 +1338,45 @@ pub struct CoreRegisters {
     /// Process stack pointer.
     /// Also known as `r13`.
     pub psp: u32,
+    /// Main stack pointer limit.
+    /// Only used in ArmV8-M. Updates of MSP below this limit raise a UsageFault.
+    pub msplim: u32,
+    /// Process stack pointer limit.
+    /// Only used in ArmV8-M. Updates of PSP below this limit raise a UsageFault.
+    pub psplim: u32,
     /// Groups APSR, IPSR and EPSR registers.
     pub psr: ProgramStatusRegister,
     /// Exception mask register.
     pub primask: MaskRegister,
+    /// Base priority mask register.
+    /// When non-zero, raises the execution priority to its value. Not present in ArmV6-M.
+    pub basepri: u32,
     /// Fault mask register.
     pub faultmask: MaskRegister,
     /// Special purpose control register.
     pub control: ControlRegister,
+    /// Privileged pointer authentication key, as four 32-bit words.
+    /// Only used when the PACBTI extension is enabled.
+    pub pac_key_p: [u32; 4],
+    /// Unprivileged pointer authentication key, as four 32-bit words.
+    /// Only used when the PACBTI extension is enabled.
+    pub pac_key_u: [u32; 4],
+    /// Floating-point extension registers S0 to S31.
+    /// Only used when the floating-point extension is enabled.
+    pub s: [u32; 32],
+    /// Floating-point status and control register.
+    pub fpscr: FloatingPointStatusControlRegister,
+    /// Vector predication status and control register.
+    /// Only used when the M-profile Vector Extension is enabled.
+    pub vpr: VectorPredicationStatusRegister,
     /// Current execution mode.
     /// Used in particular to return MSP or PSP when SP is requested.
     pub mode: Mode,
+    /// Current security state.
+    /// Always [SecurityState::Secure] if the Security Extension is not implemented.
+    pub security_state: SecurityState,
+    /// Registers of the security state which is not the current one.
+    pub banked: BankedRegisters,
     /// Event register.
     pub event: bool,
 }
@@ -854,15 +1401,71 @@ impl CoreRegisters {
             pc: 0,
             msp: 0,
             psp: 0,
+            msplim: 0,
+            psplim: 0,
             psr: ProgramStatusRegister::new(),
             primask: MaskRegister::new(),
+            basepri: 0,
             faultmask: MaskRegister::new(),
             control: ControlRegister::new(),
+            pac_key_p: [0; 4],
+            pac_key_u: [0; 4],
+            s: [0; 32],
+            fpscr: FloatingPointStatusControlRegister::new(),
+            vpr: VectorPredicationStatusRegister::new(),
             mode: Mode::Thread,
+            security_state: SecurityState::Secure,
+            banked: BankedRegisters::default(),
             event: false,
         }
     }
 
+    /// Changes the current security state, swapping the banked registers if the state changes.
+    ///
+    /// ```
+    /// # use armagnac::registers::{CoreRegisters, SecurityState};
+    /// let mut regs = CoreRegisters::new();
+    /// regs.msp = 0x20001000;
+    /// regs.set_security_state(SecurityState::NonSecure);
+    /// assert_eq!(regs.msp, 0);
+    /// assert_eq!(regs.banked.msp, 0x20001000);
+    /// ```
+    pub fn set_security_state(&mut self, state: SecurityState) {
+        if state == self.security_state {
+            return;
+        }
+        self.security_state = state;
+        let banked = &mut self.banked;
+        std::mem::swap(&mut self.msp, &mut banked.msp);
+        std::mem::swap(&mut self.psp, &mut banked.psp);
+        std::mem::swap(&mut self.msplim, &mut banked.msplim);
+        std::mem::swap(&mut self.psplim, &mut banked.psplim);
+        std::mem::swap(&mut self.primask, &mut banked.primask);
+        std::mem::swap(&mut self.basepri, &mut banked.basepri);
+        std::mem::swap(&mut self.faultmask, &mut banked.faultmask);
+        std::mem::swap(&mut self.pac_key_p, &mut banked.pac_key_p);
+        std::mem::swap(&mut self.pac_key_u, &mut banked.pac_key_u);
+        // FPCA and SFPA are not banked.
+        let control = self.control.0;
+        self.control.0 = control & !0xf3 | banked.control.0 & 0xf3;
+        banked.control.0 = control & 0xf3;
+    }
+
+    /// Returns [`RegisterIndex::Msp`] or [`RegisterIndex::Psp`] for the stack pointer of the other
+    /// security state, as selected by current mode and the banked control register value.
+    fn translate_sp_ns(&self) -> RegisterIndex {
+        match self.mode {
+            Mode::Handler => RegisterIndex::MspNs,
+            Mode::Thread => {
+                if self.banked.control.spsel() {
+                    RegisterIndex::PspNs
+                } else {
+                    RegisterIndex::MspNs
+                }
+            }
+        }
+    }
+
     /// Returns [`RegisterIndex::Msp`] or [`RegisterIndex::Psp`] depending on current execution mode
     /// and control register value.
     pub fn translate_sp(&self) -> RegisterIndex {
@@ -890,6 +1493,64 @@ impl CoreRegisters {
         }
     }
 
+    /// Returns the stack limit of stack pointer `sp`, which must be [`RegisterIndex::Msp`] or
+    /// [`RegisterIndex::Psp`].
+    ///
+    /// ```
+    /// # use armagnac::registers::{CoreRegisters, RegisterIndex};
+    /// let mut regs = CoreRegisters::new();
+    /// regs.psplim = 0x20000800;
+    /// assert_eq!(regs.sp_limit(RegisterIndex::Msp), 0);
+    /// assert_eq!(regs.sp_limit(RegisterIndex::Psp), 0x20000800);
+    /// ```
+    pub fn sp_limit(&self, sp: RegisterIndex) -> u32 {
+        match sp {
+            RegisterIndex::Msp => self.msplim,
+            RegisterIndex::Psp => self.psplim,
+            _ => panic!(),
+        }
+    }
+
+    /// Returns the value of double-precision register `Dn`, which aliases registers `S2n` and
+    /// `S2n+1`.
+    ///
+    /// ```
+    /// # use armagnac::registers::CoreRegisters;
+    /// let mut regs = CoreRegisters::new();
+    /// regs.s[2] = 0x89abcdef;
+    /// regs.s[3] = 0x01234567;
+    /// assert_eq!(regs.d(1), 0x0123456789abcdef);
+    /// ```
+    pub fn d(&self, n: usize) -> u64 {
+        (self.s[n * 2 + 1] as u64) << 32 | self.s[n * 2] as u64
+    }
+
+    /// Sets the value of double-precision register `Dn`, which aliases registers `S2n` and
+    /// `S2n+1`.
+    pub fn set_d(&mut self, n: usize, value: u64) {
+        self.s[n * 2] = value as u32;
+        self.s[n * 2 + 1] = (value >> 32) as u32;
+    }
+
+    /// Returns the value of vector register `Qn`, which aliases registers `S4n` to `S4n+3`.
+    ///
+    /// ```
+    /// # use armagnac::registers::CoreRegisters;
+    /// let mut regs = CoreRegisters::new();
+    /// regs.set_d(2, 0x0123456789abcdef);
+    /// regs.set_d(3, 0xfedcba9876543210);
+    /// assert_eq!(regs.q(1), 0xfedcba98765432100123456789abcdef);
+    /// ```
+    pub fn q(&self, n: usize) -> u128 {
+        (self.d(n * 2 + 1) as u128) << 64 | self.d(n * 2) as u128
+    }
+
+    /// Sets the value of vector register `Qn`, which aliases registers `S4n` to `S4n+3`.
+    pub fn set_q(&mut self, n: usize, value: u128) {
+        self.set_d(n * 2, value as u64);
+        self.set_d(n * 2 + 1, (value >> 64) as u64);
+    }
+
     /// Sets a register value
     ///
     /// # Arguments
@@ -919,19 +1580,45 @@ impl CoreRegisters {
             RegisterIndex::Lr => self.lr = value,
             RegisterIndex::Pc => self.pc = value,
             RegisterIndex::Apsr => self.psr.set_apsr(value),
-            RegisterIndex::Iapsr => todo!(),
-            RegisterIndex::Eapsr => todo!(),
+            RegisterIndex::Iapsr => {
+                self.psr.set_apsr(value);
+                self.psr.set_ipsr(value)
+            }
+            RegisterIndex::Eapsr => {
+                self.psr.set_apsr(value);
+                self.psr.set_epsr(value)
+            }
             RegisterIndex::Xpsr => self.psr.set(value),
             RegisterIndex::Ipsr => self.psr.set_ipsr(value),
             RegisterIndex::Epsr => self.psr.set_epsr(value),
-            RegisterIndex::Iepsr => todo!(),
+            RegisterIndex::Iepsr => {
+                self.psr.set_ipsr(value);
+                self.psr.set_epsr(value)
+            }
             RegisterIndex::Msp => self.msp = value,
             RegisterIndex::Psp => self.psp = value,
+            RegisterIndex::Msplim => self.msplim = value,
+            RegisterIndex::Psplim => self.psplim = value,
             RegisterIndex::Primask => self.primask.0 = value,
-            RegisterIndex::Basepri => todo!(),
-            RegisterIndex::BasepriMax => todo!(),
+            RegisterIndex::Basepri | RegisterIndex::BasepriMax => self.basepri = value,
             RegisterIndex::FaultMask => self.faultmask.0 = value,
             RegisterIndex::Control => self.control.0 = value,
+            RegisterIndex::MspNs => self.banked.msp = value,
+            RegisterIndex::PspNs => self.banked.psp = value,
+            RegisterIndex::MsplimNs => self.banked.msplim = value,
+            RegisterIndex::PsplimNs => self.banked.psplim = value,
+            RegisterIndex::PrimaskNs => self.banked.primask.0 = value,
+            RegisterIndex::BasepriNs => self.banked.basepri = value,
+            RegisterIndex::FaultMaskNs => self.banked.faultmask.0 = value,
+            RegisterIndex::ControlNs => self.banked.control.0 = value,
+            RegisterIndex::SpNs => {
+                let index = self.translate_sp_ns();
+                self.set(index, value)
+            }
+            RegisterIndex::PacKeyP(n) => self.pac_key_p[n as usize] = value,
+            RegisterIndex::PacKeyU(n) => self.pac_key_u[n as usize] = value,
+            RegisterIndex::PacKeyPNs(n) => self.banked.pac_key_p[n as usize] = value,
+            RegisterIndex::PacKeyUNs(n) => self.banked.pac_key_u[n as usize] = value,
         }
     }
 }
@@ -975,20 +1662,35 @@ impl Index<RegisterIndex> for CoreRegisters {
             },
             RegisterIndex::Lr => &self.lr,
             RegisterIndex::Pc => &self.pc,
-            RegisterIndex::Apsr => &self.psr.0,
-            RegisterIndex::Iapsr => todo!(),
-            RegisterIndex::Eapsr => todo!(),
-            RegisterIndex::Xpsr => todo!(),
-            RegisterIndex::Ipsr => todo!(),
-            RegisterIndex::Epsr => todo!(),
-            RegisterIndex::Iepsr => todo!(),
+            // All views of the program status register return its full value.
+            RegisterIndex::Apsr
+            | RegisterIndex::Iapsr
+            | RegisterIndex::Eapsr
+            | RegisterIndex::Xpsr
+            | RegisterIndex::Ipsr
+            | RegisterIndex::Epsr
+            | RegisterIndex::Iepsr => &self.psr.0,
             RegisterIndex::Msp => &self.msp,
             RegisterIndex::Psp => &self.psp,
+            RegisterIndex::Msplim => &self.msplim,
+            RegisterIndex::Psplim => &self.psplim,
             RegisterIndex::Primask => &self.primask.0,
-            RegisterIndex::Basepri => todo!(),
-            RegisterIndex::BasepriMax => todo!(),
+            RegisterIndex::Basepri | RegisterIndex::BasepriMax => &self.basepri,
             RegisterIndex::FaultMask => &self.faultmask.0,
             RegisterIndex::Control => &self.control.0,
+            RegisterIndex::MspNs => &self.banked.msp,
+            RegisterIndex::PspNs => &self.banked.psp,
+            RegisterIndex::MsplimNs => &self.banked.msplim,
+            RegisterIndex::PsplimNs => &self.banked.psplim,
+            RegisterIndex::PrimaskNs => &self.banked.primask.0,
+            RegisterIndex::BasepriNs => &self.banked.basepri,
+            RegisterIndex::FaultMaskNs => &self.banked.faultmask.0,
+            RegisterIndex::ControlNs => &self.banked.control.0,
+            RegisterIndex::SpNs => &self[self.translate_sp_ns()],
+            RegisterIndex::PacKeyP(n) => &self.pac_key_p[n as usize],
+            RegisterIndex::PacKeyU(n) => &self.pac_key_u[n as usize],
+            RegisterIndex::PacKeyPNs(n) => &self.banked.pac_key_p[n as usize],
+            RegisterIndex::PacKeyUNs(n) => &self.banked.pac_key_u[n as usize],
         }
     }
 }
@@ -1013,33 +1715,33 @@ mod tests {
         // Test forward iteration
         assert!(MainRegisterList(0xaaaa)
             .iter()
-            .eq((1..16).step_by(2).map(|i| RegisterIndex::new_main(i))));
+            .eq((1..16).step_by(2).map(RegisterIndex::new_main)));
         assert!(MainRegisterList(0x5555)
             .iter()
-            .eq((0..16).step_by(2).map(|i| RegisterIndex::new_main(i))));
+            .eq((0..16).step_by(2).map(RegisterIndex::new_main)));
         assert!(MainRegisterList(0xff00)
             .iter()
-            .eq((8..16).map(|i| RegisterIndex::new_main(i))));
+            .eq((8..16).map(RegisterIndex::new_main)));
         assert!(MainRegisterList(0x00ff)
             .iter()
-            .eq((0..8).map(|i| RegisterIndex::new_main(i))));
+            .eq((0..8).map(RegisterIndex::new_main)));
         // Test backward iteration
         assert!(MainRegisterList(0xaaaa)
             .iter()
             .rev()
-            .eq((1..16).step_by(2).rev().map(|i| RegisterIndex::new_main(i))));
+            .eq((1..16).step_by(2).rev().map(RegisterIndex::new_main)));
         assert!(MainRegisterList(0x5555)
             .iter()
             .rev()
-            .eq((0..16).step_by(2).rev().map(|i| RegisterIndex::new_main(i))));
+            .eq((0..16).step_by(2).rev().map(RegisterIndex::new_main)));
         assert!(MainRegisterList(0xff00)
             .iter()
             .rev()
-            .eq((8..16).rev().map(|i| RegisterIndex::new_main(i))));
+            .eq((8..16).rev().map(RegisterIndex::new_main)));
         assert!(MainRegisterList(0x00ff)
             .iter()
             .rev()
-            .eq((0..8).rev().map(|i| RegisterIndex::new_main(i))));
+            .eq((0..8).rev().map(RegisterIndex::new_main)));
     }
 
     #[test]
@@ -1063,4 +1765,18 @@ mod tests {
             assert_eq!(psr.flags(), i);
         }
     }
+
+    #[test]
+    fn test_program_status_register_ge() {
+        let mut psr = ProgramStatusRegister::new();
+        psr.set_flags(0b11111);
+        psr.set_ipsr(3);
+        for ge in 0..16 {
+            psr.set_ge(ge);
+            assert_eq!(psr.ge(), ge);
+            // Other fields are preserved.
+            assert_eq!(psr.flags(), 0b11111);
+            assert_eq!(psr.ipsr(), 3);
+        }
+    }
 }
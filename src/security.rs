@@ -0,0 +1,430 @@
+//! ArmV8-M Security Extension peripherals.
+//!
+//! The Security Attribution Unit (SAU) attributes each address of the memory space to the Secure
+//! or Non-secure state, eventually marking Secure regions as Non-secure callable. It is completed
+//! by an optional Implementation Defined Attribution Unit (IDAU) provided by the platform. The
+//! SAU registers, along with SFSR and SFAR which report SecureFault exceptions, are mapped in the
+//! System Control Space at address `0xe000edd0`. They are only accessible from Secure state.
+
+use crate::{
+    helpers::BitAccess,
+    memory::{
+        Env, MemoryAccessError, MemoryReadResult, MemoryWriteResult, RegistersMemoryInterface,
+    },
+};
+use num_enum::TryFromPrimitive;
+
+#[derive(TryFromPrimitive)]
+#[repr(u32)]
+pub enum Register {
+    Ctrl = 0x00,
+    Type = 0x04,
+    Rnr = 0x08,
+    Rbar = 0x0c,
+    Rlar = 0x10,
+    Sfsr = 0x14,
+    Sfar = 0x18,
+}
+
+/// Security attribution of an address returned by an [ImplementationDefinedAttributionUnit].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdauAttributes {
+    /// True if the address is exempt from security checks, and is accessible from both states.
+    pub exempt: bool,
+    /// True if the address is Non-secure.
+    pub non_secure: bool,
+    /// True if the address is Secure and Non-secure callable. Ignored if `non_secure` is true.
+    pub non_secure_callable: bool,
+    /// IDAU region number, reported by the TT instruction, if any.
+    pub region: Option<u8>,
+}
+
+impl Default for IdauAttributes {
+    /// Attributes which let the SAU alone decide of the security attribution.
+    fn default() -> Self {
+        Self {
+            exempt: false,
+            non_secure: true,
+            non_secure_callable: false,
+            region: None,
+        }
+    }
+}
+
+/// Trait to be implemented by platforms defining an Implementation Defined Attribution Unit.
+///
+/// The IDAU can only make the attribution given by the SAU more secure. When no IDAU is installed
+/// on the processor, all addresses are reported as Non-secure by the IDAU.
+pub trait ImplementationDefinedAttributionUnit {
+    /// Returns the security attribution of `address`.
+    fn check(&self, address: u32) -> IdauAttributes;
+}
+
+/// Result of the security attribution of an address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SecurityAttributes {
+    /// True if the address is Non-secure.
+    pub non_secure: bool,
+    /// True if the address is Secure and Non-secure callable.
+    pub non_secure_callable: bool,
+    /// SAU region matching the address, if any.
+    pub sau_region: Option<u8>,
+    /// IDAU region matching the address, if any.
+    pub idau_region: Option<u8>,
+}
+
+/// Returns `true` if `address` belongs to a region which is exempt from security attribution:
+/// the ITM, DWT, FPB, System Control Space, Non-secure System Control Space alias and debug
+/// ROM tables.
+pub fn is_exempt(address: u32) -> bool {
+    matches!(
+        address,
+        0xe0000000..=0xe0003fff
+            | 0xe000e000..=0xe000efff
+            | 0xe002e000..=0xe002efff
+            | 0xe0040000..=0xe0041fff
+            | 0xe00ff000..=0xe00fffff
+    )
+}
+
+/// SAU_CTRL register.
+#[derive(Default)]
+struct Ctrl(u32);
+
+impl Ctrl {
+    fn write(&mut self, value: u32) -> MemoryWriteResult {
+        if value & 0xfffffffc != 0 {
+            return Err(MemoryAccessError::InvalidValue);
+        }
+        self.0 = value;
+        Ok(())
+    }
+
+    /// Returns ENABLE bit value.
+    fn enable(&self) -> bool {
+        self.0.bit(0)
+    }
+
+    /// Returns ALLNS bit value.
+    /// When the SAU is disabled, this bit selects whether all memory is Non-secure or Secure.
+    fn allns(&self) -> bool {
+        self.0.bit(1)
+    }
+}
+
+/// SAU_RLAR register.
+#[derive(Default, Clone, Copy)]
+struct RlarRegister(u32);
+
+impl RlarRegister {
+    /// Returns ENABLE bit value.
+    fn enable(&self) -> bool {
+        self.0.bit(0)
+    }
+
+    /// Returns NSC bit value.
+    fn nsc(&self) -> bool {
+        self.0.bit(1)
+    }
+
+    /// Returns the region limit address (inclusive).
+    fn limit(&self) -> u32 {
+        self.0 | 0x1f
+    }
+}
+
+/// SFSR (Secure Fault Status Register).
+#[derive(Default)]
+pub struct Sfsr(u32);
+
+impl Sfsr {
+    /// Clears the bits written to one.
+    /// Returns [MemoryAccessError::InvalidValue] when attempting to write a reserved bit.
+    pub fn write(&mut self, value: u32) -> MemoryWriteResult {
+        if value & !0xff != 0 {
+            return Err(MemoryAccessError::InvalidValue);
+        }
+        self.0 &= !value;
+        Ok(())
+    }
+
+    /// Returns INVEP bit value.
+    pub fn invep(&self) -> bool {
+        self.0.bit(0)
+    }
+
+    /// Sets INVEP bit value.
+    pub fn set_invep(&mut self, value: bool) {
+        self.0.set_bit(0, value)
+    }
+
+    /// Returns INVIS bit value.
+    pub fn invis(&self) -> bool {
+        self.0.bit(1)
+    }
+
+    /// Sets INVIS bit value.
+    pub fn set_invis(&mut self, value: bool) {
+        self.0.set_bit(1, value)
+    }
+
+    /// Returns INVER bit value.
+    pub fn inver(&self) -> bool {
+        self.0.bit(2)
+    }
+
+    /// Sets INVER bit value.
+    pub fn set_inver(&mut self, value: bool) {
+        self.0.set_bit(2, value)
+    }
+
+    /// Returns AUVIOL bit value.
+    pub fn auviol(&self) -> bool {
+        self.0.bit(3)
+    }
+
+    /// Sets AUVIOL bit value.
+    pub fn set_auviol(&mut self, value: bool) {
+        self.0.set_bit(3, value)
+    }
+
+    /// Returns INVTRAN bit value.
+    pub fn invtran(&self) -> bool {
+        self.0.bit(4)
+    }
+
+    /// Sets INVTRAN bit value.
+    pub fn set_invtran(&mut self, value: bool) {
+        self.0.set_bit(4, value)
+    }
+
+    /// Returns LSPERR bit value.
+    pub fn lsperr(&self) -> bool {
+        self.0.bit(5)
+    }
+
+    /// Sets LSPERR bit value.
+    pub fn set_lsperr(&mut self, value: bool) {
+        self.0.set_bit(5, value)
+    }
+
+    /// Returns SFARVALID bit value.
+    pub fn sfarvalid(&self) -> bool {
+        self.0.bit(6)
+    }
+
+    /// Sets SFARVALID bit value.
+    pub fn set_sfarvalid(&mut self, value: bool) {
+        self.0.set_bit(6, value)
+    }
+
+    /// Returns LSERR bit value.
+    pub fn lserr(&self) -> bool {
+        self.0.bit(7)
+    }
+
+    /// Sets LSERR bit value.
+    pub fn set_lserr(&mut self, value: bool) {
+        self.0.set_bit(7, value)
+    }
+}
+
+/// Security Attribution Unit, with the SecureFault status registers.
+pub struct SecurityAttributionUnit {
+    /// SAU_CTRL register.
+    ctrl: Ctrl,
+    /// SAU_RNR register.
+    rnr: u32,
+    /// SAU_RBAR registers.
+    rbar: Vec<u32>,
+    /// SAU_RLAR registers.
+    rlar: Vec<RlarRegister>,
+    /// Secure Fault Status Register.
+    pub sfsr: Sfsr,
+    /// Secure Fault Address Register.
+    /// Holds the faulting address when SFSR.SFARVALID is set.
+    pub sfar: u32,
+}
+
+impl SecurityAttributionUnit {
+    pub fn new(region_count: usize) -> Self {
+        assert!(region_count <= 255);
+        Self {
+            ctrl: Default::default(),
+            rnr: 0,
+            rbar: vec![0; region_count],
+            rlar: vec![Default::default(); region_count],
+            sfsr: Default::default(),
+            sfar: 0,
+        }
+    }
+
+    /// Returns the security attribution of `address`.
+    ///
+    /// `idau` is the attribution given by the Implementation Defined Attribution Unit, and
+    /// `secure` is the current security state of the processor, which is used for exempt
+    /// regions.
+    ///
+    /// Corresponds to `SecurityCheck()` in the Arm Architecture Reference Manual.
+    pub fn check(&self, address: u32, idau: IdauAttributes, secure: bool) -> SecurityAttributes {
+        let mut result = SecurityAttributes {
+            idau_region: idau.region,
+            ..Default::default()
+        };
+        if idau.exempt || is_exempt(address) {
+            result.non_secure = !secure;
+            return result;
+        }
+        if self.ctrl.enable() {
+            let mut regions =
+                self.rbar
+                    .iter()
+                    .zip(self.rlar.iter())
+                    .enumerate()
+                    .filter(|(_, (rbar, rlar))| {
+                        rlar.enable() && (*rbar & 0xffffffe0..=rlar.limit()).contains(&address)
+                    });
+            if let Some((region, (_, rlar))) = regions.next() {
+                // An address matching multiple regions is Secure.
+                if regions.next().is_none() {
+                    result.non_secure = !rlar.nsc();
+                    result.non_secure_callable = rlar.nsc();
+                    result.sau_region = Some(region as u8);
+                }
+            }
+        } else {
+            result.non_secure = self.ctrl.allns();
+        }
+        // The IDAU can only make the attribution more secure.
+        if !idau.non_secure
+            && (result.non_secure || (result.non_secure_callable && !idau.non_secure_callable))
+        {
+            result.non_secure = false;
+            result.non_secure_callable = idau.non_secure_callable;
+        }
+        result
+    }
+}
+
+impl Default for SecurityAttributionUnit {
+    fn default() -> Self {
+        Self::new(8)
+    }
+}
+
+impl RegistersMemoryInterface for SecurityAttributionUnit {
+    type Register = Register;
+
+    fn read32(&mut self, reg: Self::Register, env: &mut Env) -> MemoryReadResult<u32> {
+        if !env.privileged {
+            return Err(MemoryAccessError::PrivilegedOnly);
+        }
+        if !env.secure {
+            // RAZ/WI from Non-secure state.
+            return Ok(0);
+        }
+        Ok(match reg {
+            Register::Ctrl => self.ctrl.0,
+            Register::Type => self.rbar.len() as u32,
+            Register::Rnr => self.rnr,
+            Register::Rbar => self.rbar[self.rnr as usize],
+            Register::Rlar => self.rlar[self.rnr as usize].0,
+            Register::Sfsr => self.sfsr.0,
+            Register::Sfar => self.sfar,
+        })
+    }
+
+    fn write32(&mut self, reg: Self::Register, value: u32, env: &mut Env) -> MemoryWriteResult {
+        if !env.privileged {
+            return Err(MemoryAccessError::PrivilegedOnly);
+        }
+        if !env.secure {
+            return Ok(());
+        }
+        match reg {
+            Register::Ctrl => self.ctrl.write(value)?,
+            // SAU_TYPE is read-only.
+            Register::Type => {}
+            Register::Rnr => {
+                if value as usize >= self.rbar.len() {
+                    return Err(MemoryAccessError::InvalidValue);
+                }
+                self.rnr = value
+            }
+            Register::Rbar => self.rbar[self.rnr as usize] = value & 0xffffffe0,
+            Register::Rlar => self.rlar[self.rnr as usize].0 = value & 0xffffffe3,
+            Register::Sfsr => self.sfsr.write(value)?,
+            Register::Sfar => self.sfar = value,
+        }
+        Ok(())
+    }
+
+    fn size(&self) -> u32 {
+        0x1c
+    }
+
+    fn scheduled_updates(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IdauAttributes, Register, SecurityAttributionUnit};
+    use crate::memory::{Env, RegistersMemoryInterface};
+
+    #[test]
+    fn test_sau_attribution() {
+        let mut sau = SecurityAttributionUnit::new(8);
+        let mut env = Env::new(0, true);
+        let idau = IdauAttributes::default();
+
+        // At reset the SAU is disabled and ALLNS is cleared, all memory is Secure.
+        assert!(!sau.check(0x20000000, idau, true).non_secure);
+        // Exempt regions take the current security state.
+        assert!(sau.check(0xe000ed00, idau, false).non_secure);
+        assert!(!sau.check(0xe000ed00, idau, true).non_secure);
+
+        // Region 0: Non-secure RAM, region 1: Non-secure callable veneers.
+        sau.write32(Register::Rnr, 0, &mut env).unwrap();
+        sau.write32(Register::Rbar, 0x20010000, &mut env).unwrap();
+        sau.write32(Register::Rlar, 0x2001ffe1, &mut env).unwrap();
+        sau.write32(Register::Rnr, 1, &mut env).unwrap();
+        sau.write32(Register::Rbar, 0x00007f00, &mut env).unwrap();
+        sau.write32(Register::Rlar, 0x00007fe3, &mut env).unwrap();
+        sau.write32(Register::Ctrl, 1, &mut env).unwrap();
+
+        let a = sau.check(0x20010100, idau, true);
+        assert!(a.non_secure);
+        assert_eq!(a.sau_region, Some(0));
+        let a = sau.check(0x00007f20, idau, true);
+        assert!(!a.non_secure);
+        assert!(a.non_secure_callable);
+        assert_eq!(a.sau_region, Some(1));
+        let a = sau.check(0x20000000, idau, true);
+        assert!(!a.non_secure);
+        assert_eq!(a.sau_region, None);
+
+        // Overlapping regions are Secure.
+        sau.write32(Register::Rnr, 2, &mut env).unwrap();
+        sau.write32(Register::Rbar, 0x2001f000, &mut env).unwrap();
+        sau.write32(Register::Rlar, 0x2001ffe1, &mut env).unwrap();
+        assert!(!sau.check(0x2001f000, idau, true).non_secure);
+
+        // IDAU can make the attribution more secure, but not less.
+        let idau_secure = IdauAttributes {
+            non_secure: false,
+            region: Some(3),
+            ..idau
+        };
+        let a = sau.check(0x20010100, idau_secure, true);
+        assert!(!a.non_secure);
+        assert!(!a.non_secure_callable);
+        assert_eq!(a.idau_region, Some(3));
+
+        // Registers are RAZ/WI from Non-secure state.
+        let mut env = Env::new(0, true);
+        env.secure = false;
+        assert_eq!(sau.read32(Register::Ctrl, &mut env), Ok(0));
+    }
+}
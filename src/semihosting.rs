@@ -0,0 +1,707 @@
+//! Arm semihosting support.
+//!
+//! Semihosting allows emulated programs to use the input/output facilities of the host, such as
+//! the console or files. Programs request semihosting operations with `BKPT 0xAB` instruction:
+//! R0 holds the operation number and R1 its parameter, and the result is returned in R0.
+//!
+//! [Semihosted] adds semihosting to any [Emulator] giving access to its [Processor], and
+//! [crate::harness::ElfHarness::run_semihosted] runs programs linked with newlib
+//! `--specs=rdimon.specs` for instance.
+//!
+//! The following operations are supported: SYS_OPEN, SYS_CLOSE, SYS_WRITEC, SYS_WRITE0,
+//! SYS_WRITE, SYS_READ, SYS_ISTTY, SYS_SEEK, SYS_FLEN, SYS_CLOCK, SYS_TIME, SYS_ERRNO,
+//! SYS_GET_CMDLINE, SYS_HEAPINFO, SYS_EXIT and SYS_EXIT_EXTENDED. Other operations return -1.
+
+use crate::core::{Emulator, Event, Processor, RunError};
+use std::{
+    borrow::{Borrow, BorrowMut},
+    collections::{BTreeMap, VecDeque},
+    fs::{File, OpenOptions},
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::{Component, Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Immediate value of the `BKPT` instruction used for semihosting calls.
+pub const SEMIHOSTING_BKPT: u8 = 0xab;
+
+const SYS_OPEN: u32 = 0x01;
+const SYS_CLOSE: u32 = 0x02;
+const SYS_WRITEC: u32 = 0x03;
+const SYS_WRITE0: u32 = 0x04;
+const SYS_WRITE: u32 = 0x05;
+const SYS_READ: u32 = 0x06;
+const SYS_ISTTY: u32 = 0x09;
+const SYS_SEEK: u32 = 0x0a;
+const SYS_FLEN: u32 = 0x0c;
+const SYS_CLOCK: u32 = 0x10;
+const SYS_TIME: u32 = 0x11;
+const SYS_ERRNO: u32 = 0x13;
+const SYS_GET_CMDLINE: u32 = 0x15;
+const SYS_HEAPINFO: u32 = 0x16;
+const SYS_EXIT: u32 = 0x18;
+const SYS_EXIT_EXTENDED: u32 = 0x20;
+
+/// Exit reason reported by programs terminating normally.
+pub const ADP_STOPPED_APPLICATION_EXIT: u32 = 0x20026;
+
+const EBADF: u32 = 9;
+const EIO: u32 = 5;
+const ENOENT: u32 = 2;
+const EACCES: u32 = 13;
+const EINVAL: u32 = 22;
+const ENOSYS: u32 = 88;
+
+/// Returned by SYS_OPEN on `:semihosting-features` special file. Indicates support of
+/// SYS_EXIT_EXTENDED and of opening stdout and stderr separately with `:tt`.
+const FEATURES: [u8; 5] = [b'S', b'H', b'F', b'B', 0x03];
+
+/// Files which can be accessed by the emulated program.
+pub enum FileSystem {
+    /// Files are stored in memory, indexed by their name. Writing after a seek beyond the end of
+    /// a file fails with EINVAL.
+    Memory(BTreeMap<String, Vec<u8>>),
+    /// Files are stored in a host directory. Names are relative to that directory, and absolute
+    /// names or names with `..` components are rejected.
+    Directory(PathBuf),
+}
+
+/// Heap and stack location returned by SYS_HEAPINFO. Zero values let the C library use its
+/// default values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HeapInfo {
+    pub heap_base: u32,
+    pub heap_limit: u32,
+    pub stack_base: u32,
+    pub stack_limit: u32,
+}
+
+/// Program termination requested with SYS_EXIT or SYS_EXIT_EXTENDED.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Exit {
+    /// Exit reason, [ADP_STOPPED_APPLICATION_EXIT] for normal termination.
+    pub reason: u32,
+    /// Exit status given with SYS_EXIT_EXTENDED, 0 for SYS_EXIT.
+    pub subcode: u32,
+}
+
+impl Exit {
+    /// Returns the exit status of the program: the subcode for normal termination, and 1 for any
+    /// other reason.
+    pub fn status(&self) -> i32 {
+        if self.reason == ADP_STOPPED_APPLICATION_EXIT {
+            self.subcode as i32
+        } else {
+            1
+        }
+    }
+}
+
+/// File opened by the program.
+enum Handle {
+    Stdin,
+    Stdout,
+    Stderr,
+    Features { position: usize },
+    Memory { name: String, position: usize },
+    Host(File),
+}
+
+/// Implements semihosting operations.
+pub struct Semihosting {
+    /// Files which can be opened by the program.
+    pub files: FileSystem,
+    /// Command line returned by SYS_GET_CMDLINE.
+    pub cmdline: String,
+    /// Value returned by SYS_HEAPINFO.
+    pub heap_info: HeapInfo,
+    /// Processor clock frequency in Hz, used to convert cycles to time for SYS_CLOCK.
+    pub frequency: u64,
+    /// Data read by the program from the console.
+    pub stdin: VecDeque<u8>,
+    /// Data written by the program to the console standard output.
+    pub stdout: Vec<u8>,
+    /// Data written by the program to the console standard error.
+    pub stderr: Vec<u8>,
+    /// When `true`, console outputs are also written to the host standard output and error.
+    pub echo: bool,
+    /// Set once the program requested termination.
+    pub exit: Option<Exit>,
+    /// Opened files. A handle is the index in this vector plus one.
+    handles: Vec<Option<Handle>>,
+    /// Error number of the last failed operation.
+    errno: u32,
+}
+
+impl Default for Semihosting {
+    fn default() -> Self {
+        Self {
+            files: FileSystem::Memory(BTreeMap::new()),
+            cmdline: String::new(),
+            heap_info: HeapInfo::default(),
+            frequency: 16_000_000,
+            stdin: VecDeque::new(),
+            stdout: Vec::new(),
+            stderr: Vec::new(),
+            echo: false,
+            exit: None,
+            handles: Vec::new(),
+            errno: 0,
+        }
+    }
+}
+
+impl Semihosting {
+    /// Creates a semihosting implementation with an empty in-memory file system.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a semihosting implementation whose files are stored in the host directory `root`.
+    pub fn with_directory(root: impl Into<PathBuf>) -> Self {
+        Self {
+            files: FileSystem::Directory(root.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Processes the semihosting operation requested by the processor with `BKPT 0xAB`
+    /// instruction. R0 is set to the operation result.
+    ///
+    /// An error is returned if the operation parameters cannot be read or if results cannot be
+    /// written to memory.
+    pub fn call(&mut self, proc: &mut Processor) -> Result<(), RunError> {
+        let op = proc.registers.r0;
+        let param = proc.registers.r1;
+        let arg = |proc: &mut Processor, n: u32| proc.read_u32le_iface(param.wrapping_add(n * 4));
+        let result = match op {
+            SYS_OPEN => {
+                let name = arg(proc, 0)?;
+                let mode = arg(proc, 1)?;
+                let len = arg(proc, 2)?;
+                let name = String::from_utf8_lossy(&proc.read_bytes_iface(name, len)?).into();
+                self.open(name, mode)
+            }
+            SYS_CLOSE => {
+                let handle = arg(proc, 0)?;
+                match self.handle_index(handle) {
+                    Some(i) => {
+                        self.handles[i] = None;
+                        0
+                    }
+                    None => self.fail(EBADF),
+                }
+            }
+            SYS_WRITEC => {
+                let c = proc.read_u8_iface(param)?;
+                self.console(&[c], false);
+                proc.registers.r0
+            }
+            SYS_WRITE0 => {
+                let mut data = Vec::new();
+                loop {
+                    let c = proc.read_u8_iface(param.wrapping_add(data.len() as u32))?;
+                    if c == 0 {
+                        break;
+                    }
+                    data.push(c);
+                }
+                self.console(&data, false);
+                proc.registers.r0
+            }
+            SYS_WRITE => {
+                let handle = arg(proc, 0)?;
+                let buffer = arg(proc, 1)?;
+                let len = arg(proc, 2)?;
+                let data = proc.read_bytes_iface(buffer, len)?;
+                match self.write(handle, &data) {
+                    Ok(()) => 0,
+                    Err(errno) => {
+                        self.errno = errno;
+                        len
+                    }
+                }
+            }
+            SYS_READ => {
+                let handle = arg(proc, 0)?;
+                let buffer = arg(proc, 1)?;
+                let len = arg(proc, 2)?;
+                match self.read(handle, len as usize) {
+                    Ok(data) => {
+                        proc.write_bytes_iface(buffer, &data)?;
+                        len - data.len() as u32
+                    }
+                    Err(errno) => self.fail(errno),
+                }
+            }
+            SYS_ISTTY => {
+                let handle = arg(proc, 0)?;
+                match self.handle_index(handle).map(|i| &self.handles[i]) {
+                    Some(Some(Handle::Stdin | Handle::Stdout | Handle::Stderr)) => 1,
+                    Some(_) => 0,
+                    None => self.fail(EBADF),
+                }
+            }
+            SYS_SEEK => {
+                let handle = arg(proc, 0)?;
+                let position = arg(proc, 1)?;
+                match self.seek(handle, position as usize) {
+                    Ok(()) => 0,
+                    Err(errno) => self.fail(errno),
+                }
+            }
+            SYS_FLEN => {
+                let handle = arg(proc, 0)?;
+                match self.len(handle) {
+                    Ok(len) => len as u32,
+                    Err(errno) => self.fail(errno),
+                }
+            }
+            SYS_CLOCK => (proc.cycles * 100 / self.frequency.max(1)) as u32,
+            SYS_TIME => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs() as u32),
+            SYS_ERRNO => self.errno,
+            SYS_GET_CMDLINE => {
+                let buffer = arg(proc, 0)?;
+                let len = arg(proc, 1)?;
+                if self.cmdline.len() < len as usize {
+                    let mut data = self.cmdline.as_bytes().to_vec();
+                    data.push(0);
+                    proc.write_bytes_iface(buffer, &data)?;
+                    proc.write_u32le_iface(param.wrapping_add(4), self.cmdline.len() as u32)?;
+                    0
+                } else {
+                    self.fail(EINVAL)
+                }
+            }
+            SYS_HEAPINFO => {
+                let block = proc.read_u32le_iface(param)?;
+                let info = self.heap_info;
+                for (i, value) in [
+                    info.heap_base,
+                    info.heap_limit,
+                    info.stack_base,
+                    info.stack_limit,
+                ]
+                .into_iter()
+                .enumerate()
+                {
+                    proc.write_u32le_iface(block.wrapping_add(i as u32 * 4), value)?;
+                }
+                0
+            }
+            SYS_EXIT => {
+                self.exit = Some(Exit {
+                    reason: param,
+                    subcode: 0,
+                });
+                0
+            }
+            SYS_EXIT_EXTENDED => {
+                self.exit = Some(Exit {
+                    reason: arg(proc, 0)?,
+                    subcode: arg(proc, 1)?,
+                });
+                0
+            }
+            _ => self.fail(ENOSYS),
+        };
+        proc.registers.r0 = result;
+        Ok(())
+    }
+
+    /// Records `errno` as the error of the last operation and returns -1.
+    fn fail(&mut self, errno: u32) -> u32 {
+        self.errno = errno;
+        u32::MAX
+    }
+
+    /// Writes data to the console standard output, or standard error if `error` is `true`.
+    fn console(&mut self, data: &[u8], error: bool) {
+        if error {
+            self.stderr.extend(data);
+            if self.echo {
+                let _ = io::stderr().write_all(data);
+            }
+        } else {
+            self.stdout.extend(data);
+            if self.echo {
+                let _ = io::stdout().write_all(data);
+            }
+        }
+    }
+
+    /// Returns the index in [Self::handles] of an opened file handle.
+    fn handle_index(&self, handle: u32) -> Option<usize> {
+        let i = (handle as usize).checked_sub(1)?;
+        self.handles.get(i)?.as_ref().map(|_| i)
+    }
+
+    /// Opens a file and returns its handle, or -1 on error. `mode` is the index of the
+    /// equivalent `fopen` mode in `r`, `rb`, `r+`, `r+b`, `w`, `wb`, `w+`, `w+b`, `a`, `ab`, `a+`
+    /// and `a+b`.
+    fn open(&mut self, name: String, mode: u32) -> u32 {
+        if mode > 11 {
+            return self.fail(EINVAL);
+        }
+        let (write, truncate, append) = match mode / 4 {
+            0 => (mode & 2 != 0, false, false),
+            1 => (true, true, false),
+            _ => (true, false, true),
+        };
+        let handle = match name.as_str() {
+            // Console. Its mode selects the standard stream.
+            ":tt" => match mode / 4 {
+                0 => Handle::Stdin,
+                1 => Handle::Stdout,
+                _ => Handle::Stderr,
+            },
+            ":semihosting-features" if !write => Handle::Features { position: 0 },
+            _ => match &mut self.files {
+                FileSystem::Memory(files) => {
+                    if !(files.contains_key(&name) || truncate || append) {
+                        return self.fail(ENOENT);
+                    }
+                    let data = files.entry(name.clone()).or_default();
+                    if truncate {
+                        data.clear();
+                    }
+                    let position = if append { data.len() } else { 0 };
+                    Handle::Memory { name, position }
+                }
+                FileSystem::Directory(root) => {
+                    let Some(path) = sandboxed_path(root, &name) else {
+                        return self.fail(EACCES);
+                    };
+                    let file = OpenOptions::new()
+                        .read(!write || mode & 2 != 0)
+                        .write(write)
+                        .create(truncate || append)
+                        .truncate(truncate)
+                        .append(append)
+                        .open(path);
+                    match file {
+                        Ok(file) => Handle::Host(file),
+                        Err(e) => return self.fail(errno(&e)),
+                    }
+                }
+            },
+        };
+        let i = match self.handles.iter().position(|h| h.is_none()) {
+            Some(i) => {
+                self.handles[i] = Some(handle);
+                i
+            }
+            None => {
+                self.handles.push(Some(handle));
+                self.handles.len() - 1
+            }
+        };
+        i as u32 + 1
+    }
+
+    fn write(&mut self, handle: u32, data: &[u8]) -> Result<(), u32> {
+        let i = self.handle_index(handle).ok_or(EBADF)?;
+        match self.handles[i].as_mut().unwrap() {
+            Handle::Stdin | Handle::Features { .. } => return Err(EBADF),
+            Handle::Stdout => self.console(data, false),
+            Handle::Stderr => self.console(data, true),
+            Handle::Memory { name, position } => {
+                let FileSystem::Memory(files) = &mut self.files else {
+                    unreachable!()
+                };
+                let file = files.get_mut(name).ok_or(ENOENT)?;
+                // The position is controlled by the program, so files are not extended with a
+                // gap, which could require allocating gigabytes.
+                if *position > file.len() {
+                    return Err(EINVAL);
+                }
+                let end = *position + data.len();
+                if file.len() < end {
+                    file.resize(end, 0);
+                }
+                file[*position..end].copy_from_slice(data);
+                *position = end;
+            }
+            Handle::Host(file) => file.write_all(data).map_err(|e| errno(&e))?,
+        }
+        Ok(())
+    }
+
+    fn read(&mut self, handle: u32, len: usize) -> Result<Vec<u8>, u32> {
+        let i = self.handle_index(handle).ok_or(EBADF)?;
+        Ok(match self.handles[i].as_mut().unwrap() {
+            Handle::Stdout | Handle::Stderr => return Err(EBADF),
+            Handle::Stdin => {
+                let n = len.min(self.stdin.len());
+                self.stdin.drain(..n).collect()
+            }
+            Handle::Features { position } => read_slice(&FEATURES, position, len),
+            Handle::Memory { name, position } => {
+                let FileSystem::Memory(files) = &self.files else {
+                    unreachable!()
+                };
+                read_slice(files.get(name).ok_or(ENOENT)?, position, len)
+            }
+            Handle::Host(file) => {
+                let mut data = Vec::new();
+                file.take(len as u64)
+                    .read_to_end(&mut data)
+                    .map_err(|e| errno(&e))?;
+                data
+            }
+        })
+    }
+
+    fn seek(&mut self, handle: u32, target: usize) -> Result<(), u32> {
+        let i = self.handle_index(handle).ok_or(EBADF)?;
+        match self.handles[i].as_mut().unwrap() {
+            Handle::Stdin | Handle::Stdout | Handle::Stderr => return Err(EBADF),
+            Handle::Features { position } | Handle::Memory { position, .. } => *position = target,
+            Handle::Host(file) => {
+                file.seek(SeekFrom::Start(target as u64))
+                    .map_err(|e| errno(&e))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn len(&mut self, handle: u32) -> Result<usize, u32> {
+        let i = self.handle_index(handle).ok_or(EBADF)?;
+        match self.handles[i].as_ref().unwrap() {
+            Handle::Stdin | Handle::Stdout | Handle::Stderr => Err(EBADF),
+            Handle::Features { .. } => Ok(FEATURES.len()),
+            Handle::Memory { name, .. } => {
+                let FileSystem::Memory(files) = &self.files else {
+                    unreachable!()
+                };
+                Ok(files.get(name).ok_or(ENOENT)?.len())
+            }
+            Handle::Host(file) => Ok(file.metadata().map_err(|e| errno(&e))?.len() as usize),
+        }
+    }
+}
+
+/// Reads up to `len` bytes of `data` at `position`, and advances `position`.
+fn read_slice(data: &[u8], position: &mut usize, len: usize) -> Vec<u8> {
+    let start = (*position).min(data.len());
+    let end = start.saturating_add(len).min(data.len());
+    *position = end;
+    data[start..end].to_vec()
+}
+
+/// Returns the path of file `name` in directory `root`, or `None` if `name` would designate a
+/// file outside of `root`.
+fn sandboxed_path(root: &Path, name: &str) -> Option<PathBuf> {
+    let path = Path::new(name);
+    path.components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+        .then(|| root.join(path))
+}
+
+/// Returns the error number of a host I/O error.
+fn errno(error: &io::Error) -> u32 {
+    error.raw_os_error().map_or(EIO, |e| e as u32)
+}
+
+/// Adds semihosting to an emulator.
+///
+/// Semihosting calls are processed and their [Event::Break] events are not returned, excepted
+/// when the program requests termination. The exit status is then available in
+/// [Semihosting::exit].
+pub struct Semihosted<E> {
+    pub emulator: E,
+    pub semihosting: Semihosting,
+}
+
+impl<E: Emulator + BorrowMut<Processor>> Semihosted<E> {
+    pub fn new(emulator: E, semihosting: Semihosting) -> Self {
+        Self {
+            emulator,
+            semihosting,
+        }
+    }
+}
+
+impl<E: Emulator + BorrowMut<Processor>> Emulator for Semihosted<E> {
+    fn next_event(&mut self) -> Result<Event, RunError> {
+        loop {
+            let event = self.emulator.next_event()?;
+            if let Event::Break(SEMIHOSTING_BKPT) = event {
+                self.semihosting.call(self.emulator.borrow_mut())?;
+                if self.semihosting.exit.is_none() {
+                    continue;
+                }
+            }
+            return Ok(event);
+        }
+    }
+}
+
+impl<E: Borrow<Processor>> Borrow<Processor> for Semihosted<E> {
+    fn borrow(&self) -> &Processor {
+        self.emulator.borrow()
+    }
+}
+
+impl<E: BorrowMut<Processor>> BorrowMut<Processor> for Semihosted<E> {
+    fn borrow_mut(&mut self) -> &mut Processor {
+        self.emulator.borrow_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Exit, FileSystem, HeapInfo, Semihosted, Semihosting, ADP_STOPPED_APPLICATION_EXIT,
+    };
+    use crate::core::{Config, Emulator, Event, Processor, RunOptions};
+
+    /// Address of the parameter block.
+    const PARAM: u32 = 0x20000000;
+    /// Address of data buffers.
+    const BUFFER: u32 = 0x20000100;
+
+    /// Processes semihosting operation `op` with the parameter block `args`, and returns R0.
+    fn call(sh: &mut Semihosting, proc: &mut Processor, op: u32, args: &[u32]) -> u32 {
+        let block: Vec<u8> = args.iter().flat_map(|a| a.to_le_bytes()).collect();
+        proc.write_bytes_iface(PARAM, &block).unwrap();
+        proc.registers.r0 = op;
+        proc.registers.r1 = PARAM;
+        sh.call(proc).unwrap();
+        proc.registers.r0
+    }
+
+    #[test]
+    fn test_files() {
+        let mut proc = Processor::new(Config::v7m());
+        proc.map_ram(0x20000000, 0x200).unwrap();
+        let mut sh = Semihosting::new();
+        proc.write_bytes_iface(BUFFER, b"a.txt").unwrap();
+
+        // Opening a missing file for reading fails.
+        assert_eq!(call(&mut sh, &mut proc, 0x01, &[BUFFER, 0, 5]), u32::MAX);
+        assert_eq!(call(&mut sh, &mut proc, 0x13, &[]), 2);
+
+        // Write, seek and overwrite.
+        assert_eq!(call(&mut sh, &mut proc, 0x01, &[BUFFER, 4, 5]), 1);
+        proc.write_bytes_iface(BUFFER + 0x10, b"hello").unwrap();
+        assert_eq!(call(&mut sh, &mut proc, 0x05, &[1, BUFFER + 0x10, 5]), 0);
+        assert_eq!(call(&mut sh, &mut proc, 0x0a, &[1, 1]), 0);
+        assert_eq!(call(&mut sh, &mut proc, 0x05, &[1, BUFFER + 0x10, 1]), 0);
+        assert_eq!(call(&mut sh, &mut proc, 0x0c, &[1]), 5);
+        assert_eq!(call(&mut sh, &mut proc, 0x09, &[1]), 0);
+        assert_eq!(call(&mut sh, &mut proc, 0x02, &[1]), 0);
+        assert_eq!(call(&mut sh, &mut proc, 0x02, &[1]), u32::MAX);
+        let FileSystem::Memory(files) = &sh.files else {
+            panic!()
+        };
+        assert_eq!(files["a.txt"], b"hhllo");
+
+        // Read until end of file.
+        assert_eq!(call(&mut sh, &mut proc, 0x01, &[BUFFER, 1, 5]), 1);
+        assert_eq!(call(&mut sh, &mut proc, 0x06, &[1, BUFFER + 0x20, 3]), 0);
+        assert_eq!(call(&mut sh, &mut proc, 0x06, &[1, BUFFER + 0x23, 3]), 1);
+        assert_eq!(call(&mut sh, &mut proc, 0x06, &[1, BUFFER + 0x25, 3]), 3);
+        assert_eq!(proc.read_bytes_iface(BUFFER + 0x20, 5).unwrap(), b"hhllo");
+
+        // Console.
+        proc.write_bytes_iface(BUFFER, b":tt").unwrap();
+        assert_eq!(call(&mut sh, &mut proc, 0x01, &[BUFFER, 4, 3]), 2);
+        assert_eq!(call(&mut sh, &mut proc, 0x09, &[2]), 1);
+        assert_eq!(call(&mut sh, &mut proc, 0x05, &[2, BUFFER + 0x10, 5]), 0);
+        assert_eq!(call(&mut sh, &mut proc, 0x01, &[BUFFER, 8, 3]), 3);
+        assert_eq!(call(&mut sh, &mut proc, 0x05, &[3, BUFFER + 0x10, 2]), 0);
+        assert_eq!(sh.stdout, b"hello");
+        assert_eq!(sh.stderr, b"he");
+
+        // Features.
+        proc.write_bytes_iface(BUFFER, b":semihosting-features")
+            .unwrap();
+        assert_eq!(call(&mut sh, &mut proc, 0x01, &[BUFFER, 0, 21]), 4);
+        assert_eq!(call(&mut sh, &mut proc, 0x0c, &[4]), 5);
+        assert_eq!(call(&mut sh, &mut proc, 0x06, &[4, BUFFER + 0x20, 5]), 0);
+        assert_eq!(
+            proc.read_bytes_iface(BUFFER + 0x20, 5).unwrap(),
+            b"SHFB\x03"
+        );
+    }
+
+    #[test]
+    fn test_host_directory_sandbox() {
+        let mut proc = Processor::new(Config::v7m());
+        proc.map_ram(0x20000000, 0x200).unwrap();
+        let mut sh = Semihosting::with_directory("/tmp");
+        for name in ["../etc/passwd", "/etc/passwd"] {
+            proc.write_bytes_iface(BUFFER, name.as_bytes()).unwrap();
+            let len = name.len() as u32;
+            assert_eq!(call(&mut sh, &mut proc, 0x01, &[BUFFER, 0, len]), u32::MAX);
+            assert_eq!(call(&mut sh, &mut proc, 0x13, &[]), 13);
+        }
+    }
+
+    #[test]
+    fn test_system() {
+        let mut proc = Processor::new(Config::v7m());
+        proc.map_ram(0x20000000, 0x200).unwrap();
+        let mut sh = Semihosting::new();
+        sh.cmdline = "test -v".into();
+        sh.heap_info = HeapInfo {
+            heap_base: 0x20001000,
+            heap_limit: 0x20002000,
+            stack_base: 0x20004000,
+            stack_limit: 0x20003000,
+        };
+
+        assert_eq!(call(&mut sh, &mut proc, 0x15, &[BUFFER, 7]), u32::MAX);
+        assert_eq!(call(&mut sh, &mut proc, 0x15, &[BUFFER, 8]), 0);
+        assert_eq!(proc.read_bytes_iface(BUFFER, 8).unwrap(), b"test -v\0");
+        assert_eq!(proc.read_u32le_iface(PARAM + 4).unwrap(), 7);
+
+        assert_eq!(call(&mut sh, &mut proc, 0x16, &[BUFFER]), 0);
+        assert_eq!(proc.read_u32le_iface(BUFFER).unwrap(), 0x20001000);
+        assert_eq!(proc.read_u32le_iface(BUFFER + 12).unwrap(), 0x20003000);
+
+        proc.cycles = 32_000_000;
+        assert_eq!(call(&mut sh, &mut proc, 0x10, &[]), 200);
+        assert!(call(&mut sh, &mut proc, 0x11, &[]) > 1700000000);
+        assert_eq!(call(&mut sh, &mut proc, 0x17, &[]), u32::MAX);
+    }
+
+    #[test]
+    fn test_semihosted() {
+        let mut proc = Processor::new(Config::v7m());
+        // movs r0, #4; bkpt #0xab; movs r0, #0x20; mov r1, r2; bkpt #0xab; b .
+        proc.map(
+            0x100,
+            &[
+                0x04, 0x20, 0xab, 0xbe, 0x20, 0x20, 0x11, 0x46, 0xab, 0xbe, 0xfe, 0xe7,
+            ],
+        )
+        .unwrap();
+        proc.map_ram(0x20000000, 0x200).unwrap();
+        proc.write_bytes_iface(0x20000000, b"hi\0").unwrap();
+        proc.write_u32le_iface(0x20000010, ADP_STOPPED_APPLICATION_EXIT)
+            .unwrap();
+        proc.write_u32le_iface(0x20000014, 3).unwrap();
+        proc.registers.r1 = 0x20000000;
+        proc.registers.r2 = 0x20000010;
+        proc.set_pc(0x100);
+
+        let mut emulator = Semihosted::new(proc, Semihosting::new());
+        let event = emulator.run(RunOptions::new().gas(10)).unwrap();
+        assert!(matches!(event, Some(Event::Break(0xab))));
+        assert_eq!(emulator.semihosting.stdout, b"hi");
+        let exit = emulator.semihosting.exit.unwrap();
+        assert_eq!(
+            exit,
+            Exit {
+                reason: ADP_STOPPED_APPLICATION_EXIT,
+                subcode: 3
+            }
+        );
+        assert_eq!(exit.status(), 3);
+        assert_eq!(emulator.emulator.pc(), 0x10a);
+    }
+}
@@ -1,14 +1,17 @@
+use std::{cell::RefCell, rc::Rc};
+
 use crate::{
-    core::Irq,
+    core::{ArmVersion, Irq},
     helpers::{BitAccess, MaskedRegister},
     memory::{
-        Env, MemoryAccessError, MemoryOpAction, MemoryReadResult, MemoryWriteResult,
-        RegistersMemoryInterface,
+        Env, MemoryAccessError, MemoryInterface, MemoryOpAction, MemoryReadResult,
+        MemoryWriteResult, RegistersMemoryInterface,
     },
 };
 
 pub enum SystemControlRegister {
     // Master control register is at 0x000 but is reserved.
+    Ictr,
     Actlr,
     Stcsr,
     Strvr,
@@ -27,14 +30,14 @@ pub enum SystemControlRegister {
     Dfsr,
     Mmfar,
     Bfar,
-    IdIsar0,
-    IdIsar1,
-    IdIsar2,
-    IdIsar3,
-    IdIsar4,
+    IdIsar(u8),
     Cpacr,
     NvicIser(u8),
     NvicIcer(u8),
+    NvicIspr(u8),
+    NvicIcpr(u8),
+    NvicIabr(u8),
+    NvicItns(u8),
     NvicIpr(u8),
 }
 
@@ -42,10 +45,11 @@ impl TryFrom<u32> for SystemControlRegister {
     type Error = ();
 
     fn try_from(value: u32) -> Result<Self, Self::Error> {
-        if value % 4 != 0 {
+        if !value.is_multiple_of(4) {
             return Err(());
         }
         Ok(match value {
+            0x004 => SystemControlRegister::Ictr,
             0x008 => SystemControlRegister::Actlr,
             0x010 => SystemControlRegister::Stcsr,
             0x014 => SystemControlRegister::Strvr,
@@ -64,15 +68,15 @@ impl TryFrom<u32> for SystemControlRegister {
             0xd30 => SystemControlRegister::Dfsr,
             0xd34 => SystemControlRegister::Mmfar,
             0xd38 => SystemControlRegister::Bfar,
-            0xd60 => SystemControlRegister::IdIsar0,
-            0xd64 => SystemControlRegister::IdIsar1,
-            0xd68 => SystemControlRegister::IdIsar2,
-            0xd6c => SystemControlRegister::IdIsar3,
-            0xd70 => SystemControlRegister::IdIsar4,
+            0xd60..=0xd74 => SystemControlRegister::IdIsar(((value - 0xd60) / 4) as u8),
             0xd88 => SystemControlRegister::Cpacr,
             0x100..=0x13c => SystemControlRegister::NvicIser(((value - 0x100) / 4) as u8),
-            0x180..=0x19c => SystemControlRegister::NvicIcer(((value - 0x180) / 4) as u8),
-            0x400..=0x7ec => SystemControlRegister::NvicIpr(((value - 0x400) / 4) as u8),
+            0x180..=0x1bc => SystemControlRegister::NvicIcer(((value - 0x180) / 4) as u8),
+            0x200..=0x23c => SystemControlRegister::NvicIspr(((value - 0x200) / 4) as u8),
+            0x280..=0x2bc => SystemControlRegister::NvicIcpr(((value - 0x280) / 4) as u8),
+            0x300..=0x33c => SystemControlRegister::NvicIabr(((value - 0x300) / 4) as u8),
+            0x380..=0x3bc => SystemControlRegister::NvicItns(((value - 0x380) / 4) as u8),
+            0x400..=0x5ec => SystemControlRegister::NvicIpr(((value - 0x400) / 4) as u8),
             _ => return Err(()),
         })
     }
@@ -138,16 +142,114 @@ impl Vtor {
     }
 }
 
+/// ICSR (Interrupt Control and State Register).
+///
+/// The read-only fields giving the active and pending exceptions are updated by the processor
+/// with [Icsr::update]. Writing the set-pending and clear-pending bits emits interrupt requests to
+/// the processor.
+#[derive(Default)]
+pub struct Icsr(u32);
+
+impl Icsr {
+    /// Processes set-pending and clear-pending bits written to one. Other bits are ignored.
+    fn write(&mut self, value: u32, env: &mut Env) {
+        if value.bit(31) {
+            env.request_interrupt(Irq::Nmi);
+        }
+        if value.bit(28) {
+            env.request_interrupt(Irq::PendSV);
+        } else if value.bit(27) {
+            env.cancel_interrupt(Irq::PendSV);
+        }
+        if value.bit(26) {
+            env.request_interrupt(Irq::SysTick);
+        } else if value.bit(25) {
+            env.cancel_interrupt(Irq::SysTick);
+        }
+    }
+
+    /// Updates the read-only fields of the register.
+    ///
+    /// # Arguments
+    ///
+    /// * `active` - Number of the active exception, or 0 in Thread mode.
+    /// * `pending` - Number of the highest priority pending and enabled exception, or 0 if none.
+    /// * `rettobase` - `true` if there is no other active exception than `active`.
+    /// * `pending_set` - Pending exceptions, as requested by the processor.
+    pub fn update(
+        &mut self,
+        active: u16,
+        pending: u16,
+        rettobase: bool,
+        pending_set: impl Iterator<Item = Irq>,
+    ) {
+        let mut value = active as u32 & 0x1ff;
+        value.set_bit(11, rettobase && active != 0);
+        value |= (pending as u32 & 0x1ff) << 12;
+        for irq in pending_set {
+            match irq {
+                Irq::Nmi => value.set_bit(31, true),
+                Irq::PendSV => value.set_bit(28, true),
+                Irq::SysTick => value.set_bit(26, true),
+                Irq::External(_) => value.set_bit(22, true),
+                _ => {}
+            }
+        }
+        self.0 = value;
+    }
+
+    /// Returns VECTACTIVE field value.
+    pub fn vectactive(&self) -> u16 {
+        (self.0 & 0x1ff) as u16
+    }
+
+    /// Returns VECTPENDING field value.
+    pub fn vectpending(&self) -> u16 {
+        ((self.0 >> 12) & 0x1ff) as u16
+    }
+
+    /// Returns ISRPENDING bit value.
+    pub fn isrpending(&self) -> bool {
+        self.0.bit(22)
+    }
+
+    /// Returns PENDSVSET bit value.
+    pub fn pendsvset(&self) -> bool {
+        self.0.bit(28)
+    }
+
+    /// Returns PENDSTSET bit value.
+    pub fn pendstset(&self) -> bool {
+        self.0.bit(26)
+    }
+}
+
 pub struct Aircr(u32);
 
 impl Aircr {
-    fn write(&mut self, value: u32, env: &mut Env) -> Result<(), MemoryAccessError> {
-        if value & 0x000078f8 != 0 {
-            // Reserved
+    /// Changes the register value when written with the VECTKEY. BFHFNMINS can only be written
+    /// from Secure state when the Security Extension is implemented.
+    fn write(
+        &mut self,
+        value: u32,
+        security_extension: bool,
+        env: &mut Env,
+    ) -> Result<(), MemoryAccessError> {
+        let reserved = if security_extension {
+            0x000058f8
+        } else {
+            0x000078f8
+        };
+        if value & reserved != 0 {
             return Err(MemoryAccessError::InvalidValue);
         }
         if value >> 16 == 0x5fa {
-            self.0 = (value & 0x00000705) | 0xfa050000;
+            let bfhfnmins = if env.secure {
+                value & 0x2000
+            } else {
+                self.0 & 0x2000
+            };
+            self.0 = (value & 0x00000705) | bfhfnmins | 0xfa050000;
             if value.bit(0) {
                 // VECTRESET
                 env.actions.push(MemoryOpAction::Reset)
@@ -163,6 +265,19 @@ impl Aircr {
     pub fn endianess(&self) -> bool {
         self.0.bit(15)
     }
+
+    /// Returns PRIGROUP field value.
+    /// This field defines the split of exception priorities between group priority and
+    /// subpriority: bits `[PRIGROUP:0]` of a priority value are the subpriority.
+    pub fn prigroup(&self) -> u8 {
+        ((self.0 >> 8) & 7) as u8
+    }
+
+    /// Returns BFHFNMINS bit value.
+    /// When set, BusFault, HardFault and NMI target the Non-secure state.
+    pub fn bfhfnmins(&self) -> bool {
+        self.0.bit(13)
+    }
 }
 
 impl Default for Aircr {
@@ -171,13 +286,44 @@ impl Default for Aircr {
     }
 }
 
+/// SCR (System Control Register).
+#[derive(Default)]
+pub struct Scr(u32);
+
+impl Scr {
+    /// Set new value. SLEEPDEEPS is only writable when the Security Extension is implemented.
+    fn write(&mut self, value: u32, security_extension: bool) -> MemoryWriteResult {
+        let mask = if security_extension { 0x1e } else { 0x16 };
+        if value & !mask != 0 {
+            return Err(MemoryAccessError::InvalidValue);
+        }
+        self.0 = value & mask;
+        Ok(())
+    }
+
+    /// Returns SLEEPONEXIT bit value.
+    pub fn sleeponexit(&self) -> bool {
+        self.0.bit(1)
+    }
+
+    /// Returns SLEEPDEEP bit value.
+    pub fn sleepdeep(&self) -> bool {
+        self.0.bit(2)
+    }
+
+    /// Returns SEVONPEND bit value.
+    pub fn sevonpend(&self) -> bool {
+        self.0.bit(4)
+    }
+}
+
 /// Configuration and Control Register.
 pub struct Ccr(u32);
 
 impl Ccr {
     /// Set new value.
     pub fn write(&mut self, value: u32) -> MemoryWriteResult {
-        let mask = 0x0000031f;
+        let mask = 0x0000071f;
         if value & !mask != 0 {
             return Err(MemoryAccessError::InvalidValue);
         }
@@ -244,6 +390,18 @@ impl Ccr {
     pub fn set_stkalign(&mut self, value: bool) {
         self.0.set_bit(9, value);
     }
+
+    /// Returns STKOFHFNMIGN bit value.
+    ///
+    /// When set, stack limit violations are ignored by handlers running at priority -1 or -2.
+    pub fn stkofhfnmign(&self) -> bool {
+        self.0.bit(10)
+    }
+
+    /// Sets STKOFHFNMIGN bit value.
+    pub fn set_stkofhfnmign(&mut self, value: bool) {
+        self.0.set_bit(10, value);
+    }
 }
 
 impl Default for Ccr {
@@ -256,11 +414,15 @@ impl Default for Ccr {
 
 /// SHCSR register.
 #[derive(Default)]
-struct Shcsr(u32);
+pub struct Shcsr(u32);
 
 impl Shcsr {
-    fn write(&mut self, value: u32) -> Result<(), MemoryAccessError> {
-        let reserved_mask = 0xfff80274;
+    fn write(&mut self, value: u32, security_extension: bool) -> Result<(), MemoryAccessError> {
+        let reserved_mask = if security_extension {
+            0xfff00274
+        } else {
+            0xfff80274
+        };
         self.0 = value & !reserved_mask;
         if value & reserved_mask != 0 {
             Err(MemoryAccessError::InvalidValue)
@@ -268,6 +430,26 @@ impl Shcsr {
             Ok(())
         }
     }
+
+    /// Returns MEMFAULTENA bit value.
+    pub fn memfaultena(&self) -> bool {
+        self.0.bit(16)
+    }
+
+    /// Returns BUSFAULTENA bit value.
+    pub fn busfaultena(&self) -> bool {
+        self.0.bit(17)
+    }
+
+    /// Returns USGFAULTENA bit value.
+    pub fn usgfaultena(&self) -> bool {
+        self.0.bit(18)
+    }
+
+    /// Returns SECUREFAULTENA bit value.
+    pub fn securefaultena(&self) -> bool {
+        self.0.bit(19)
+    }
 }
 
 /// CAPCR register.
@@ -275,7 +457,7 @@ impl Shcsr {
 /// Current implementation defines the reset value at 0, which may not be accurate depending on the
 /// platform.
 #[derive(Default)]
-struct Cpacr(u32);
+pub struct Cpacr(u32);
 
 impl Cpacr {
     fn write(&mut self, value: u32) -> Result<(), MemoryAccessError> {
@@ -286,48 +468,266 @@ impl Cpacr {
         self.0 = value & mask;
         Ok(())
     }
+
+    /// Returns the 2-bit access privileges field of coprocessor `index`.
+    ///
+    /// `0b00` denies any access, `0b01` grants privileged access only and `0b11` grants full
+    /// access. `0b10` is reserved.
+    pub fn cp(&self, index: u8) -> u8 {
+        debug_assert!(index < 16);
+        ((self.0 >> (index * 2)) & 3) as u8
+    }
 }
 
 pub struct SystemControl {
+    /// Auxiliary Control Register.
+    /// Its content is implementation defined, it is only stored.
+    pub actlr: u32,
     stcsr: Stcsr,
     strvr: MaskedRegister,
     stcvr: u32,
+    /// Cycle up to which SysTick counter has been decremented.
+    systick_cycles: u64,
+    /// SysTick Calibration Value Register.
+    /// Reset value indicates no reference clock and an unknown calibration value.
+    pub stcr: u32,
     pub cpuid: CpuId,
+    pub icsr: Icsr,
+    /// Vector Table Offset Register.
+    /// This is the Secure VTOR when the Security Extension is implemented.
     pub vtor: Vtor,
+    /// Non-secure Vector Table Offset Register, when the Security Extension is implemented.
+    pub vtor_ns: Vtor,
     pub aircr: Aircr,
+    pub scr: Scr,
     pub ccr: Ccr,
     shpr: [u32; 3],
-    shcsr: Shcsr,
+    pub shcsr: Shcsr,
     pub cfsr: Cfsr,
-    cpacr: Cpacr,
-    nvic_iser: [u32; 16],
-    nvic_icer: [u32; 16],
+    pub hfsr: Hfsr,
+    /// Debug Fault Status Register.
+    /// Bits are cleared by writing one.
+    pub dfsr: MaskedRegister,
+    /// MemManage Fault Address Register.
+    /// Holds the faulting address when CFSR.MMARVALID is set.
+    pub mmfar: u32,
+    /// BusFault Address Register.
+    /// Holds the faulting address when CFSR.BFARVALID is set.
+    pub bfar: u32,
+    /// Instruction Set Attribute Registers ID_ISAR0 to ID_ISAR5.
+    /// Values depend on the architecture version, see [SystemControl::set_version]. ID_ISAR5 is
+    /// only implemented by ArmV8.1-M, and reads as zero otherwise.
+    pub id_isar: [u32; 6],
+    pub cpacr: Cpacr,
+    /// External interrupts enable bits, set by NVIC_ISER and cleared by NVIC_ICER registers.
+    nvic_enable: [u32; 16],
+    /// External interrupts pending bits, updated by the processor.
+    nvic_pending: [u32; 16],
+    /// External interrupts active bits, updated by the processor.
+    nvic_active: [u32; 16],
+    /// External interrupts target state bits, set for interrupts targeting the Non-secure state.
+    nvic_itns: [u32; 16],
     nvic_ipr: [u32; 124],
+    /// Whether the ArmV8-M Security Extension is implemented. When it is, registers accessed from
+    /// Non-secure state are banked or masked according to NVIC_ITNS.
+    pub(crate) security_extension: bool,
+    /// Number of external interrupts implemented by the processor. Pending bits of other
+    /// interrupts are RAZ/WI.
+    pub(crate) external_interrupts: usize,
 }
 
 impl SystemControl {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Decrements SysTick counter for all the cycles elapsed up to `cycles`, excluded. Returns
+    /// `true` if the counter has been reloaded, which is when SysTick exception is requested.
+    fn advance_systick(&mut self, cycles: u64) -> bool {
+        let elapsed = cycles.saturating_sub(self.systick_cycles);
+        self.systick_cycles = cycles;
+        if !self.stcsr.enable() || elapsed <= self.stcvr as u64 {
+            if self.stcsr.enable() {
+                self.stcvr -= elapsed as u32;
+            }
+            return false;
+        }
+        let elapsed = elapsed - self.stcvr as u64 - 1;
+        let reload = self.strvr.value;
+        self.stcvr = reload - (elapsed % (reload as u64 + 1)) as u32;
+        true
+    }
+
+    /// Requests a peripheral update at the cycle SysTick counter will be reloaded.
+    fn schedule_systick(&self, env: &mut Env) {
+        if self.stcsr.enable() {
+            env.request_update(self.stcvr);
+        }
+    }
+
+    /// Sets CPUID architecture field and ID_ISAR registers to the values of typical processors
+    /// of the given architecture version. ArmV6-M does not implement ID_ISAR registers, which
+    /// read as zero.
+    pub fn set_version(&mut self, version: ArmVersion) {
+        let architecture = match version {
+            ArmVersion::V6M => 0xc,
+            _ => 0xf,
+        };
+        self.cpuid.0 = self.cpuid.0 & 0xfff0ffff | architecture << 16;
+        self.id_isar = match version {
+            ArmVersion::V6M => [0; 6],
+            // Cortex-M3
+            ArmVersion::V7M => [
+                0x01141110, 0x02111000, 0x21112231, 0x01111110, 0x01310102, 0,
+            ],
+            // Cortex-M4
+            ArmVersion::V7EM => [
+                0x01141110, 0x02112000, 0x21232231, 0x01111110, 0x01310132, 0,
+            ],
+            // Cortex-M33
+            ArmVersion::V8M | ArmVersion::V81M => [
+                0x01101110, 0x02212000, 0x20232232, 0x01111131, 0x01310132, 0,
+            ],
+        };
+    }
+
+    /// Sets ID_ISAR5.PACBTI to report whether the PACBTI extension is implemented. The
+    /// authentication code algorithm is IMPLEMENTATION DEFINED.
+    pub fn set_pacbti(&mut self, pacbti: bool) {
+        self.id_isar[5] = self.id_isar[5] & 0xff0fffff | if pacbti { 0b0010 << 20 } else { 0 };
+    }
+
+    /// Returns the priority of a configurable exception, as defined in SHPR registers for system
+    /// exceptions or in NVIC_IPR registers for external interrupts.
+    ///
+    /// Panics if `irq` has a fixed priority (Reset, NMI or HardFault).
+    pub fn exception_priority(&self, irq: Irq) -> u8 {
+        let (registers, index) = match irq {
+            Irq::Reset | Irq::Nmi | Irq::HardFault => panic!("exception has fixed priority"),
+            Irq::External(n) => (self.nvic_ipr.as_slice(), n as usize),
+            _ => (self.shpr.as_slice(), irq.number() as usize - 4),
+        };
+        (registers[index / 4] >> (index % 4 * 8)) as u8
+    }
+
+    /// Returns `true` if external interrupt `n` is enabled in the NVIC.
+    pub fn is_interrupt_enabled(&self, n: u16) -> bool {
+        self.nvic_enable[n as usize / 32].bit(n as usize % 32)
+    }
+
+    /// Returns `true` if external interrupt `n` is pending.
+    pub fn is_interrupt_pending(&self, n: u16) -> bool {
+        self.nvic_pending[n as usize / 32].bit(n as usize % 32)
+    }
+
+    /// Returns `true` if external interrupt `n` is active.
+    pub fn is_interrupt_active(&self, n: u16) -> bool {
+        self.nvic_active[n as usize / 32].bit(n as usize % 32)
+    }
+
+    /// Updates NVIC pending and active bits of the external interrupts.
+    pub(crate) fn update_nvic_status(
+        &mut self,
+        pending: impl Iterator<Item = Irq>,
+        active: impl Iterator<Item = Irq>,
+    ) {
+        self.nvic_pending = interrupt_bits(pending);
+        self.nvic_active = interrupt_bits(active);
+    }
+
+    /// Returns the mask of the external interrupts implemented in the 32 interrupts NVIC register
+    /// `i`.
+    fn implemented_mask(&self, i: u8) -> u32 {
+        let count = self.external_interrupts.saturating_sub(i as usize * 32);
+        if count >= 32 {
+            0xffffffff
+        } else {
+            (1 << count) - 1
+        }
+    }
+
+    /// Returns `true` if external interrupt `n` targets the Non-secure state, as configured in
+    /// NVIC_ITNS registers.
+    pub fn is_interrupt_non_secure(&self, n: u16) -> bool {
+        self.nvic_itns[n as usize / 32].bit(n as usize % 32)
+    }
+
+    /// Returns the mask of the bits of the 32 interrupts NVIC register `i` which can be accessed
+    /// with the security state of `env`. Non-secure software can only access the interrupts
+    /// targeting the Non-secure state.
+    fn nvic_mask(&self, i: u8, env: &Env) -> u32 {
+        if env.secure {
+            0xffffffff
+        } else {
+            self.nvic_itns[i as usize]
+        }
+    }
+
+    /// Same as [Self::nvic_mask], for NVIC_IPR register `i` which holds the priorities of 4
+    /// interrupts.
+    fn nvic_ipr_mask(&self, i: u8, env: &Env) -> u32 {
+        let itns = self.nvic_mask(i / 8, env) >> (i % 8 * 4);
+        (0..4)
+            .filter(|j| itns.bit(*j))
+            .fold(0, |mask, j| mask | (0xff << (j * 8)))
+    }
+
+    /// Returns the VTOR register for the security state of `env`.
+    fn vtor_mut(&mut self, env: &Env) -> &mut Vtor {
+        if env.secure {
+            &mut self.vtor
+        } else {
+            &mut self.vtor_ns
+        }
+    }
+}
+
+/// Returns the NVIC register bits of the external interrupts in `irqs`.
+fn interrupt_bits(irqs: impl Iterator<Item = Irq>) -> [u32; 16] {
+    let mut bits = [0; 16];
+    for irq in irqs {
+        if let Irq::External(n) = irq {
+            bits[n as usize / 32].set_bit(n as usize % 32, true);
+        }
+    }
+    bits
 }
 
 impl Default for SystemControl {
     fn default() -> Self {
         Self {
+            actlr: 0,
             stcsr: Default::default(),
             strvr: MaskedRegister::new(0).reserved(0xff000000),
             stcvr: Default::default(),
+            systick_cycles: 0,
+            stcr: 0xc0000000,
             cpuid: Default::default(),
+            icsr: Default::default(),
             vtor: Default::default(),
+            vtor_ns: Default::default(),
             aircr: Default::default(),
+            scr: Default::default(),
             ccr: Default::default(),
             shpr: Default::default(),
             shcsr: Default::default(),
             cfsr: Default::default(),
+            hfsr: Default::default(),
+            dfsr: MaskedRegister::new(0)
+                .write_mask_reserved(0x1f)
+                .write_mask(0)
+                .clear_at_one(0x1f),
+            mmfar: 0,
+            bfar: 0,
+            id_isar: [0; 6],
             cpacr: Default::default(),
-            nvic_iser: Default::default(),
-            nvic_icer: Default::default(),
+            nvic_enable: Default::default(),
+            nvic_pending: Default::default(),
+            nvic_active: Default::default(),
+            nvic_itns: Default::default(),
             nvic_ipr: [0; 124],
+            security_extension: false,
+            external_interrupts: 0,
         }
     }
 }
@@ -335,35 +735,49 @@ impl Default for SystemControl {
 impl RegistersMemoryInterface for SystemControl {
     type Register = SystemControlRegister;
 
-    fn read32(&mut self, reg: SystemControlRegister, _env: &mut Env) -> MemoryReadResult<u32> {
+    fn read32(&mut self, reg: SystemControlRegister, env: &mut Env) -> MemoryReadResult<u32> {
         Ok(match reg {
-            SystemControlRegister::Actlr => todo!(),
+            SystemControlRegister::Ictr => {
+                (self.external_interrupts.div_ceil(32).max(1) - 1) as u32
+            }
+            SystemControlRegister::Actlr => self.actlr,
             SystemControlRegister::Stcsr => self.stcsr.read(),
             SystemControlRegister::Strvr => self.strvr.value,
-            SystemControlRegister::Stcvr => self.stcvr,
-            SystemControlRegister::Stcr => todo!(),
+            SystemControlRegister::Stcvr => {
+                self.advance_systick(env.cycles);
+                self.stcvr
+            }
+            SystemControlRegister::Stcr => self.stcr,
             SystemControlRegister::Cpuid => self.cpuid.0,
-            SystemControlRegister::Icsr => todo!(),
-            SystemControlRegister::Vtor => todo!(),
+            SystemControlRegister::Icsr => self.icsr.0,
+            SystemControlRegister::Vtor => self.vtor_mut(env).offset(),
             SystemControlRegister::Aircr => self.aircr.0,
-            SystemControlRegister::Scr => todo!(),
+            SystemControlRegister::Scr => self.scr.0,
             SystemControlRegister::Ccr => self.ccr.0,
             SystemControlRegister::Shpr(i) => self.shpr[i as usize],
             SystemControlRegister::Shcsr => self.shcsr.0,
             SystemControlRegister::Cfsr => self.cfsr.0,
-            SystemControlRegister::Hfsr => todo!(),
-            SystemControlRegister::Dfsr => todo!(),
-            SystemControlRegister::Mmfar => todo!(),
-            SystemControlRegister::Bfar => todo!(),
-            SystemControlRegister::IdIsar0 => todo!(),
-            SystemControlRegister::IdIsar1 => todo!(),
-            SystemControlRegister::IdIsar2 => todo!(),
-            SystemControlRegister::IdIsar3 => todo!(),
-            SystemControlRegister::IdIsar4 => todo!(),
+            SystemControlRegister::Hfsr => self.hfsr.0,
+            SystemControlRegister::Dfsr => self.dfsr.value,
+            SystemControlRegister::Mmfar => self.mmfar,
+            SystemControlRegister::Bfar => self.bfar,
+            SystemControlRegister::IdIsar(i) => self.id_isar[i as usize],
             SystemControlRegister::Cpacr => self.cpacr.0,
-            SystemControlRegister::NvicIser(i) => self.nvic_iser[i as usize],
-            SystemControlRegister::NvicIcer(i) => self.nvic_icer[i as usize],
-            SystemControlRegister::NvicIpr(i) => self.nvic_ipr[i as usize],
+            SystemControlRegister::NvicIser(i) | SystemControlRegister::NvicIcer(i) => {
+                self.nvic_enable[i as usize] & self.nvic_mask(i, env)
+            }
+            SystemControlRegister::NvicIspr(i) | SystemControlRegister::NvicIcpr(i) => {
+                self.nvic_pending[i as usize] & self.nvic_mask(i, env)
+            }
+            SystemControlRegister::NvicIabr(i) => {
+                self.nvic_active[i as usize] & self.nvic_mask(i, env)
+            }
+            // NVIC_ITNS is RAZ/WI from Non-secure state.
+            SystemControlRegister::NvicItns(i) if env.secure => self.nvic_itns[i as usize],
+            SystemControlRegister::NvicItns(_) => 0,
+            SystemControlRegister::NvicIpr(i) => {
+                self.nvic_ipr[i as usize] & self.nvic_ipr_mask(i, env)
+            }
         })
     }
 
@@ -374,34 +788,69 @@ impl RegistersMemoryInterface for SystemControl {
         env: &mut Env,
     ) -> MemoryWriteResult {
         match reg {
-            SystemControlRegister::Actlr => todo!(),
-            SystemControlRegister::Stcsr => self.stcsr.write(value)?,
+            // ICTR is read only
+            SystemControlRegister::Ictr => {}
+            SystemControlRegister::Actlr => self.actlr = value,
+            SystemControlRegister::Stcsr => {
+                self.advance_systick(env.cycles);
+                self.stcsr.write(value)?;
+                self.schedule_systick(env);
+            }
             SystemControlRegister::Strvr => self.strvr.write(value)?,
-            SystemControlRegister::Stcvr => self.stcvr = 0,
-            SystemControlRegister::Stcr => todo!(),
-            // CPUID is read only
-            SystemControlRegister::Cpuid => {}
-            SystemControlRegister::Icsr => todo!(),
-            SystemControlRegister::Vtor => self.vtor.write(value)?,
-            SystemControlRegister::Aircr => self.aircr.write(value, env)?,
-            SystemControlRegister::Scr => todo!(),
+            SystemControlRegister::Stcvr => {
+                self.advance_systick(env.cycles);
+                self.stcvr = 0;
+                self.schedule_systick(env);
+            }
+            // STCR, CPUID and ID_ISAR registers are read only
+            SystemControlRegister::Stcr
+            | SystemControlRegister::Cpuid
+            | SystemControlRegister::IdIsar(_) => {}
+            SystemControlRegister::Icsr => self.icsr.write(value, env),
+            SystemControlRegister::Vtor => self.vtor_mut(env).write(value)?,
+            SystemControlRegister::Aircr => {
+                self.aircr.write(value, self.security_extension, env)?
+            }
+            SystemControlRegister::Scr => self.scr.write(value, self.security_extension)?,
             SystemControlRegister::Ccr => self.ccr.write(value)?,
             SystemControlRegister::Shpr(i) => self.shpr[i as usize] = value,
-            SystemControlRegister::Shcsr => self.shcsr.write(value)?,
+            SystemControlRegister::Shcsr => self.shcsr.write(value, self.security_extension)?,
             SystemControlRegister::Cfsr => self.cfsr.write(value)?,
-            SystemControlRegister::Hfsr => todo!(),
-            SystemControlRegister::Dfsr => todo!(),
-            SystemControlRegister::Mmfar => todo!(),
-            SystemControlRegister::Bfar => todo!(),
-            SystemControlRegister::IdIsar0 => todo!(),
-            SystemControlRegister::IdIsar1 => todo!(),
-            SystemControlRegister::IdIsar2 => todo!(),
-            SystemControlRegister::IdIsar3 => todo!(),
-            SystemControlRegister::IdIsar4 => todo!(),
+            SystemControlRegister::Hfsr => self.hfsr.write(value)?,
+            SystemControlRegister::Dfsr => self.dfsr.write(value)?,
+            SystemControlRegister::Mmfar => self.mmfar = value,
+            SystemControlRegister::Bfar => self.bfar = value,
             SystemControlRegister::Cpacr => self.cpacr.write(value)?,
-            SystemControlRegister::NvicIser(i) => self.nvic_iser[i as usize] = value,
-            SystemControlRegister::NvicIcer(i) => self.nvic_icer[i as usize] = value,
-            SystemControlRegister::NvicIpr(i) => self.nvic_ipr[i as usize] = value,
+            SystemControlRegister::NvicIser(i) => {
+                self.nvic_enable[i as usize] |= value & self.nvic_mask(i, env)
+            }
+            SystemControlRegister::NvicIcer(i) => {
+                self.nvic_enable[i as usize] &= !(value & self.nvic_mask(i, env))
+            }
+            SystemControlRegister::NvicIspr(i) | SystemControlRegister::NvicIcpr(i) => {
+                let set = matches!(reg, SystemControlRegister::NvicIspr(_));
+                let value = value & self.nvic_mask(i, env) & self.implemented_mask(i);
+                for bit in (0..32).filter(|bit| value.bit(*bit)) {
+                    let irq = Irq::External(i as u16 * 32 + bit as u16);
+                    if set {
+                        env.request_interrupt(irq)
+                    } else {
+                        env.cancel_interrupt(irq)
+                    }
+                }
+            }
+            // NVIC_IABR is read only
+            SystemControlRegister::NvicIabr(_) => {}
+            SystemControlRegister::NvicItns(i) => {
+                if env.secure {
+                    self.nvic_itns[i as usize] = value
+                }
+            }
+            SystemControlRegister::NvicIpr(i) => {
+                let mask = self.nvic_ipr_mask(i, env);
+                let ipr = &mut self.nvic_ipr[i as usize];
+                *ipr = *ipr & !mask | value & mask
+            }
         }
         Ok(())
     }
@@ -410,17 +859,106 @@ impl RegistersMemoryInterface for SystemControl {
         0xd90 // Up to MPU area
     }
 
+    fn scheduled_updates(&self) -> bool {
+        true
+    }
+
     fn update(&mut self, env: &mut Env) {
+        // The counter is decremented at the end of each cycle.
+        if self.advance_systick(env.cycles + 1) && self.stcsr.tickint() {
+            env.request_interrupt(Irq::SysTick);
+        }
         if self.stcsr.enable() {
-            if self.stcvr > 0 {
-                self.stcvr -= 1;
-            } else {
-                self.stcvr = self.strvr.value;
-                if self.stcsr.tickint() {
-                    env.request_interrupt(Irq::SysTick);
-                }
-            }
+            env.request_update(self.stcvr + 1);
+        }
+    }
+}
+
+/// Non-secure alias of the System Control Space.
+///
+/// When the Security Extension is implemented, this alias is mapped at `0xe002e000` and gives
+/// Secure software access to the Non-secure view of the System Control Space, such as the
+/// Non-secure VTOR. It is RAZ/WI from Non-secure state.
+pub struct SystemControlNonSecureAlias(pub Rc<RefCell<SystemControl>>);
+
+impl RegistersMemoryInterface for SystemControlNonSecureAlias {
+    type Register = SystemControlRegister;
+
+    fn read32(&mut self, reg: SystemControlRegister, env: &mut Env) -> MemoryReadResult<u32> {
+        if !env.secure {
+            return Ok(0);
+        }
+        env.secure = false;
+        let result = self.0.borrow_mut().read32(reg, env);
+        env.secure = true;
+        result
+    }
+
+    fn write32(
+        &mut self,
+        reg: SystemControlRegister,
+        value: u32,
+        env: &mut Env,
+    ) -> MemoryWriteResult {
+        if !env.secure {
+            return Ok(());
+        }
+        env.secure = false;
+        let result = self.0.borrow_mut().write32(reg, value, env);
+        env.secure = true;
+        result
+    }
+
+    fn size(&self) -> u32 {
+        0xd90
+    }
+
+    fn scheduled_updates(&self) -> bool {
+        true
+    }
+
+    fn update(&mut self, env: &mut Env) {
+        // SysTick updates may be requested through this alias.
+        RegistersMemoryInterface::update(&mut *self.0.borrow_mut(), env)
+    }
+}
+
+/// STIR (Software Triggered Interrupt Register).
+///
+/// STIR is at `0xe000ef00`, apart from the other registers of [SystemControl] since the
+/// floating-point context control registers are mapped in between. Unprivileged writes are
+/// permitted only when CCR.USERSETMPEND is set.
+pub struct SoftwareTriggerInterrupt(pub Rc<RefCell<SystemControl>>);
+
+impl MemoryInterface for SoftwareTriggerInterrupt {
+    fn read_u32le(&mut self, _address: u32, _env: &mut Env) -> MemoryReadResult<u32> {
+        // Write-only register
+        Ok(0)
+    }
+
+    fn write_u32le(&mut self, _address: u32, value: u32, env: &mut Env) -> MemoryWriteResult {
+        let system_control = self.0.borrow();
+        if !env.privileged && !system_control.ccr.usersetmpend() {
+            return Err(MemoryAccessError::PrivilegedOnly);
+        }
+        if value & !0x1ff != 0 {
+            return Err(MemoryAccessError::InvalidValue);
         }
+        let n = value as u16;
+        if (n as usize) < system_control.external_interrupts
+            && (env.secure || system_control.is_interrupt_non_secure(n))
+        {
+            env.request_interrupt(Irq::External(n));
+        }
+        Ok(())
+    }
+
+    fn size(&self) -> u32 {
+        4
+    }
+
+    fn scheduled_updates(&self) -> bool {
+        true
     }
 }
 
@@ -486,18 +1024,59 @@ impl Default for CpuId {
     }
 }
 
+/// HFSR (HardFault Status Register).
+#[derive(Default)]
+pub struct Hfsr(u32);
+
+impl Hfsr {
+    /// Clears the bits written to one.
+    /// Returns [MemoryAccessError::InvalidValue] when attempting to write a reserved bit.
+    pub fn write(&mut self, value: u32) -> MemoryWriteResult {
+        if value & !0xc0000002 != 0 {
+            return Err(MemoryAccessError::InvalidValue);
+        }
+        self.0 &= !value;
+        Ok(())
+    }
+
+    /// Returns VECTTBL bit value.
+    pub fn vecttbl(&self) -> bool {
+        self.0.bit(1)
+    }
+
+    /// Sets VECTTBL bit value.
+    pub fn set_vecttbl(&mut self, value: bool) {
+        self.0.set_bit(1, value)
+    }
+
+    /// Returns FORCED bit value.
+    pub fn forced(&self) -> bool {
+        self.0.bit(30)
+    }
+
+    /// Sets FORCED bit value.
+    pub fn set_forced(&mut self, value: bool) {
+        self.0.set_bit(30, value)
+    }
+
+    /// Returns DEBUGEVT bit value.
+    pub fn debugevt(&self) -> bool {
+        self.0.bit(31)
+    }
+}
+
 /// CFSR (Configurable Fault Status Register).
 #[derive(Default)]
 pub struct Cfsr(u32);
 
 impl Cfsr {
-    /// Sets new register value.
+    /// Clears the bits written to one.
     /// Returns [MemoryAccessError::InvalidValue] when attempting to write a reserved bit.
     pub fn write(&mut self, value: u32) -> MemoryWriteResult {
-        if value & !0x030fbfbb != 0 {
+        if value & !0x031fbfbb != 0 {
             return Err(MemoryAccessError::InvalidValue);
         }
-        self.0 = value;
+        self.0 &= !value;
         Ok(())
     }
 
@@ -671,6 +1250,16 @@ impl Cfsr {
         self.0.set_bit(19, value)
     }
 
+    /// Returns STKOF bit value.
+    pub fn stkof(&self) -> bool {
+        self.0.bit(20)
+    }
+
+    /// Sets STKOF bit value.
+    pub fn set_stkof(&mut self, value: bool) {
+        self.0.set_bit(20, value)
+    }
+
     /// Returns UNALIGNED bit value.
     pub fn unaligned(&self) -> bool {
         self.0.bit(24)
@@ -694,9 +1283,13 @@ impl Cfsr {
 
 #[cfg(test)]
 mod tests {
-    use crate::{memory::MemoryAccessError, system_control::CpuId};
+    use crate::{
+        core::{ArmVersion, Irq},
+        memory::{Env, MemoryAccessError, MemoryOpAction, RegistersMemoryInterface},
+        system_control::CpuId,
+    };
 
-    use super::Vtor;
+    use super::{SystemControl, SystemControlRegister, Vtor};
 
     #[test]
     fn test_vtor_register() {
@@ -717,4 +1310,83 @@ mod tests {
         reg.set_implementer(0xaa);
         assert_eq!(reg.0, 0xaa8f1f25);
     }
+
+    #[test]
+    fn test_fault_status_registers() {
+        let mut scb = SystemControl::new();
+        let mut env = Env::new(0, true);
+        scb.cfsr.set_undefinstr(true);
+        scb.cfsr.set_preciserr(true);
+        scb.dfsr.value = 0x1b;
+        scb.write32(SystemControlRegister::Cfsr, 1 << 16, &mut env)
+            .unwrap();
+        scb.write32(SystemControlRegister::Dfsr, 0x03, &mut env)
+            .unwrap();
+        assert_eq!(
+            scb.read32(SystemControlRegister::Cfsr, &mut env),
+            Ok(1 << 9)
+        );
+        assert_eq!(scb.read32(SystemControlRegister::Dfsr, &mut env), Ok(0x18));
+        assert_eq!(
+            scb.write32(SystemControlRegister::Dfsr, 0x20, &mut env),
+            Err(MemoryAccessError::InvalidValue)
+        );
+    }
+
+    #[test]
+    fn test_id_registers() {
+        let mut scb = SystemControl::new();
+        let mut env = Env::new(0, true);
+        scb.set_version(ArmVersion::V7EM);
+        assert_eq!(
+            scb.read32(SystemControlRegister::Cpuid, &mut env),
+            Ok(0x410f0000)
+        );
+        assert_eq!(
+            scb.read32(SystemControlRegister::IdIsar(4), &mut env),
+            Ok(0x01310132)
+        );
+        scb.set_version(ArmVersion::V6M);
+        assert_eq!(
+            scb.read32(SystemControlRegister::Cpuid, &mut env),
+            Ok(0x410c0000)
+        );
+        assert_eq!(
+            scb.read32(SystemControlRegister::IdIsar(0), &mut env),
+            Ok(0)
+        );
+    }
+
+    #[test]
+    fn test_systick() {
+        let mut scb = SystemControl::new();
+        let mut env = Env::new(0, true);
+        scb.write32(SystemControlRegister::Strvr, 3, &mut env)
+            .unwrap();
+        scb.write32(SystemControlRegister::Stcsr, 3, &mut env)
+            .unwrap();
+        // Counter is clear, so it is reloaded at the end of the first cycle, and an update is
+        // requested for the next reload.
+        scb.update(&mut env);
+        assert!(matches!(
+            env.actions[..],
+            [
+                MemoryOpAction::Update(0),
+                MemoryOpAction::Irq(Irq::SysTick),
+                MemoryOpAction::Update(4)
+            ]
+        ));
+        // Counter value only depends on the elapsed cycles.
+        env.cycles = 3;
+        assert_eq!(scb.read32(SystemControlRegister::Stcvr, &mut env), Ok(1));
+        env.cycles = 11;
+        assert_eq!(scb.read32(SystemControlRegister::Stcvr, &mut env), Ok(1));
+        env.cycles = 12;
+        assert_eq!(scb.read32(SystemControlRegister::Stcvr, &mut env), Ok(0));
+        // Disabled counter does not change.
+        scb.write32(SystemControlRegister::Stcsr, 0, &mut env)
+            .unwrap();
+        env.cycles = 100;
+        assert_eq!(scb.read32(SystemControlRegister::Stcvr, &mut env), Ok(0));
+    }
 }
@@ -0,0 +1,769 @@
+//! M-profile Vector Extension (MVE) helpers.
+//!
+//! Vector instructions are executed in four beats, each beat processing 32 bits of the 128-bit
+//! vector registers, as a word holding 4, 2 or 1 element of 8, 16 or 32 bits. Predication is
+//! applied with a mask holding one bit per byte of the beat. These helpers extract and insert the
+//! elements of a beat word and apply the masks.
+
+use crate::{
+    core::{Effect, Processor, RunError},
+    decoder::DecodeError,
+    float::fp_compare,
+    helpers::BitAccess,
+    instructions::{indexing_args, other, undefined, unpredictable, DecodeHelper},
+    registers::{CoreRegisters, FloatingPointStatusControlRegister as Fpscr, RegisterIndex},
+};
+use std::fmt::Display;
+
+/// Returns the number of elements of `esize` bits in a beat.
+pub fn elements_per_beat(esize: u32) -> u32 {
+    32 / esize
+}
+
+/// Returns the value of element `index` of `esize` bits in the beat `word`.
+pub fn element(word: u32, esize: u32, index: u32) -> u32 {
+    if esize == 32 {
+        word
+    } else {
+        (word >> (index * esize)) & ((1 << esize) - 1)
+    }
+}
+
+/// Returns the beat `word` with element `index` of `esize` bits replaced by the `esize` least
+/// significant bits of `value`.
+pub fn with_element(word: u32, esize: u32, index: u32, value: u32) -> u32 {
+    if esize == 32 {
+        value
+    } else {
+        let mask = ((1 << esize) - 1) << (index * esize);
+        word & !mask | (value << (index * esize)) & mask
+    }
+}
+
+/// Returns the byte mask bits covering element `index` of `esize` bits.
+pub fn element_mask(esize: u32, index: u32) -> u8 {
+    let bytes = esize / 8;
+    (((1 << bytes) - 1) << (index * bytes)) as u8
+}
+
+/// Returns `true` if element `index` of `esize` bits is active in the byte `mask`.
+pub fn element_active(mask: u8, esize: u32, index: u32) -> bool {
+    mask & (1 << (index * esize / 8)) != 0
+}
+
+/// Returns the bytes of `new` whose bit is set in `mask`, and the bytes of `old` otherwise.
+pub fn merge(old: u32, new: u32, mask: u8) -> u32 {
+    let mask = (0..4)
+        .filter(|i| mask & (1 << i) != 0)
+        .fold(0, |acc, i| acc | 0xff << (i * 8));
+    new & mask | old & !mask
+}
+
+/// Sign-extends the `bits` least significant bits of `value`.
+pub fn sign_extend(value: u32, bits: u32) -> i32 {
+    let shift = 32 - bits;
+    ((value << shift) as i32) >> shift
+}
+
+/// Decodes a vector register index from the 3 bits at `lsb_index` of `ins`. The bit at
+/// `high_index` would select registers Q8 to Q15, which don't exist, so it is UNDEFINED when set.
+pub fn qreg(ins: u32, lsb_index: u8, high_index: u8) -> Result<u8, DecodeError> {
+    undefined(ins.bit(high_index as usize))?;
+    Ok(ins.imm3(lsb_index) as u8)
+}
+
+/// Decodes the VPT block mask from bit 22 and bits 15 to 13 of VPT and VPST instructions.
+pub fn vpt_mask(ins: u32) -> u8 {
+    ((ins.bit(22) as u8) << 3) | ins.imm3(13) as u8
+}
+
+/// Returns the then/else suffix of VPT and VPST instructions for the given block `mask`, such as
+/// "tte" for a VPST with mask `0b1010`.
+///
+/// Each mask bit above the lowest set one gives an additional instruction in the block, and a
+/// set bit inverts the predicate for the next instruction.
+pub fn vpt_suffix(mask: u8) -> String {
+    let mut suffix = String::from("t");
+    let mut current = 't';
+    let mut mask = mask;
+    while mask & 0b0111 != 0 {
+        if mask & 0b1000 != 0 {
+            current = if current == 't' { 'e' } else { 't' };
+        }
+        suffix.push(current);
+        mask = (mask << 1) & 0xf;
+    }
+    suffix
+}
+
+/// Returns a beat word with all its elements of `esize` bits set to the `esize` least
+/// significant bits of `value`.
+pub fn duplicate(value: u32, esize: u32) -> u32 {
+    (0..elements_per_beat(esize)).fold(0, |word, i| with_element(word, esize, i, value))
+}
+
+/// Returns the data type suffix of instructions operating on integer or floating-point elements,
+/// such as "i32" or "f16".
+pub fn int_or_float_type(float: bool, esize: u32) -> String {
+    format!("{}{esize}", if float { 'f' } else { 'i' })
+}
+
+/// Reads an element of `msize` bits from memory for a vector load, zero-extended to 32 bits.
+/// Vector accesses must be aligned to the element size.
+pub fn read_element(proc: &mut Processor, address: u32, msize: u32) -> Result<u32, RunError> {
+    match msize {
+        8 => proc.read_u8(address).map(u32::from),
+        16 => proc.read_u16_aligned(address).map(u32::from),
+        _ => proc.read_u32_aligned(address),
+    }
+}
+
+/// Writes the `msize` least significant bits of `value` to memory for a vector store. Vector
+/// accesses must be aligned to the element size.
+pub fn write_element(
+    proc: &mut Processor,
+    address: u32,
+    msize: u32,
+    value: u32,
+) -> Result<(), RunError> {
+    match msize {
+        8 => proc.write_u8(address, value as u8),
+        16 => proc.write_u16_aligned(address, value as u16),
+        _ => proc.write_u32_aligned(address, value),
+    }
+}
+
+/// Executes the beats of an element-wise vector operation, setting the active elements of
+/// vector register `qd` to `op(a, b, fpscr)`, where `a` is the element of vector register `qn`
+/// and `b` the element of `operand`. Inactive elements of `qd` are unchanged.
+fn execute_elementwise<F>(
+    proc: &mut Processor,
+    qd: u8,
+    qn: u8,
+    operand: VectorOperand,
+    esize: u32,
+    mut op: F,
+) -> Result<(), RunError>
+where
+    F: FnMut(u32, u32, &mut Fpscr) -> u32,
+{
+    let scalar = operand.scalar_word(&proc.registers, esize);
+    proc.execute_beats(|proc, beat, mask| {
+        let regs = &mut proc.registers;
+        let n = regs.s[qn as usize * 4 + beat];
+        let m = scalar.unwrap_or_else(|| operand.beat_word(regs, beat));
+        let mut result = regs.s[qd as usize * 4 + beat];
+        for i in 0..elements_per_beat(esize) {
+            if element_active(mask, esize, i) {
+                let value = op(element(n, esize, i), element(m, esize, i), &mut regs.fpscr);
+                result = with_element(result, esize, i, value);
+            }
+        }
+        regs.s[qd as usize * 4 + beat] = result;
+        Ok(())
+    })
+}
+
+/// Second operand of vector instructions, which can be a vector register or a general-purpose
+/// register whose value is used for all the elements.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VectorOperand {
+    /// Vector register index.
+    Vector(u8),
+    /// General-purpose register holding the scalar value.
+    Scalar(RegisterIndex),
+}
+
+impl VectorOperand {
+    /// For a scalar operand, returns the beat word with all elements set to the register value.
+    /// PC is the zero register of VCMP and VPT instructions.
+    fn scalar_word(&self, regs: &CoreRegisters, esize: u32) -> Option<u32> {
+        match self {
+            VectorOperand::Vector(_) => None,
+            VectorOperand::Scalar(r) if r.is_pc() => Some(0),
+            VectorOperand::Scalar(r) => Some(duplicate(regs[*r], esize)),
+        }
+    }
+
+    /// Returns the beat word of a vector operand, or the duplicated value of a scalar operand.
+    fn beat_word(&self, regs: &CoreRegisters, beat: usize) -> u32 {
+        match self {
+            VectorOperand::Vector(q) => regs.s[*q as usize * 4 + beat],
+            VectorOperand::Scalar(_) => panic!(),
+        }
+    }
+}
+
+impl Display for VectorOperand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VectorOperand::Vector(q) => write!(f, "q{q}"),
+            VectorOperand::Scalar(r) if r.is_pc() => write!(f, "zr"),
+            VectorOperand::Scalar(r) => write!(f, "{r}"),
+        }
+    }
+}
+
+/// Comparison condition of VCMP and VPT instructions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VectorCondition {
+    /// Equal.
+    Eq,
+    /// Not equal.
+    Ne,
+    /// Unsigned higher or same.
+    Cs,
+    /// Unsigned higher.
+    Hi,
+    /// Signed greater than or equal.
+    Ge,
+    /// Signed less than.
+    Lt,
+    /// Signed greater than.
+    Gt,
+    /// Signed less than or equal.
+    Le,
+}
+
+impl VectorCondition {
+    /// Decodes the condition from the three `fc` bits of VCMP and VPT instructions encodings,
+    /// given from the most significant to the least significant.
+    pub fn from_fc(fc2: bool, fc1: bool, fc0: bool) -> Self {
+        match (fc2, fc1, fc0) {
+            (false, false, false) => Self::Eq,
+            (false, false, true) => Self::Ne,
+            (false, true, false) => Self::Cs,
+            (false, true, true) => Self::Hi,
+            (true, false, false) => Self::Ge,
+            (true, false, true) => Self::Lt,
+            (true, true, false) => Self::Gt,
+            (true, true, true) => Self::Le,
+        }
+    }
+
+    /// Returns `true` for unsigned conditions, which are not defined for floating-point
+    /// comparisons.
+    pub fn is_unsigned(self) -> bool {
+        matches!(self, Self::Cs | Self::Hi)
+    }
+
+    /// Returns `true` for signed conditions.
+    pub fn is_signed(self) -> bool {
+        matches!(self, Self::Ge | Self::Lt | Self::Gt | Self::Le)
+    }
+
+    /// Tests the condition on integer elements `a` and `b` of `esize` bits.
+    pub fn test_int(self, a: u32, b: u32, esize: u32) -> bool {
+        let (sa, sb) = (sign_extend(a, esize), sign_extend(b, esize));
+        match self {
+            Self::Eq => a == b,
+            Self::Ne => a != b,
+            Self::Cs => a >= b,
+            Self::Hi => a > b,
+            Self::Ge => sa >= sb,
+            Self::Lt => sa < sb,
+            Self::Gt => sa > sb,
+            Self::Le => sa <= sb,
+        }
+    }
+
+    /// Tests the condition on floating-point elements `a` and `b` of `esize` bits, updating
+    /// FPSCR cumulative exception flags. Unordered operands only pass the [VectorCondition::Ne],
+    /// [VectorCondition::Lt] and [VectorCondition::Le] conditions.
+    pub fn test_float(self, a: u32, b: u32, esize: u32, fpscr: &mut Fpscr) -> bool {
+        let signal_nans = self.is_signed();
+        let nzcv = if esize == 16 {
+            fp_compare(a as u16, b as u16, signal_nans, fpscr)
+        } else {
+            fp_compare(a, b, signal_nans, fpscr)
+        };
+        let (n, z, v) = (nzcv & 8 != 0, nzcv & 4 != 0, nzcv & 1 != 0);
+        match self {
+            Self::Eq => z,
+            Self::Ne => !z,
+            Self::Ge => n == v,
+            Self::Lt => n != v,
+            Self::Gt => !z && n == v,
+            Self::Le => z || n != v,
+            Self::Cs | Self::Hi => panic!("unsigned floating-point comparison"),
+        }
+    }
+}
+
+impl Display for VectorCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Eq => "eq",
+            Self::Ne => "ne",
+            Self::Cs => "cs",
+            Self::Hi => "hi",
+            Self::Ge => "ge",
+            Self::Lt => "lt",
+            Self::Gt => "gt",
+            Self::Le => "le",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// Operands of element-wise vector operations such as VADD, VSUB or VMUL.
+pub struct VectorOperation {
+    /// Destination vector register.
+    pub qd: u8,
+    /// First operand vector register.
+    pub qn: u8,
+    /// Second operand.
+    pub operand: VectorOperand,
+    /// Element size in bits.
+    pub esize: u32,
+    /// `true` for floating-point elements.
+    pub float: bool,
+}
+
+impl VectorOperation {
+    /// Decodes the operands of an element-wise operation encoding. For floating-point
+    /// operations, half-precision is selected by bit 28 in scalar encodings and by bit 20 in
+    /// vector encodings. For integer operations, the element size is given by bits 21 and 20.
+    pub fn decode(ins: u32, float: bool, scalar: bool) -> Result<Self, DecodeError> {
+        let esize = if float {
+            if ins.bit(if scalar { 28 } else { 20 }) {
+                16
+            } else {
+                32
+            }
+        } else {
+            let size = ins.imm2(20);
+            other(size == 3)?;
+            8 << size
+        };
+        let operand = if scalar {
+            let rm = ins.reg4(0);
+            unpredictable(rm.is_sp_or_pc())?;
+            VectorOperand::Scalar(rm)
+        } else {
+            VectorOperand::Vector(qreg(ins, 1, 5)?)
+        };
+        Ok(Self {
+            qd: qreg(ins, 13, 22)?,
+            qn: qreg(ins, 17, 7)?,
+            operand,
+            esize,
+            float,
+        })
+    }
+
+    /// Executes the operation, setting the active elements of the destination register to
+    /// `op(a, b, fpscr)`. See [execute_elementwise].
+    pub fn execute<F>(&self, proc: &mut Processor, op: F) -> Result<Effect, RunError>
+    where
+        F: FnMut(u32, u32, &mut Fpscr) -> u32,
+    {
+        proc.execute_mve_check(self.float)?;
+        execute_elementwise(proc, self.qd, self.qn, self.operand, self.esize, op)?;
+        Ok(Effect::None)
+    }
+
+    /// Returns the instruction name with the data type suffix, such as "vadd.i8".
+    pub fn name(&self, mnemonic: &str) -> String {
+        format!("{mnemonic}.{}", int_or_float_type(self.float, self.esize))
+    }
+
+    /// Returns the instruction arguments string, such as "q0, q1, r2".
+    pub fn args(&self) -> String {
+        format!("q{}, q{}, {}", self.qd, self.qn, self.operand)
+    }
+}
+
+/// Comparison of VCMP and VPT instructions.
+pub struct VectorComparison {
+    /// First operand vector register.
+    pub qn: u8,
+    /// Second operand.
+    pub operand: VectorOperand,
+    /// Comparison condition.
+    pub cond: VectorCondition,
+    /// Element size in bits.
+    pub esize: u32,
+    /// `true` for floating-point elements.
+    pub float: bool,
+}
+
+impl VectorComparison {
+    /// Decodes the comparison fields shared by VCMP and VPT encodings.
+    pub fn decode(ins: u32) -> Result<Self, DecodeError> {
+        let size = ins.imm2(20);
+        let float = size == 3;
+        other(!float && !ins.bit(28))?;
+        let esize = match (float, ins.bit(28)) {
+            (true, true) => 16,
+            (true, false) => 32,
+            (false, _) => 8 << size,
+        };
+        let scalar = ins.bit(6);
+        let operand = if scalar {
+            let rm = ins.reg4(0);
+            other(rm.is_sp())?;
+            VectorOperand::Scalar(rm)
+        } else {
+            other(ins.bit(5))?;
+            VectorOperand::Vector(ins.imm3(1) as u8)
+        };
+        let fc1 = if scalar { ins.bit(5) } else { ins.bit(0) };
+        let cond = VectorCondition::from_fc(ins.bit(12), fc1, ins.bit(7));
+        other(float && cond.is_unsigned())?;
+        Ok(Self {
+            qn: ins.imm3(17) as u8,
+            operand,
+            cond,
+            esize,
+            float,
+        })
+    }
+
+    /// Compares the elements of a beat, and returns the predicate flags of the beat, one per
+    /// byte.
+    pub fn compare_beat(&self, regs: &mut CoreRegisters, beat: usize) -> u8 {
+        let n = regs.s[self.qn as usize * 4 + beat];
+        let m = self
+            .operand
+            .scalar_word(regs, self.esize)
+            .unwrap_or_else(|| self.operand.beat_word(regs, beat));
+        let mut predicate = 0;
+        for i in 0..elements_per_beat(self.esize) {
+            let (a, b) = (element(n, self.esize, i), element(m, self.esize, i));
+            let pass = if self.float {
+                self.cond.test_float(a, b, self.esize, &mut regs.fpscr)
+            } else {
+                self.cond.test_int(a, b, self.esize)
+            };
+            if pass {
+                predicate |= element_mask(self.esize, i);
+            }
+        }
+        predicate
+    }
+
+    /// Returns the data type suffix of the instruction, such as "i8", "u16", "s32" or "f32".
+    pub fn data_type(&self) -> String {
+        let prefix = if self.float {
+            'f'
+        } else if self.cond.is_unsigned() {
+            'u'
+        } else if self.cond.is_signed() {
+            's'
+        } else {
+            'i'
+        };
+        format!("{prefix}{}", self.esize)
+    }
+
+    /// Returns the instruction arguments string, such as "gt, q0, q1".
+    pub fn args(&self) -> String {
+        format!("{}, q{}, {}", self.cond, self.qn, self.operand)
+    }
+}
+
+/// Returns the mnemonic of vector loads and stores, such as "vldrb.s16" or "vstrh.32".
+fn access_name(load: bool, msize: u32, esize: u32, unsigned: bool) -> String {
+    let letter = match msize {
+        8 => 'b',
+        16 => 'h',
+        _ => 'w',
+    };
+    if load {
+        let sign = if unsigned { 'u' } else { 's' };
+        format!("vldr{letter}.{sign}{esize}")
+    } else {
+        format!("vstr{letter}.{esize}")
+    }
+}
+
+/// Contiguous vector load or store, accessing consecutive elements in memory at an address
+/// given by a base register and an immediate offset.
+pub struct ContiguousAccess {
+    /// Vector register loaded or stored.
+    pub qd: u8,
+    /// Base register.
+    pub rn: RegisterIndex,
+    /// Offset from base.
+    pub imm32: u32,
+    /// `true` to apply the offset before the access, `false` after (post-indexed).
+    pub index: bool,
+    /// `true` if the offset is added, `false` if subtracted.
+    pub add: bool,
+    /// `true` to write the offset address back to the base register.
+    pub wback: bool,
+    /// Memory access size of each element in bits.
+    pub msize: u32,
+    /// Element size in bits. Larger than `msize` for widening loads and narrowing stores.
+    pub esize: u32,
+    /// `true` to zero-extend loaded elements, `false` to sign-extend them.
+    pub unsigned: bool,
+}
+
+impl ContiguousAccess {
+    /// Decodes the fields of a contiguous load or store encoding. When `widening` is `false`,
+    /// memory accesses and elements have the same size. Otherwise, this is a widening load or
+    /// narrowing store encoding, which only supports R0 to R7 as base register.
+    pub fn decode(ins: u32, widening: bool) -> Result<Self, DecodeError> {
+        let (index, add, wback) = (ins.bit(24), ins.bit(23), ins.bit(21));
+        other(!index && !wback)?;
+        let size = ins.imm2(7);
+        let (rn, msize, esize, unsigned): (_, u32, u32, _) = if widening {
+            let msize = if ins.bit(19) { 16 } else { 8 };
+            let esize = 8 << size;
+            other(esize <= msize || size == 3)?;
+            let unsigned = ins.bit(28);
+            undefined(unsigned && !ins.bit(20))?;
+            (ins.reg3(16), msize, esize, unsigned)
+        } else {
+            other(size == 3)?;
+            (ins.reg4(16), 8 << size, 8 << size, true)
+        };
+        unpredictable(rn.is_pc())?;
+        Ok(Self {
+            qd: qreg(ins, 13, 22)?,
+            rn,
+            imm32: ins.imm7(0) << (msize / 8).trailing_zeros(),
+            index,
+            add,
+            wback,
+            msize,
+            esize,
+            unsigned,
+        })
+    }
+
+    /// Returns the address of the first element, and the offset address for write-back.
+    pub fn addresses(&self, regs: &CoreRegisters) -> (u32, u32) {
+        let base = regs[self.rn];
+        let offset_addr = if self.add {
+            base.wrapping_add(self.imm32)
+        } else {
+            base.wrapping_sub(self.imm32)
+        };
+        (if self.index { offset_addr } else { base }, offset_addr)
+    }
+
+    /// Returns the address of element `index` of the given beat, `start` being the address of
+    /// the first element.
+    pub fn element_address(&self, start: u32, beat: usize, index: u32) -> u32 {
+        let element = beat as u32 * elements_per_beat(self.esize) + index;
+        start.wrapping_add(element * self.msize / 8)
+    }
+
+    /// Returns the instruction name, such as "vldrb.s16" or "vstrw.32".
+    pub fn name(&self, load: bool) -> String {
+        access_name(load, self.msize, self.esize, self.unsigned)
+    }
+
+    /// Returns the instruction arguments string, such as "q0, [r1, #-12]!".
+    pub fn args(&self) -> String {
+        let address = indexing_args(self.rn, self.imm32, false, self.index, self.add, self.wback);
+        format!("q{}, {address}", self.qd)
+    }
+}
+
+/// Vector gather load or scatter store, accessing elements at addresses given by a vector.
+pub struct GatherAccess {
+    /// Vector register loaded or stored.
+    pub qd: u8,
+    /// Addressing mode.
+    pub addressing: GatherAddressing,
+    /// Memory access size of each element in bits.
+    pub msize: u32,
+    /// Element size in bits.
+    pub esize: u32,
+    /// `true` to zero-extend loaded elements, `false` to sign-extend them.
+    pub unsigned: bool,
+}
+
+impl GatherAccess {
+    /// Decodes the fields of a gather load or scatter store encoding. When `vector_base` is
+    /// `true`, addresses are given by a vector of base addresses plus an immediate offset,
+    /// otherwise by a base register plus a vector of offsets.
+    pub fn decode(ins: u32, vector_base: bool) -> Result<Self, DecodeError> {
+        let load = ins.bit(20);
+        let qd = qreg(ins, 13, 22)?;
+        let access = if vector_base {
+            let (add, wback) = (ins.bit(23), ins.bit(21));
+            let qm = ins.imm3(17) as u8;
+            unpredictable(load && qd == qm)?;
+            Self {
+                qd,
+                addressing: GatherAddressing::Bases {
+                    qm,
+                    imm32: ins.imm7(0) << 2,
+                    add,
+                    wback,
+                },
+                msize: 32,
+                esize: 32,
+                unsigned: true,
+            }
+        } else {
+            let size = ins.imm2(7);
+            let msz = ((ins.bit(6) as u32) << 1) | ins.bit(4) as u32;
+            other(size == 3 || msz == 3)?;
+            let (esize, msize) = (8 << size, 8 << msz);
+            let unsigned = ins.bit(28);
+            let scaled = ins.bit(0);
+            undefined(msize > esize)?;
+            undefined(scaled && msize == 8)?;
+            undefined(load && !unsigned && msize == esize)?;
+            undefined(!load && unsigned)?;
+            let rn = ins.reg4(16);
+            let qm = qreg(ins, 1, 5)?;
+            unpredictable(rn.is_pc())?;
+            unpredictable(load && qd == qm)?;
+            Self {
+                qd,
+                addressing: GatherAddressing::Offsets { rn, qm, scaled },
+                msize,
+                esize,
+                unsigned,
+            }
+        };
+        Ok(access)
+    }
+
+    /// Returns the address of element `index` of the given beat.
+    pub fn element_address(&self, regs: &CoreRegisters, beat: usize, index: u32) -> u32 {
+        self.addressing
+            .address(regs, beat, self.esize, index, self.msize)
+    }
+
+    /// Returns the instruction name, such as "vldrh.s32" or "vstrw.32".
+    pub fn name(&self, load: bool) -> String {
+        access_name(load, self.msize, self.esize, self.unsigned)
+    }
+
+    /// Returns the instruction arguments string, such as "q0, [r1, q2, uxtw #1]".
+    pub fn args(&self) -> String {
+        format!("q{}, {}", self.qd, self.addressing.args(self.msize))
+    }
+}
+
+/// Addressing mode of vector gather loads and scatter stores.
+pub enum GatherAddressing {
+    /// Base register plus a vector of unsigned offsets, optionally scaled by the memory access
+    /// size.
+    Offsets {
+        /// Base register.
+        rn: RegisterIndex,
+        /// Offsets vector register.
+        qm: u8,
+        /// `true` if offsets are shifted left by the memory access size.
+        scaled: bool,
+    },
+    /// Vector of base addresses plus an immediate offset, with optional write-back of the
+    /// addresses to the vector.
+    Bases {
+        /// Base addresses vector register.
+        qm: u8,
+        /// Immediate offset.
+        imm32: u32,
+        /// `true` if the offset is added, `false` if subtracted.
+        add: bool,
+        /// `true` for write-back.
+        wback: bool,
+    },
+}
+
+impl GatherAddressing {
+    /// Returns the address of element `index` of `esize` bits in the given beat, for memory
+    /// accesses of `msize` bits.
+    pub fn address(
+        &self,
+        regs: &CoreRegisters,
+        beat: usize,
+        esize: u32,
+        index: u32,
+        msize: u32,
+    ) -> u32 {
+        match *self {
+            GatherAddressing::Offsets { rn, qm, scaled } => {
+                let offset = element(regs.s[qm as usize * 4 + beat], esize, index);
+                let shift = if scaled {
+                    (msize / 8).trailing_zeros()
+                } else {
+                    0
+                };
+                regs[rn].wrapping_add(offset << shift)
+            }
+            GatherAddressing::Bases { qm, imm32, add, .. } => {
+                let base = regs.s[qm as usize * 4 + beat];
+                if add {
+                    base.wrapping_add(imm32)
+                } else {
+                    base.wrapping_sub(imm32)
+                }
+            }
+        }
+    }
+
+    /// Writes back the addresses of the elements of a beat, for the vector of base addresses
+    /// addressing mode with write-back.
+    pub fn write_back(&self, regs: &mut CoreRegisters, beat: usize) {
+        if let GatherAddressing::Bases {
+            qm, wback: true, ..
+        } = *self
+        {
+            regs.s[qm as usize * 4 + beat] = self.address(regs, beat, 32, 0, 32);
+        }
+    }
+
+    /// Returns the memory operand string, such as "[r0, q1, uxtw #2]" or "[q1, #-8]!".
+    pub fn args(&self, msize: u32) -> String {
+        match *self {
+            GatherAddressing::Offsets { rn, qm, scaled } => {
+                if scaled {
+                    let shift = (msize / 8).trailing_zeros();
+                    format!("[{rn}, q{qm}, uxtw #{shift}]")
+                } else {
+                    format!("[{rn}, q{qm}]")
+                }
+            }
+            GatherAddressing::Bases {
+                qm,
+                imm32,
+                add,
+                wback,
+            } => {
+                let neg = if add { "" } else { "-" };
+                let imm = if imm32 != 0 || !add {
+                    format!(", #{neg}{imm32}")
+                } else {
+                    "".into()
+                };
+                let wback = if wback { "!" } else { "" };
+                format!("[q{qm}{imm}]{wback}")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{duplicate, element, element_mask, merge, vpt_suffix, with_element};
+
+    #[test]
+    fn test_elements() {
+        assert_eq!(element(0x11223344, 8, 1), 0x33);
+        assert_eq!(element(0x11223344, 16, 1), 0x1122);
+        assert_eq!(with_element(0x11223344, 8, 2, 0x1ff), 0x11ff3344);
+        assert_eq!(with_element(0x11223344, 16, 0, 0xabcd), 0x1122abcd);
+        assert_eq!(element_mask(16, 1), 0b1100);
+        assert_eq!(merge(0x11223344, 0xaabbccdd, 0b0101), 0x11bb33dd);
+        assert_eq!(duplicate(0x1234, 8), 0x34343434);
+    }
+
+    #[test]
+    fn test_vpt_suffix() {
+        assert_eq!(vpt_suffix(0b1000), "t");
+        assert_eq!(vpt_suffix(0b1100), "te");
+        assert_eq!(vpt_suffix(0b1110), "tet");
+        assert_eq!(vpt_suffix(0b0101), "ttee");
+        assert_eq!(vpt_suffix(0b1011), "teet");
+    }
+}
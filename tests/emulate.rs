@@ -1,8 +1,12 @@
 use armagnac::{
-    core::Irq::SysTick,
-    core::{Emulator, Event},
+    core::Irq::{self, External, SysTick},
+    core::{
+        Config, Emulator, Event, FloatingPointExtension, MveExtension, Processor, RunError,
+        RunOptions,
+    },
     harness::{ElfHarness, ADDR_RAM, STACK_SIZE},
     memory::{Env, MemoryInterface},
+    semihosting::FileSystem,
 };
 use std::{cell::RefCell, rc::Rc};
 
@@ -17,7 +21,7 @@ fn test_memcpy() {
     assert_eq!(
         helper
             .proc
-            .read_bytes_iface(ADDR_RAM, expect.bytes().len() as u32)
+            .read_bytes_iface(ADDR_RAM, expect.len() as u32)
             .unwrap()
             .as_slice(),
         expect.as_bytes()
@@ -67,7 +71,7 @@ fn test_bkpt() {
     let mut helper = ElfHarness::new(elf);
     let arg = 6.00f32;
     helper.proc.registers.r0 = arg.to_bits();
-    let address = helper.symbols["test_bkpt".into()];
+    let address = helper.symbols["test_bkpt"];
     helper.proc.set_pc(address & 0xfffffffe); // We drop lsb (thumb mode bit)
     helper.proc.registers.lr = 0xfffffffe;
     helper.proc.set_sp(ADDR_RAM + STACK_SIZE);
@@ -75,14 +79,13 @@ fn test_bkpt() {
 
     loop {
         match helper.proc.next_event() {
-            Ok(event) => match event {
-                Event::Break(value) => {
+            Ok(event) => {
+                if let Event::Break(value) = event {
                     // We have only one BKPT instruction.
                     assert_eq!(breakpoint, None);
                     breakpoint = Some(value)
                 }
-                _ => {}
-            },
+            }
             Err(_) => panic!(),
         }
         // Run util code branches back to initial LR
@@ -116,7 +119,7 @@ fn test_wfe_wfi() {
         }
 
         fn update(&mut self, _env: &mut Env) {
-            if (_env.cycles % 1000 == 0) && (_env.cycles > 0) {
+            if _env.cycles.is_multiple_of(1000) && (_env.cycles > 0) {
                 _env.request_interrupt(SysTick);
             }
         }
@@ -130,11 +133,1429 @@ fn test_wfe_wfi() {
         .unwrap();
     let result = helper.call("test_wfe");
     let cycles = helper.proc.cycles;
-    assert!((cycles >= 1000) && (cycles < 1100));
+    assert!((1000..1100).contains(&cycles));
     assert_eq!(result, 0xdeadbeef);
 
     let result = helper.call("test_wfi");
     let cycles = helper.proc.cycles;
-    assert!((cycles >= 2000) && (cycles < 2100));
+    assert!((2000..2100).contains(&cycles));
     assert_eq!(result, 0xcafeb105);
 }
+
+/// Encoding of `b .` instruction.
+const B_SELF: u16 = 0xe7fe;
+/// Encoding of `udf #0` instruction.
+const UDF: u16 = 0xde00;
+/// Encoding of `bx lr` instruction.
+const BX_LR: u16 = 0x4770;
+
+/// Builds a 0x300 bytes memory image with a vector table and some Thumb code.
+///
+/// `vectors` lists exception numbers and their handler address. Initial SP is set to 0x20000400.
+/// `code` lists addresses and halfwords of the instructions to be placed at those addresses.
+fn image(vectors: &[(usize, u32)], code: &[(usize, &[u16])]) -> Vec<u8> {
+    let mut result = vec![0; 0x300];
+    result[0..4].copy_from_slice(&0x20000400u32.to_le_bytes());
+    for (number, address) in vectors {
+        result[number * 4..number * 4 + 4].copy_from_slice(&(address | 1).to_le_bytes());
+    }
+    for (address, halfwords) in code {
+        for (i, halfword) in halfwords.iter().enumerate() {
+            let offset = address + i * 2;
+            result[offset..offset + 2].copy_from_slice(&halfword.to_le_bytes());
+        }
+    }
+    result
+}
+
+/// Checks that an unprivileged store to a region which is read-only for unprivileged code raises
+/// a MemManage fault, with the faulting address reported in MMFAR.
+#[test]
+fn test_mpu_memmanage() {
+    let mut proc = Processor::new(Config::v7m());
+    let code = image(
+        &[(4, 0x200)], // MemManage
+        &[
+            (0x100, &[0x6008]), // str r0, [r1]
+            (0x200, &[B_SELF]),
+        ],
+    );
+    proc.map(0, &code).unwrap();
+    proc.map_ram(0x20000000, 0x400).unwrap();
+
+    // Region 0: code, 4 KiB, full access.
+    proc.write_u32le_iface(0xe000ed9c, 0x00000010).unwrap();
+    proc.write_u32le_iface(0xe000eda0, 0x03000017).unwrap();
+    // Region 1: RAM, 1 KiB, privileged RW, unprivileged RO.
+    proc.write_u32le_iface(0xe000ed9c, 0x20000011).unwrap();
+    proc.write_u32le_iface(0xe000eda0, 0x02000013).unwrap();
+    proc.write_u32le_iface(0xe000ed94, 1).unwrap();
+    // Enable MemManage fault, otherwise it escalates to HardFault.
+    proc.write_u32le_iface(0xe000ed24, 1 << 16).unwrap();
+
+    proc.registers.control.set_privileged_bit(true);
+    proc.registers.r0 = 0x12345678;
+    proc.registers.r1 = 0x20000010;
+    proc.set_sp(0x20000400);
+    proc.set_pc(0x100);
+    proc.next_event().unwrap();
+
+    assert_eq!(proc.pc(), 0x200);
+    assert_eq!(proc.read_u32le_iface(0x20000010).unwrap(), 0);
+    // CFSR.MMARVALID and CFSR.DACCVIOL
+    assert_eq!(proc.read_u32le_iface(0xe000ed28).unwrap(), 0x82);
+    assert_eq!(proc.read_u32le_iface(0xe000ed34).unwrap(), 0x20000010);
+    // Stacked return address is the faulting instruction.
+    let sp = proc.sp();
+    assert_eq!(proc.read_u32le_iface(sp + 0x18).unwrap(), 0x100);
+}
+
+/// Checks that a MPU violation while stacking raises a derived MemManage fault, which escalates to
+/// HardFault when MemManage is disabled.
+#[test]
+fn test_mpu_stacking_fault() {
+    let code = image(
+        &[(3, 0x200), (4, 0x220), (15, 0x240)], // HardFault, MemManage, SysTick
+        &[
+            (0x100, &[B_SELF]),
+            (0x200, &[B_SELF]),
+            (0x220, &[B_SELF]),
+            (0x240, &[B_SELF]),
+        ],
+    );
+    for (memfaultena, handler, hfsr) in [(false, 0x200, 1 << 30), (true, 0x220, 0)] {
+        let mut proc = Processor::new(Config::v7m());
+        proc.map(0, &code).unwrap();
+        proc.map_ram(0x20000000, 0x400).unwrap();
+        // Region 0: code, 4 KiB, full access.
+        proc.write_u32le_iface(0xe000ed9c, 0x00000010).unwrap();
+        proc.write_u32le_iface(0xe000eda0, 0x03000017).unwrap();
+        // Region 1: RAM, 1 KiB, full access.
+        proc.write_u32le_iface(0xe000ed9c, 0x20000011).unwrap();
+        proc.write_u32le_iface(0xe000eda0, 0x03000013).unwrap();
+        // Region 2: last 32 bytes of RAM, privileged read-only.
+        proc.write_u32le_iface(0xe000ed9c, 0x200003f2).unwrap();
+        proc.write_u32le_iface(0xe000eda0, 0x05000009).unwrap();
+        proc.write_u32le_iface(0xe000ed94, 1).unwrap();
+        proc.write_u32le_iface(0xe000ed24, (memfaultena as u32) << 16)
+            .unwrap();
+        // SysTick has a lower priority than MemManage.
+        proc.write_u32le_iface(0xe000ed20, 0x80000000).unwrap();
+        proc.set_sp(0x20000400);
+        proc.set_pc(0x100);
+        proc.request_interrupt(SysTick);
+        proc.next_event().unwrap();
+        assert_eq!(proc.pc(), 0x240);
+        proc.next_event().unwrap();
+        assert_eq!(proc.pc(), handler);
+        // CFSR.MSTKERR
+        assert_eq!(proc.read_u32le_iface(0xe000ed28).unwrap(), 1 << 4);
+        assert_eq!(proc.read_u32le_iface(0xe000ed2c).unwrap(), hfsr);
+    }
+}
+
+/// Checks exception preemption rules: NVIC enable bits, PRIMASK masking, priorities, priority
+/// grouping and tail-chaining.
+#[test]
+fn test_exception_priorities() {
+    let mut proc = Processor::new(Config::v7m().external_exceptions(2));
+    let code = image(
+        &[(15, 0x220), (16, 0x200), (17, 0x210)], // SysTick, external interrupts 0 and 1
+        &[
+            (0x100, &[B_SELF]),
+            (0x200, &[B_SELF]),
+            (0x210, &[BX_LR]),
+            (0x220, &[B_SELF]),
+        ],
+    );
+    proc.map(0, &code).unwrap();
+    proc.map_ram(0x20000000, 0x400).unwrap();
+    proc.set_sp(0x20000400);
+    proc.set_pc(0x100);
+
+    // External interrupt 0 priority is 0x80, external interrupt 1 priority is 0x40.
+    proc.write_u32le_iface(0xe000e400, 0x4080).unwrap();
+    // SysTick priority is 0x60.
+    proc.write_u32le_iface(0xe000ed20, 0x60000000).unwrap();
+
+    // Disabled interrupts are not taken.
+    proc.request_interrupt(External(0));
+    proc.next_event().unwrap();
+    assert_eq!(proc.pc(), 0x100);
+
+    // Enabled interrupts are masked by PRIMASK.
+    proc.write_u32le_iface(0xe000e100, 3).unwrap();
+    assert_eq!(proc.read_u32le_iface(0xe000e180).unwrap(), 3);
+    proc.registers.primask.set_pm(true);
+    proc.next_event().unwrap();
+    assert_eq!(proc.pc(), 0x100);
+    proc.registers.primask.set_pm(false);
+    proc.next_event().unwrap();
+    assert_eq!(proc.pc(), 0x200);
+    assert_eq!(proc.execution_priority, 0x80);
+    let sp = proc.sp();
+
+    // External interrupt 1 preempts external interrupt 0. When it returns, SysTick is
+    // tail-chained as it has higher priority than external interrupt 0.
+    proc.request_interrupt(SysTick);
+    proc.request_interrupt(External(1));
+    proc.next_event().unwrap();
+    assert_eq!(proc.pc(), 0x220);
+    assert_eq!(proc.sp(), sp - 0x20);
+    assert_eq!(proc.lr(), 0xfffffff1);
+    assert_eq!(proc.execution_priority, 0x60);
+
+    // With PRIGROUP set to 6, external interrupt 1 and SysTick have the same group priority, so
+    // no preemption occurs.
+    proc.write_u32le_iface(0xe000ed0c, 0x05fa0600).unwrap();
+    proc.request_interrupt(External(1));
+    proc.next_event().unwrap();
+    assert_eq!(proc.pc(), 0x220);
+    assert_eq!(proc.execution_priority, 0);
+
+    // Disabling the interrupt with NVIC_ICER keeps it pending.
+    proc.write_u32le_iface(0xe000ed0c, 0x05fa0000).unwrap();
+    proc.write_u32le_iface(0xe000e180, 2).unwrap();
+    assert_eq!(proc.read_u32le_iface(0xe000e100).unwrap(), 1);
+    proc.next_event().unwrap();
+    assert_eq!(proc.pc(), 0x220);
+    proc.write_u32le_iface(0xe000e100, 2).unwrap();
+    proc.next_event().unwrap();
+    // External interrupt 1 has been taken and returned to SysTick handler.
+    assert_eq!(proc.pc(), 0x220);
+    assert_eq!(proc.sp(), sp - 0x20);
+    assert_eq!(proc.execution_priority, 0x60);
+}
+
+/// Checks BASEPRI masking and BASEPRI_MAX conditional writes, as used by FreeRTOS critical
+/// sections.
+#[test]
+fn test_basepri() {
+    let mut proc = Processor::new(Config::v7m().external_exceptions(1));
+    let code = image(
+        &[(16, 0x200)], // External interrupt 0
+        &[
+            (0x100, &[0x2050, 0xf380, 0x8812]), // movs r0, #0x50; msr basepri_max, r0
+            (0x106, &[0x2160, 0xf381, 0x8812]), // movs r1, #0x60; msr basepri_max, r1
+            (0x10c, &[0xf3ef, 0x8211]),         // mrs r2, basepri
+            (0x110, &[0x2000, 0xf380, 0x8811]), // movs r0, #0; msr basepri, r0
+            (0x116, &[B_SELF]),
+            (0x200, &[B_SELF]),
+        ],
+    );
+    proc.map(0, &code).unwrap();
+    proc.map_ram(0x20000000, 0x400).unwrap();
+    proc.set_sp(0x20000400);
+    proc.set_pc(0x100);
+    // External interrupt 0 priority is 0x80.
+    proc.write_u32le_iface(0xe000e400, 0x80).unwrap();
+    proc.write_u32le_iface(0xe000e100, 1).unwrap();
+
+    proc.next_event().unwrap();
+    proc.next_event().unwrap();
+    assert_eq!(proc.registers.basepri, 0x50);
+    proc.request_interrupt(External(0));
+
+    // BASEPRI_MAX cannot lower the execution priority.
+    for _ in 0..4 {
+        proc.next_event().unwrap();
+    }
+    assert_eq!(proc.pc(), 0x112);
+    assert_eq!(proc.registers.r2, 0x50);
+    assert_eq!(proc.execution_priority, 0x50);
+
+    // Clearing BASEPRI unmasks the interrupt.
+    proc.next_event().unwrap();
+    proc.next_event().unwrap();
+    assert_eq!(proc.pc(), 0x200);
+    assert_eq!(proc.read_u32le_iface(proc.sp() + 0x18).unwrap(), 0x116);
+}
+
+/// Checks PendSV triggering through ICSR, as done by RTOS context switches, and ICSR active and
+/// pending exception fields.
+#[test]
+fn test_icsr() {
+    let mut proc = Processor::new(Config::v7m());
+    let code = image(
+        &[(14, 0x200)], // PendSV
+        &[
+            (0x100, &[0xf64e, 0x5004, 0xf2ce, 0x0000]), // movw r0, #0xed04; movt r0, #0xe000
+            (0x108, &[0xf04f, 0x5180, 0x6001, 0x6803]), // mov.w r1, #0x10000000; str r1, [r0]; ldr r3, [r0]
+            (0x110, &[B_SELF]),
+            (0x200, &[0x6802, B_SELF]), // ldr r2, [r0]
+        ],
+    );
+    proc.map(0, &code).unwrap();
+    proc.map_ram(0x20000000, 0x400).unwrap();
+    proc.set_sp(0x20000400);
+    proc.set_pc(0x100);
+    proc.registers.primask.set_pm(true);
+
+    // PendSV is pending but masked by PRIMASK.
+    for _ in 0..6 {
+        proc.next_event().unwrap();
+    }
+    assert_eq!(proc.pc(), 0x110);
+    assert_eq!(proc.registers.r3, 0x1000e000);
+
+    // PendSV is taken, its handler is the only active exception.
+    proc.registers.primask.set_pm(false);
+    proc.next_event().unwrap();
+    assert_eq!(proc.pc(), 0x202);
+    assert_eq!(proc.registers.r2, 0x0000080e);
+
+    // PENDSVCLR removes the pending state.
+    proc.write_u32le_iface(0xe000ed04, 1 << 28).unwrap();
+    proc.next_event().unwrap();
+    assert_eq!(proc.read_u32le_iface(0xe000ed04).unwrap(), 0x1000e80e);
+    proc.write_u32le_iface(0xe000ed04, 1 << 27).unwrap();
+    proc.next_event().unwrap();
+    assert_eq!(proc.read_u32le_iface(0xe000ed04).unwrap(), 0x0000080e);
+}
+
+/// Checks pending and active states of external interrupts through NVIC registers, and interrupt
+/// triggering by software with NVIC_ISPR and STIR.
+#[test]
+fn test_nvic_pending() {
+    let mut proc = Processor::new(Config::v7m().external_exceptions(40));
+    let code = image(
+        &[(48, 0x200), (49, 0x200)], // External interrupts 32 and 33
+        &[(0x100, &[B_SELF]), (0x200, &[B_SELF])],
+    );
+    proc.map(0, &code).unwrap();
+    proc.map_ram(0x20000000, 0x400).unwrap();
+    proc.set_sp(0x20000400);
+    proc.set_pc(0x100);
+    assert_eq!(proc.read_u32le_iface(0xe000e004).unwrap(), 1);
+    proc.write_u32le_iface(0xe000e104, 3).unwrap();
+    proc.registers.primask.set_pm(true);
+
+    // Interrupt 63 is not implemented and cannot be pended.
+    proc.write_u32le_iface(0xe000e204, 0x80000002).unwrap();
+    proc.next_event().unwrap();
+    assert_eq!(proc.read_u32le_iface(0xe000e204).unwrap(), 2);
+    assert_eq!(proc.read_u32le_iface(0xe000e284).unwrap(), 2);
+    assert!(proc.system_control().is_interrupt_pending(33));
+
+    // Un-pending with NVIC_ICPR, pending again with STIR.
+    proc.write_u32le_iface(0xe000e284, 2).unwrap();
+    proc.next_event().unwrap();
+    assert!(!proc.system_control().is_interrupt_pending(33));
+    proc.write_u32le_iface(0xe000ef00, 32).unwrap();
+    proc.next_event().unwrap();
+    assert_eq!(proc.read_u32le_iface(0xe000e204).unwrap(), 1);
+
+    proc.registers.primask.set_pm(false);
+    proc.next_event().unwrap();
+    assert_eq!(proc.pc(), 0x200);
+    assert!(!proc.system_control().is_interrupt_pending(32));
+    assert!(proc.system_control().is_interrupt_active(32));
+    assert_eq!(proc.read_u32le_iface(0xe000e304).unwrap(), 1);
+}
+
+/// Checks SEV and WFE, wakeup on pending interrupts with SCR.SEVONPEND, and sleep on exception
+/// return with SCR.SLEEPONEXIT.
+#[test]
+fn test_sleep() {
+    let mut proc = Processor::new(Config::v7m().external_exceptions(1));
+    let code = image(
+        &[(16, 0x200)], // External interrupt 0
+        &[
+            (0x100, &[0xbf40, 0xbf20, 0xbf20, B_SELF]), // sev; wfe; wfe
+            (0x200, &[BX_LR]),
+        ],
+    );
+    proc.map(0, &code).unwrap();
+    proc.map_ram(0x20000000, 0x400).unwrap();
+    proc.set_sp(0x20000400);
+    proc.set_pc(0x100);
+
+    // SEV sets the local event register, so the first WFE does not sleep.
+    assert!(matches!(proc.next_event(), Ok(Event::SendEvent)));
+    proc.next_event().unwrap();
+    proc.next_event().unwrap();
+    assert!(!proc.registers.event);
+    proc.next_event().unwrap();
+    assert_eq!(proc.pc(), 0x106);
+
+    // With SEVONPEND, a disabled interrupt becoming pending wakes up the processor.
+    proc.write_u32le_iface(0xe000ed10, 0x10).unwrap();
+    proc.request_interrupt(External(0));
+    assert!(proc.registers.event);
+    proc.next_event().unwrap();
+    assert_eq!(proc.pc(), 0x106);
+    assert!(proc.system_control().is_interrupt_pending(0));
+
+    // When enabled, the interrupt is taken, and the processor sleeps when returning to Thread
+    // mode.
+    proc.write_u32le_iface(0xe000ed10, 0x06).unwrap();
+    proc.write_u32le_iface(0xe000e100, 1).unwrap();
+    proc.next_event().unwrap();
+    assert!(matches!(proc.next_event(), Ok(Event::DeepSleep)));
+    assert_eq!(proc.pc(), 0x106);
+    assert!(!proc.system_control().is_interrupt_active(0));
+}
+
+/// Checks that cycles are skipped while the processor sleeps, up to the next SysTick reload.
+#[test]
+fn test_idle_fast_forward() {
+    let mut proc = Processor::new(Config::v7m());
+    let code = image(
+        &[(15, 0x200)], // SysTick
+        &[
+            (0x100, &[0xbf30, 0xe7fd]), // wfi; b 0x100
+            (0x200, &[0xbe01, BX_LR]),  // bkpt #1
+        ],
+    );
+    proc.map(0, &code).unwrap();
+    proc.map_ram(0x20000000, 0x400).unwrap();
+    proc.set_sp(0x20000400);
+    proc.set_pc(0x100);
+
+    // Reload value, counter clear, and enable with interrupt.
+    proc.write_u32le_iface(0xe000e014, 0xffffff).unwrap();
+    proc.write_u32le_iface(0xe000e018, 0).unwrap();
+    proc.write_u32le_iface(0xe000e010, 3).unwrap();
+
+    // Counter is clear, so first SysTick exception is requested immediately. Then it is requested
+    // every 2^24 cycles, which would take a while without skipping cycles.
+    for i in 0..100 {
+        assert!(matches!(
+            proc.run(RunOptions::new()),
+            Ok(Some(Event::Break(1)))
+        ));
+        assert!((i * 0x1000000..i * 0x1000000 + 10).contains(&proc.cycles));
+    }
+    let stcvr = proc.read_u32le_iface(0xe000e018).unwrap();
+    assert_eq!(stcvr, 0xffffff - (proc.cycles - 99 * 0x1000000 - 1) as u32);
+}
+
+/// Checks that a DWT comparator watching writes pends the DebugMonitor exception, or halts the
+/// execution when halting debug is enabled.
+#[test]
+fn test_dwt_watchpoint() {
+    let mut proc = Processor::new(Config::v7m());
+    let code = image(
+        &[(12, 0x200)], // DebugMonitor
+        &[
+            (0x100, &[0x6008, 0x6048, B_SELF]), // str r0, [r1]; str r0, [r1, #4]
+            (0x200, &[0xbe02, BX_LR]),          // bkpt #2
+        ],
+    );
+    proc.map(0, &code).unwrap();
+    proc.map_ram(0x20000000, 0x400).unwrap();
+    proc.set_sp(0x20000400);
+    proc.set_pc(0x100);
+    proc.registers.r1 = 0x20000000;
+
+    // DEMCR.TRCENA and DEMCR.MON_EN, then comparator 0 watches writes at 0x20000004.
+    proc.write_u32le_iface(0xe000edfc, 0x01010000).unwrap();
+    proc.write_u32le_iface(0xe0001020, 0x20000004).unwrap();
+    proc.write_u32le_iface(0xe0001028, 0b0110).unwrap();
+
+    assert!(matches!(
+        proc.run(RunOptions::new()),
+        Ok(Some(Event::Break(2)))
+    ));
+    assert_eq!(proc.read_u32le_iface(0xe000ed30).unwrap(), 1 << 2); // DFSR.DWTTRAP
+    assert_eq!(proc.read_u32le_iface(0xe0001028).unwrap(), 0x01000006); // MATCHED
+
+    // With halting debug, the watchpoint stops execution after the write.
+    proc.debug().halting_debug = true;
+    proc.set_pc(0x100);
+    assert!(matches!(
+        proc.run(RunOptions::new()),
+        Ok(Some(Event::Watchpoint(0)))
+    ));
+    assert_eq!(proc.pc(), 0x104);
+}
+
+/// Checks that the Flash Patch and Breakpoint unit remaps instructions and literal loads to SRAM,
+/// and replaces instructions by breakpoints.
+#[test]
+fn test_fpb() {
+    let mut proc = Processor::new(Config::v7m());
+    let code = image(
+        &[],
+        &[
+            (0x100, &[0x2001, 0x6811, 0xbf00, B_SELF]), // movs r0, #1; ldr r1, [r2]; nop
+            (0x200, &[0x1111, 0x1111]),
+        ],
+    );
+    proc.map(0, &code).unwrap();
+    let ram = proc.map_ram(0x20000000, 0x400).unwrap();
+    proc.set_sp(0x20000400);
+    proc.set_pc(0x100);
+    proc.registers.r2 = 0x200;
+
+    // Remap table: movs r0, #2; ldr r1, [r2] for comparator 0, and literal for comparator 6.
+    ram.borrow_mut()
+        .write_u32le(0x00, 0x68112002, &mut Env::new(0, true))
+        .unwrap();
+    ram.borrow_mut()
+        .write_u32le(0x18, 0x22222222, &mut Env::new(0, true))
+        .unwrap();
+    proc.write_u32le_iface(0xe0002004, 0x20000000).unwrap(); // FP_REMAP
+    proc.write_u32le_iface(0xe0002008, 0x00000101).unwrap(); // Remap 0x100
+    proc.write_u32le_iface(0xe000200c, 0x40000105).unwrap(); // Breakpoint at 0x104
+    proc.write_u32le_iface(0xe0002020, 0x00000201).unwrap(); // Remap literal 0x200
+    proc.write_u32le_iface(0xe0002000, 3).unwrap(); // FP_CTRL.ENABLE with KEY
+
+    assert!(matches!(
+        proc.run(RunOptions::new()),
+        Ok(Some(Event::Break(0)))
+    ));
+    assert_eq!(proc.registers.r0, 2);
+    assert_eq!(proc.registers.r1, 0x22222222);
+    assert_eq!(proc.read_u32le_iface(0x200).unwrap(), 0x11111111);
+}
+
+/// Checks that writes to the ITM stimulus ports are collected and encoded as SWO packets.
+#[test]
+fn test_itm() {
+    let mut proc = Processor::new(Config::v7m());
+    // strb r0, [r1]; strh r0, [r1, #4]; str r0, [r1, #8]; bkpt #0
+    let code = image(&[], &[(0x100, &[0x7008, 0x8088, 0x6088, 0xbe00])]);
+    proc.map(0, &code).unwrap();
+    proc.set_pc(0x100);
+    proc.registers.r0 = 0x12345641;
+    proc.registers.r1 = 0xe0000000;
+    proc.itm().swo = Some(Vec::new());
+
+    // DEMCR.TRCENA, ITM_TCR.ITMENA, and enable ports 0 and 1.
+    proc.write_u32le_iface(0xe000edfc, 1 << 24).unwrap();
+    proc.write_u32le_iface(0xe0000e80, 1).unwrap();
+    proc.write_u32le_iface(0xe0000e00, 3).unwrap();
+
+    assert!(matches!(
+        proc.run(RunOptions::new()),
+        Ok(Some(Event::Break(0)))
+    ));
+    let itm = proc.itm();
+    assert_eq!(itm.ports[0], b"A");
+    assert_eq!(itm.ports[1], [0x41, 0x56]);
+    assert!(itm.ports[2].is_empty());
+    assert_eq!(
+        itm.swo.as_deref(),
+        Some([0x01, 0x41, 0x0a, 0x41, 0x56].as_slice())
+    );
+}
+
+/// Checks that an undefined instruction escalates to HardFault when UsageFault is disabled, and
+/// that a fault in the HardFault handler locks up the processor.
+#[test]
+fn test_fault_escalation_and_lockup() {
+    let mut proc = Processor::new(Config::v7m());
+    let code = image(
+        &[(3, 0x200), (6, 0x220)], // HardFault, UsageFault
+        &[(0x100, &[UDF]), (0x200, &[UDF]), (0x220, &[B_SELF])],
+    );
+    proc.map(0, &code).unwrap();
+    proc.map_ram(0x20000000, 0x400).unwrap();
+    proc.set_sp(0x20000400);
+    proc.set_pc(0x100);
+
+    assert!(matches!(proc.next_event(), Ok(Event::Lockup)));
+    assert_eq!(proc.pc(), 0xeffffffe);
+    // CFSR.UNDEFINSTR
+    assert_eq!(proc.read_u32le_iface(0xe000ed28).unwrap(), 1 << 16);
+    // HFSR.FORCED
+    assert_eq!(proc.read_u32le_iface(0xe000ed2c).unwrap(), 1 << 30);
+
+    // Once UsageFault is enabled, it is taken without escalation.
+    let mut proc = Processor::new(Config::v7m());
+    proc.map(0, &code).unwrap();
+    proc.map_ram(0x20000000, 0x400).unwrap();
+    proc.set_sp(0x20000400);
+    proc.set_pc(0x100);
+    proc.write_u32le_iface(0xe000ed24, 1 << 18).unwrap();
+    proc.next_event().unwrap();
+    assert_eq!(proc.pc(), 0x220);
+    assert_eq!(proc.read_u32le_iface(0xe000ed2c).unwrap(), 0);
+    assert_eq!(proc.read_u32le_iface(proc.sp() + 0x18).unwrap(), 0x100);
+}
+
+/// Checks that the processor keeps reporting lockup until a NMI is taken, and that a reset also
+/// leaves lockup state.
+#[test]
+fn test_lockup_exit() {
+    let mut proc = Processor::new(Config::v7m());
+    let code = image(
+        &[(1, 0x100), (2, 0x240), (3, 0x200)], // Reset, NMI, HardFault
+        &[(0x100, &[UDF]), (0x200, &[UDF]), (0x240, &[B_SELF])],
+    );
+    proc.map(0, &code).unwrap();
+    proc.map_ram(0x20000000, 0x400).unwrap();
+    proc.reset().unwrap();
+    assert_eq!(proc.pc(), 0x100);
+    assert_eq!(proc.sp(), 0x20000400);
+
+    assert!(matches!(proc.next_event(), Ok(Event::Lockup)));
+    assert!(matches!(proc.next_event(), Ok(Event::Lockup)));
+    assert_eq!(proc.pc(), 0xeffffffe);
+
+    proc.request_interrupt(Irq::Nmi);
+    assert!(matches!(proc.next_event(), Ok(Event::Instruction { .. })));
+    assert_eq!(proc.pc(), 0x240);
+    assert_eq!(proc.read_u32le_iface(proc.sp() + 0x18).unwrap(), 0xeffffffe);
+
+    proc.reset().unwrap();
+    assert_eq!(proc.pc(), 0x100);
+    assert_eq!(proc.sp(), 0x20000400);
+    assert!(matches!(proc.next_event(), Ok(Event::Lockup)));
+}
+
+/// Checks that an exception return with an illegal EXC_RETURN value raises an INVPC UsageFault.
+#[test]
+fn test_invalid_exception_return() {
+    let mut proc = Processor::new(Config::v7m());
+    let code = image(
+        &[(6, 0x200)], // UsageFault
+        &[
+            (0x100, &[UDF]),
+            // mvn r0, #10
+            // bx r0
+            (0x200, &[0xf06f, 0x000a, 0x4700]),
+        ],
+    );
+    proc.map(0, &code).unwrap();
+    proc.map_ram(0x20000000, 0x400).unwrap();
+    proc.set_sp(0x20000400);
+    proc.set_pc(0x100);
+    proc.write_u32le_iface(0xe000ed24, 1 << 18).unwrap();
+    for _ in 0..2 {
+        proc.next_event().unwrap();
+    }
+    assert_eq!(proc.pc(), 0x200);
+    assert_eq!(proc.lr(), 0xfffffff5);
+    // CFSR.INVPC and CFSR.UNDEFINSTR
+    assert_eq!(proc.read_u32le_iface(0xe000ed28).unwrap(), 0x00050000);
+    // The frame stacked on the first UsageFault is still there.
+    assert_eq!(proc.sp(), 0x20000400 - 0x20);
+}
+
+/// Checks that APSR.GE flags are restored on exception return when the DSP extension is
+/// implemented.
+#[test]
+fn test_exception_return_ge_flags() {
+    let code = image(&[(15, 0x200)], &[(0x100, &[B_SELF]), (0x200, &[BX_LR])]); // SysTick
+    for (config, ge) in [(Config::v7em(), 0x000f0000), (Config::v7m(), 0)] {
+        let mut proc = Processor::new(config);
+        proc.map(0, &code).unwrap();
+        proc.map_ram(0x20000000, 0x400).unwrap();
+        proc.set_sp(0x20000400);
+        proc.set_pc(0x100);
+        proc.registers.psr.set(0x010f0000);
+        proc.request_interrupt(SysTick);
+        proc.next_event().unwrap();
+        assert_eq!(proc.pc(), 0x100);
+        assert_eq!(proc.registers.psr.get() & 0x000f0000, ge);
+    }
+}
+
+/// Checks that a load from an unmapped address aborts emulation by default, and raises a precise
+/// BusFault when bus faults are enabled.
+#[test]
+fn test_bus_fault() {
+    let code = image(
+        &[(3, 0x200), (5, 0x220)], // HardFault, BusFault
+        &[
+            (0x100, &[0x6808]), // ldr r0, [r1]
+            (0x200, &[B_SELF]),
+            (0x220, &[B_SELF]),
+        ],
+    );
+    for bus_faults in [false, true] {
+        let mut proc = Processor::new(Config::v7m().bus_faults(bus_faults));
+        proc.map(0, &code).unwrap();
+        proc.map_ram(0x20000000, 0x400).unwrap();
+        proc.set_sp(0x20000400);
+        proc.set_pc(0x100);
+        proc.registers.r1 = 0x30000000;
+        // Enable BusFault.
+        proc.write_u32le_iface(0xe000ed24, 1 << 17).unwrap();
+        let result = proc.next_event();
+        if !bus_faults {
+            assert!(matches!(
+                result,
+                Err(RunError::MemRead {
+                    address: 0x30000000,
+                    ..
+                })
+            ));
+            continue;
+        }
+        assert!(result.is_ok());
+        assert_eq!(proc.pc(), 0x220);
+        // CFSR.BFARVALID and CFSR.PRECISERR
+        assert_eq!(proc.read_u32le_iface(0xe000ed28).unwrap(), 0x8200);
+        assert_eq!(proc.read_u32le_iface(0xe000ed38).unwrap(), 0x30000000);
+        assert_eq!(proc.read_u32le_iface(proc.sp() + 0x18).unwrap(), 0x100);
+
+        // Instruction fetch from an unmapped address in the BusFault handler escalates to
+        // HardFault.
+        proc.set_pc(0x30000000);
+        proc.next_event().unwrap();
+        assert_eq!(proc.pc(), 0x200);
+        // CFSR.IBUSERR
+        assert_ne!(proc.read_u32le_iface(0xe000ed28).unwrap() & 0x100, 0);
+        // HFSR.FORCED
+        assert_eq!(proc.read_u32le_iface(0xe000ed2c).unwrap(), 1 << 30);
+    }
+}
+
+/// Checks that a bus error while stacking raises a derived BusFault, which escalates to HardFault
+/// when BusFault is disabled.
+#[test]
+fn test_stacking_bus_fault() {
+    let code = image(
+        &[(3, 0x200), (5, 0x220), (15, 0x240)], // HardFault, BusFault, SysTick
+        &[
+            (0x100, &[B_SELF]),
+            (0x200, &[B_SELF]),
+            (0x220, &[B_SELF]),
+            (0x240, &[B_SELF]),
+        ],
+    );
+    for (busfaultena, handler, hfsr) in [(false, 0x200, 1 << 30), (true, 0x220, 0)] {
+        let mut proc = Processor::new(Config::v7m().bus_faults(true));
+        proc.map(0, &code).unwrap();
+        proc.map_ram(0x20000000, 0x400).unwrap();
+        // The upper half of the frame is out of RAM.
+        proc.set_sp(0x20000410);
+        proc.set_pc(0x100);
+        proc.write_u32le_iface(0xe000ed24, (busfaultena as u32) << 17)
+            .unwrap();
+        // SysTick has a lower priority than BusFault.
+        proc.write_u32le_iface(0xe000ed20, 0x80000000).unwrap();
+        proc.request_interrupt(SysTick);
+        proc.next_event().unwrap();
+        assert_eq!(proc.pc(), 0x240);
+        proc.next_event().unwrap();
+        assert_eq!(proc.pc(), handler);
+        // CFSR.STKERR
+        assert_eq!(proc.read_u32le_iface(0xe000ed28).unwrap(), 1 << 12);
+        assert_eq!(proc.read_u32le_iface(0xe000ed2c).unwrap(), hfsr);
+    }
+}
+
+/// Checks that a bus error while fetching an exception vector takes HardFault, and leaves the
+/// exception pending.
+#[test]
+fn test_vector_fetch_bus_fault() {
+    let code = image(&[], &[(0x100, &[B_SELF]), (0x200, &[B_SELF])]);
+    let mut proc = Processor::new(Config::v7m().bus_faults(true));
+    proc.map(0, &code).unwrap();
+    proc.map_ram(0x20000000, 0x400).unwrap();
+    // Relocated vector table, which is too small for the SysTick vector.
+    let vectors = proc.map_ram(0x10000000, 0x20).unwrap();
+    vectors.borrow_mut().data[0xc..0x10].copy_from_slice(&0x201u32.to_le_bytes());
+    proc.write_u32le_iface(0xe000ed08, 0x10000000).unwrap();
+    proc.set_sp(0x20000400);
+    proc.set_pc(0x100);
+    proc.request_interrupt(SysTick);
+    proc.next_event().unwrap();
+    assert_eq!(proc.pc(), 0x200);
+    // HFSR.VECTTBL
+    assert_eq!(proc.read_u32le_iface(0xe000ed2c).unwrap(), 1 << 1);
+    // ICSR.PENDSTSET
+    assert_ne!(proc.read_u32le_iface(0xe000ed04).unwrap() & 1 << 26, 0);
+    assert_eq!(proc.read_u32le_iface(proc.sp() + 0x18).unwrap(), 0x100);
+}
+
+/// Checks that floating-point instructions raise a NOCP UsageFault until access to the
+/// floating-point extension is granted in CPACR, and then execute normally.
+#[test]
+fn test_fpu() {
+    let code = image(
+        &[(6, 0x200)], // UsageFault
+        &[
+            (0x100, &[0xee00, 0x0a10]), // vmov s0, r0
+            (0x104, &[0xee00, 0x1a90]), // vmov s1, r1
+            (0x108, &[0xee20, 0x1a20]), // vmul.f32 s2, s0, s1
+            (0x10c, &[0xee11, 0x2a10]), // vmov r2, s2
+            (0x110, &[0xeeb4, 0x1a40]), // vcmp.f32 s2, s0
+            (0x114, &[0xeef1, 0xfa10]), // vmrs APSR_nzcv, fpscr
+            (0x200, &[B_SELF]),
+        ],
+    );
+    for cpacr in [0, 0x00f00000] {
+        let mut proc = Processor::new(Config::v7em().fpu(FloatingPointExtension::SinglePrecision));
+        proc.map(0, &code).unwrap();
+        proc.map_ram(0x20000000, 0x400).unwrap();
+        proc.set_sp(0x20000400);
+        proc.set_pc(0x100);
+        proc.registers.r0 = 1.5f32.to_bits();
+        proc.registers.r1 = (-2.0f32).to_bits();
+        // Enable UsageFault.
+        proc.write_u32le_iface(0xe000ed24, 1 << 18).unwrap();
+        proc.write_u32le_iface(0xe000ed88, cpacr).unwrap();
+        if cpacr == 0 {
+            proc.next_event().unwrap();
+            assert_eq!(proc.pc(), 0x200);
+            // CFSR.NOCP
+            assert_eq!(proc.read_u32le_iface(0xe000ed28).unwrap(), 1 << 19);
+            continue;
+        }
+        for _ in 0..6 {
+            proc.next_event().unwrap();
+        }
+        assert_eq!(proc.pc(), 0x118);
+        assert_eq!(proc.registers.r2, (-3.0f32).to_bits());
+        assert!(proc.registers.psr.n());
+        assert!(!proc.registers.psr.c());
+    }
+}
+
+/// Checks that double-precision instructions are only executed when the processor implements
+/// the double-precision floating-point extension, and are not decoded otherwise.
+#[test]
+fn test_fpu_double_precision() {
+    let code = image(
+        &[],
+        &[
+            (0x100, &[0xec41, 0x0b10]), // vmov d0, r0, r1
+            (0x104, &[0xeeb1, 0x1bc0]), // vsqrt.f64 d1, d0
+            (0x108, &[0xfebb, 0x2b41]), // vrintm.f64 d2, d1
+            (0x10c, &[0xec53, 0x2b12]), // vmov r2, r3, d2
+        ],
+    );
+    for fpu in [
+        FloatingPointExtension::SinglePrecision,
+        FloatingPointExtension::DoublePrecision,
+    ] {
+        let mut proc = Processor::new(Config::v7em().fpu(fpu));
+        proc.map(0, &code).unwrap();
+        proc.map_ram(0x20000000, 0x400).unwrap();
+        proc.set_sp(0x20000400);
+        proc.set_pc(0x100);
+        let value = 10.0f64.to_bits();
+        proc.registers.r0 = value as u32;
+        proc.registers.r1 = (value >> 32) as u32;
+        proc.write_u32le_iface(0xe000ed88, 0x00f00000).unwrap();
+        if fpu == FloatingPointExtension::SinglePrecision {
+            proc.next_event().unwrap();
+            assert!(matches!(
+                proc.next_event(),
+                Err(RunError::InstructionUnknown)
+            ));
+            continue;
+        }
+        for _ in 0..4 {
+            proc.next_event().unwrap();
+        }
+        assert_eq!(proc.pc(), 0x110);
+        // floor(sqrt(10)) = 3
+        assert_eq!(proc.registers.r2, 0);
+        assert_eq!(proc.registers.r3, 0x40080000);
+    }
+}
+
+/// Checks that an extended frame is stacked when an exception interrupts a floating-point
+/// context, with and without lazy state preservation.
+#[test]
+fn test_fpu_extended_frame() {
+    let code = image(
+        &[(16, 0x200), (17, 0x210)], // External interrupts 0 and 1
+        &[
+            (0x100, &[0xee00, 0x0a10]), // vmov s0, r0
+            (0x104, &[B_SELF]),
+            (0x200, &[0xee00, 0x1a10]), // vmov s0, r1
+            (0x204, &[BX_LR]),
+            (0x210, &[0xbf00, BX_LR]), // nop
+        ],
+    );
+    for lspen in [true, false] {
+        let mut proc = Processor::new(
+            Config::v7em()
+                .fpu(FloatingPointExtension::SinglePrecision)
+                .external_exceptions(2),
+        );
+        proc.map(0, &code).unwrap();
+        proc.map_ram(0x20000000, 0x400).unwrap();
+        proc.set_sp(0x20000400);
+        proc.set_pc(0x100);
+        proc.registers.r0 = 0x11111111;
+        proc.registers.r1 = 0x22222222;
+        proc.write_u32le_iface(0xe000ed88, 0x00f00000).unwrap();
+        proc.write_u32le_iface(0xe000e100, 3).unwrap();
+        // DEMCR.MON_EN
+        proc.write_u32le_iface(0xe000edfc, 1 << 16).unwrap();
+        if !lspen {
+            // FPCCR.ASPEN only
+            proc.write_u32le_iface(0xe000ef34, 0x80000000).unwrap();
+        }
+
+        // Executing a floating-point instruction activates the floating-point context.
+        assert!(!proc.registers.control.fpca());
+        proc.next_event().unwrap();
+        assert!(proc.registers.control.fpca());
+
+        // The handler does not use floating-point registers: nothing is saved with lazy state
+        // preservation.
+        proc.request_interrupt(External(1));
+        proc.next_event().unwrap();
+        assert_eq!(proc.pc(), 0x212);
+        assert_eq!(proc.sp(), 0x20000400 - 0x68);
+        assert_eq!(proc.lr(), 0xffffffe9);
+        assert!(!proc.registers.control.fpca());
+        let fpccr = proc.read_u32le_iface(0xe000ef34).unwrap();
+        assert_eq!(fpccr & 1 != 0, lspen); // LSPACT
+        assert_eq!(fpccr & 1 << 8 != 0, lspen); // MONRDY
+        let saved = proc.read_u32le_iface(0x200003b8).unwrap();
+        assert_eq!(saved, if lspen { 0 } else { 0x11111111 });
+        proc.next_event().unwrap();
+        assert_eq!(proc.pc(), 0x104);
+        assert_eq!(proc.sp(), 0x20000400);
+        assert!(proc.registers.control.fpca());
+        assert_eq!(proc.read_u32le_iface(0xe000ef34).unwrap() & 1, 0);
+
+        // The handler uses floating-point registers, which are saved before being modified and
+        // restored on exception return.
+        proc.request_interrupt(External(0));
+        proc.next_event().unwrap();
+        assert_eq!(proc.pc(), 0x204);
+        if lspen {
+            assert_eq!(proc.read_u32le_iface(0xe000ef38).unwrap(), 0x200003b8);
+        }
+        assert_eq!(proc.read_u32le_iface(0x200003b8).unwrap(), 0x11111111);
+        assert_eq!(proc.registers.s[0], 0x22222222);
+        assert!(proc.registers.control.fpca());
+        proc.next_event().unwrap();
+        assert_eq!(proc.pc(), 0x104);
+        assert_eq!(proc.sp(), 0x20000400);
+        assert_eq!(proc.registers.s[0], 0x11111111);
+    }
+}
+
+/// Checks transitions between Secure and Non-secure states: Secure code calling a Non-secure
+/// function with BLXNS, a Non-secure interrupt preempting Secure code, Non-secure code calling a
+/// Secure function through a SG instruction, and a Non-secure access to Secure memory raising a
+/// SecureFault.
+#[test]
+fn test_security_extension() {
+    let code = image(
+        &[(7, 0x180)], // SecureFault
+        &[
+            (0x100, &[0x4784, B_SELF]),         // blxns r0
+            (0x180, &[B_SELF]),                 //
+            (0x1e0, &[0xe97f, 0xe97f, 0x4774]), // sg, bxns lr
+            (0x200, &[BX_LR]),                  //
+            (0x210, &[0x6808]),                 // ldr r0, [r1]
+            (0x220, &[0x4790, B_SELF]),         // blx r2
+            (0x230, &[0xbf00, BX_LR]),          // nop
+            (0x2c0, &[0x0231, 0x0000]),         // Non-secure vector of external interrupt 0
+        ],
+    );
+    let mut proc = Processor::new(
+        Config::v8m()
+            .security_extension(true)
+            .external_exceptions(1),
+    );
+    proc.map(0, &code).unwrap();
+    proc.map_ram(0x20000000, 0x800).unwrap();
+    proc.set_sp(0x20000400);
+    proc.registers.banked.msp = 0x20000800;
+    // SAU region 0: Non-secure code, region 1: Non-secure callable, region 2: Non-secure RAM.
+    for (rnr, rbar, rlar) in [
+        (0, 0x200, 0x2e1),
+        (1, 0x1e0, 0x1e3),
+        (2, 0x20000400, 0x200007e1),
+    ] {
+        proc.write_u32le_iface(0xe000edd8, rnr).unwrap();
+        proc.write_u32le_iface(0xe000eddc, rbar).unwrap();
+        proc.write_u32le_iface(0xe000ede0, rlar).unwrap();
+    }
+    proc.write_u32le_iface(0xe000edd0, 1).unwrap();
+    // SECUREFAULTENA
+    proc.write_u32le_iface(0xe000ed24, 1 << 19).unwrap();
+
+    // Call to a Non-secure function, which returns to Secure state using FNC_RETURN.
+    proc.set_pc(0x100);
+    proc.registers.r0 = 0x200;
+    proc.next_event().unwrap();
+    assert!(!proc.is_secure());
+    assert_eq!(proc.pc(), 0x200);
+    assert_eq!(proc.lr(), 0xfeffffff);
+    assert_eq!(proc.sp(), 0x20000800);
+    assert_eq!(proc.read_u32le_iface(0x200003f8).unwrap(), 0x103);
+    proc.next_event().unwrap();
+    assert!(proc.is_secure());
+    assert_eq!(proc.pc(), 0x102);
+    assert_eq!(proc.sp(), 0x20000400);
+
+    // Non-secure interrupt preempting Secure code. Callee-saved registers are stacked with the
+    // integrity signature, and cleared before entering the handler.
+    proc.write_u32le_iface(0xe000e380, 1).unwrap(); // NVIC_ITNS0
+    proc.write_u32le_iface(0xe000e100, 1).unwrap();
+    proc.write_u32le_iface(0xe002ed08, 0x280).unwrap(); // VTOR_NS
+    proc.registers.r4 = 0x44444444;
+    proc.request_interrupt(External(0));
+    proc.next_event().unwrap();
+    assert!(!proc.is_secure());
+    assert_eq!(proc.pc(), 0x232);
+    assert_eq!(proc.lr(), 0xffffffd8);
+    assert_eq!(proc.registers.r4, 0);
+    assert_eq!(proc.registers.banked.msp, 0x20000400 - 0x48);
+    assert_eq!(proc.read_u32le_iface(0x200003b8).unwrap(), 0xfefa125b);
+    proc.next_event().unwrap();
+    assert!(proc.is_secure());
+    assert_eq!(proc.pc(), 0x102);
+    assert_eq!(proc.registers.r4, 0x44444444);
+    assert_eq!(proc.sp(), 0x20000400);
+
+    // Non-secure code calls a Secure function through the SG instruction, which returns with
+    // BXNS.
+    proc.set_pc(0x100);
+    proc.registers.r0 = 0x220;
+    proc.registers.r2 = 0x1e1;
+    for _ in 0..3 {
+        proc.next_event().unwrap();
+    }
+    assert!(proc.is_secure());
+    assert_eq!(proc.pc(), 0x1e4);
+    assert_eq!(proc.lr(), 0x222);
+    proc.next_event().unwrap();
+    assert!(!proc.is_secure());
+    assert_eq!(proc.pc(), 0x222);
+
+    // Non-secure access to Secure memory.
+    proc.set_pc(0x210);
+    proc.registers.r1 = 0x20000000;
+    proc.next_event().unwrap();
+    assert!(proc.is_secure());
+    assert_eq!(proc.pc(), 0x180);
+    assert_eq!(proc.lr(), 0xffffffb9);
+    // SFSR.AUVIOL and SFSR.SFARVALID
+    assert_eq!(proc.read_u32le_iface(0xe000ede4).unwrap(), 0x48);
+    assert_eq!(proc.read_u32le_iface(0xe000ede8).unwrap(), 0x20000000);
+}
+
+/// Checks that low-overhead loops and branch futures of ArmV8.1-M use the branch information
+/// cache, so the loop end and branch instructions are only executed once.
+#[test]
+fn test_low_overhead_branches() {
+    let code = image(
+        &[],
+        &[
+            (0x100, &[0x2000, 0x2103]),         // movs r0, #0; movs r1, #3
+            (0x104, &[0xf041, 0xe001]),         // dls lr, r1
+            (0x108, &[0x3001]),                 // adds r0, #1
+            (0x10a, &[0xf00f, 0xc803, B_SELF]), // le lr, 0x108
+            (0x120, &[0xf042, 0xc801]),         // wls lr, r2, 0x126
+            (0x124, &[0x3001]),                 // adds r0, #1
+            (0x126, &[0xf0c0, 0xe803]),         // bf 0x12c, 0x130
+            (0x12a, &[0xbf00]),                 // nop
+            (0x12c, &[0xe000, B_SELF]),         // b 0x130
+            (0x130, &[0xe7fd]),                 // b 0x12e
+            (0x140, &[0xf023, 0xe001]),         // dlstp.32 lr, r3
+            (0x144, &[0x3001]),                 // adds r0, #1
+            (0x146, &[0xf01f, 0xc803, B_SELF]), // letp lr, 0x144
+        ],
+    );
+    let mut proc = Processor::new(
+        Config::v81m()
+            .fpu(FloatingPointExtension::SinglePrecision)
+            .mve(MveExtension::Integer),
+    );
+    proc.map(0, &code).unwrap();
+    proc.set_pc(0x100);
+    // movs, movs, dls, adds, le, then two more iterations from the cache.
+    for _ in 0..7 {
+        proc.next_event().unwrap();
+    }
+    assert_eq!(proc.registers.r0, 3);
+    assert_eq!(proc.lr(), 1);
+    assert_eq!(proc.pc(), 0x10a);
+    assert!(proc.lo_branch_info.is_some());
+    proc.next_event().unwrap();
+    assert_eq!(proc.pc(), 0x10e);
+    assert_eq!(proc.lo_branch_info, None);
+
+    // WLS skips the loop when the iteration count is zero. The branch future then skips the
+    // branch instruction at 0x12c.
+    proc.set_pc(0x120);
+    proc.registers.r2 = 0;
+    for _ in 0..4 {
+        proc.next_event().unwrap();
+    }
+    assert_eq!(proc.registers.r0, 3);
+    assert_eq!(proc.pc(), 0x12e);
+    assert_eq!(proc.lo_branch_info, None);
+
+    // Tail predicated loop over 10 words: 3 iterations of 4 elements.
+    proc.write_u32le_iface(0xe000ed88, 0x00f00000).unwrap();
+    proc.registers.r0 = 0;
+    proc.registers.r3 = 10;
+    proc.set_pc(0x140);
+    proc.next_event().unwrap();
+    assert_eq!(proc.registers.fpscr.ltpsize(), 2);
+    for _ in 0..5 {
+        proc.next_event().unwrap();
+    }
+    assert_eq!(proc.registers.r0, 3);
+    assert_eq!(proc.pc(), 0x14a);
+    assert_eq!(proc.registers.fpscr.ltpsize(), 4);
+}
+
+/// Checks that tail predication of an interrupted DLSTP loop does not apply to the exception
+/// handler, and that it is restored on exception return, with and without lazy floating-point
+/// state preservation.
+#[test]
+fn test_tail_predication_exception() {
+    let code = image(
+        &[(15, 0x200)], // SysTick
+        &[
+            (0x100, &[0xf023, 0xe001]),         // dlstp.32 lr, r3
+            (0x104, &[0xee21, 0x0f42]),         // vadd.i32 q0, q0, r2
+            (0x108, &[0xbf00, 0xbf00]),         // nop; nop
+            (0x10c, &[0xbf00, 0xbf00]),         // nop; nop
+            (0x110, &[0xf01f, 0xc009, B_SELF]), // letp lr, 0x104
+            (0x200, &[0xee21, 0x0f42, BX_LR]),  // vadd.i32 q0, q0, r2; bx lr
+        ],
+    );
+    for lspen in [true, false] {
+        let mut proc = Processor::new(
+            Config::v81m()
+                .fpu(FloatingPointExtension::SinglePrecision)
+                .mve(MveExtension::Integer),
+        );
+        proc.map(0, &code).unwrap();
+        proc.map_ram(0x20000000, 0x400).unwrap();
+        proc.set_sp(0x20000400);
+        proc.write_u32le_iface(0xe000ed88, 0x00f00000).unwrap();
+        // FPCCR.ASPEN and FPCCR.LSPEN
+        proc.write_u32le_iface(0xe000ef34, 1 << 31 | (lspen as u32) << 30)
+            .unwrap();
+        proc.registers.r2 = 1;
+        proc.registers.r3 = 2;
+        proc.set_pc(0x100);
+        proc.next_event().unwrap();
+        assert_eq!(proc.registers.fpscr.ltpsize(), 2);
+
+        // All the lanes are processed by the handler.
+        proc.request_interrupt(SysTick);
+        proc.next_event().unwrap();
+        assert_eq!(proc.pc(), 0x204);
+        assert_eq!(proc.registers.fpscr.ltpsize(), 4);
+        assert_eq!(proc.registers.q(0), 0x00000001_00000001_00000001_00000001);
+        proc.next_event().unwrap();
+        assert_eq!(proc.pc(), 0x104);
+        assert_eq!(proc.registers.fpscr.ltpsize(), 2);
+
+        // Q0 has been restored on exception return, and only the two first lanes are processed
+        // by the loop.
+        for _ in 0..6 {
+            proc.next_event().unwrap();
+        }
+        assert_eq!(proc.pc(), 0x114);
+        assert_eq!(proc.registers.fpscr.ltpsize(), 4);
+        assert_eq!(proc.registers.q(0), 0x00000000_00000000_00000001_00000001);
+    }
+}
+
+/// Checks M-profile Vector Extension instructions with a tail predicated dot product loop, VPT
+/// predication and a gather load. Floating-point vector instructions are UNDEFINED when only the
+/// integer subset is implemented.
+#[test]
+fn test_mve() {
+    let code = image(
+        &[(6, 0x200)], // UsageFault
+        &[
+            (0x100, &[0xf012, 0xe001]),         // dlstp.16 lr, r2
+            (0x104, &[0xecb0, 0x1e88]),         // vldrh.u16 q0, [r0], #16
+            (0x108, &[0xecb1, 0x3e88]),         // vldrh.u16 q1, [r1], #16
+            (0x10c, &[0xeef0, 0x4e22]),         // vmlava.s16 r4, q0, q1
+            (0x110, &[0xf01f, 0xc009, B_SELF]), // letp lr, 0x104
+            (0x140, &[0xed90, 0x1f00]),         // vldrw.u32 q0, [r0]
+            (0x144, &[0xfe61, 0x1fcf]),         // vpt.s32 lt, q0, zr
+            (0x148, &[0xeea0, 0x3b10]),         // vdupt.32 q0, r3
+            (0x14c, &[0xed80, 0x1f00, B_SELF]), // vstrw.32 q0, [r0]
+            (0x160, &[0xfc90, 0x0f43]),         // vldrw.u32 q0, [r0, q1, uxtw #2]
+            (0x164, &[0xee21, 0x0f42]),         // vadd.i32 q0, q0, r2
+            (0x168, &[0xed81, 0x1f00]),         // vstrw.32 q0, [r1]
+            (0x16c, &[0xef00, 0x0d40, B_SELF]), // vadd.f32 q0, q0, q0
+            (0x200, &[B_SELF]),
+        ],
+    );
+    let mut proc = Processor::new(
+        Config::v81m()
+            .fpu(FloatingPointExtension::SinglePrecision)
+            .mve(MveExtension::Integer),
+    );
+    proc.map(0, &code).unwrap();
+    proc.map_ram(0x20000000, 0x400).unwrap();
+    proc.set_sp(0x20000400);
+    // Enable UsageFault and grant access to the floating-point and vector extensions.
+    proc.write_u32le_iface(0xe000ed24, 1 << 18).unwrap();
+    proc.write_u32le_iface(0xe000ed88, 0x00f00000).unwrap();
+
+    // Dot product of two vectors of 11 halfwords, in one iteration of 8 elements and one of 3.
+    // The halfwords following the vectors are not part of the result.
+    let a: Vec<i16> = (0..12).map(|i| i - 5).collect();
+    let b: Vec<i16> = (0..12).map(|i| 3 * i + 1).collect();
+    for i in 0..12 {
+        let address = 0x20000000 + 2 * i as u32;
+        proc.write_u16le_iface(address, a[i] as u16).unwrap();
+        proc.write_u16le_iface(address + 0x40, b[i] as u16).unwrap();
+    }
+    proc.registers.r0 = 0x20000000;
+    proc.registers.r1 = 0x20000040;
+    proc.registers.r2 = 11;
+    proc.registers.r4 = 100;
+    proc.set_pc(0x100);
+    for _ in 0..9 {
+        proc.next_event().unwrap();
+    }
+    let dot: i32 = (0..11).map(|i| a[i] as i32 * b[i] as i32).sum();
+    assert_eq!(proc.registers.r4 as i32, 100 + dot);
+    assert_eq!(proc.registers.r0, 0x20000020);
+    assert_eq!(proc.pc(), 0x114);
+    assert_eq!(proc.registers.fpscr.ltpsize(), 4);
+
+    // Negative elements are cleared in a VPT block.
+    for (i, value) in [5, -3, 0, -7].into_iter().enumerate() {
+        proc.write_u32le_iface(0x20000100 + 4 * i as u32, value as u32)
+            .unwrap();
+    }
+    proc.registers.r0 = 0x20000100;
+    proc.registers.r3 = 0;
+    proc.set_pc(0x140);
+    for _ in 0..4 {
+        proc.next_event().unwrap();
+    }
+    assert_eq!(proc.registers.q(0), 5);
+    assert_eq!(proc.read_u32le_iface(0x20000104).unwrap(), 0);
+    assert_eq!(proc.read_u32le_iface(0x2000010c).unwrap(), 0);
+    assert!(!proc.registers.vpr.in_vpt_block());
+
+    // Gather load from a table, using scaled offsets.
+    for i in 0..16 {
+        proc.write_u32le_iface(0x20000200 + 4 * i, 10 * i).unwrap();
+    }
+    proc.registers.r0 = 0x20000200;
+    proc.registers.r1 = 0x20000300;
+    proc.registers.r2 = 1;
+    proc.registers.set_q(1, 15 << 96 | 7 << 64 | 3);
+    proc.set_pc(0x160);
+    for _ in 0..3 {
+        proc.next_event().unwrap();
+    }
+    assert_eq!(
+        proc.read_bytes_iface(0x20000300, 16).unwrap(),
+        [31u32, 1, 71, 151]
+            .iter()
+            .flat_map(|x| x.to_le_bytes())
+            .collect::<Vec<_>>()
+    );
+
+    // Floating-point vector instructions are UNDEFINED.
+    proc.next_event().unwrap();
+    assert_eq!(proc.pc(), 0x200);
+    // CFSR.UNDEFINSTR
+    assert_eq!(proc.read_u32le_iface(0xe000ed28).unwrap(), 1 << 16);
+
+    // MVFR1.MVE reports integer vector instructions only, or both integer and floating-point.
+    assert_eq!(proc.read_u32le_iface(0xe000ef44).unwrap(), 0x11000111);
+    let mut proc = Processor::new(
+        Config::v81m()
+            .fpu(FloatingPointExtension::SinglePrecision)
+            .mve(MveExtension::FloatingPoint),
+    );
+    assert_eq!(proc.read_u32le_iface(0xe000ef44).unwrap(), 0x11000211);
+}
+
+/// Checks that an indirect branch to an instruction other than BTI or PACBTI raises an INVSTATE
+/// UsageFault when branch target identification is enabled, and that a corrupted return address
+/// is detected by pointer authentication.
+#[test]
+fn test_pacbti() {
+    let code = image(
+        &[(6, 0x200)], // UsageFault
+        &[
+            (0x100, &[0x2050, 0xf380, 0x8814]), // movs r0, #0x50; msr control, r0
+            (0x106, &[0xf240, 0x1121, 0x4788]), // movw r1, #0x121; blx r1
+            (0x10c, &[0xf240, 0x1141, 0x4708]), // movw r1, #0x141; bx r1
+            (0x120, &[0xf3af, 0x800d]),         // pacbti r12, lr, sp
+            (0x124, &[0xe92d, 0x5000, 0x3001]), // push {r12, lr}; adds r0, #1
+            (0x12a, &[0xe8bd, 0x5000]),         // pop {r12, lr}
+            (0x12e, &[0xf3af, 0x802d, BX_LR]),  // aut r12, lr, sp; bx lr
+            (0x140, &[0xbf00]),                 // nop
+            (0x160, &[0xf3af, 0x800d]),         // pacbti r12, lr, sp
+            (0x164, &[0xe92d, 0x5000, 0x9201]), // push {r12, lr}; str r2, [sp, #4]
+            (0x16a, &[0xe8bd, 0x5000]),         // pop {r12, lr}
+            (0x16e, &[0xfb5e, 0xcf1d]),         // bxaut r12, lr, sp
+            (0x200, &[B_SELF]),
+        ],
+    );
+    let new_proc = |pc| {
+        let mut proc = Processor::new(Config::v81m().pacbti(true));
+        proc.map(0, &code).unwrap();
+        proc.map_ram(0x20000000, 0x400).unwrap();
+        proc.set_sp(0x20000400);
+        proc.set_pc(pc);
+        proc.registers.pac_key_p = [0x01234567, 0x89abcdef, 0xdeadbeef, 0xcafebabe];
+        proc.write_u32le_iface(0xe000ed24, 1 << 18).unwrap();
+        proc
+    };
+
+    // ID_ISAR5.PACBTI reports an IMPLEMENTATION DEFINED algorithm.
+    let mut proc = new_proc(0x100);
+    assert_eq!(proc.read_u32le_iface(0xe000ed74).unwrap(), 0x00200000);
+    let mut proc = Processor::new(Config::v81m());
+    assert_eq!(proc.read_u32le_iface(0xe000ed74).unwrap(), 0);
+
+    // The function called with BLX starts with PACBTI, and returns with BX LR which is not
+    // checked. BX to the NOP raises the fault.
+    let mut proc = new_proc(0x100);
+    for _ in 0..13 {
+        proc.next_event().unwrap();
+    }
+    assert_eq!(proc.registers.r0, 0x51);
+    assert_eq!(proc.pc(), 0x200);
+    // CFSR.INVSTATE
+    assert_eq!(proc.read_u32le_iface(0xe000ed28).unwrap(), 1 << 17);
+    assert_eq!(proc.read_u32le_iface(proc.sp() + 0x18).unwrap(), 0x140);
+    // Stacked EPSR.B
+    assert_eq!(
+        proc.read_u32le_iface(proc.sp() + 0x1c).unwrap() >> 21 & 1,
+        1
+    );
+    assert!(!proc.registers.psr.b());
+
+    // The return address is overwritten on the stack, BXAUT detects it.
+    let mut proc = new_proc(0x160);
+    proc.registers.control.set_pac_en(true);
+    proc.set_lr(0x10d);
+    proc.registers.r2 = 0x141;
+    for _ in 0..6 {
+        proc.next_event().unwrap();
+    }
+    assert_eq!(proc.pc(), 0x200);
+    assert_eq!(proc.read_u32le_iface(0xe000ed28).unwrap(), 1 << 17);
+    assert_eq!(proc.read_u32le_iface(proc.sp() + 0x18).unwrap(), 0x16e);
+}
+
+#[test]
+fn test_stack_limit() {
+    let code = image(
+        &[(3, 0x220), (6, 0x200), (11, 0x201)], // HardFault, UsageFault, SVCall
+        &[
+            (0x100, &[0xf380, 0x880a, 0xb0c0]), // msr msplim, r0; sub sp, #0x100
+            (0x110, &[0xdf00]),                 // svc #0
+            (0x120, &[0xb40f]),                 // push {r0-r3}
+            (0x200, &[B_SELF]),
+            (0x220, &[B_SELF]),
+        ],
+    );
+    let new_proc = |pc| {
+        let mut proc = Processor::new(Config::v8m());
+        proc.map(0, &code).unwrap();
+        proc.map_ram(0x20000000, 0x400).unwrap();
+        proc.set_sp(0x20000400);
+        proc.set_pc(pc);
+        proc.write_u32le_iface(0xe000ed24, 1 << 18).unwrap();
+        proc
+    };
+
+    // SUB overflows the stack and is not executed. The exception frame still fits above the
+    // limit.
+    let mut proc = new_proc(0x100);
+    proc.registers.r0 = 0x200003e4;
+    proc.next_event().unwrap();
+    assert_eq!(proc.registers.msplim, 0x200003e0);
+    proc.next_event().unwrap();
+    assert_eq!(proc.pc(), 0x200);
+    // CFSR.STKOF
+    assert_eq!(proc.read_u32le_iface(0xe000ed28).unwrap(), 1 << 20);
+    assert_eq!(proc.sp(), 0x200003e0);
+    assert_eq!(proc.read_u32le_iface(proc.sp() + 0x18).unwrap(), 0x104);
+
+    // Exception entry overflows the stack: SP stops at the limit, and only the part of the frame
+    // above the limit is written.
+    let mut proc = new_proc(0x110);
+    proc.registers.msplim = 0x200003f0;
+    proc.set_sp(0x200003f8);
+    proc.next_event().unwrap();
+    proc.next_event().unwrap();
+    assert_eq!(proc.pc(), 0x200);
+    assert_eq!(proc.read_u32le_iface(0xe000ed28).unwrap(), 1 << 20);
+    assert_eq!(proc.sp(), 0x200003f0);
+    assert_eq!(proc.read_u32le_iface(0x200003f0).unwrap(), 0x112);
+    assert_eq!(proc.read_u32le_iface(0x200003e0).unwrap(), 0);
+
+    // Same, with UsageFault disabled: the derived UsageFault escalates to HardFault.
+    let mut proc = new_proc(0x110);
+    proc.write_u32le_iface(0xe000ed24, 0).unwrap();
+    proc.registers.msplim = 0x200003f0;
+    proc.set_sp(0x200003f8);
+    for _ in 0..3 {
+        proc.next_event().unwrap();
+    }
+    assert_eq!(proc.pc(), 0x220);
+    assert_eq!(proc.read_u32le_iface(0xe000ed28).unwrap(), 1 << 20);
+    // HFSR.FORCED
+    assert_eq!(proc.read_u32le_iface(0xe000ed2c).unwrap(), 1 << 30);
+
+    // PUSH overflows the stack: SP is not updated and no register is stored. Only the end of the
+    // exception frame is written above the limit.
+    let mut proc = new_proc(0x120);
+    proc.registers.msplim = 0x200003f8;
+    [proc.registers.r0, proc.registers.r1] = [0x11111111, 0x22222222];
+    [proc.registers.r2, proc.registers.r3] = [0x33333333, 0x44444444];
+    proc.next_event().unwrap();
+    assert_eq!(proc.pc(), 0x200);
+    assert_eq!(proc.read_u32le_iface(0xe000ed28).unwrap(), 1 << 20);
+    assert_eq!(proc.sp(), 0x200003f8);
+    assert_eq!(proc.read_u32le_iface(0x200003f8).unwrap(), 0x120);
+    assert_eq!(proc.read_u32le_iface(0x200003f0).unwrap(), 0);
+    assert_eq!(proc.read_u32le_iface(0x200003f4).unwrap(), 0);
+}
+
+/// Runs a program opening an in-memory file with semihosting, seeking far beyond its end and
+/// writing to it. The write must fail without growing the file.
+#[test]
+fn test_semihosted_seek_write() {
+    let elf = include_bytes!("tests.elf");
+    let mut helper = ElfHarness::new(elf);
+    let code: [u16; 12] = [
+        0x2001, 0x4621, 0xbeab, // movs r0, #1; mov r1, r4; bkpt #0xab (SYS_OPEN)
+        0x6028, 0x6030, // str r0, [r5]; str r0, [r6]
+        0x200a, 0x4629, 0xbeab, // movs r0, #0x0a; mov r1, r5; bkpt #0xab (SYS_SEEK)
+        0x2005, 0x4631, 0xbeab, // movs r0, #5; mov r1, r6; bkpt #0xab (SYS_WRITE)
+        BX_LR,
+    ];
+    let code: Vec<u8> = code.iter().flat_map(|h| h.to_le_bytes()).collect();
+    helper.proc.map(0x30000000, &code).unwrap();
+    helper.symbols.insert("seek_write".into(), 0x30000001);
+
+    // File name, and parameter blocks of SYS_OPEN, SYS_SEEK and SYS_WRITE.
+    let words = [
+        u32::from_le_bytes(*b"a.tx"),
+        u32::from_le_bytes(*b"t\0\0\0"),
+        ADDR_RAM,
+        4,
+        5,
+        0,
+        0xfffffff0,
+        0,
+        ADDR_RAM,
+        4,
+    ];
+    for (i, word) in words.iter().enumerate() {
+        helper
+            .proc
+            .write_u32le_iface(ADDR_RAM + 4 * i as u32, *word)
+            .unwrap();
+    }
+    helper.proc.registers.r4 = ADDR_RAM + 8;
+    helper.proc.registers.r5 = ADDR_RAM + 0x14;
+    helper.proc.registers.r6 = ADDR_RAM + 0x1c;
+
+    // SYS_WRITE returns the number of bytes which have not been written.
+    assert_eq!(helper.run_semihosted("seek_write"), 4);
+    let FileSystem::Memory(files) = &helper.semihosting.files else {
+        panic!()
+    };
+    assert!(files["a.txt"].is_empty());
+}
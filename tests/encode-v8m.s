@@ -0,0 +1,464 @@
+// This file is compiled to generate encodings of instructions which are only
+// available in ArmV8-M and ArmV8.1-M architectures, that we then try to decode
+// during testing.
+//
+// See encode.s for more details.
+//
+// This is not a real program, don't try to understand what it does!
+
+.syntax unified
+.thumb
+.org 0x1000
+
+// AUT
+// T1
+aut     r12, lr, sp
+
+// AUTG
+// T1
+autg    r0, r1, r2
+autg    r9, r10, r11
+autg    r12, lr, sp
+
+// BF
+// T1
+label_bf_a:
+bf      label_bf_b, label_bf_a
+nop
+label_bf_b:
+nop
+bf      label_bf_c, label_bf_d
+nop
+nop
+label_bf_c:
+nop
+
+// BFCSEL
+// T1
+bfcsel  2, label_bf_a, 4, eq
+bfcsel  4, label_bf_d, 6, ne
+bfcsel  30, label_bf_a, 32, gt
+
+// BFL
+// T1
+bfl     label_bfl_a, label_bf_d
+nop
+label_bfl_a:
+nop
+
+// BFLX
+// T1
+bflx    label_bflx_a, r0
+nop
+label_bflx_a:
+bflx    label_bflx_b, r12
+nop
+nop
+label_bflx_b:
+nop
+
+// BFX
+// T1
+bfx     label_bfx_a, r5
+nop
+label_bfx_a:
+bfx     label_bfx_b, lr
+nop
+nop
+label_bfx_b:
+label_bf_d:
+nop
+
+// BTI
+// T1
+bti
+
+// BXAUT
+// T1
+bxaut   r0, r1, r2
+bxaut   r12, lr, sp
+
+// CX1
+// T1
+cx1     p0, r0, #0
+cx1     p0, r12, #8191
+cx1     p7, apsr_nzcv, #5
+cx1a    p1, r2, #1
+cx1d    p0, r0, r1, #0
+cx1d    p1, r10, r11, #8191
+cx1da   p0, r2, r3, #2
+
+// CX2
+// T1
+cx2     p0, r0, r1, #0
+cx2     p0, r0, r1, #511
+cx2     p0, r0, apsr_nzcv, #1
+cx2a    p2, r4, r5, #3
+cx2d    p0, r0, r1, r2, #0
+cx2da   p3, r4, r5, r6, #511
+
+// CX3
+// T1
+cx3     p0, r0, r1, r2, #0
+cx3     p0, r0, r1, r2, #63
+cx3     p6, apsr_nzcv, lr, apsr_nzcv, #12
+cx3a    p4, r5, r6, r7, #1
+cx3d    p0, r0, r1, r2, r3, #0
+cx3da   p5, r8, r9, r10, r11, #63
+
+// DLS
+// T1
+dls     lr, r0
+dls     lr, r7
+dls     lr, r12
+
+// DLSTP
+// T1
+dlstp.8 lr, r1
+dlstp.16 lr, r2
+dlstp.32 lr, r10
+dlstp.64 lr, lr
+
+// LCTP
+// T1
+lctp
+
+// LDA
+// T1
+lda     r0, [r1]
+lda     r7, [r12]
+lda     lr, [sp]
+
+// LDAB
+// T1
+ldab    r2, [r3]
+ldab    r11, [r0]
+
+// LDAH
+// T1
+ldah    r4, [r5]
+ldah    r9, [lr]
+
+// LDAEX
+// T1
+ldaex   r0, [r1]
+ldaex   r10, [r6]
+
+// LDAEXB
+// T1
+ldaexb  r3, [r2]
+ldaexb  r12, [r8]
+
+// LDAEXH
+// T1
+ldaexh  r5, [r4]
+ldaexh  r1, [sp]
+
+// LE
+// T1
+label_le_a:
+nop
+le      lr, label_le_a
+le      lr, label_le_a
+// T2
+le      label_le_a
+
+// LETP
+// T1
+letp    lr, label_le_a
+
+// MRS
+// T1
+mrs     r0, pac_key_p_0
+mrs     r5, pac_key_u_3
+mrs     r9, pac_key_p_2_ns
+mrs     r1, msplim
+mrs     r2, psplim
+mrs     r3, msplim_ns
+mrs     r4, psplim_ns
+mrs     r5, basepri_ns
+
+// MSR
+// T1
+msr     pac_key_p_1, r2
+msr     pac_key_u_0, r12
+msr     pac_key_u_3_ns, r7
+msr     msplim, r1
+msr     psplim, r2
+msr     msplim_ns, r3
+msr     psplim_ns, r4
+msr     basepri_ns, r5
+
+// PAC
+// T1
+pac     r12, lr, sp
+
+// PACBTI
+// T1
+pacbti  r12, lr, sp
+
+// PACG
+// T1
+pacg    r0, r1, r2
+pacg    r11, r12, lr
+
+// STL
+// T1
+stl     r0, [r1]
+stl     r8, [r12]
+
+// STLB
+// T1
+stlb    r2, [r3]
+stlb    lr, [r7]
+
+// STLH
+// T1
+stlh    r4, [r5]
+stlh    r6, [sp]
+
+// STLEX
+// T1
+stlex   r0, r1, [r2]
+stlex   r9, r10, [r11]
+
+// STLEXB
+// T1
+stlexb  r3, r4, [r5]
+stlexb  r12, r0, [lr]
+
+// STLEXH
+// T1
+stlexh  r6, r7, [r8]
+stlexh  r1, r2, [sp]
+
+// VADD (vector)
+// T1
+vadd.i8 q0, q1, q2
+vadd.i16 q7, q0, q3
+vadd.i32 q3, q4, q5
+// T2
+vadd.f32 q0, q1, q2
+vadd.f16 q6, q7, q0
+// T3
+vadd.i8 q1, q2, r3
+vadd.i32 q3, q4, lr
+// T4
+vadd.f32 q0, q1, r12
+vadd.f16 q0, q1, r2
+
+// VCMP (vector)
+// T1
+vcmp.i32 eq, q0, q1
+vcmp.i8 ne, q7, q6
+vcmp.u16 cs, q1, q2
+vcmp.u32 hi, q3, q4
+vcmp.s8 ge, q5, q6
+vcmp.s16 lt, q0, q7
+vcmp.s32 gt, q2, r3
+vcmp.s8 le, q1, r0
+vcmp.u8 hi, q1, zr
+vcmp.i16 eq, q1, lr
+vcmp.f32 le, q1, q7
+vcmp.f16 ne, q1, zr
+vcmp.f32 lt, q4, r5
+vcmp.f16 ge, q3, q2
+
+// VCTP
+// T1
+vctp.8 r0
+vctp.16 r5
+vctp.32 r12
+vctp.64 lr
+
+// VCX1
+// T1
+vcx1    p0, s0, #0
+vcx1    p0, s31, #2047
+vcx1a   p1, s5, #1
+vcx1    p0, d0, #0
+vcx1    p0, d15, #2047
+vcx1a   p1, d5, #1
+vcx1    p0, q0, #0
+vcx1    p0, q7, #4095
+vcx1a   p1, q5, #1
+
+// VCX2
+// T1
+vcx2    p0, s0, s1, #0
+vcx2    p0, s31, s30, #63
+vcx2a   p2, s5, s6, #1
+vcx2    p0, d0, d1, #0
+vcx2    p0, d15, d14, #63
+vcx2    p0, q0, q1, #0
+vcx2a   p3, q7, q6, #127
+
+// VCX3
+// T1
+vcx3    p0, s0, s1, s2, #0
+vcx3    p0, s31, s30, s29, #7
+vcx3a   p2, s5, s6, s7, #1
+vcx3    p0, d0, d1, d2, #0
+vcx3    p0, d15, d14, d13, #7
+vcx3    p0, q0, q1, q2, #0
+vcx3a   p4, q7, q6, q5, #15
+
+// VDUP
+// T1
+vdup.8 q1, r2
+vdup.16 q7, r12
+vdup.32 q0, lr
+
+// VLDRB, VLDRH, VLDRW
+// T1
+vldrb.s16 q0, [r1, #12]
+vldrb.u32 q0, [r1, #-12]!
+vldrh.s32 q0, [r1, #12]
+vldrh.u32 q7, [r7], #-12
+vldrb.u16 q3, [r0]
+// T5
+vldrb.u8 q0, [r1, #12]
+vldrb.u8 q0, [r1, #-12]!
+vldrb.u8 q0, [r1], #12
+vldrh.u16 q0, [r1, #12]
+vldrh.u16 q4, [r11]
+vldrw.u32 q0, [sp, #12]
+vldrw.u32 q7, [r1, #-508]
+vldrw.u32 q2, [lr], #508
+// Gather
+vldrb.u8 q0, [r1, q2]
+vldrb.s16 q0, [r1, q2]
+vldrb.u32 q3, [sp, q7]
+vldrh.s32 q0, [r1, q2, uxtw #1]
+vldrh.u16 q0, [r1, q2]
+vldrh.u32 q5, [r12, q6]
+vldrw.u32 q0, [r1, q2, uxtw #2]
+vldrw.u32 q0, [r9, q2]
+vldrw.u32 q0, [q1, #-8]!
+vldrw.u32 q0, [q1]
+vldrw.u32 q7, [q6, #508]
+
+// VMLADAV
+// T1
+vmlav.s32 r0, q1, q2
+vmlava.u16 r2, q1, q2
+vmladavx.s32 r0, q1, q2
+vmladavax.s16 lr, q7, q0
+vmlav.u8 r0, q1, q2
+vmlava.s8 r12, q3, q4
+
+// VMUL (vector)
+// T1
+vmul.i32 q0, q1, q2
+vmul.i8 q7, q6, q5
+// T2
+vmul.f16 q0, q1, q2
+vmul.f32 q3, q1, q4
+// T3
+vmul.i8 q0, q1, r2
+vmul.i16 q0, q7, lr
+// T4
+vmul.f32 q0, q1, r2
+vmul.f16 q5, q1, r9
+
+// VORR
+// T1
+vorr q0, q1, q2
+vorr q7, q6, q5
+vmov q0, q1
+
+// VPNOT
+// T1
+vpnot
+
+// VPST
+// T1
+vpst
+vaddt.i32 q0, q1, q2
+vpstt
+vaddt.i32 q0, q1, q2
+vmult.f32 q0, q1, q2
+vpste
+vsubt.i16 q0, q1, q2
+vsube.i16 q0, q1, q2
+vpsttee
+vaddt.i8 q0, q1, q2
+vaddt.i8 q0, q1, q2
+vadde.i8 q0, q1, q2
+vorre q0, q1, q2
+vpsteet
+vdupt.32 q0, r1
+vcmpe.s32 gt, q0, q1
+vctpe.32 r0
+vmlavt.s32 r0, q1, q2
+
+// VPT
+// T1
+vpt.i8 eq, q0, q1
+vaddt.i8 q0, q1, q2
+vpte.s32 lt, q2, r1
+vaddt.i8 q0, q1, q2
+vadde.i8 q0, q1, q2
+vptt.u16 hi, q0, q7
+vaddt.i8 q0, q1, q2
+vaddt.i8 q0, q1, q2
+vpttet.f32 le, q1, r3
+vaddt.i8 q0, q1, q2
+vaddt.i8 q0, q1, q2
+vadde.i8 q0, q1, q2
+vaddt.i8 q0, q1, q2
+vpt.f16 eq, q1, zr
+vpnott
+
+// VSTRB, VSTRH, VSTRW
+// T1
+vstrb.16 q0, [r1, #12]
+vstrb.32 q0, [r1, #-12]!
+vstrh.32 q0, [r1, #12]
+vstrh.32 q7, [r6], #-2
+// T5
+vstrb.8 q0, [r1, #12]
+vstrh.16 q0, [r1, #-12]!
+vstrw.32 q3, [r1], #-4
+vstrw.32 q3, [sp]
+// Scatter
+vstrb.8 q0, [r1, q2]
+vstrb.16 q0, [r1, q2]
+vstrh.32 q0, [r1, q2, uxtw #1]
+vstrh.16 q0, [r1, q2]
+vstrw.32 q0, [r1, q2]
+vstrw.32 q0, [r1, q2, uxtw #2]
+vstrw.32 q0, [q1, #-8]!
+vstrw.32 q0, [q1]
+vstrw.32 q0, [q1, #252]
+
+// VSUB (vector)
+// T1
+vsub.i16 q0, q1, q2
+vsub.i32 q7, q1, q2
+// T2
+vsub.f32 q0, q1, q2
+vsub.f16 q7, q6, q5
+// T3
+vsub.i8 q0, q1, r2
+vsub.i32 q0, q1, lr
+// T4
+vsub.f32 q0, q1, r3
+vsub.f16 q0, q1, r3
+
+// WLS
+// T1
+wls     lr, r0, label_wls_a
+wls     lr, r9, label_wls_a
+nop
+label_wls_a:
+nop
+
+// WLSTP
+// T1
+wlstp.8 lr, r1, label_wlstp_a
+wlstp.16 lr, r2, label_wlstp_a
+wlstp.32 lr, r3, label_wlstp_a
+wlstp.64 lr, r12, label_wlstp_a
+label_wlstp_a:
+nop
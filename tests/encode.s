@@ -972,21 +972,26 @@ mrs     r8, epsr
 mrs     r7, msp
 mrs     r6, psp
 mrs     r5, primask
-//mrs     r4, basepri
-//mrs     r3, basepri_max
-//mrs     r2, faultmask
+mrs     r4, basepri
+mrs     r3, basepri_max
+mrs     r2, faultmask
 mrs     r1, control
 
 // MSR
 // T1
-//msr     ipsr, r14
+msr     apsr_nzcvq, r14
+msr     apsr_g, r6
+msr     apsr_nzcvqg, r5
+msr     iapsr_nzcvq, r4
+msr     xpsr_nzcvqg, r3
+msr     ipsr, r14
 msr     epsr, r11
 msr     msp, r10
 msr     psp, r8
-//msr     primask, r5
-//msr     basepri, r4
-//msr     basepri_max, r3
-//msr     faultmask, r2
+msr     primask, r5
+msr     basepri, r4
+msr     basepri_max, r3
+msr     faultmask, r2
 msr     control, r1
 
 // MUL
@@ -1073,6 +1078,15 @@ orr.w  r7, r10, r14, lsl #31
 orr.w  r3, r0, r7, lsr #7
 orrs.w  r3, r0, r7, asl #3
 
+// PKHBT, PKHTB
+// T1
+pkhbt   r0, r1, r2
+pkhbt   r3, r4, r5, lsl #1
+pkhbt   r8, r9, r10, lsl #31
+pkhtb   r0, r1, r2, asr #1
+pkhtb   r12, r14, r0, asr #31
+pkhtb   r3, r4, r5, asr #32
+
 // PLD (immediate)
 // T1
 pld     [r0, #0]
@@ -1172,6 +1186,13 @@ qadd16  r1, r2, r3
 qadd16  r10, r11, r12
 qadd16  r2, r2, r4
 
+// QASX
+// T1
+qasx    r0, r1, r2
+qasx    r8, r10, r12
+qasx    r14, r7, r0
+qasx    r2, r2, r9
+
 // QDADD
 // T1
 qdadd   r0, r1, r2
@@ -1186,6 +1207,13 @@ qdsub   r1, r2, r3
 qdsub   r10, r11, r12
 qdsub   r2, r2, r4
 
+// QSAX
+// T1
+qsax    r0, r1, r2
+qsax    r8, r10, r12
+qsax    r14, r7, r0
+qsax    r2, r2, r9
+
 // QSUB
 // T1
 qsub    r0, r1, r2
@@ -1317,6 +1345,13 @@ sadd16  r14, r7, r0
 sadd16  r2, r2, r8
 sadd16  r3, r9, r7
 
+// SASX
+// T1
+sasx    r0, r1, r2
+sasx    r8, r10, r12
+sasx    r14, r7, r0
+sasx    r2, r2, r9
+
 // SBC (immediate)
 // T1
 sbc     r0, r0, #0
@@ -1356,9 +1391,71 @@ sbfx    r14, r0, #31, #1
 //sdiv    r2, r3, r7
 //sdiv    r14, r1, r9
 
+// SEL
+// T1
+sel     r0, r1, r2
+sel     r8, r10, r12
+sel     r14, r7, r0
+sel     r2, r2, r9
+
 // SEV
 //sev
 
+// SHADD8
+// T1
+shadd8  r0, r1, r2
+shadd8  r8, r10, r12
+shadd8  r14, r7, r0
+shadd8  r2, r2, r9
+
+// SHADD16
+// T1
+shadd16 r0, r1, r2
+shadd16 r8, r10, r12
+shadd16 r14, r7, r0
+shadd16 r2, r2, r9
+
+// SHASX
+// T1
+shasx   r0, r1, r2
+shasx   r8, r10, r12
+shasx   r14, r7, r0
+shasx   r2, r2, r9
+
+// SHSAX
+// T1
+shsax   r0, r1, r2
+shsax   r8, r10, r12
+shsax   r14, r7, r0
+shsax   r2, r2, r9
+
+// SHSUB8
+// T1
+shsub8  r0, r1, r2
+shsub8  r8, r10, r12
+shsub8  r14, r7, r0
+shsub8  r2, r2, r9
+
+// SHSUB16
+// T1
+shsub16 r0, r1, r2
+shsub16 r8, r10, r12
+shsub16 r14, r7, r0
+shsub16 r2, r2, r9
+
+// SMLABB, SMLABT, SMLATB, SMLATT
+// T1
+smlabb  r0, r1, r2, r3
+smlabt  r8, r10, r12, r14
+smlatb  r5, r9, r7, r4
+smlatt  r0, r1, r2, r3
+
+// SMLAD, SMLADX
+// T1
+smlad   r0, r1, r2, r3
+smlad   r8, r10, r12, r14
+smladx  r5, r9, r7, r4
+
 // SMLAL
 // T1
 smlal   r0, r5, r7, r14
@@ -1367,6 +1464,68 @@ smlal   r12, r9, r12, r5
 smlal   r7, r1, r11, r10
 smlal   r5, r4, r3, r6
 
+// SMLALBB, SMLALBT, SMLALTB, SMLALTT
+// T1
+smlalbb r0, r1, r2, r3
+smlalbt r10, r14, r0, r9
+smlaltb r7, r1, r11, r10
+smlaltt r0, r1, r2, r3
+
+// SMLALD, SMLALDX
+// T1
+smlald  r0, r1, r2, r3
+smlald  r10, r14, r0, r9
+smlaldx r7, r1, r11, r10
+
+// SMLAWB, SMLAWT
+// T1
+smlawb  r0, r1, r2, r3
+smlawb  r8, r10, r12, r14
+smlawt  r5, r9, r7, r4
+
+// SMLSD, SMLSDX
+// T1
+smlsd   r0, r1, r2, r3
+smlsd   r8, r10, r12, r14
+smlsdx  r5, r9, r7, r4
+
+// SMLSLD, SMLSLDX
+// T1
+smlsld  r0, r1, r2, r3
+smlsld  r10, r14, r0, r9
+smlsldx r7, r1, r11, r10
+
+// SMMLA, SMMLAR
+// T1
+smmla   r0, r1, r2, r3
+smmla   r8, r10, r12, r14
+smmlar  r5, r9, r7, r4
+
+// SMMLS, SMMLSR
+// T1
+smmls   r0, r1, r2, r3
+smmls   r8, r10, r12, r14
+smmlsr  r5, r9, r7, r4
+
+// SMMUL, SMMULR
+// T1
+smmul   r0, r1, r2
+smmul   r8, r10, r12
+smmulr  r14, r7, r0
+
+// SMUAD, SMUADX
+// T1
+smuad   r0, r1, r2
+smuad   r8, r10, r12
+smuadx  r14, r7, r0
+
+// SMULBB, SMULBT, SMULTB, SMULTT
+// T1
+smulbb  r0, r1, r2
+smulbt  r8, r10, r12
+smultb  r14, r7, r0
+smultt  r2, r2, r9
+
 // SMULL
 // T1
 smull   r0, r5, r7, r14
@@ -1375,6 +1534,18 @@ smull   r12, r9, r12, r5
 smull   r7, r1, r11, r10
 smull   r5, r4, r3, r6
 
+// SMULWB, SMULWT
+// T1
+smulwb  r0, r1, r2
+smulwb  r8, r10, r12
+smulwt  r14, r7, r0
+
+// SMUSD, SMUSDX
+// T1
+smusd   r0, r1, r2
+smusd   r8, r10, r12
+smusdx  r14, r7, r0
+
 // SSAT
 // T1
 ssat    r0, #1, r3
@@ -1383,6 +1554,33 @@ ssat    r14, #5, r10
 ssat    r5, #7, r8
 ssat    r2, #20, r7
 
+// SSAT16
+// T1
+ssat16  r0, #1, r1
+ssat16  r8, #16, r9
+ssat16  r14, #7, r2
+
+// SSAX
+// T1
+ssax    r0, r1, r2
+ssax    r8, r10, r12
+ssax    r14, r7, r0
+ssax    r2, r2, r9
+
+// SSUB8
+// T1
+ssub8   r0, r1, r2
+ssub8   r8, r10, r12
+ssub8   r14, r7, r0
+ssub8   r2, r2, r9
+
+// SSUB16
+// T1
+ssub16  r0, r1, r2
+ssub16  r8, r10, r12
+ssub16  r14, r7, r0
+ssub16  r2, r2, r9
+
 // STC
 // T1
 stc     p0, c2, [r0]
@@ -1672,6 +1870,27 @@ svc     #62
 svc     #133
 svc     #255
 
+// SXTAB
+// T1
+sxtab   r0, r1, r2
+sxtab   r3, r4, r5, ror #8
+sxtab   r8, r9, r10, ror #16
+sxtab   r12, r14, r0, ror #24
+
+// SXTAB16
+// T1
+sxtab16 r0, r1, r2
+sxtab16 r3, r4, r5, ror #8
+sxtab16 r8, r9, r10, ror #16
+sxtab16 r12, r14, r0, ror #24
+
+// SXTAH
+// T1
+sxtah   r0, r1, r2
+sxtah   r3, r4, r5, ror #8
+sxtah   r8, r9, r10, ror #16
+sxtah   r12, r14, r0, ror #24
+
 // SXTB
 // T1
 sxtb.n  r0, r1
@@ -1686,6 +1905,13 @@ sxtb.w  r14, r9, ror #16
 sxtb.w  r7, r10, ror #24
 sxtb.w  r5, r14, ror #24
 
+// SXTB16
+// T1
+sxtb16  r0, r1
+sxtb16  r3, r4, ror #8
+sxtb16  r14, r12, ror #16
+sxtb16  r7, r8, ror #24
+
 // SXTH
 // T1
 sxth.n  r0, r1
@@ -1751,6 +1977,27 @@ tst.w   r8, r7, lsl #31
 tst.w   r9, r10, asr #5
 tst.w   r12, r14, asl #9
 
+// UADD8
+// T1
+uadd8   r0, r1, r2
+uadd8   r8, r10, r12
+uadd8   r14, r7, r0
+uadd8   r2, r2, r9
+
+// UADD16
+// T1
+uadd16  r0, r1, r2
+uadd16  r8, r10, r12
+uadd16  r14, r7, r0
+uadd16  r2, r2, r9
+
+// UASX
+// T1
+uasx    r0, r1, r2
+uasx    r8, r10, r12
+uasx    r14, r7, r0
+uasx    r2, r2, r9
+
 // UBFX
 // T1
 ubfx    r0, r14, #0, #1
@@ -1775,6 +2022,54 @@ udf     #0xff
 //udiv    r2, r3, r7
 //udiv    r14, r1, r9
 
+// UHADD8
+// T1
+uhadd8  r0, r1, r2
+uhadd8  r8, r10, r12
+uhadd8  r14, r7, r0
+uhadd8  r2, r2, r9
+
+// UHADD16
+// T1
+uhadd16 r0, r1, r2
+uhadd16 r8, r10, r12
+uhadd16 r14, r7, r0
+uhadd16 r2, r2, r9
+
+// UHASX
+// T1
+uhasx   r0, r1, r2
+uhasx   r8, r10, r12
+uhasx   r14, r7, r0
+uhasx   r2, r2, r9
+
+// UHSAX
+// T1
+uhsax   r0, r1, r2
+uhsax   r8, r10, r12
+uhsax   r14, r7, r0
+uhsax   r2, r2, r9
+
+// UHSUB8
+// T1
+uhsub8  r0, r1, r2
+uhsub8  r8, r10, r12
+uhsub8  r14, r7, r0
+uhsub8  r2, r2, r9
+
+// UHSUB16
+// T1
+uhsub16 r0, r1, r2
+uhsub16 r8, r10, r12
+uhsub16 r14, r7, r0
+uhsub16 r2, r2, r9
+
+// UMAAL
+// T1
+umaal   r0, r1, r2, r3
+umaal   r10, r14, r0, r9
+umaal   r7, r1, r11, r10
+
 // UMLAL
 // T1
 umlal   r0, r12, r2, r1
@@ -1791,6 +2086,61 @@ umull   r7, r5, r7, r3
 umull   r9, r0, r11, r7
 umull   r14, r1, r6, r5
 
+// UQADD8
+// T1
+uqadd8  r0, r1, r2
+uqadd8  r8, r10, r12
+uqadd8  r14, r7, r0
+uqadd8  r2, r2, r9
+
+// UQADD16
+// T1
+uqadd16 r0, r1, r2
+uqadd16 r8, r10, r12
+uqadd16 r14, r7, r0
+uqadd16 r2, r2, r9
+
+// UQASX
+// T1
+uqasx   r0, r1, r2
+uqasx   r8, r10, r12
+uqasx   r14, r7, r0
+uqasx   r2, r2, r9
+
+// UQSAX
+// T1
+uqsax   r0, r1, r2
+uqsax   r8, r10, r12
+uqsax   r14, r7, r0
+uqsax   r2, r2, r9
+
+// UQSUB8
+// T1
+uqsub8  r0, r1, r2
+uqsub8  r8, r10, r12
+uqsub8  r14, r7, r0
+uqsub8  r2, r2, r9
+
+// UQSUB16
+// T1
+uqsub16 r0, r1, r2
+uqsub16 r8, r10, r12
+uqsub16 r14, r7, r0
+uqsub16 r2, r2, r9
+
+// USAD8
+// T1
+usad8   r0, r1, r2
+usad8   r8, r10, r12
+usad8   r14, r7, r0
+usad8   r2, r2, r9
+
+// USADA8
+// T1
+usada8  r0, r1, r2, r3
+usada8  r8, r10, r12, r14
+usada8  r5, r9, r7, r4
+
 // USAT
 // T1
 usat    r0, 1, r10
@@ -1807,6 +2157,48 @@ usat16  r8, 8, r14
 usat16  r12, 15, r2
 usat16  r14, 0, r0
 
+// USAX
+// T1
+usax    r0, r1, r2
+usax    r8, r10, r12
+usax    r14, r7, r0
+usax    r2, r2, r9
+
+// USUB8
+// T1
+usub8   r0, r1, r2
+usub8   r8, r10, r12
+usub8   r14, r7, r0
+usub8   r2, r2, r9
+
+// USUB16
+// T1
+usub16  r0, r1, r2
+usub16  r8, r10, r12
+usub16  r14, r7, r0
+usub16  r2, r2, r9
+
+// UXTAB
+// T1
+uxtab   r0, r1, r2
+uxtab   r3, r4, r5, ror #8
+uxtab   r8, r9, r10, ror #16
+uxtab   r12, r14, r0, ror #24
+
+// UXTAB16
+// T1
+uxtab16 r0, r1, r2
+uxtab16 r3, r4, r5, ror #8
+uxtab16 r8, r9, r10, ror #16
+uxtab16 r12, r14, r0, ror #24
+
+// UXTAH
+// T1
+uxtah   r0, r1, r2
+uxtah   r3, r4, r5, ror #8
+uxtah   r8, r9, r10, ror #16
+uxtah   r12, r14, r0, ror #24
+
 // UXTB
 // T1
 uxtb.n  r0, r7 
@@ -1821,6 +2213,13 @@ uxtb.w  r7, r8, ror #16
 uxtb.w  r9, r10, ror #24
 uxtb.w  r12, r11, ror #24
 
+// UXTB16
+// T1
+uxtb16  r0, r1
+uxtb16  r3, r4, ror #8
+uxtb16  r14, r12, ror #16
+uxtb16  r7, r8, ror #24
+
 // UXTH
 // T1
 uxth.n  r0, r7 
@@ -1835,6 +2234,237 @@ uxth.w  r7, r8, ror #16
 uxth.w  r9, r10, ror #24
 uxth.w  r12, r11, ror #24
 
+// VABS
+// T1
+vabs.f32 s0, s1
+vabs.f64 d0, d1
+
+// VADD
+// T1
+vadd.f32 s0, s1, s31
+vadd.f64 d0, d1, d15
+
+// VCMP, VCMPE
+// T1
+vcmp.f32 s0, s1
+vcmpe.f32 s0, s1
+vcmp.f64 d0, d1
+// T2
+vcmp.f32 s2, #0
+vcmpe.f32 s2, #0
+vcmpe.f64 d2, #0
+
+// VCVT, VCVTR (between floating-point and integer)
+// T1
+vcvt.s32.f32 s0, s1
+vcvtr.s32.f32 s0, s1
+vcvt.u32.f32 s0, s1
+vcvtr.u32.f32 s0, s1
+vcvt.f32.s32 s0, s1
+vcvt.f32.u32 s0, s1
+vcvt.s32.f64 s0, d1
+vcvtr.u32.f64 s0, d1
+vcvt.f64.u32 d0, s1
+
+// VCVT (between floating-point and fixed-point)
+// T1
+vcvt.f32.s32 s0, s0, #16
+vcvt.f32.u16 s0, s0, #3
+vcvt.s16.f32 s1, s1, #1
+vcvt.u32.f32 s1, s1, #32
+vcvt.f64.s16 d0, d0, #3
+vcvt.u32.f64 d1, d1, #32
+
+// VCVT (between double-precision and single-precision)
+// T1
+vcvt.f64.f32 d0, s1
+vcvt.f32.f64 s0, d1
+
+// VCVTA, VCVTN, VCVTP, VCVTM
+// T1
+vcvta.s32.f32 s0, s1
+vcvta.s32.f64 s0, d1
+vcvtn.u32.f32 s0, s1
+vcvtp.s32.f64 s0, d1
+vcvtm.u32.f32 s0, s1
+
+// VCVTB, VCVTT
+// T1
+vcvtb.f32.f16 s0, s1
+vcvtt.f32.f16 s0, s1
+vcvtb.f16.f32 s0, s1
+vcvtt.f16.f32 s0, s1
+vcvtb.f64.f16 d0, s1
+vcvtt.f16.f64 s0, d1
+
+// VDIV
+// T1
+vdiv.f32 s0, s1, s2
+vdiv.f64 d0, d1, d2
+
+// VFMA, VFMS
+// T1
+vfma.f32 s0, s1, s2
+vfms.f32 s0, s1, s2
+vfma.f64 d0, d1, d2
+
+// VFNMA, VFNMS
+// T1
+vfnma.f32 s0, s1, s2
+vfnms.f32 s0, s1, s2
+vfnms.f64 d0, d1, d2
+
+// VLDM
+// T1
+vldmia r0!, {d0-d3}
+// T2
+vldmia r0, {s0-s3}
+vldmdb r1!, {s4}
+
+// VLDR
+// T1
+vldr d1, [r2, #1020]
+// T2
+vldr s0, [r0]
+vldr s0, [r0, #-8]
+vldr s3, [pc, #8]
+
+// VMAXNM, VMINNM
+// T1
+vmaxnm.f32 s0, s1, s2
+vmaxnm.f64 d0, d1, d2
+vminnm.f32 s0, s1, s2
+
+// VMLA, VMLS
+// T1
+vmla.f32 s0, s1, s2
+vmls.f32 s0, s1, s2
+vmla.f64 d0, d1, d2
+
+// VMOV (immediate)
+// T1
+vmov.f32 s0, #1.0
+vmov.f32 s0, #-0.125
+vmov.f32 s0, #31.0
+vmov.f64 d0, #1.0
+vmov.f64 d1, #-0.125
+
+// VMOV (register)
+// T1
+vmov.f32 s0, s1
+vmov.f64 d0, d1
+
+// VMOV (Arm core register to scalar)
+// T1
+vmov.32 d0[1], r2
+
+// VMOV (scalar to Arm core register)
+// T1
+vmov.32 r3, d1[0]
+
+// VMOV (between Arm core register and single-precision register)
+// T1
+vmov s0, r1
+vmov r2, s3
+
+// VMOV (between two Arm core registers and two single-precision registers)
+// T1
+vmov s0, s1, r2, r3
+vmov r2, r3, s0, s1
+
+// VMOV (between two Arm core registers and a doubleword register)
+// T1
+vmov d0, r2, r3
+vmov r2, r3, d15
+
+// VMRS
+// T1
+vmrs APSR_nzcv, fpscr
+vmrs r0, fpscr
+
+// VMSR
+// T1
+vmsr fpscr, r1
+
+// VMUL
+// T1
+vmul.f32 s0, s1, s2
+vmul.f64 d0, d1, d2
+
+// VNEG
+// T1
+vneg.f32 s0, s1
+vneg.f64 d0, d1
+
+// VNMLA, VNMLS, VNMUL
+// T1
+vnmla.f32 s0, s1, s2
+vnmls.f32 s0, s1, s2
+vnmla.f64 d0, d1, d2
+// T2
+vnmul.f32 s0, s1, s2
+vnmul.f64 d0, d1, d2
+
+// VPOP
+// T1
+vpop {d8-d15}
+// T2
+vpop {s0-s1}
+
+// VPUSH
+// T1
+vpush {d8-d15}
+// T2
+vpush {s16}
+
+// VRINTA, VRINTN, VRINTP, VRINTM
+// T1
+vrinta.f32 s0, s1
+vrinta.f64 d0, d1
+vrintn.f32 s0, s1
+vrintp.f64 d0, d1
+vrintm.f32 s0, s1
+
+// VRINTR, VRINTZ
+// T1
+vrintr.f32 s0, s1
+vrintz.f64 d0, d1
+
+// VRINTX
+// T1
+vrintx.f32 s0, s1
+vrintx.f64 d0, d1
+
+// VSEL
+// T1
+vseleq.f32 s0, s1, s2
+vselvs.f64 d0, d1, d2
+vselge.f32 s0, s1, s2
+vselgt.f64 d0, d1, d2
+
+// VSQRT
+// T1
+vsqrt.f32 s0, s1
+vsqrt.f64 d0, d1
+
+// VSTM
+// T1
+vstmia r0!, {d0-d3}
+// T2
+vstmia r0, {s0-s3}
+vstmdb r1!, {s4, s5}
+
+// VSTR
+// T1
+vstr d3, [sp, #-4]
+// T2
+vstr s0, [r0, #4]
+
+// VSUB
+// T1
+vsub.f32 s3, s4, s5
+vsub.f64 d3, d4, d5
+
 // YIELD
 // T1
 yield
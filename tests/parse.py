@@ -2,9 +2,13 @@
 
 import subprocess
 import re
+import sys
+
+# Object file to be disassembled, encode.o by default.
+obj = sys.argv[1] if len(sys.argv) > 1 else "encode.o"
 
 disassembly = subprocess.check_output(
-    ["llvm-objdump-18", "-d", "encode.o", "--no-print-imm-hex"]
+    ["llvm-objdump-18", "-d", obj, "--no-print-imm-hex"]
 ).splitlines()
 
 for line in disassembly:
@@ -31,5 +35,5 @@ for line in disassembly:
         op = m.groups()[2]
         args = m.groups()[3].lower().strip()
         # strip() required when instruction has no arguments
-        vector = f"{data.hex():<8} {op:<9}{args}".strip()
+        vector = f"{data.hex():<8} {op:<8} {args}".strip()
         print(vector)